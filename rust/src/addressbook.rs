@@ -0,0 +1,35 @@
+//! Local names for frequently used addresses
+//!
+//! `~/.squads/addressbook.json` is a JSON object mapping name to address, e.g.
+//! `{"ops": "BJbRt...", "usdc": "EPjFW..."}`. Any pubkey argument that isn't a valid address is
+//! looked up there, and the resolved address is printed next to the name. An address always wins
+//! over a name, so a name can never shadow a real key. The file is optional.
+
+use crate::registry::{load, squads_file};
+use solana_sdk::pubkey::Pubkey;
+
+/// `~/.squads/addressbook.json`
+const ADDRESSBOOK_FILE: &str = "addressbook.json";
+
+/// Parse `value` as an address, or resolve it as an address book name
+pub fn try_resolve_pubkey(value: &str) -> Result<Pubkey, String> {
+    if let Ok(pubkey) = value.parse() {
+        return Ok(pubkey);
+    }
+    let not_found = || format!("'{}' is neither an address nor a name in ~/.squads/{}", value, ADDRESSBOOK_FILE);
+    let path = squads_file(ADDRESSBOOK_FILE).ok_or_else(not_found)?;
+    let address = load(&path)?.remove(value).ok_or_else(not_found)?;
+    let pubkey = address
+        .parse()
+        .map_err(|_| format!("address book entry '{}' in {} is not a valid address: {}", value, path.display(), address))?;
+    println!("Resolved '{}' -> {}", value, pubkey);
+    Ok(pubkey)
+}
+
+/// `try_resolve_pubkey`, exiting with the error
+pub fn resolve_pubkey(value: &str) -> Pubkey {
+    try_resolve_pubkey(value).unwrap_or_else(|e| {
+        println!("Error: {}", e);
+        std::process::exit(1);
+    })
+}
@@ -0,0 +1,54 @@
+//! Devnet/localnet airdrops for onboarding
+//!
+//! The public devnet faucet rate-limits aggressively, so requests are retried with backoff.
+
+use crate::network::Network;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+const MAX_ATTEMPTS: u32 = 5;
+const INITIAL_BACKOFF: Duration = Duration::from_secs(2);
+const CONFIRM_TIMEOUT: Duration = Duration::from_secs(60);
+const CONFIRM_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Request `lamports` for `recipient` and wait until the airdrop is confirmed.
+///
+/// Refuses on mainnet, which has no faucet.
+pub fn airdrop(client: &RpcClient, network: Network, recipient: &Pubkey, lamports: u64) -> Result<Signature, String> {
+    if network == Network::Mainnet {
+        return Err("Airdrops are only available on devnet and localnet".to_string());
+    }
+
+    let mut backoff = INITIAL_BACKOFF;
+    let mut attempt = 1;
+    let signature = loop {
+        match client.request_airdrop(recipient, lamports) {
+            Ok(signature) => break signature,
+            Err(e) if attempt < MAX_ATTEMPTS => {
+                println!(
+                    "Airdrop request failed (attempt {}/{}): {}. Retrying in {}s...",
+                    attempt,
+                    MAX_ATTEMPTS,
+                    e,
+                    backoff.as_secs()
+                );
+                sleep(backoff);
+                backoff *= 2;
+                attempt += 1;
+            }
+            Err(e) => return Err(format!("Airdrop failed after {} attempts: {}", MAX_ATTEMPTS, e)),
+        }
+    };
+
+    let started = Instant::now();
+    while started.elapsed() < CONFIRM_TIMEOUT {
+        match client.confirm_transaction(&signature) {
+            Ok(true) => return Ok(signature),
+            Ok(false) => sleep(CONFIRM_POLL_INTERVAL),
+            Err(e) => return Err(format!("Failed to confirm airdrop {}: {}", signature, e)),
+        }
+    }
+    Err(format!("Airdrop {} was not confirmed within {}s", signature, CONFIRM_TIMEOUT.as_secs()))
+}
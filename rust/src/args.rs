@@ -0,0 +1,62 @@
+//! Helpers for pulling optional flags out of a script's arguments
+//!
+//! Scripts take positional arguments followed by an optional network name, so flags are removed
+//! from `args` first and the remaining positions stay where the script expects them.
+
+/// Remove a boolean flag from `args`, returning whether it was present
+pub fn take_flag(args: &mut Vec<String>, flag: &str) -> bool {
+    let present = args.iter().any(|a| a == flag);
+    args.retain(|a| a != flag);
+    present
+}
+
+/// Remove `flag <value>` from `args`, returning the value if the flag was present
+pub fn take_flag_value(args: &mut Vec<String>, flag: &str) -> Result<Option<String>, String> {
+    match args.iter().position(|a| a == flag) {
+        Some(i) => {
+            if i + 1 >= args.len() {
+                return Err(format!("{} requires a value", flag));
+            }
+            let value = args.remove(i + 1);
+            args.remove(i);
+            Ok(Some(value))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Remove every `flag <value>` pair from `args`, returning the values in order
+pub fn take_flag_values(args: &mut Vec<String>, flag: &str) -> Result<Vec<String>, String> {
+    let mut values = Vec::new();
+    while let Some(value) = take_flag_value(args, flag)? {
+        values.push(value);
+    }
+    Ok(values)
+}
+
+/// Fail on any `--flag` left in `args` once every known flag has been taken, so a typo like
+/// `--simualte` is reported instead of shifting positions or being silently ignored
+pub fn reject_unknown_flags(args: &[String]) -> Result<(), String> {
+    let unknown: Vec<&str> = args.iter().skip(1).filter(|a| a.starts_with("--")).map(|a| a.as_str()).collect();
+    if unknown.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("unknown flag(s): {}", unknown.join(", ")))
+    }
+}
+
+/// `reject_unknown_flags`, printing the error and exiting if it fails
+pub fn reject_unknown_flags_or_exit(args: &[String]) {
+    if let Err(e) = reject_unknown_flags(args) {
+        println!("Error: {}", e);
+        println!("Run with --help for usage.");
+        std::process::exit(1);
+    }
+}
+
+/// Remove `--help` and `-h` from `args`, returning whether either was present. Scripts answer
+/// with their usage text and exit successfully.
+pub fn take_help(args: &mut Vec<String>) -> bool {
+    let help = take_flag(args, "--help");
+    take_flag(args, "-h") || help
+}
@@ -0,0 +1,80 @@
+//! Local audit log of governance actions
+//!
+//! With `--log-dir <path>` (or `SQUADS_LOG_DIR`), every successful send appends one JSON line
+//! to `<path>/<YYYY-MM-DD>.jsonl` (UTC date): timestamp, binary, cluster, multisig, action,
+//! signer and signature. The flag is taken with the send options, so every sending script has
+//! it. Logging is best-effort: a failed write prints a warning to stderr and the command still
+//! succeeds, since the transaction has already landed.
+
+use crate::args::take_flag_value;
+use crate::format::format_unix_timestamp;
+use crate::network::Network;
+use serde_json::json;
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Environment variable used when `--log-dir` isn't given
+const LOG_DIR_ENV: &str = "SQUADS_LOG_DIR";
+
+static LOG_DIR: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+/// Remove `--log-dir <path>` from `args`, falling back to `SQUADS_LOG_DIR`
+pub fn take_log_dir(args: &mut Vec<String>) -> Result<(), String> {
+    let dir = take_flag_value(args, "--log-dir")?
+        .or_else(|| std::env::var(LOG_DIR_ENV).ok())
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from);
+    let _ = LOG_DIR.set(dir);
+    Ok(())
+}
+
+/// The running binary's name, e.g. `approve-proposal`
+fn binary_name() -> String {
+    std::env::args()
+        .next()
+        .and_then(|path| Path::new(&path).file_name().map(|name| name.to_string_lossy().into_owned()))
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Append one entry for a landed transaction. Does nothing without a log directory; a failed
+/// write only warns.
+pub fn record(network: Network, multisig: &Pubkey, action: &str, signer: &Pubkey, signature: &Signature) {
+    let Some(Some(dir)) = LOG_DIR.get() else {
+        return;
+    };
+    if let Err(e) = append(dir, network, multisig, action, signer, signature) {
+        eprintln!("Warning: failed to write audit log in {}: {}", dir.display(), e);
+    }
+}
+
+fn append(
+    dir: &Path,
+    network: Network,
+    multisig: &Pubkey,
+    action: &str,
+    signer: &Pubkey,
+    signature: &Signature,
+) -> std::io::Result<()> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs() as i64);
+    let timestamp = format_unix_timestamp(now);
+    let entry = json!({
+        "timestamp": timestamp,
+        "unix_timestamp": now,
+        "binary": binary_name(),
+        "cluster": network.name(),
+        "multisig": multisig.to_string(),
+        "action": action,
+        "signer": signer.to_string(),
+        "signature": signature.to_string(),
+    });
+
+    fs::create_dir_all(dir)?;
+    // format_unix_timestamp starts with the YYYY-MM-DD date
+    let path = dir.join(format!("{}.jsonl", &timestamp[..10]));
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", entry)
+}
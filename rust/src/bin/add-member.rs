@@ -1,102 +1,282 @@
 //! Add a member to a Squads v4 Multisig (config authority only)
 //!
+//! By default the config authority (member1.json) adds the member directly with MultisigAddMember.
+//! With `--via-proposal`, the change is put to a vote instead. The program refuses config
+//! transactions for a multisig with a config authority, so this only works when the config
+//! authority is one of the multisig's own vaults: the MultisigAddMember instruction goes in a
+//! vault transaction proposal, and the vault signs it when the proposal executes.
+//!
 //! Usage:
-//!   cargo run --bin add_member -- <multisig_address> <new_member_address> [mainnet]
+//!   cargo run --bin add_member -- <multisig_address> <new_member_address> [--permissions <ive>] [--if-not-present] [--via-proposal] [mainnet]
+//!
+//! Options:
+//!   --permissions <ive>  - Any of i (Initiate), v (Vote), e (Execute); default ive (full)
+//!   --if-not-present     - Skip (exit 0) if the address is already a member, for re-runnable scripts
+//!   --via-proposal       - Put the add to a vote (the config authority must be one of the multisig's vaults)
 //!
 //! Example:
 //!   cargo run --bin add_member -- BJbRtXM8wecvRrJNbbpNLfuG8FTSoU6zPYW1NFrMH6Q3 NewMemberPubkeyHere mainnet
+//!   cargo run --bin add_member -- BJbRt... AuditorPubkey --permissions v mainnet
 
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::{
     commitment_config::CommitmentConfig,
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
-    signature::{read_keypair_file, Signer},
-    transaction::Transaction,
+    signature::{read_keypair_file, Keypair, Signer},
+    system_program,
 };
-use squads_multisig::anchor_lang::InstructionData;
+use squads_multisig::anchor_lang::{AnchorSerialize, InstructionData, ToAccountMetas};
+use squads_multisig::pda::{get_proposal_pda, get_transaction_pda, get_vault_pda};
 use squads_multisig::squads_multisig_program;
-use squads_multisig::state::{Member, Permission, Permissions};
+use squads_multisig::state::{Member, Multisig};
+use squads_multisig::vault_transaction::VaultTransactionMessageExt;
+use squads_multisig_program::TransactionMessage;
+use squads_rust::addressbook::resolve_pubkey;
+use squads_rust::args::{reject_unknown_flags_or_exit, take_flag, take_flag_value, take_help};
+use squads_rust::audit::record;
+use squads_rust::multisig::{check_voters, fetch_multisig_or_exit};
+use squads_rust::permissions::{parse_permissions, PermissionsExt};
+use squads_rust::network::Network;
+use squads_rust::program::{program_id, take_program_id_or_exit};
+use squads_rust::send::{build_and_send, SendOptions};
 use std::env;
 
-const DEVNET_RPC: &str = "https://api.devnet.solana.com";
-const MAINNET_RPC: &str = "https://api.mainnet-beta.solana.com";
+/// MultisigAddMember signed by `config_authority`, which also pays rent for the larger account
+fn add_member_ix(multisig_pda: &Pubkey, config_authority: &Pubkey, new_member: Member) -> Instruction {
+    let instruction_data = squads_multisig_program::instruction::MultisigAddMember {
+        args: squads_multisig_program::MultisigAddMemberArgs {
+            new_member,
+            memo: None,
+        },
+    };
+
+    let accounts = vec![
+        AccountMeta::new(*multisig_pda, false),
+        AccountMeta::new_readonly(*config_authority, true),
+        AccountMeta::new(*config_authority, true), // rent_payer
+        AccountMeta::new_readonly(system_program::ID, false), // system_program
+    ];
+
+    Instruction {
+        program_id: program_id(),
+        accounts,
+        data: instruction_data.data(),
+    }
+}
+
+/// Propose the add as a vault transaction signed by the vault that is the config authority
+fn propose_add_member(
+    client: &RpcClient,
+    network: Network,
+    multisig_pda: &Pubkey,
+    multisig: &Multisig,
+    creator: &Keypair,
+    new_member: Member,
+    send_options: &SendOptions,
+) {
+    if multisig.config_authority == Pubkey::default() {
+        println!("Error: this multisig has no config authority, so member changes already need a config transaction.");
+        println!("Propose it from the Squads UI; --via-proposal is for multisigs with a config authority.");
+        std::process::exit(1);
+    }
+    // The vault that is the config authority signs MultisigAddMember when the proposal executes
+    let vault_index = match (0..=u8::MAX)
+        .find(|&index| get_vault_pda(multisig_pda, index, Some(&program_id())).0 == multisig.config_authority)
+    {
+        Some(index) => index,
+        None => {
+            println!(
+                "Error: the config authority {} is not one of this multisig's vaults, so no proposal can sign for it.",
+                multisig.config_authority
+            );
+            println!("Run without --via-proposal to add the member directly as the config authority.");
+            std::process::exit(1);
+        }
+    };
+    let vault_pda = multisig.config_authority;
+
+    let new_transaction_index = multisig.transaction_index + 1;
+    let (transaction_pda, _) = get_transaction_pda(multisig_pda, new_transaction_index, Some(&program_id()));
+    let (proposal_pda, _) = get_proposal_pda(multisig_pda, new_transaction_index, Some(&program_id()));
+
+    println!("=== Propose Adding Member to Multisig ({}) ===\n", network.name().to_uppercase());
+    println!("Multisig: {}", multisig_pda);
+    println!("Creator: {}", creator.pubkey());
+    println!("Threshold: {} of {}", multisig.threshold, multisig.members.len());
+    println!("New Member: {}", new_member.key);
+    println!("Permissions: {}", new_member.permissions.to_string());
+    println!();
+    println!(
+        "Path: proposal (--via-proposal; the config authority is vault {}, and the program refuses config",
+        vault_index
+    );
+    println!("      transactions for a multisig with a config authority, so the vault signs MultisigAddMember on execution)");
+    println!("Vault {} ({}) pays the rent for the larger multisig account; keep it funded.", vault_index, vault_pda);
+    println!();
+    println!("Transaction Index: {}", new_transaction_index);
+    println!("Proposal PDA: {}", proposal_pda);
+
+    let transaction_message = TransactionMessage::try_compile(
+        &vault_pda,
+        &[add_member_ix(multisig_pda, &vault_pda, new_member.clone())],
+        &[],
+    )
+    .expect("Failed to compile transaction message");
+    let message_bytes = transaction_message
+        .try_to_vec()
+        .expect("Failed to serialize message");
+
+    let vault_tx_accounts = squads_multisig_program::accounts::VaultTransactionCreate {
+        multisig: *multisig_pda,
+        transaction: transaction_pda,
+        creator: creator.pubkey(),
+        rent_payer: creator.pubkey(),
+        system_program: system_program::ID,
+    };
+    let vault_tx_data = squads_multisig_program::instruction::VaultTransactionCreate {
+        args: squads_multisig_program::instructions::VaultTransactionCreateArgs {
+            vault_index,
+            ephemeral_signers: 0,
+            transaction_message: message_bytes,
+            memo: None,
+        },
+    };
+    let create_vault_tx_ix = Instruction {
+        program_id: program_id(),
+        accounts: vault_tx_accounts.to_account_metas(Some(false)),
+        data: vault_tx_data.data(),
+    };
+
+    let proposal_accounts = squads_multisig_program::accounts::ProposalCreate {
+        multisig: *multisig_pda,
+        proposal: proposal_pda,
+        creator: creator.pubkey(),
+        rent_payer: creator.pubkey(),
+        system_program: system_program::ID,
+    };
+    let proposal_data = squads_multisig_program::instruction::ProposalCreate {
+        args: squads_multisig_program::instructions::ProposalCreateArgs {
+            transaction_index: new_transaction_index,
+            draft: false, // Active immediately so members can vote
+        },
+    };
+    let create_proposal_ix = Instruction {
+        program_id: program_id(),
+        accounts: proposal_accounts.to_account_metas(Some(false)),
+        data: proposal_data.data(),
+    };
+
+    println!("\nCreating proposal...");
+
+    match build_and_send(client, &[create_vault_tx_ix, create_proposal_ix], &creator.pubkey(), &[creator], send_options) {
+        Ok(sig) => {
+            record(network, multisig_pda, &format!("propose {}: add member {}", new_transaction_index, new_member.key), &creator.pubkey(), &sig);
+            println!("\nProposal created successfully!");
+            println!("Transaction: {}", sig);
+            println!();
+            println!("Members approve with:");
+            println!("  cargo run --bin approve-proposal -- {} {} {}", multisig_pda, new_transaction_index, network.cli_arg());
+            println!();
+            println!("After threshold is met, execute with:");
+            println!("  cargo run --bin execute-proposal -- {} {} {}", multisig_pda, new_transaction_index, network.cli_arg());
+
+            let cluster_param = network.cluster_param();
+            println!("\nView on Solana Explorer:");
+            println!("https://explorer.solana.com/tx/{}{}", sig, cluster_param);
+        }
+        Err(e) => {
+            println!("\nFailed to create proposal: {}", e);
+        }
+    }
+}
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    let mut args: Vec<String> = env::args().collect();
+    let help = take_help(&mut args);
+    take_program_id_or_exit(&mut args);
+    let send_options = SendOptions::take_from_or_exit(&mut args);
+    let if_not_present = take_flag(&mut args, "--if-not-present");
+    let via_proposal = take_flag(&mut args, "--via-proposal");
+    let permissions = match take_flag_value(&mut args, "--permissions") {
+        Ok(value) => parse_permissions(value.as_deref().unwrap_or("ive")),
+        Err(e) => Err(e),
+    }
+    .unwrap_or_else(|e| {
+        println!("Error: {}", e);
+        std::process::exit(1);
+    });
+    reject_unknown_flags_or_exit(&args);
 
-    if args.len() < 3 {
-        println!("Usage: cargo run --bin add_member -- <multisig_address> <new_member_address> [mainnet]");
+    if help || args.len() < 3 {
+        println!("Usage: cargo run --bin add_member -- <multisig_address> <new_member_address> [--permissions <ive>] [--if-not-present] [--via-proposal] [mainnet]");
         println!("Example: cargo run --bin add_member -- BJbRtXM8wecvRrJNbbpNLfuG8FTSoU6zPYW1NFrMH6Q3 NewMemberPubkeyHere mainnet");
+        println!();
+        println!("Options:");
+        println!("  --permissions <ive>  - Any of i (Initiate), v (Vote), e (Execute); default ive (full)");
+        println!("  --if-not-present     - Skip (exit 0) if the address is already a member");
+        println!("  --via-proposal       - Put the add to a vote (the config authority must be one of the multisig's vaults)");
         return;
     }
 
-    let multisig_pda: Pubkey = args[1].parse().expect("Invalid multisig address");
-    let new_member_pubkey: Pubkey = args[2].parse().expect("Invalid new member address");
-    let network = args.get(3).map(|s| s.as_str()).unwrap_or("devnet");
-
-    let rpc_url = match network {
-        "mainnet" => MAINNET_RPC,
-        _ => DEVNET_RPC,
-    };
+    let multisig_pda: Pubkey = resolve_pubkey(&args[1]);
+    let new_member_pubkey: Pubkey = resolve_pubkey(&args[2]);
+    let network = Network::from_arg(args.get(3).map(|s| s.as_str()).unwrap_or("devnet"));
 
-    let client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
+    let client = RpcClient::new_with_commitment(network.rpc_url(), CommitmentConfig::confirmed());
     let config_authority = read_keypair_file("../member1.json").expect("Failed to read member1.json");
 
-    // New member gets full permissions (Initiate, Vote, Execute)
-    let all_permissions = Permissions {
-        mask: Permission::Initiate as u8 | Permission::Vote as u8 | Permission::Execute as u8,
-    };
-
     let new_member = Member {
         key: new_member_pubkey,
-        permissions: all_permissions,
+        permissions,
     };
 
-    println!("=== Add Member to Multisig ({}) ===\n", network.to_uppercase());
+    // Check the membership the add would produce before sending
+    let multisig = fetch_multisig_or_exit(&client, &multisig_pda);
+    if let Some(i) = multisig.is_member(new_member_pubkey) {
+        if if_not_present {
+            println!(
+                "{} is already a member ({}), skipping",
+                new_member_pubkey,
+                multisig.members[i].permissions.to_string()
+            );
+            return;
+        }
+        println!("Error: {} is already a member of this multisig", new_member_pubkey);
+        println!("Pass --if-not-present to skip existing members instead of failing.");
+        std::process::exit(1);
+    }
+    let mut members = multisig.members.clone();
+    members.push(new_member.clone());
+    if let Err(e) = check_voters(&members, multisig.threshold) {
+        println!("Error: {}", e);
+        std::process::exit(1);
+    }
+
+    if via_proposal {
+        propose_add_member(&client, network, &multisig_pda, &multisig, &config_authority, new_member, &send_options);
+        return;
+    }
+
+    println!("=== Add Member to Multisig ({}) ===\n", network.name().to_uppercase());
     println!("Multisig: {}", multisig_pda);
     println!("Config Authority: {}", config_authority.pubkey());
     println!("New Member: {}", new_member_pubkey);
-    println!("Permissions: Initiate, Vote, Execute");
+    println!("Permissions: {}", permissions.to_string());
+    println!("Path: direct (the config authority signs MultisigAddMember; pass --via-proposal to put it to a vote)");
 
-    let instruction_data = squads_multisig_program::instruction::MultisigAddMember {
-        args: squads_multisig_program::MultisigAddMemberArgs {
-            new_member,
-            memo: None,
-        },
-    };
-
-    let accounts = vec![
-        AccountMeta::new(multisig_pda, false),
-        AccountMeta::new_readonly(config_authority.pubkey(), true),
-        AccountMeta::new(config_authority.pubkey(), true), // rent_payer
-        AccountMeta::new_readonly(solana_sdk::system_program::ID, false), // system_program
-    ];
-
-    let instruction = Instruction {
-        program_id: squads_multisig_program::ID,
-        accounts,
-        data: instruction_data.data(),
-    };
+    let instruction = add_member_ix(&multisig_pda, &config_authority.pubkey(), new_member);
 
     println!("\nAdding member...");
 
-    let recent_blockhash = client.get_latest_blockhash().expect("Failed to get blockhash");
-    let transaction = Transaction::new_signed_with_payer(
-        &[instruction],
-        Some(&config_authority.pubkey()),
-        &[&config_authority],
-        recent_blockhash,
-    );
-
-    match client.send_and_confirm_transaction(&transaction) {
+    match build_and_send(&client, &[instruction], &config_authority.pubkey(), &[&config_authority], &send_options) {
         Ok(sig) => {
+            record(network, &multisig_pda, &format!("add member {}", new_member_pubkey), &config_authority.pubkey(), &sig);
             println!("\nMember added successfully!");
             println!("Transaction: {}", sig);
 
-            let cluster_param = if network == "mainnet" { "" } else { "?cluster=devnet" };
             println!("\nView on Solana Explorer:");
-            println!("https://explorer.solana.com/tx/{}{}", sig, cluster_param);
+            println!("{}", network.explorer_tx_url(&sig));
         }
         Err(e) => {
             println!("\nFailed to add member: {}", e);
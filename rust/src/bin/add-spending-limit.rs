@@ -15,8 +15,14 @@
 //!                       (default: all current multisig members)
 //!   --destinations <addrs> - Comma-separated allowed destination addresses
 //!                            (default: any destination)
+//!   --force           - Add the limit even if one with the same mint, vault and period exists
 //!   mainnet           - Use mainnet instead of devnet
 //!
+//! Before creating, the multisig's existing spending limits are scanned (getProgramAccounts,
+//! which needs a dedicated RPC on mainnet). A limit with the same mint, vault and period would
+//! split one allowance across two limits, so the script prints it and stops unless `--force`
+//! is given. The 'combinator' limit itself can only exist once; remove it first to replace it.
+//!
 //! Examples:
 //!   # 1 SOL daily limit on devnet
 //!   cargo run --bin add-spending-limit -- BJbRt... 1000000000 day
@@ -31,17 +37,21 @@ use solana_sdk::{
     pubkey::Pubkey,
     signature::{read_keypair_file, Signer},
     system_program,
-    transaction::Transaction,
 };
-use squads_multisig::anchor_lang::{AccountDeserialize, InstructionData};
+use squads_multisig::anchor_lang::InstructionData;
 use squads_multisig::pda::get_spending_limit_pda;
 use squads_multisig::squads_multisig_program;
-use squads_multisig::state::{Multisig, Period};
+use squads_multisig::state::{Period, SpendingLimit};
+use squads_rust::addressbook::resolve_pubkey;
+use squads_rust::args::{reject_unknown_flags_or_exit, take_flag, take_flag_value, take_help};
+use squads_rust::audit::record;
+use squads_rust::multisig::{fetch_multisig_or_exit, fetch_spending_limits, normalize_members};
+use squads_rust::network::Network;
+use squads_rust::program::{program_id, take_program_id_or_exit};
+use squads_rust::send::{build_and_send, SendOptions};
+use squads_rust::vault::parse_vault_index;
 use std::env;
 
-const DEVNET_RPC: &str = "https://api.devnet.solana.com";
-const MAINNET_RPC: &str = "https://api.mainnet-beta.solana.com";
-
 fn parse_period(s: &str) -> Option<Period> {
     match s.to_lowercase().as_str() {
         "one-time" | "onetime" | "once" => Some(Period::OneTime),
@@ -52,10 +62,73 @@ fn parse_period(s: &str) -> Option<Period> {
     }
 }
 
+/// Stop if the multisig already has a limit for the same mint, vault and period, unless `force`.
+/// An existing limit at `spending_limit_pda` always stops: the create would fail.
+fn check_duplicate_limits(
+    client: &RpcClient,
+    multisig_pda: &Pubkey,
+    spending_limit_pda: &Pubkey,
+    mint: Pubkey,
+    vault_index: u8,
+    period: Period,
+    force: bool,
+) {
+    let limits = match fetch_spending_limits(client, multisig_pda) {
+        Ok(limits) => limits,
+        Err(e) if force => {
+            println!("WARNING: Could not check for duplicate spending limits: {}\n", e);
+            return;
+        }
+        Err(e) => {
+            println!("Error: Could not check for duplicate spending limits: {}", e);
+            println!("Pass --force to create the limit without the check.");
+            std::process::exit(1);
+        }
+    };
+    let duplicates: Vec<&(Pubkey, SpendingLimit)> = limits
+        .iter()
+        .filter(|(_, limit)| limit.mint == mint && limit.vault_index == vault_index && limit.period == period)
+        .collect();
+    if duplicates.is_empty() {
+        return;
+    }
+
+    println!("A spending limit for this mint, vault and period already exists:");
+    for (address, limit) in &duplicates {
+        println!("  {} (amount {}, {} remaining)", address, limit.amount, limit.remaining_amount);
+    }
+    if duplicates.iter().any(|(address, _)| address == spending_limit_pda) {
+        println!("\nError: The 'combinator' spending limit {} already exists.", spending_limit_pda);
+        println!("Remove it with remove-spending-limit before adding a new one.");
+        std::process::exit(1);
+    }
+    if !force {
+        println!("\nError: Adding another would split the allowance across separate limits.");
+        println!("Pass --force to add it anyway.");
+        std::process::exit(1);
+    }
+    println!("Continuing because of --force.\n");
+}
+
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    let mut args: Vec<String> = env::args().collect();
+    let help = take_help(&mut args);
+    take_program_id_or_exit(&mut args);
+    let send_options = SendOptions::take_from_or_exit(&mut args);
+    let force = take_flag(&mut args, "--force");
+    let mut take = |flag: &str| {
+        take_flag_value(&mut args, flag).unwrap_or_else(|e| {
+            println!("Error: {}", e);
+            std::process::exit(1);
+        })
+    };
+    let mint_arg = take("--mint");
+    let vault_arg = take("--vault");
+    let members_arg = take("--members");
+    let destinations_arg = take("--destinations");
+    reject_unknown_flags_or_exit(&args);
 
-    if args.len() < 4 {
+    if help || args.len() < 4 {
         println!("Usage: cargo run --bin add-spending-limit -- <multisig_address> <amount> <period> [options]");
         println!();
         println!("Arguments:");
@@ -68,6 +141,7 @@ fn main() {
         println!("  --vault <index>   - Vault index (default: 0)");
         println!("  --members <addrs> - Comma-separated list of members who can use this limit");
         println!("  --destinations <addrs> - Comma-separated allowed destination addresses");
+        println!("  --force           - Add the limit even if one with the same mint, vault and period exists");
         println!("  mainnet           - Use mainnet instead of devnet");
         println!();
         println!("Examples:");
@@ -76,73 +150,38 @@ fn main() {
         return;
     }
 
-    let multisig_pda: Pubkey = args[1].parse().expect("Invalid multisig address");
+    let multisig_pda: Pubkey = resolve_pubkey(&args[1]);
     let amount: u64 = args[2].parse().expect("Invalid amount");
     let period = parse_period(&args[3]).expect("Invalid period. Use: one-time, day, week, or month");
+    let network = Network::from_arg(args.get(4).map(|s| s.as_str()).unwrap_or("devnet"));
 
-    // Parse optional arguments
-    let mut mint = Pubkey::default(); // SOL
-    let mut vault_index: u8 = 0;
-    let mut specified_members: Option<Vec<Pubkey>> = None;
-    let mut destinations: Vec<Pubkey> = Vec::new();
-    let mut network = "devnet";
-
-    let mut i = 4;
-    while i < args.len() {
-        match args[i].as_str() {
-            "--mint" => {
-                i += 1;
-                mint = args[i].parse().expect("Invalid mint address");
-            }
-            "--vault" => {
-                i += 1;
-                vault_index = args[i].parse().expect("Invalid vault index");
-            }
-            "--members" => {
-                i += 1;
-                specified_members = Some(
-                    args[i]
-                        .split(',')
-                        .map(|s| s.trim().parse().expect("Invalid member address"))
-                        .collect(),
-                );
-            }
-            "--destinations" => {
-                i += 1;
-                destinations = args[i]
-                    .split(',')
-                    .map(|s| s.trim().parse().expect("Invalid destination address"))
-                    .collect();
-            }
-            "mainnet" => {
-                network = "mainnet";
-            }
-            _ => {}
+    let mint = mint_arg.map(|value| resolve_pubkey(&value)).unwrap_or_default(); // SOL
+    let vault_index: u8 = match vault_arg.as_deref().map(parse_vault_index).transpose() {
+        Ok(index) => index.unwrap_or(0),
+        Err(e) => {
+            println!("Error: {}", e);
+            std::process::exit(1);
         }
-        i += 1;
-    }
-
-    let rpc_url = match network {
-        "mainnet" => MAINNET_RPC,
-        _ => DEVNET_RPC,
     };
+    let parse_list = |value: &str| -> Vec<Pubkey> { value.split(',').map(|s| resolve_pubkey(s.trim())).collect() };
+    let specified_members: Option<Vec<Pubkey>> = members_arg.as_deref().map(parse_list);
+    let destinations: Vec<Pubkey> = destinations_arg.as_deref().map(parse_list).unwrap_or_default();
 
-    let client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
+    let client = RpcClient::new_with_commitment(network.rpc_url(), CommitmentConfig::confirmed());
     let config_authority = read_keypair_file("../member1.json").expect("Failed to read member1.json");
 
     // Fetch multisig to get members if not specified
-    let multisig_account = client
-        .get_account(&multisig_pda)
-        .expect("Failed to fetch multisig account");
-    let multisig = Multisig::try_deserialize(&mut multisig_account.data.as_slice())
-        .expect("Failed to deserialize multisig");
+    let multisig = fetch_multisig_or_exit(&client, &multisig_pda);
 
     // Use specified members or default to all multisig members
     let mut members: Vec<Pubkey> = specified_members.unwrap_or_else(|| {
         multisig.members.iter().map(|m| m.key).collect()
     });
-    // Members must be sorted for the spending limit invariant
-    members.sort();
+    // Members must be sorted and unique for the spending limit invariant
+    if let Err(e) = normalize_members(&mut members, |key| *key) {
+        println!("Error: --members: {}", e);
+        std::process::exit(1);
+    }
 
     // Derive a deterministic create_key from "combinator" label
     // This allows us to always find the spending limit PDA for any multisig
@@ -150,9 +189,11 @@ fn main() {
         &[b"combinator"],
         &squads_multisig_program::ID,
     );
-    let (spending_limit_pda, _) = get_spending_limit_pda(&multisig_pda, &create_key, None);
+    let (spending_limit_pda, _) = get_spending_limit_pda(&multisig_pda, &create_key, Some(&program_id()));
+
+    check_duplicate_limits(&client, &multisig_pda, &spending_limit_pda, mint, vault_index, period, force);
 
-    println!("=== Add Spending Limit ({}) ===\n", network.to_uppercase());
+    println!("=== Add Spending Limit ({}) ===\n", network.name().to_uppercase());
     println!("Multisig: {}", multisig_pda);
     println!("Config Authority: {}", config_authority.pubkey());
     println!("Spending Limit PDA: {}", spending_limit_pda);
@@ -204,31 +245,23 @@ fn main() {
     ];
 
     let instruction = Instruction {
-        program_id: squads_multisig_program::ID,
+        program_id: program_id(),
         accounts,
         data: instruction_data.data(),
     };
 
     println!("\nCreating spending limit...");
 
-    let recent_blockhash = client.get_latest_blockhash().expect("Failed to get blockhash");
-    let transaction = Transaction::new_signed_with_payer(
-        &[instruction],
-        Some(&config_authority.pubkey()),
-        &[&config_authority],
-        recent_blockhash,
-    );
-
-    match client.send_and_confirm_transaction(&transaction) {
+    match build_and_send(&client, &[instruction], &config_authority.pubkey(), &[&config_authority], &send_options) {
         Ok(sig) => {
+            record(network, &multisig_pda, &format!("add spending limit {} ({} per {:?}, mint {})", spending_limit_pda, amount, period, mint), &config_authority.pubkey(), &sig);
             println!("\nSpending limit created successfully!");
             println!("Transaction: {}", sig);
             println!("\nSpending Limit Address: {}", spending_limit_pda);
             println!("Create Key: {} (derived from 'combinator' - no need to save)", create_key);
 
-            let cluster_param = if network == "mainnet" { "" } else { "?cluster=devnet" };
             println!("\nView on Solana Explorer:");
-            println!("https://explorer.solana.com/tx/{}{}", sig, cluster_param);
+            println!("{}", network.explorer_tx_url(&sig));
         }
         Err(e) => {
             println!("\nFailed to create spending limit: {}", e);
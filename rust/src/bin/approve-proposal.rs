@@ -4,10 +4,22 @@
 //! Once threshold approvals are reached, the proposal can be executed.
 //!
 //! Usage:
-//!   cargo run --bin approve-proposal -- <multisig_address> <proposal_index> [mainnet]
+//!   cargo run --bin approve-proposal -- <multisig_address> <proposal_index> [options] [mainnet]
+//!
+//! Options:
+//!   --expect-destination <address> - Refuse to approve unless every transfer pays this address
+//!   --expect-amount <amount>       - Refuse to approve unless every transfer moves exactly this amount
+//!   --links                        - Print explorer and Squads app links after approving
+//!   --open                         - Open the Squads app page in the browser (prints the URL if that fails)
+//!
+//! With either `--expect-*` flag, every inner instruction must be a matching transfer or the
+//! creation of an ATA one of them pays into; anything else is printed and the vote is refused.
 //!
 //! Example:
 //!   cargo run --bin approve-proposal -- BJbRt... 1 mainnet
+//!
+//!   # Only approve if the proposal pays exactly 0.1 SOL to DestAddr...
+//!   cargo run --bin approve-proposal -- BJbRt... 1 --expect-destination DestAddr... --expect-amount 100000000
 
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::{
@@ -15,58 +27,81 @@ use solana_sdk::{
     instruction::Instruction,
     pubkey::Pubkey,
     signature::{read_keypair_file, Signer},
-    transaction::Transaction,
 };
 use squads_multisig::anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
-use squads_multisig::pda::get_proposal_pda;
+use squads_multisig::pda::{get_proposal_pda, get_transaction_pda};
 use squads_multisig::squads_multisig_program;
-use squads_multisig::state::{Multisig, Proposal, ProposalStatus};
+use squads_multisig_program::VaultTransaction;
+use squads_rust::addressbook::resolve_pubkey;
+use squads_rust::args::{reject_unknown_flags_or_exit, take_flag, take_flag_value, take_help};
+use squads_rust::audit::record;
+use squads_rust::expect::{decode_actions, expectation_mismatches};
+use squads_rust::format::progress_bar;
+use squads_rust::multisig::{fetch_multisig_or_exit, print_vote_progress};
+use squads_rust::network::{open_in_browser, squads_app_tx_url, Network};
+use squads_rust::program::{program_id, take_program_id_or_exit};
+use squads_rust::proposal::fetch_proposal_state;
+use squads_rust::send::{build_and_send, SendOptions};
 use std::env;
 
-const DEVNET_RPC: &str = "https://api.devnet.solana.com";
-const MAINNET_RPC: &str = "https://api.mainnet-beta.solana.com";
+/// Take `--expect-destination <address>` and `--expect-amount <amount>`
+fn take_expectations(args: &mut Vec<String>) -> Result<(Option<Pubkey>, Option<u64>), String> {
+    let destination = take_flag_value(args, "--expect-destination")?.map(|value| resolve_pubkey(&value));
+    let amount = take_flag_value(args, "--expect-amount")?
+        .map(|value| value.parse().map_err(|_| format!("invalid --expect-amount '{}'", value)))
+        .transpose()?;
+    Ok((destination, amount))
+}
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-
-    if args.len() < 3 {
-        println!("Usage: cargo run --bin approve-proposal -- <multisig_address> <proposal_index> [mainnet]");
+    let mut args: Vec<String> = env::args().collect();
+    let help = take_help(&mut args);
+    take_program_id_or_exit(&mut args);
+    let send_options = SendOptions::take_from_or_exit(&mut args);
+    let links = take_flag(&mut args, "--links");
+    let open = take_flag(&mut args, "--open");
+    let (expect_destination, expect_amount) = take_expectations(&mut args).unwrap_or_else(|e| {
+        println!("Error: {}", e);
+        std::process::exit(1);
+    });
+    reject_unknown_flags_or_exit(&args);
+
+    if help || args.len() < 3 {
+        println!("Usage: cargo run --bin approve-proposal -- <multisig_address> <proposal_index> [options] [mainnet]");
+        println!();
+        println!("Options:");
+        println!("  --expect-destination <address> - Only approve if every transfer pays this address");
+        println!("  --expect-amount <amount>       - Only approve if every transfer moves exactly this amount");
+        println!("  --links                        - Print explorer and Squads app links after approving");
+        println!("  --open                         - Open the Squads app page in the browser (prints the URL if that fails)");
         println!();
         println!("Example:");
         println!("  cargo run --bin approve-proposal -- BJbRt... 1 mainnet");
+        println!("  cargo run --bin approve-proposal -- BJbRt... 1 --expect-destination DestAddr... --expect-amount 100000000");
         return;
     }
 
-    let multisig_pda: Pubkey = args[1].parse().expect("Invalid multisig address");
+    let multisig_pda: Pubkey = resolve_pubkey(&args[1]);
     let proposal_index: u64 = args[2].parse().expect("Invalid proposal index");
-    let network = args.get(3).map(|s| s.as_str()).unwrap_or("devnet");
 
-    let rpc_url = match network {
-        "mainnet" => MAINNET_RPC,
-        _ => DEVNET_RPC,
-    };
+    let network = Network::from_arg(args.get(3).map(|s| s.as_str()).unwrap_or("devnet"));
 
-    let client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
+    let client = RpcClient::new_with_commitment(network.rpc_url(), CommitmentConfig::confirmed());
     let member = read_keypair_file("../member1.json").expect("Failed to read member1.json");
 
     // Derive proposal PDA
-    let (proposal_pda, _) = get_proposal_pda(&multisig_pda, proposal_index, None);
+    let (proposal_pda, _) = get_proposal_pda(&multisig_pda, proposal_index, Some(&program_id()));
 
     // Fetch multisig info
-    let multisig_account = client
-        .get_account(&multisig_pda)
-        .expect("Failed to fetch multisig account");
-    let multisig = Multisig::try_deserialize(&mut multisig_account.data.as_slice())
-        .expect("Failed to deserialize multisig");
+    let multisig = fetch_multisig_or_exit(&client, &multisig_pda);
 
     // Fetch proposal info
-    let proposal_account = client
-        .get_account(&proposal_pda)
-        .expect("Failed to fetch proposal account. Does this proposal exist?");
-    let proposal = Proposal::try_deserialize(&mut proposal_account.data.as_slice())
-        .expect("Failed to deserialize proposal");
+    let (proposal, state) = fetch_proposal_state(&client, &proposal_pda).unwrap_or_else(|e| {
+        println!("Error: {}", e);
+        std::process::exit(1);
+    });
 
-    println!("=== Approve Proposal ({}) ===\n", network.to_uppercase());
+    println!("=== Approve Proposal ({}) ===\n", network.name().to_uppercase());
     println!("Multisig: {}", multisig_pda);
     println!("Member: {}", member.pubkey());
     println!();
@@ -74,23 +109,11 @@ fn main() {
     println!("Proposal Address: {}", proposal_pda);
 
     // Check proposal status
-    let status_str = match &proposal.status {
-        ProposalStatus::Draft { .. } => "Draft",
-        ProposalStatus::Active { .. } => "Active",
-        ProposalStatus::Rejected { .. } => "Rejected",
-        ProposalStatus::Approved { .. } => "Approved",
-        ProposalStatus::Executed { .. } => "Executed",
-        ProposalStatus::Cancelled { .. } => "Cancelled",
-        _ => "Unknown",
-    };
-    println!("Status: {}", status_str);
+    println!("Status: {}", state.label());
     println!();
 
     // Show current votes
-    println!("Current Approvals: {} of {} required", proposal.approved.len(), multisig.threshold);
-    for approver in &proposal.approved {
-        println!("  - {}", approver);
-    }
+    print_vote_progress("Current Approvals", &proposal.approved, &multisig);
 
     // Check if member already approved
     if proposal.approved.contains(&member.pubkey()) {
@@ -99,8 +122,8 @@ fn main() {
     }
 
     // Check if proposal is active
-    if !matches!(proposal.status, ProposalStatus::Active { .. }) {
-        println!("\nError: Proposal is not active. Current status: {}", status_str);
+    if !state.is_votable() {
+        println!("\nError: Proposal is not active. Current status: {}", state.name());
         return;
     }
 
@@ -110,6 +133,50 @@ fn main() {
         return;
     }
 
+    // Verify the proposal does what the member expects before voting for it
+    if expect_destination.is_some() || expect_amount.is_some() {
+        let (transaction_pda, _) = get_transaction_pda(&multisig_pda, proposal_index, Some(&program_id()));
+        let vault_transaction = match client
+            .get_account(&transaction_pda)
+            .ok()
+            .and_then(|account| VaultTransaction::try_deserialize(&mut account.data.as_slice()).ok())
+        {
+            Some(vault_transaction) => vault_transaction,
+            None => {
+                println!("\nError: Could not decode a vault transaction at {}", transaction_pda);
+                println!("Refusing to approve: the proposal's actions cannot be verified.");
+                std::process::exit(1);
+            }
+        };
+
+        let actions = decode_actions(&vault_transaction.message);
+
+        println!("\nDecoded actions:");
+        if actions.is_empty() {
+            println!("  (no instructions)");
+        }
+        for (i, action) in actions.iter().enumerate() {
+            println!("  #{}: {}", i, action.description());
+        }
+
+        let mismatches = expectation_mismatches(&actions, expect_destination, expect_amount);
+        if !mismatches.is_empty() {
+            println!("\nError: Proposal does not match expectations. Refusing to approve.");
+            if let Some(dest) = expect_destination {
+                println!("  Expected destination: {}", dest);
+            }
+            if let Some(amount) = expect_amount {
+                println!("  Expected amount: {}", amount);
+            }
+            for mismatch in &mismatches {
+                println!("  Mismatch: {}", mismatch);
+            }
+            std::process::exit(1);
+        }
+
+        println!("\nEvery instruction is a transfer matching expectations (or ATA creation for one).");
+    }
+
     let accounts = squads_multisig_program::accounts::ProposalVote {
         multisig: multisig_pda,
         proposal: proposal_pda,
@@ -121,41 +188,39 @@ fn main() {
     };
 
     let instruction = Instruction {
-        program_id: squads_multisig_program::ID,
+        program_id: program_id(),
         accounts: accounts.to_account_metas(Some(false)),
         data: data.data(),
     };
 
     println!("\nApproving proposal...");
 
-    let recent_blockhash = client.get_latest_blockhash().expect("Failed to get blockhash");
-    let transaction = Transaction::new_signed_with_payer(
-        &[instruction],
-        Some(&member.pubkey()),
-        &[&member],
-        recent_blockhash,
-    );
-
-    match client.send_and_confirm_transaction(&transaction) {
+    match build_and_send(&client, &[instruction], &member.pubkey(), &[&member], &send_options) {
         Ok(sig) => {
+            record(network, &multisig_pda, &format!("approve proposal {}", proposal_index), &member.pubkey(), &sig);
             let new_approval_count = proposal.approved.len() + 1;
             println!("\nProposal approved successfully!");
             println!("Transaction: {}", sig);
             println!();
-            println!("Approvals: {} of {} required", new_approval_count, multisig.threshold);
+            println!("Approvals: {}", progress_bar(new_approval_count, usize::from(multisig.threshold)));
 
             if new_approval_count >= multisig.threshold as usize {
                 println!("\nThreshold reached! The proposal can now be executed:");
                 println!("  cargo run --bin execute-proposal -- {} {} {}",
-                         multisig_pda, proposal_index, if network == "mainnet" { "mainnet" } else { "" });
+                         multisig_pda, proposal_index, network.cli_arg());
             } else {
                 let remaining = multisig.threshold as usize - new_approval_count;
                 println!("\n{} more approval(s) needed before execution.", remaining);
             }
 
-            let cluster_param = if network == "mainnet" { "" } else { "?cluster=devnet" };
             println!("\nView on Solana Explorer:");
-            println!("https://explorer.solana.com/tx/{}{}", sig, cluster_param);
+            println!("{}", network.explorer_tx_url(&sig));
+            if links {
+                network.print_links(&multisig_pda, Some(proposal_index), Some(&sig));
+            }
+            if open {
+                open_in_browser(&squads_app_tx_url(&multisig_pda, proposal_index));
+            }
         }
         Err(e) => {
             println!("\nFailed to approve proposal: {}", e);
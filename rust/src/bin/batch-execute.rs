@@ -0,0 +1,292 @@
+//! Execute several approved proposals, one transaction each
+//!
+//! Takes either `--all-approved` (every proposal currently in `Approved` status) or a list of
+//! proposal indexes, and executes them in index order. Each proposal gets its own transaction:
+//! inner instructions differ per proposal, so bundling them would let one failure roll back the
+//! rest. A failure is reported and the run moves on to the next proposal.
+//!
+//! Vault transactions and config transactions are both executed; the transaction account's
+//! discriminator decides which instruction to build. Batches are skipped (their inner
+//! transactions execute one by one, see inspect-batch). Vault transactions with irreversible
+//! actions (see execute-proposal) wait for `finalized` before the next proposal runs. Address
+//! lookup tables an inner message uses are fetched, and with `--tx-version 0` the execute
+//! transaction loads accounts from them too.
+//!
+//! Executing a config transaction makes every other unexecuted config transaction stale, so a
+//! second approved config transaction in the same run fails with StaleProposal.
+//!
+//! member1 pays the fees and executes unless `--executor <path>` names another member with
+//! Execute permission.
+//!
+//! Usage:
+//!   cargo run --bin batch-execute -- <multisig_address> (--all-approved | <index>...) [--executor <path>] [mainnet]
+//!
+//! Example:
+//!   cargo run --bin batch-execute -- BJbRt... --all-approved mainnet
+//!   cargo run --bin batch-execute -- BJbRt... 4 5 7
+
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    address_lookup_table::AddressLookupTableAccount,
+    commitment_config::CommitmentConfig,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair, Signature, Signer},
+};
+use squads_multisig::anchor_lang::{AccountDeserialize, Discriminator};
+use squads_multisig::pda::{get_proposal_pda, get_transaction_pda};
+use squads_multisig::squads_multisig_program::VaultTransaction;
+use squads_multisig::state::{Batch, ConfigTransaction, Permission, Proposal};
+use squads_rust::addressbook::resolve_pubkey;
+use squads_rust::args::{reject_unknown_flags_or_exit, take_flag, take_flag_value, take_help};
+use squads_rust::audit::record;
+use squads_rust::multisig::{fetch_accounts, fetch_multisig_or_exit, fetch_proposals};
+use squads_rust::network::Network;
+use squads_rust::program::{program_id, take_program_id_or_exit};
+use squads_rust::proposal::{
+    config_transaction_execute_ix, irreversible_actions, vault_transaction_execute_ix, ProposalState,
+};
+use squads_rust::send::{fetch_lookup_tables, send_and_track, wait_for_finalized, SendOptions, TxVersion, PACKET_DATA_SIZE};
+use std::env;
+
+const DEFAULT_EXECUTOR: &str = "../member1.json";
+
+fn exit_with(message: String) -> ! {
+    println!("Error: {}", message);
+    std::process::exit(1);
+}
+
+/// What happened to one requested proposal
+enum Outcome {
+    Executed(Signature),
+    Failed(String),
+    Skipped(String),
+}
+
+/// The execute instruction for the transaction at `index`
+struct ExecuteIx {
+    instruction: Instruction,
+    /// Lookup tables the inner message loads accounts from; a v0 execute transaction uses them too
+    lookup_tables: Vec<AddressLookupTableAccount>,
+    wait_finalized: bool,
+}
+
+fn build_execute_ix(
+    client: &RpcClient,
+    multisig_pda: &Pubkey,
+    index: u64,
+    executor: &Pubkey,
+) -> Result<ExecuteIx, Outcome> {
+    let (transaction_pda, _) = get_transaction_pda(multisig_pda, index, Some(&program_id()));
+    let account = client
+        .get_account(&transaction_pda)
+        .map_err(|e| Outcome::Failed(format!("failed to fetch transaction {}: {}", transaction_pda, e)))?;
+    let data = account.data.as_slice();
+
+    if data.starts_with(&VaultTransaction::DISCRIMINATOR) {
+        let vault_transaction = VaultTransaction::try_deserialize(&mut &data[..])
+            .map_err(|e| Outcome::Failed(format!("failed to deserialize vault transaction: {}", e)))?;
+        let irreversible = irreversible_actions(&vault_transaction.message);
+        for action in &irreversible {
+            println!("  Irreversible: {} (waiting for finalized)", action);
+        }
+        let table_keys: Vec<Pubkey> =
+            vault_transaction.message.address_table_lookups.iter().map(|lookup| lookup.account_key).collect();
+        let lookup_tables = fetch_lookup_tables(client, &table_keys).map_err(Outcome::Failed)?;
+        let instruction = vault_transaction_execute_ix(multisig_pda, index, &vault_transaction, &lookup_tables, executor)
+            .map_err(Outcome::Failed)?;
+        Ok(ExecuteIx { instruction, lookup_tables, wait_finalized: !irreversible.is_empty() })
+    } else if data.starts_with(&ConfigTransaction::DISCRIMINATOR) {
+        let config_transaction = ConfigTransaction::try_deserialize(&mut &data[..])
+            .map_err(|e| Outcome::Failed(format!("failed to deserialize config transaction: {}", e)))?;
+        println!("  Config transaction ({} action(s))", config_transaction.actions.len());
+        Ok(ExecuteIx {
+            instruction: config_transaction_execute_ix(multisig_pda, index, &config_transaction, executor),
+            lookup_tables: Vec::new(),
+            wait_finalized: false,
+        })
+    } else if data.starts_with(&Batch::DISCRIMINATOR) {
+        Err(Outcome::Skipped("batch transactions execute one inner transaction at a time; see inspect-batch".to_string()))
+    } else {
+        Err(Outcome::Skipped("unrecognized transaction account".to_string()))
+    }
+}
+
+/// Execute the approved proposal at `index` in its own transaction
+fn execute_one(
+    client: &RpcClient,
+    multisig_pda: &Pubkey,
+    index: u64,
+    payer: &Keypair,
+    executor: &Keypair,
+    send_options: &SendOptions,
+) -> Outcome {
+    let ExecuteIx { instruction, mut lookup_tables, wait_finalized } =
+        match build_execute_ix(client, multisig_pda, index, &executor.pubkey()) {
+        Ok(built) => built,
+        Err(outcome) => return outcome,
+    };
+
+    let (recent_blockhash, last_valid_block_height) =
+        match client.get_latest_blockhash_with_commitment(client.commitment()) {
+            Ok(blockhash) => blockhash,
+            Err(e) => return Outcome::Failed(format!("failed to get blockhash: {}", e)),
+        };
+    let signers: Vec<&Keypair> = if executor.pubkey() == payer.pubkey() {
+        vec![payer]
+    } else {
+        vec![payer, executor]
+    };
+    if send_options.tx_version != TxVersion::V0 {
+        lookup_tables.clear();
+    }
+    let transaction = match send_options.build_transaction_with_lookup_tables(
+        &[instruction],
+        &payer.pubkey(),
+        &signers,
+        recent_blockhash,
+        &lookup_tables,
+    ) {
+        Ok(transaction) => transaction,
+        Err(e) => return Outcome::Failed(e),
+    };
+    let size = 1 + 64 * transaction.signatures.len() + transaction.message.serialize().len();
+    if size > PACKET_DATA_SIZE {
+        return Outcome::Failed(format!(
+            "execute transaction is {} of {} bytes; use --tx-version 0, or execute-proposal with --lookup-table",
+            size, PACKET_DATA_SIZE
+        ));
+    }
+
+    match send_and_track(client, &transaction, send_options, last_valid_block_height) {
+        Ok(landed) if wait_finalized => match wait_for_finalized(client, &landed.signature) {
+            Ok(_) => Outcome::Executed(landed.signature),
+            Err(e) => Outcome::Failed(format!("landed but not finalized: {}", e)),
+        },
+        Ok(landed) => Outcome::Executed(landed.signature),
+        Err(e) => Outcome::Failed(e.to_string()),
+    }
+}
+
+fn main() {
+    let mut args: Vec<String> = env::args().collect();
+    let help = take_help(&mut args);
+    take_program_id_or_exit(&mut args);
+    let send_options = SendOptions::take_from_or_exit(&mut args);
+    let all_approved = take_flag(&mut args, "--all-approved");
+    let executor_path = take_flag_value(&mut args, "--executor").unwrap_or_else(|e| exit_with(e));
+
+    let network = match args.last() {
+        Some(arg) if args.len() > 2 && Network::is_network_arg(arg) => Network::from_arg(&args.pop().unwrap()),
+        _ => Network::Devnet,
+    };
+    reject_unknown_flags_or_exit(&args);
+
+    if help || args.len() < 2 || (all_approved == (args.len() > 2)) {
+        println!("Execute several approved proposals, one transaction each, continuing past failures");
+        println!();
+        println!("Usage:");
+        println!("  cargo run --bin batch-execute -- <multisig_address> (--all-approved | <index>...) [--executor <path>] [mainnet]");
+        println!();
+        println!("Options:");
+        println!("  --all-approved     - Execute every proposal in Approved status (instead of listing indexes)");
+        println!("  --executor <path>  - Member keypair with Execute permission (default: member1, who always pays the fee)");
+        return;
+    }
+
+    let multisig_pda = resolve_pubkey(&args[1]);
+    let requested: Vec<u64> = args[2..]
+        .iter()
+        .map(|value| value.parse().unwrap_or_else(|_| exit_with(format!("invalid proposal index '{}'", value))))
+        .collect();
+
+    let client = RpcClient::new_with_commitment(network.rpc_url(), CommitmentConfig::confirmed());
+    let payer = read_keypair_file(DEFAULT_EXECUTOR).expect("Failed to read member1.json");
+    let executor_keypair: Option<Keypair> = executor_path.map(|path| {
+        read_keypair_file(&path).unwrap_or_else(|e| exit_with(format!("failed to read executor keypair {}: {}", path, e)))
+    });
+    let executor = executor_keypair.as_ref().unwrap_or(&payer);
+
+    let multisig = fetch_multisig_or_exit(&client, &multisig_pda);
+    if !multisig.member_has_permission(executor.pubkey(), Permission::Execute) {
+        exit_with(format!("executor {} does not have Execute permission on this multisig", executor.pubkey()));
+    }
+
+    // (index, proposal status) for every requested index; None when the proposal doesn't exist
+    let proposals: Vec<(u64, Option<Proposal>)> = if all_approved {
+        fetch_proposals(&client, &multisig_pda, &multisig)
+            .unwrap_or_else(|e| exit_with(e))
+            .into_iter()
+            .filter(|(_, _, proposal)| ProposalState::of(proposal).is_executable())
+            .map(|(index, _, proposal)| (index, Some(proposal)))
+            .collect()
+    } else {
+        let mut indexes = requested.clone();
+        indexes.sort_unstable();
+        indexes.dedup();
+        let keys: Vec<Pubkey> = indexes.iter().map(|&i| get_proposal_pda(&multisig_pda, i, Some(&program_id())).0).collect();
+        let accounts = fetch_accounts(&client, &keys)
+            .unwrap_or_else(|e| exit_with(format!("failed to fetch proposals: {}", e)));
+        indexes
+            .into_iter()
+            .zip(accounts)
+            .map(|(index, account)| {
+                let proposal = account.and_then(|a| Proposal::try_deserialize(&mut a.data.as_slice()).ok());
+                (index, proposal)
+            })
+            .collect()
+    };
+
+    println!("=== Batch Execute ({}) ===\n", network.name().to_uppercase());
+    println!("Multisig: {}", multisig_pda);
+    println!("Executor: {}", executor.pubkey());
+    if executor.pubkey() != payer.pubkey() {
+        println!("Fee Payer: {}", payer.pubkey());
+    }
+    if proposals.is_empty() {
+        println!("\nNo approved proposals to execute.");
+        return;
+    }
+
+    let mut outcomes = Vec::new();
+    for (index, proposal) in &proposals {
+        println!("\nProposal {}:", index);
+        let outcome = match proposal {
+            None => Outcome::Skipped("proposal not found".to_string()),
+            Some(proposal) => match ProposalState::of(proposal) {
+                state if !state.is_executable() => {
+                    Outcome::Skipped(format!("status is {}, not Approved", state.name()))
+                }
+                _ => execute_one(&client, &multisig_pda, *index, &payer, executor, &send_options),
+            },
+        };
+        match &outcome {
+            Outcome::Executed(signature) => {
+                record(network, &multisig_pda, &format!("execute proposal {}", index), &executor.pubkey(), signature);
+                println!("  Executed: {}", network.explorer_tx_url(signature));
+            }
+            Outcome::Failed(e) => println!("  Failed: {}", e),
+            Outcome::Skipped(reason) => println!("  Skipped: {}", reason),
+        }
+        outcomes.push((*index, outcome));
+    }
+
+    let count = |f: fn(&Outcome) -> bool| outcomes.iter().filter(|(_, o)| f(o)).count();
+    let executed = count(|o| matches!(o, Outcome::Executed(_)));
+    let failed = count(|o| matches!(o, Outcome::Failed(_)));
+    let skipped = count(|o| matches!(o, Outcome::Skipped(_)));
+
+    println!("\n=== Summary ===");
+    for (index, outcome) in &outcomes {
+        let result = match outcome {
+            Outcome::Executed(signature) => format!("executed ({})", signature),
+            Outcome::Failed(e) => format!("FAILED: {}", e),
+            Outcome::Skipped(reason) => format!("skipped: {}", reason),
+        };
+        println!("  {}: {}", index, result);
+    }
+    println!("Executed: {}, Failed: {}, Skipped: {}", executed, failed, skipped);
+    if failed > 0 {
+        std::process::exit(1);
+    }
+}
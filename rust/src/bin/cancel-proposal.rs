@@ -3,70 +3,84 @@
 //! Members can vote to cancel an approved proposal before it's executed.
 //! Once enough members vote to cancel (reaching threshold), the proposal is cancelled.
 //!
+//! Cancelling is only meaningful before someone executes. With a time lock, execution is blocked
+//! for `time_lock` seconds after approval, so that is the cancel window; the script shows it
+//! against the cluster clock and warns when it has passed (or there is no time lock), since the
+//! cancel then races any executor.
+//!
 //! Usage:
-//!   cargo run --bin cancel-proposal -- <multisig_address> <proposal_index> [mainnet]
+//!   cargo run --bin cancel-proposal -- <multisig_address> <proposal_index> [--links] [--open] [mainnet]
+//!
+//! `--links` prints explorer and Squads app links for the multisig, proposal and transaction, and
+//! `--open` opens the proposal in the Squads app.
 //!
 //! Example:
 //!   cargo run --bin cancel-proposal -- BJbRt... 1 mainnet
 
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::{
+    account::from_account,
+    clock::Clock,
     commitment_config::CommitmentConfig,
     instruction::Instruction,
     pubkey::Pubkey,
     signature::{read_keypair_file, Signer},
-    transaction::Transaction,
+    sysvar,
 };
-use squads_multisig::anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use squads_multisig::anchor_lang::{InstructionData, ToAccountMetas};
 use squads_multisig::pda::get_proposal_pda;
 use squads_multisig::squads_multisig_program;
-use squads_multisig::state::{Multisig, Proposal, ProposalStatus};
+use squads_rust::addressbook::resolve_pubkey;
+use squads_rust::args::{reject_unknown_flags_or_exit, take_flag, take_help};
+use squads_rust::audit::record;
+use squads_rust::format::{format_unix_timestamp, progress_bar};
+use squads_rust::multisig::{fetch_multisig_or_exit, print_vote_progress};
+use squads_rust::network::{open_in_browser, squads_app_tx_url, Network};
+use squads_rust::program::{program_id, take_program_id_or_exit};
+use squads_rust::proposal::{fetch_proposal_state, ProposalState};
+use squads_rust::send::{build_and_send, SendOptions};
 use std::env;
 
-const DEVNET_RPC: &str = "https://api.devnet.solana.com";
-const MAINNET_RPC: &str = "https://api.mainnet-beta.solana.com";
-
 fn main() {
-    let args: Vec<String> = env::args().collect();
-
-    if args.len() < 3 {
-        println!("Usage: cargo run --bin cancel-proposal -- <multisig_address> <proposal_index> [mainnet]");
+    let mut args: Vec<String> = env::args().collect();
+    let help = take_help(&mut args);
+    take_program_id_or_exit(&mut args);
+    let send_options = SendOptions::take_from_or_exit(&mut args);
+    let links = take_flag(&mut args, "--links");
+    let open = take_flag(&mut args, "--open");
+    reject_unknown_flags_or_exit(&args);
+
+    if help || args.len() < 3 {
+        println!("Usage: cargo run --bin cancel-proposal -- <multisig_address> <proposal_index> [--links] [--open] [mainnet]");
+        println!();
+        println!("  --links  Print explorer and Squads app links after voting");
+        println!("  --open   Open the Squads app page in the browser (prints the URL if that fails)");
         println!();
         println!("Example:");
         println!("  cargo run --bin cancel-proposal -- BJbRt... 1 mainnet");
         return;
     }
 
-    let multisig_pda: Pubkey = args[1].parse().expect("Invalid multisig address");
+    let multisig_pda: Pubkey = resolve_pubkey(&args[1]);
     let proposal_index: u64 = args[2].parse().expect("Invalid proposal index");
-    let network = args.get(3).map(|s| s.as_str()).unwrap_or("devnet");
+    let network = Network::from_arg(args.get(3).map(|s| s.as_str()).unwrap_or("devnet"));
 
-    let rpc_url = match network {
-        "mainnet" => MAINNET_RPC,
-        _ => DEVNET_RPC,
-    };
-
-    let client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
+    let client = RpcClient::new_with_commitment(network.rpc_url(), CommitmentConfig::confirmed());
     let member = read_keypair_file("../member1.json").expect("Failed to read member1.json");
 
     // Derive proposal PDA
-    let (proposal_pda, _) = get_proposal_pda(&multisig_pda, proposal_index, None);
+    let (proposal_pda, _) = get_proposal_pda(&multisig_pda, proposal_index, Some(&program_id()));
 
     // Fetch multisig info
-    let multisig_account = client
-        .get_account(&multisig_pda)
-        .expect("Failed to fetch multisig account");
-    let multisig = Multisig::try_deserialize(&mut multisig_account.data.as_slice())
-        .expect("Failed to deserialize multisig");
+    let multisig = fetch_multisig_or_exit(&client, &multisig_pda);
 
     // Fetch proposal info
-    let proposal_account = client
-        .get_account(&proposal_pda)
-        .expect("Failed to fetch proposal account. Does this proposal exist?");
-    let proposal = Proposal::try_deserialize(&mut proposal_account.data.as_slice())
-        .expect("Failed to deserialize proposal");
+    let (proposal, state) = fetch_proposal_state(&client, &proposal_pda).unwrap_or_else(|e| {
+        println!("Error: {}", e);
+        std::process::exit(1);
+    });
 
-    println!("=== Cancel Proposal ({}) ===\n", network.to_uppercase());
+    println!("=== Cancel Proposal ({}) ===\n", network.name().to_uppercase());
     println!("Multisig: {}", multisig_pda);
     println!("Member: {}", member.pubkey());
     println!();
@@ -74,23 +88,11 @@ fn main() {
     println!("Proposal Address: {}", proposal_pda);
 
     // Check proposal status
-    let status_str = match &proposal.status {
-        ProposalStatus::Draft { .. } => "Draft",
-        ProposalStatus::Active { .. } => "Active",
-        ProposalStatus::Rejected { .. } => "Rejected",
-        ProposalStatus::Approved { .. } => "Approved",
-        ProposalStatus::Executed { .. } => "Executed",
-        ProposalStatus::Cancelled { .. } => "Cancelled",
-        _ => "Unknown",
-    };
-    println!("Status: {}", status_str);
+    println!("Status: {}", state.label());
     println!();
 
     // Show current cancel votes
-    println!("Current Cancel Votes: {} of {} required", proposal.cancelled.len(), multisig.threshold);
-    for canceller in &proposal.cancelled {
-        println!("  - {}", canceller);
-    }
+    print_vote_progress("Current Cancel Votes", &proposal.cancelled, &multisig);
 
     // Check if member already voted to cancel
     if proposal.cancelled.contains(&member.pubkey()) {
@@ -98,12 +100,40 @@ fn main() {
         return;
     }
 
-    // Check if proposal can be cancelled (must be Approved)
-    if !matches!(proposal.status, ProposalStatus::Approved { .. }) {
-        println!("\nError: Only approved proposals can be cancelled. Current status: {}", status_str);
+    // Check if proposal can be cancelled (must be Approved, i.e. executable)
+    if !state.is_executable() {
+        println!("\nError: Only approved proposals can be cancelled. Current status: {}", state.name());
         return;
     }
 
+    // Explain the cancel window: execution is blocked until time_lock seconds after approval
+    if let ProposalState::Approved { timestamp: approved_at } = state {
+        let clock_account = client
+            .get_account(&sysvar::clock::ID)
+            .expect("Failed to fetch clock sysvar");
+        let clock: Clock = from_account(&clock_account).expect("Failed to deserialize clock sysvar");
+        let executable_at = approved_at.saturating_add(i64::from(multisig.time_lock));
+
+        println!();
+        println!("Time Lock: {} seconds", multisig.time_lock);
+        if multisig.time_lock == 0 {
+            println!("WARNING: No time lock, so the proposal can be executed at any moment.");
+            println!("Cancelling races any member with Execute permission.");
+        } else if clock.unix_timestamp < executable_at {
+            println!(
+                "Cancel window: open until {} ({} seconds left); execution is blocked until then.",
+                format_unix_timestamp(executable_at),
+                executable_at - clock.unix_timestamp
+            );
+        } else {
+            println!(
+                "WARNING: The time lock elapsed at {}, so the proposal can be executed at any moment.",
+                format_unix_timestamp(executable_at)
+            );
+            println!("Cancelling races any member with Execute permission.");
+        }
+    }
+
     // Check if member is part of multisig
     if multisig.is_member(member.pubkey()).is_none() {
         println!("\nError: {} is not a member of this multisig", member.pubkey());
@@ -121,28 +151,21 @@ fn main() {
     };
 
     let instruction = Instruction {
-        program_id: squads_multisig_program::ID,
+        program_id: program_id(),
         accounts: accounts.to_account_metas(Some(false)),
         data: data.data(),
     };
 
     println!("\nVoting to cancel proposal...");
 
-    let recent_blockhash = client.get_latest_blockhash().expect("Failed to get blockhash");
-    let transaction = Transaction::new_signed_with_payer(
-        &[instruction],
-        Some(&member.pubkey()),
-        &[&member],
-        recent_blockhash,
-    );
-
-    match client.send_and_confirm_transaction(&transaction) {
+    match build_and_send(&client, &[instruction], &member.pubkey(), &[&member], &send_options) {
         Ok(sig) => {
+            record(network, &multisig_pda, &format!("cancel proposal {}", proposal_index), &member.pubkey(), &sig);
             let new_cancel_count = proposal.cancelled.len() + 1;
             println!("\nCancel vote recorded!");
             println!("Transaction: {}", sig);
             println!();
-            println!("Cancel Votes: {} of {} required", new_cancel_count, multisig.threshold);
+            println!("Cancel Votes: {}", progress_bar(new_cancel_count, usize::from(multisig.threshold)));
 
             if new_cancel_count >= multisig.threshold as usize {
                 println!("\nThreshold reached! The proposal has been cancelled.");
@@ -151,9 +174,14 @@ fn main() {
                 println!("\n{} more cancel vote(s) needed to cancel the proposal.", remaining);
             }
 
-            let cluster_param = if network == "mainnet" { "" } else { "?cluster=devnet" };
             println!("\nView on Solana Explorer:");
-            println!("https://explorer.solana.com/tx/{}{}", sig, cluster_param);
+            println!("{}", network.explorer_tx_url(&sig));
+            if links {
+                network.print_links(&multisig_pda, Some(proposal_index), Some(&sig));
+            }
+            if open {
+                open_in_browser(&squads_app_tx_url(&multisig_pda, proposal_index));
+            }
         }
         Err(e) => {
             println!("\nFailed to vote cancel: {}", e);
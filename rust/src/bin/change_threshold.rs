@@ -4,41 +4,83 @@ use solana_sdk::{
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
     signature::{read_keypair_file, Signer},
-    transaction::Transaction,
 };
 use squads_multisig::anchor_lang::InstructionData;
 use squads_multisig::squads_multisig_program;
+use squads_multisig::state::Permission;
+use squads_rust::addressbook::resolve_pubkey;
+use squads_rust::args::{reject_unknown_flags_or_exit, take_help};
+use squads_rust::audit::record;
+use squads_rust::multisig::fetch_multisig_or_exit;
+use squads_rust::network::Network;
+use squads_rust::permissions::PermissionsExt;
+use squads_rust::program::{program_id, take_program_id_or_exit};
+use squads_rust::send::{build_and_send, SendOptions};
 use std::env;
 
-const DEVNET_RPC: &str = "https://api.devnet.solana.com";
-const MAINNET_RPC: &str = "https://api.mainnet-beta.solana.com";
-
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    let mut args: Vec<String> = env::args().collect();
+    let help = take_help(&mut args);
+    take_program_id_or_exit(&mut args);
+    let send_options = SendOptions::take_from_or_exit(&mut args);
+    reject_unknown_flags_or_exit(&args);
 
-    if args.len() < 3 {
+    if help || args.len() < 3 {
         println!("Usage: cargo run --bin change_threshold -- <multisig_address> <new_threshold> [mainnet]");
         println!("Example: cargo run --bin change_threshold -- BJbRtXM8wecvRrJNbbpNLfuG8FTSoU6zPYW1NFrMH6Q3 2 mainnet");
         return;
     }
 
-    let multisig_pda: Pubkey = args[1].parse().expect("Invalid multisig address");
+    let multisig_pda: Pubkey = resolve_pubkey(&args[1]);
     let new_threshold: u16 = args[2].parse().expect("Invalid threshold (must be a number)");
-    let network = args.get(3).map(|s| s.as_str()).unwrap_or("devnet");
-
-    let rpc_url = match network {
-        "mainnet" => MAINNET_RPC,
-        _ => DEVNET_RPC,
-    };
+    let network = Network::from_arg(args.get(3).map(|s| s.as_str()).unwrap_or("devnet"));
 
-    let client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
+    let client = RpcClient::new_with_commitment(network.rpc_url(), CommitmentConfig::confirmed());
     let config_authority = read_keypair_file("../member1.json").expect("Failed to read member1.json");
 
-    println!("=== Change Multisig Threshold ({}) ===\n", network.to_uppercase());
+    println!("=== Change Multisig Threshold ({}) ===\n", network.name().to_uppercase());
     println!("Multisig: {}", multisig_pda);
     println!("Config Authority: {}", config_authority.pubkey());
+
+    let multisig = fetch_multisig_or_exit(&client, &multisig_pda);
+    let member_count = multisig.members.len();
+    let voters = multisig
+        .members
+        .iter()
+        .filter(|m| m.permissions.contains(Permission::Vote))
+        .count();
+
+    println!("Current Threshold: {}", multisig.threshold);
+    println!("Members: {} ({} with Vote permission)", member_count, voters);
     println!("New Threshold: {}", new_threshold);
 
+    // Check locally what the program would reject with an opaque error
+    if new_threshold == 0 {
+        println!("\nError: Threshold must be at least 1");
+        std::process::exit(1);
+    }
+    if usize::from(new_threshold) > member_count {
+        println!("\nError: Threshold {} exceeds the member count {}", new_threshold, member_count);
+        std::process::exit(1);
+    }
+    if usize::from(new_threshold) > voters {
+        println!(
+            "\nError: Threshold {} exceeds the {} member(s) with Vote permission; no proposal could pass",
+            new_threshold, voters
+        );
+        std::process::exit(1);
+    }
+    if new_threshold == multisig.threshold {
+        println!("\nThreshold is already {}. Nothing to do.", new_threshold);
+        return;
+    }
+    if new_threshold < multisig.threshold {
+        println!(
+            "\nWARNING: Lowering the threshold from {} to {} means fewer approvals are needed to move funds.",
+            multisig.threshold, new_threshold
+        );
+    }
+
     let instruction_data = squads_multisig_program::instruction::MultisigChangeThreshold {
         args: squads_multisig_program::MultisigChangeThresholdArgs {
             new_threshold,
@@ -49,34 +91,26 @@ fn main() {
     let accounts = vec![
         AccountMeta::new(multisig_pda, false),
         AccountMeta::new_readonly(config_authority.pubkey(), true),
-        AccountMeta::new_readonly(squads_multisig_program::ID, false), // rent_payer (None)
-        AccountMeta::new_readonly(squads_multisig_program::ID, false), // system_program (None)
+        AccountMeta::new_readonly(program_id(), false), // rent_payer (None)
+        AccountMeta::new_readonly(program_id(), false), // system_program (None)
     ];
 
     let instruction = Instruction {
-        program_id: squads_multisig_program::ID,
+        program_id: program_id(),
         accounts,
         data: instruction_data.data(),
     };
 
     println!("\nChanging threshold...");
 
-    let recent_blockhash = client.get_latest_blockhash().expect("Failed to get blockhash");
-    let transaction = Transaction::new_signed_with_payer(
-        &[instruction],
-        Some(&config_authority.pubkey()),
-        &[&config_authority],
-        recent_blockhash,
-    );
-
-    match client.send_and_confirm_transaction(&transaction) {
+    match build_and_send(&client, &[instruction], &config_authority.pubkey(), &[&config_authority], &send_options) {
         Ok(sig) => {
+            record(network, &multisig_pda, &format!("change threshold to {}", new_threshold), &config_authority.pubkey(), &sig);
             println!("\nThreshold changed successfully!");
             println!("Transaction: {}", sig);
 
-            let cluster_param = if network == "mainnet" { "" } else { "?cluster=devnet" };
             println!("\nView on Solana Explorer:");
-            println!("https://explorer.solana.com/tx/{}{}", sig, cluster_param);
+            println!("{}", network.explorer_tx_url(&sig));
         }
         Err(e) => {
             println!("\nFailed to change threshold: {}", e);
@@ -0,0 +1,230 @@
+//! Fast CI smoke checks against a local validator
+//!
+//! `--self-test` round-trips one proposal through the production instruction builders, so an
+//! account-ordering regression in proposal.rs fails CI without the full integration suite:
+//! 1. Airdrop to a throwaway member
+//! 2. Create a 1-of-1 multisig
+//! 3. Fund vault 0
+//! 4. Create a 1-lamport transfer to a fresh address, propose and approve it
+//! 5. Execute it
+//! 6. Assert the proposal is Executed and the destination gained 1 lamport
+//!
+//! A 1-lamport account is below the rent-exempt minimum and the runtime rejects it, so the member
+//! first funds the destination with that minimum; the vault's transfer is still exactly 1 lamport.
+//!
+//! Each step prints PASS or FAIL; the first failure stops the run and exits 1. Like demo, it
+//! requires the Squads program and an initialized program config (see init-program-config).
+//!
+//! Usage:
+//!   cargo run --bin ci-check -- --self-test [--rpc-url <url>] [--program-id <address>]
+//!
+//! Options:
+//!   --rpc-url <url>   - Validator to test against (default: the localnet RPC). Mainnet is refused.
+
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction,
+    system_program,
+};
+use squads_multisig::anchor_lang::{AccountDeserialize, AnchorSerialize};
+use squads_multisig::client::{multisig_create_v2, MultisigCreateAccountsV2, MultisigCreateArgsV2};
+use squads_multisig::pda::{
+    get_multisig_pda, get_program_config_pda, get_proposal_pda, get_transaction_pda, get_vault_pda,
+};
+use squads_multisig::squads_multisig_program::{self, TransactionMessage};
+use squads_multisig::state::{Member, Permissions};
+use squads_multisig::vault_transaction::VaultTransactionMessageExt;
+use squads_rust::airdrop::airdrop;
+use squads_rust::args::{reject_unknown_flags_or_exit, take_flag, take_flag_value, take_help};
+use squads_rust::network::Network;
+use squads_rust::permissions::PermissionsExt;
+use squads_rust::program::{program_id, take_program_id_or_exit};
+use squads_rust::proposal::{
+    fetch_proposal_state, proposal_approve_ix, proposal_create_ix, vault_transaction_create_ix,
+    vault_transaction_execute_ix, ProposalState,
+};
+use squads_rust::send::{build_and_send, SendOptions};
+use std::env;
+
+const AIRDROP_LAMPORTS: u64 = 1_000_000_000;
+const VAULT_FUNDING_LAMPORTS: u64 = 10_000_000;
+const TRANSFER_LAMPORTS: u64 = 1;
+const VAULT_INDEX: u8 = 0;
+const TRANSACTION_INDEX: u64 = 1;
+
+/// Print PASS or FAIL for `step`; a failure ends the run
+fn check<T>(step: &str, result: Result<T, String>) -> T {
+    match result {
+        Ok(value) => {
+            println!("[PASS] {}", step);
+            value
+        }
+        Err(e) => {
+            println!("[FAIL] {}: {}", step, e);
+            println!("\nSelf-test FAILED");
+            std::process::exit(1);
+        }
+    }
+}
+
+fn send(client: &RpcClient, instructions: &[Instruction], payer: &Keypair, signers: &[&Keypair]) -> Result<(), String> {
+    build_and_send(client, instructions, &payer.pubkey(), signers, &SendOptions::default())
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+fn self_test(client: &RpcClient, network: Network) {
+    // check_program skips the canonical id, but a validator without Squads loaded must fail here
+    check(
+        "Squads program is deployed",
+        client
+            .get_account_with_commitment(&program_id(), client.commitment())
+            .map_err(|e| format!("failed to fetch program {}: {}", program_id(), e))
+            .and_then(|response| match response.value {
+                Some(account) if account.executable => Ok(()),
+                Some(_) => Err(format!("{} is not an executable program", program_id())),
+                None => Err(format!("{} is not loaded on this validator", program_id())),
+            }),
+    );
+
+    let member = Keypair::new();
+    check(
+        "Airdrop to a throwaway member",
+        airdrop(client, network, &member.pubkey(), AIRDROP_LAMPORTS),
+    );
+
+    let create_key = Keypair::new();
+    let (multisig_pda, _) = get_multisig_pda(&create_key.pubkey(), Some(&program_id()));
+    let (program_config_pda, _) = get_program_config_pda(Some(&program_id()));
+    let treasury = check("Read the program config treasury", network.squads_treasury(client));
+    let create_ix = multisig_create_v2(
+        MultisigCreateAccountsV2 {
+            program_config: program_config_pda,
+            treasury,
+            multisig: multisig_pda,
+            create_key: create_key.pubkey(),
+            creator: member.pubkey(),
+            system_program: system_program::ID,
+        },
+        MultisigCreateArgsV2 {
+            config_authority: None,
+            threshold: 1,
+            members: vec![Member {
+                key: member.pubkey(),
+                permissions: Permissions::all(),
+            }],
+            time_lock: 0,
+            rent_collector: None,
+            memo: None,
+        },
+        Some(program_id()),
+    );
+    check(
+        "Create a 1-of-1 multisig",
+        send(client, &[create_ix], &member, &[&member, &create_key]),
+    );
+
+    let (vault_pda, _) = get_vault_pda(&multisig_pda, VAULT_INDEX, Some(&program_id()));
+    let fund_ix = system_instruction::transfer(&member.pubkey(), &vault_pda, VAULT_FUNDING_LAMPORTS);
+    check("Fund vault 0", send(client, &[fund_ix], &member, &[&member]));
+
+    let destination = Pubkey::new_unique();
+    let rent_exempt_minimum = check(
+        "Read the rent-exempt minimum",
+        client
+            .get_minimum_balance_for_rent_exemption(0)
+            .map_err(|e| format!("failed to fetch the rent-exempt minimum: {}", e)),
+    );
+    let open_ix = system_instruction::transfer(&member.pubkey(), &destination, rent_exempt_minimum);
+    check("Open a fresh destination", send(client, &[open_ix], &member, &[&member]));
+
+    let transfer_ix = system_instruction::transfer(&vault_pda, &destination, TRANSFER_LAMPORTS);
+    let message = check(
+        "Compile the transfer message",
+        TransactionMessage::try_compile(&vault_pda, &[transfer_ix], &[])
+            .map_err(|e| e.to_string())
+            .and_then(|message| message.try_to_vec().map_err(|e| e.to_string())),
+    );
+    let propose_ixs = [
+        vault_transaction_create_ix(&multisig_pda, TRANSACTION_INDEX, &member.pubkey(), VAULT_INDEX, 0, message),
+        proposal_create_ix(&multisig_pda, TRANSACTION_INDEX, &member.pubkey()),
+        proposal_approve_ix(&multisig_pda, TRANSACTION_INDEX, &member.pubkey()),
+    ];
+    check(
+        "Propose and approve a 1-lamport transfer",
+        send(client, &propose_ixs, &member, &[&member]),
+    );
+
+    let (transaction_pda, _) = get_transaction_pda(&multisig_pda, TRANSACTION_INDEX, Some(&program_id()));
+    let vault_transaction = check(
+        "Fetch the vault transaction",
+        client
+            .get_account(&transaction_pda)
+            .map_err(|e| format!("failed to fetch {}: {}", transaction_pda, e))
+            .and_then(|account| {
+                squads_multisig_program::VaultTransaction::try_deserialize(&mut account.data.as_slice())
+                    .map_err(|e| format!("failed to deserialize {}: {}", transaction_pda, e))
+            }),
+    );
+    let execute_ix = check(
+        "Build the execute instruction",
+        vault_transaction_execute_ix(&multisig_pda, TRANSACTION_INDEX, &vault_transaction, &[], &member.pubkey()),
+    );
+    check("Execute the proposal", send(client, &[execute_ix], &member, &[&member]));
+
+    let (proposal_pda, _) = get_proposal_pda(&multisig_pda, TRANSACTION_INDEX, Some(&program_id()));
+    check(
+        "Proposal is Executed",
+        fetch_proposal_state(client, &proposal_pda).and_then(|(_, state)| match state {
+            ProposalState::Executed { .. } => Ok(()),
+            other => Err(format!("status is {}", other.label())),
+        }),
+    );
+    check(
+        "Destination received 1 lamport",
+        client
+            .get_balance(&destination)
+            .map_err(|e| format!("failed to fetch balance of {}: {}", destination, e))
+            .and_then(|balance| match balance.checked_sub(rent_exempt_minimum) {
+                Some(TRANSFER_LAMPORTS) => Ok(()),
+                _ => Err(format!("balance is {} lamports, expected {}", balance, rent_exempt_minimum + TRANSFER_LAMPORTS)),
+            }),
+    );
+
+    println!("\nSelf-test PASSED (multisig {})", multisig_pda);
+}
+
+fn main() {
+    let mut args: Vec<String> = env::args().collect();
+    let help = take_help(&mut args);
+    take_program_id_or_exit(&mut args);
+    let self_test_requested = take_flag(&mut args, "--self-test");
+    let rpc_url = take_flag_value(&mut args, "--rpc-url").unwrap_or_else(|e| {
+        println!("Error: {}", e);
+        std::process::exit(1);
+    });
+    reject_unknown_flags_or_exit(&args);
+    if help {
+        println!("Usage: cargo run --bin ci-check -- --self-test [--rpc-url <url>] [--program-id <address>]");
+        return;
+    }
+    if !self_test_requested || args.len() > 1 {
+        println!("Usage: cargo run --bin ci-check -- --self-test [--rpc-url <url>] [--program-id <address>]");
+        std::process::exit(1);
+    }
+
+    let rpc_url = rpc_url.unwrap_or_else(|| Network::Localnet.rpc_url().to_string());
+    let network = Network::from_rpc_url(&rpc_url).unwrap_or(Network::Localnet);
+    if network != Network::Localnet {
+        println!("Error: --self-test creates throwaway accounts; point --rpc-url at a local validator, not {}", network.name());
+        std::process::exit(1);
+    }
+    let client = RpcClient::new_with_commitment(rpc_url.clone(), CommitmentConfig::confirmed());
+
+    println!("=== Squads self-test ({}) ===\n", rpc_url);
+    self_test(&client, network);
+}
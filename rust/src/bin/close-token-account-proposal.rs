@@ -0,0 +1,270 @@
+//! Create a proposal to close an empty vault-owned token account and reclaim its rent
+//!
+//! Usage:
+//!   cargo run --bin close-token-account-proposal -- <multisig_address> <token_account> [--destination <address>] [--vault <index>] [--dump-message] [mainnet]
+//!
+//! The reclaimed rent goes to `--destination` (default: the vault itself). The token account
+//! must be owned by the vault and hold a zero balance, since the token program refuses to close
+//! accounts that still hold tokens.
+//!
+//! Examples:
+//!   # Reclaim an empty ATA's rent into the vault
+//!   cargo run --bin close-token-account-proposal -- BJbRt... TokenAcct... mainnet
+//!
+//!   # Send the reclaimed rent elsewhere
+//!   cargo run --bin close-token-account-proposal -- BJbRt... TokenAcct... --destination Dest...
+
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::Instruction,
+    program_option::COption,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Signer},
+    system_program,
+};
+use spl_token_2022::instruction::close_account;
+use squads_multisig::anchor_lang::{AnchorSerialize, InstructionData, ToAccountMetas};
+use squads_multisig::pda::{get_proposal_pda, get_transaction_pda, get_vault_pda};
+use squads_multisig::squads_multisig_program;
+use squads_multisig::vault_transaction::VaultTransactionMessageExt;
+use squads_multisig_program::TransactionMessage;
+use squads_rust::addressbook::resolve_pubkey;
+use squads_rust::args::{reject_unknown_flags_or_exit, take_flag, take_flag_value, take_help};
+use squads_rust::audit::record;
+use squads_rust::message::print_compiled_message;
+use squads_rust::multisig::fetch_multisig_or_exit;
+use squads_rust::network::Network;
+use squads_rust::program::{program_id, take_program_id_or_exit};
+use squads_rust::send::{build_and_send, SendOptions};
+use squads_rust::token::{is_token_program, unpack_token_account};
+use squads_rust::vault::parse_vault_index;
+use std::env;
+
+const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
+
+fn exit_with(message: String) -> ! {
+    println!("Error: {}", message);
+    std::process::exit(1);
+}
+
+fn main() {
+    let mut args: Vec<String> = env::args().collect();
+    let help = take_help(&mut args);
+    take_program_id_or_exit(&mut args);
+    let send_options = SendOptions::take_from_or_exit(&mut args);
+
+    let dump_message = take_flag(&mut args, "--dump-message");
+    let destination = take_flag_value(&mut args, "--destination").unwrap_or_else(|e| exit_with(e));
+    let vault_index = match take_flag_value(&mut args, "--vault").unwrap_or_else(|e| exit_with(e)) {
+        Some(value) => parse_vault_index(&value).unwrap_or_else(|e| exit_with(e)),
+        None => 0,
+    };
+    reject_unknown_flags_or_exit(&args);
+
+    if help || args.len() < 3 {
+        println!("Create a proposal to close an empty vault-owned token account and reclaim its rent");
+        println!();
+        println!("Usage:");
+        println!("  cargo run --bin close-token-account-proposal -- <multisig_address> <token_account> [--destination <address>] [--vault <index>] [--dump-message] [mainnet]");
+        println!();
+        println!("Arguments:");
+        println!("  multisig_address  - The multisig PDA");
+        println!("  token_account     - The token account to close (must be owned by the vault and empty)");
+        println!();
+        println!("Options:");
+        println!("  --destination <address>  - Receives the reclaimed rent (default: the vault)");
+        println!("  --vault <index>          - Vault that owns the token account (default: 0)");
+        println!("  --dump-message           - Print the compiled vault transaction message before sending");
+        println!();
+        println!("Example:");
+        println!("  cargo run --bin close-token-account-proposal -- BJbRt... TokenAcct... mainnet");
+        return;
+    }
+
+    let multisig_pda: Pubkey = resolve_pubkey(&args[1]);
+    let token_account: Pubkey = resolve_pubkey(&args[2]);
+    let network = Network::from_arg(args.get(3).map(|s| s.as_str()).unwrap_or("devnet"));
+
+    let client = RpcClient::new_with_commitment(network.rpc_url(), CommitmentConfig::confirmed());
+    let creator = read_keypair_file("../member1.json").expect("Failed to read member1.json");
+
+    // Fetch multisig
+    let multisig = fetch_multisig_or_exit(&client, &multisig_pda);
+
+    let new_transaction_index = multisig.transaction_index + 1;
+
+    // Derive PDAs
+    let (vault_pda, _) = get_vault_pda(&multisig_pda, vault_index, Some(&program_id()));
+    let (transaction_pda, _) = get_transaction_pda(&multisig_pda, new_transaction_index, Some(&program_id()));
+    let (proposal_pda, _) = get_proposal_pda(&multisig_pda, new_transaction_index, Some(&program_id()));
+
+    let destination: Pubkey = match destination {
+        Some(address) => resolve_pubkey(&address),
+        None => vault_pda,
+    };
+
+    // The close can only execute if the account is an empty token account the vault controls,
+    // so check before creating the proposal
+    let account = client
+        .get_account(&token_account)
+        .unwrap_or_else(|e| exit_with(format!("Failed to fetch token account {}: {}", token_account, e)));
+    if !is_token_program(&account.owner) {
+        exit_with(format!("{} is not a token account (owned by {})", token_account, account.owner));
+    }
+    let token_program = account.owner;
+    let token_state = unpack_token_account(&account.data).unwrap_or_else(|e| exit_with(e));
+
+    if token_state.owner != vault_pda {
+        exit_with(format!(
+            "Token account {} is owned by {}, not vault {} (index {})",
+            token_account, token_state.owner, vault_pda, vault_index
+        ));
+    }
+    if let COption::Some(close_authority) = token_state.close_authority {
+        if close_authority != vault_pda {
+            exit_with(format!(
+                "Token account {} has close authority {}, so the vault cannot close it",
+                token_account, close_authority
+            ));
+        }
+    }
+    if token_state.amount != 0 {
+        exit_with(format!(
+            "Token account {} still holds {} tokens of mint {}; transfer or burn them first",
+            token_account, token_state.amount, token_state.mint
+        ));
+    }
+
+    println!("=== Create Close Token Account Proposal ({}) ===\n", network.name().to_uppercase());
+    println!("Multisig: {}", multisig_pda);
+    println!("Vault {} (token account owner): {}", vault_index, vault_pda);
+    println!("Creator: {}", creator.pubkey());
+    println!("Threshold: {} of {}", multisig.threshold, multisig.members.len());
+    println!();
+    println!("Token Account: {}", token_account);
+    println!("Mint: {}", token_state.mint);
+    println!("Token Program: {}", token_program);
+    println!(
+        "Rent to Reclaim: {} lamports ({:.9} SOL)",
+        account.lamports,
+        account.lamports as f64 / LAMPORTS_PER_SOL
+    );
+    println!("Rent Destination: {}{}", destination, if destination == vault_pda { " (vault)" } else { "" });
+    println!();
+    println!("Transaction Index: {}", new_transaction_index);
+
+    // Close the account; the vault PDA is the owner and signs via Squads CPI
+    let close_ix = close_account(
+        &token_program,
+        &token_account,
+        &destination,
+        &vault_pda,
+        &[],
+    ).expect("Failed to create close_account instruction");
+
+    // Compile the transaction message
+    let transaction_message = TransactionMessage::try_compile(&vault_pda, &[close_ix], &[])
+        .expect("Failed to compile transaction message");
+
+    let message_bytes = transaction_message
+        .try_to_vec()
+        .expect("Failed to serialize message");
+
+    if dump_message {
+        println!();
+        print_compiled_message(&message_bytes);
+    }
+
+    // === Instruction 1: Create Vault Transaction ===
+    let vault_tx_accounts = squads_multisig_program::accounts::VaultTransactionCreate {
+        multisig: multisig_pda,
+        transaction: transaction_pda,
+        creator: creator.pubkey(),
+        rent_payer: creator.pubkey(),
+        system_program: system_program::ID,
+    };
+
+    let vault_tx_data = squads_multisig_program::instruction::VaultTransactionCreate {
+        args: squads_multisig_program::instructions::VaultTransactionCreateArgs {
+            vault_index,
+            ephemeral_signers: 0,
+            transaction_message: message_bytes,
+            memo: None,
+        },
+    };
+
+    let create_vault_tx_ix = Instruction {
+        program_id: program_id(),
+        accounts: vault_tx_accounts.to_account_metas(Some(false)),
+        data: vault_tx_data.data(),
+    };
+
+    // === Instruction 2: Create Proposal ===
+    let proposal_accounts = squads_multisig_program::accounts::ProposalCreate {
+        multisig: multisig_pda,
+        proposal: proposal_pda,
+        creator: creator.pubkey(),
+        rent_payer: creator.pubkey(),
+        system_program: system_program::ID,
+    };
+
+    let proposal_data = squads_multisig_program::instruction::ProposalCreate {
+        args: squads_multisig_program::instructions::ProposalCreateArgs {
+            transaction_index: new_transaction_index,
+            draft: false,
+        },
+    };
+
+    let create_proposal_ix = Instruction {
+        program_id: program_id(),
+        accounts: proposal_accounts.to_account_metas(Some(false)),
+        data: proposal_data.data(),
+    };
+
+    // === Instruction 3: Creator auto-approves ===
+    let approve_accounts = squads_multisig_program::accounts::ProposalVote {
+        multisig: multisig_pda,
+        proposal: proposal_pda,
+        member: creator.pubkey(),
+    };
+
+    let approve_data = squads_multisig_program::instruction::ProposalApprove {
+        args: squads_multisig_program::instructions::ProposalVoteArgs { memo: None },
+    };
+
+    let approve_ix = Instruction {
+        program_id: program_id(),
+        accounts: approve_accounts.to_account_metas(Some(false)),
+        data: approve_data.data(),
+    };
+
+    println!("\nCreating close token account proposal...");
+
+    match build_and_send(&client, &[create_vault_tx_ix, create_proposal_ix, approve_ix], &creator.pubkey(), &[&creator], &send_options) {
+        Ok(sig) => {
+            record(network, &multisig_pda, &format!("propose {}: close token account {}", new_transaction_index, token_account), &creator.pubkey(), &sig);
+            println!("\nProposal created successfully!");
+            println!("Transaction: {}", sig);
+            println!();
+            println!("=== Proposal Details ===");
+            println!("Proposal Index: {}", new_transaction_index);
+            println!("Proposal Address: {}", proposal_pda);
+            println!("Status: Active (awaiting {} more approval(s))", multisig.threshold - 1);
+            println!();
+            println!("Share this with other members to approve:");
+            println!("  cargo run --bin approve-proposal -- {} {} {}",
+                     multisig_pda, new_transaction_index, network.cli_arg());
+            println!();
+            println!("After threshold is met, execute with:");
+            println!("  cargo run --bin execute-proposal -- {} {} {}",
+                     multisig_pda, new_transaction_index, network.cli_arg());
+
+            println!("\nView on Solana Explorer:");
+            println!("{}", network.explorer_tx_url(&sig));
+        }
+        Err(e) => {
+            println!("\nFailed to create proposal: {}", e);
+        }
+    }
+}
@@ -0,0 +1,246 @@
+//! Propose several config changes as one config transaction
+//!
+//! Adding two members and raising the threshold as separate proposals leaves the multisig in
+//! an intermediate state between executions (or fails outright if a step alone is invalid).
+//! This batches the actions into one `ConfigTransaction`, so they apply atomically when the
+//! proposal executes, the way the Squads UI batches config edits.
+//!
+//! Action flags can be repeated. Actions are applied as: removals, then additions, then the
+//! threshold and time lock. The membership and threshold after all of them are checked before
+//! the proposal is created.
+//!
+//! Config transactions are only for autonomous multisigs (no config authority). A multisig
+//! with a config authority changes settings directly with add-member, remove-member and
+//! change_threshold; when that authority is one of its vaults, `add-member --via-proposal`
+//! still puts an add to a vote.
+//!
+//! Members approve with approve-proposal. execute-proposal only executes vault transactions,
+//! so execute the approved config transaction from the Squads UI.
+//!
+//! Usage:
+//!   cargo run --bin config-transaction-proposal -- <multisig_address> [actions...] [mainnet]
+//!
+//! Actions:
+//!   --add-member <address>[:<ive>]  - Add a member; permissions default to ive (full)
+//!   --remove-member <address>       - Remove a member
+//!   --set-threshold <n>             - Change the threshold
+//!   --set-time-lock <seconds>       - Change the time lock
+//!
+//! Options:
+//!   --links  - Print explorer and Squads app links for the new proposal
+//!   --open   - Open the Squads app page in the browser (prints the URL if that fails)
+//!
+//! Example:
+//!   cargo run --bin config-transaction-proposal -- BJbRt... --add-member AbC... --add-member DeF...:v --set-threshold 3
+
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Signer},
+};
+use squads_multisig::pda::get_proposal_pda;
+use squads_multisig::squads_multisig_program::ConfigAction;
+use squads_multisig::state::{Member, Permission};
+use squads_rust::addressbook::{resolve_pubkey, try_resolve_pubkey};
+use squads_rust::args::{reject_unknown_flags_or_exit, take_flag, take_flag_value, take_flag_values, take_help};
+use squads_rust::audit::record;
+use squads_rust::multisig::{check_members, check_voters, fetch_multisig_or_exit};
+use squads_rust::permissions::{parse_permissions, PermissionsExt};
+use squads_rust::network::{open_in_browser, squads_app_tx_url, Network};
+use squads_rust::program::{program_id, take_program_id_or_exit};
+use squads_rust::proposal::{config_transaction_create_ix, proposal_approve_ix, proposal_create_ix};
+use squads_rust::send::{build_and_send, SendOptions};
+use std::env;
+
+fn exit_with(message: String) -> ! {
+    println!("Error: {}", message);
+    std::process::exit(1);
+}
+
+/// Parse `<address>[:<ive>]` for `--add-member`
+fn parse_new_member(value: &str) -> Result<Member, String> {
+    let (address, permissions) = value.split_once(':').unwrap_or((value, "ive"));
+    let key = try_resolve_pubkey(address).map_err(|e| format!("invalid --add-member: {}", e))?;
+    Ok(Member { key, permissions: parse_permissions(permissions)? })
+}
+
+/// The config actions from the command line, in the order they are applied
+fn take_actions(args: &mut Vec<String>) -> Result<Vec<ConfigAction>, String> {
+    let mut actions = Vec::new();
+    for value in take_flag_values(args, "--remove-member")? {
+        let old_member = try_resolve_pubkey(&value).map_err(|e| format!("invalid --remove-member: {}", e))?;
+        actions.push(ConfigAction::RemoveMember { old_member });
+    }
+    for value in take_flag_values(args, "--add-member")? {
+        actions.push(ConfigAction::AddMember { new_member: parse_new_member(&value)? });
+    }
+    if let Some(value) = take_flag_value(args, "--set-threshold")? {
+        let new_threshold = value
+            .parse()
+            .map_err(|_| format!("invalid --set-threshold value '{}'", value))?;
+        actions.push(ConfigAction::ChangeThreshold { new_threshold });
+    }
+    if let Some(value) = take_flag_value(args, "--set-time-lock")? {
+        let new_time_lock = value
+            .parse()
+            .map_err(|_| format!("invalid --set-time-lock value '{}'", value))?;
+        actions.push(ConfigAction::SetTimeLock { new_time_lock });
+    }
+    Ok(actions)
+}
+
+/// Apply `actions` to a copy of the members and threshold, rejecting what the program would
+fn compose(members: &[Member], threshold: u16, actions: &[ConfigAction]) -> Result<(Vec<Member>, u16), String> {
+    let mut members = members.to_vec();
+    let mut threshold = threshold;
+    for action in actions {
+        match action {
+            ConfigAction::RemoveMember { old_member } => {
+                let i = members
+                    .iter()
+                    .position(|m| m.key == *old_member)
+                    .ok_or_else(|| format!("{} is not a member of this multisig", old_member))?;
+                members.remove(i);
+            }
+            ConfigAction::AddMember { new_member } => {
+                if members.iter().any(|m| m.key == new_member.key) {
+                    return Err(format!("{} is already a member of this multisig", new_member.key));
+                }
+                members.push(new_member.clone());
+            }
+            ConfigAction::ChangeThreshold { new_threshold } => threshold = *new_threshold,
+            _ => {}
+        }
+    }
+    if members.is_empty() {
+        return Err("the multisig would have no members".to_string());
+    }
+    if threshold == 0 {
+        return Err("threshold must be at least 1".to_string());
+    }
+    check_members(&mut members)?;
+    check_voters(&members, threshold)?;
+    Ok((members, threshold))
+}
+
+fn describe(action: &ConfigAction) -> String {
+    match action {
+        ConfigAction::AddMember { new_member } => {
+            format!("Add member {} ({})", new_member.key, new_member.permissions.to_string())
+        }
+        ConfigAction::RemoveMember { old_member } => format!("Remove member {}", old_member),
+        ConfigAction::ChangeThreshold { new_threshold } => format!("Set threshold to {}", new_threshold),
+        ConfigAction::SetTimeLock { new_time_lock } => format!("Set time lock to {} seconds", new_time_lock),
+        _ => "Other config change".to_string(),
+    }
+}
+
+fn main() {
+    let mut args: Vec<String> = env::args().collect();
+    let help = take_help(&mut args);
+    take_program_id_or_exit(&mut args);
+    let send_options = SendOptions::take_from_or_exit(&mut args);
+    let links = take_flag(&mut args, "--links");
+    let open = take_flag(&mut args, "--open");
+    let actions = take_actions(&mut args).unwrap_or_else(|e| exit_with(e));
+    reject_unknown_flags_or_exit(&args);
+
+    if help || args.len() < 2 || actions.is_empty() {
+        println!("Propose several config changes, applied atomically as one config transaction");
+        println!();
+        println!("Usage:");
+        println!("  cargo run --bin config-transaction-proposal -- <multisig_address> [actions...] [mainnet]");
+        println!();
+        println!("Actions (repeatable; applied as removals, additions, then threshold and time lock):");
+        println!("  --add-member <address>[:<ive>]  - Add a member; permissions default to ive (full)");
+        println!("  --remove-member <address>       - Remove a member");
+        println!("  --set-threshold <n>             - Change the threshold");
+        println!("  --set-time-lock <seconds>       - Change the time lock");
+        println!();
+        println!("Options:");
+        println!("  --links  - Print explorer and Squads app links for the new proposal");
+        println!("  --open   - Open the Squads app page in the browser (prints the URL if that fails)");
+        println!();
+        println!("Example:");
+        println!("  cargo run --bin config-transaction-proposal -- BJbRt... --add-member AbC... --add-member DeF...:v --set-threshold 3");
+        return;
+    }
+
+    let multisig_pda: Pubkey = resolve_pubkey(&args[1]);
+    let network = Network::from_arg(args.get(2).map(|s| s.as_str()).unwrap_or("devnet"));
+
+    let client = RpcClient::new_with_commitment(network.rpc_url(), CommitmentConfig::confirmed());
+    let creator = read_keypair_file("../member1.json").expect("Failed to read member1.json");
+
+    let multisig = fetch_multisig_or_exit(&client, &multisig_pda);
+    if multisig.config_authority != Pubkey::default() {
+        println!("Error: this multisig has a config authority ({}), so it can't use config transactions.", multisig.config_authority);
+        println!("The config authority changes settings directly with add-member, remove-member and change_threshold.");
+        println!("If it is one of this multisig's vaults, add-member --via-proposal puts an add to a vote.");
+        std::process::exit(1);
+    }
+    if !multisig.member_has_permission(creator.pubkey(), Permission::Initiate) {
+        exit_with(format!("{} cannot propose (not a member, or no Initiate permission)", creator.pubkey()));
+    }
+    let (members, threshold) =
+        compose(&multisig.members, multisig.threshold, &actions).unwrap_or_else(|e| exit_with(e));
+    let auto_approve = multisig.member_has_permission(creator.pubkey(), Permission::Vote);
+
+    let transaction_index = multisig.transaction_index + 1;
+    let (proposal_pda, _) = get_proposal_pda(&multisig_pda, transaction_index, Some(&program_id()));
+
+    println!("=== Config Transaction Proposal ({}) ===\n", network.name().to_uppercase());
+    println!("Multisig: {}", multisig_pda);
+    println!("Creator: {}", creator.pubkey());
+    println!();
+    println!("Actions:");
+    for (i, action) in actions.iter().enumerate() {
+        println!("  {}. {}", i + 1, describe(action));
+    }
+    println!();
+    println!("Members: {} -> {}", multisig.members.len(), members.len());
+    println!("Threshold: {} -> {}", multisig.threshold, threshold);
+    println!();
+    println!("Transaction Index: {}", transaction_index);
+    println!("Proposal PDA: {}", proposal_pda);
+
+    let mut instructions = vec![
+        config_transaction_create_ix(&multisig_pda, transaction_index, &creator.pubkey(), actions),
+        proposal_create_ix(&multisig_pda, transaction_index, &creator.pubkey()),
+    ];
+    if auto_approve {
+        instructions.push(proposal_approve_ix(&multisig_pda, transaction_index, &creator.pubkey()));
+    }
+
+    println!("\nCreating proposal...");
+
+    match build_and_send(&client, &instructions, &creator.pubkey(), &[&creator], &send_options) {
+        Ok(sig) => {
+            record(network, &multisig_pda, &format!("propose {}: config transaction", transaction_index), &creator.pubkey(), &sig);
+            println!("\nProposal created{}!", if auto_approve { " and approved" } else { "" });
+            println!("Transaction: {}", sig);
+            println!();
+            println!("Share this with other members to approve:");
+            println!("  cargo run --bin approve-proposal -- {} {} {}", multisig_pda, transaction_index, network.cli_arg());
+
+            println!();
+            println!("Once approved, execute it from the Squads UI (execute-proposal handles vault transactions only).");
+
+            println!("\nView on Solana Explorer:");
+            println!("{}", network.explorer_tx_url(&sig));
+            println!("\nView on Squads UI:");
+            println!("{}", squads_app_tx_url(&multisig_pda, transaction_index));
+            if links {
+                network.print_links(&multisig_pda, Some(transaction_index), Some(&sig));
+            }
+            if open {
+                open_in_browser(&squads_app_tx_url(&multisig_pda, transaction_index));
+            }
+        }
+        Err(e) => {
+            println!("\nFailed to create proposal: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
@@ -15,6 +15,30 @@
 //! Usage:
 //!   cargo run --bin create-dao-multisigs              # Devnet
 //!   cargo run --bin create-dao-multisigs -- mainnet   # Mainnet
+//!   cargo run --bin create-dao-multisigs -- --json    # Print both multisigs as JSON
+//!   cargo run --bin create-dao-multisigs -- --quiet   # Print only the treasury and mint addresses
+//!   cargo run --bin create-dao-multisigs -- --rent-collector self   # Each multisig's vault reclaims its rent
+//!   cargo run --bin create-dao-multisigs -- --name "Acme DAO"   # "Acme DAO Treasury" / "Acme DAO Mint"
+//!   cargo run --bin create-dao-multisigs -- --yes     # Create without the confirmation prompt
+//!   cargo run --bin create-dao-multisigs -- --cosigner-permissions iv   # Cosigner can't execute
+//!
+//! Each member's permissions come from the `*_PERMISSIONS` constants below, as a string of `i`
+//! (Initiate), `v` (Vote) and `e` (Execute); all default to `ive`. `--cosigner-permissions`
+//! overrides the treasury cosigner's, e.g. `iv` so it can approve but never execute on its own.
+//! Both multisigs are rejected before anything is sent if fewer members can vote than the
+//! threshold, or if no member can initiate or execute.
+//!
+//! `--rent-collector <addr|self>` applies to both multisigs and sets who can reclaim rent from
+//! closed transaction and proposal accounts; `self` means each multisig's own vault 0. The
+//! default is none, so that rent stays locked.
+//!
+//! `--name <string>` names the pair: the multisigs are named "<name> Treasury" and "<name> Mint"
+//! in their creation memos and in `~/.squads/registry.json`, which inspect_multisig reads.
+//!
+//! Before sending, both configurations are printed (threshold, members with their permissions,
+//! config authority, time lock, rent collector) along with the Squads creation fee read from the
+//! program config, and the script asks for confirmation; `--yes` skips the prompt and is
+//! required with `--json` or `--quiet`. Nothing is sent until both multisigs are confirmed.
 
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::{
@@ -22,13 +46,25 @@ use solana_sdk::{
     pubkey::Pubkey,
     signature::{read_keypair_file, Keypair, Signer},
     system_program,
-    transaction::Transaction,
 };
 use squads_multisig::{
     client::{multisig_create_v2, MultisigCreateAccountsV2, MultisigCreateArgsV2},
     pda::{get_multisig_pda, get_program_config_pda, get_vault_pda},
-    state::{Member, Permissions},
+    state::Member,
 };
+use squads_rust::args::{reject_unknown_flags, take_flag, take_flag_value, take_help};
+use squads_rust::audit::record;
+use squads_rust::confirm::confirm;
+use squads_rust::format::describe_lamports;
+use squads_rust::multisig::{
+    check_new_multisig_or_exit, fault_tolerance, print_create_args, CreationCost, RentCollector,
+};
+use squads_rust::network::{squads_app_url, Network};
+use squads_rust::output::{multisig_json, OutputMode};
+use squads_rust::permissions::{parse_permissions, PermissionsExt};
+use squads_rust::program::{check_program_or_exit, program_id, take_program_id_or_exit};
+use squads_rust::registry::{record_name, take_name};
+use squads_rust::send::{build_and_send, SendOptions};
 use std::env;
 
 // ============================================================================
@@ -41,41 +77,105 @@ const TREASURY_MULTISIG_KEY_A: &str = "HHroB8P1q3kijtyML9WPvfTXG8JicfmUoGZjVzam6
 const TREASURY_MULTISIG_KEY_B: &str = "3ogXyF6ovq5SqsneuGY6gHLG27NK6gw13SqfXMwRBYai";
 const TREASURY_COSIGNER: &str = "Dobm8QnaCPQoc6koxC3wqBQqPTfDwspATb2u6EcWC9Aw";
 const TREASURY_THRESHOLD: u16 = 2; // 2-of-3
+const TREASURY_KEY_A_PERMISSIONS: &str = "ive";
+const TREASURY_KEY_B_PERMISSIONS: &str = "ive";
+const TREASURY_COSIGNER_PERMISSIONS: &str = "ive";
 
 // Mint multisig configuration
 const MINT_MULTISIG_CONFIG_AUTH: &str = "Dobm8QnaCPQoc6koxC3wqBQqPTfDwspATb2u6EcWC9Aw";
 const MINT_MULTISIG_KEY_A: &str = "Dobm8QnaCPQoc6koxC3wqBQqPTfDwspATb2u6EcWC9Aw";
 const MINT_MULTISIG_KEY_B: &str = "2xrEGvtxXKujqnHceiSzYDTAbTJEX3yGGPJgywH7LmcD";
 const MINT_THRESHOLD: u16 = 2; // 2-of-2
-
-// All permissions mask (Initiate | Vote | Execute = 1 | 2 | 4 = 7)
-const ALL_PERMISSIONS: u8 = 7;
+const MINT_KEY_A_PERMISSIONS: &str = "ive";
+const MINT_KEY_B_PERMISSIONS: &str = "ive";
 
 // ============================================================================
 // Network Configuration
 // ============================================================================
 const CREATOR_KEYPAIR_PATH: &str = "../member1.json";
-const DEVNET_RPC: &str = "https://api.devnet.solana.com";
-const MAINNET_RPC: &str = "https://api.mainnet-beta.solana.com";
-const SQUADS_TREASURY_DEVNET: &str = "HM5y4mz3Bt9JY9mr1hkyhnvqxSH4H2u2451j7Hc2dtvK";
-const SQUADS_TREASURY_MAINNET: &str = "5DH2e3cJmFpyi6mk65EGFediunm4ui6BiKNUNrhWtD1b";
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    let mut args: Vec<String> = env::args().collect();
+    let help = take_help(&mut args);
+    take_program_id_or_exit(&mut args);
+    let send_options = SendOptions::take_from_or_exit(&mut args);
+    let output = OutputMode::take_from(&mut args).unwrap_or_else(|e| {
+        eprintln!("ERROR: {}", e);
+        std::process::exit(1);
+    });
+    // Progress and banners are only for the interactive (default) output
+    macro_rules! say {
+        ($($arg:tt)*) => {
+            if output.is_human() {
+                println!($($arg)*);
+            }
+        };
+    }
+    let yes = take_flag(&mut args, "--yes");
 
-    let network = args.get(1).map(|s| s.as_str()).unwrap_or("devnet");
-    let cosigner: Pubkey = TREASURY_COSIGNER.parse().unwrap();
+    let rent_collector = RentCollector::take_from(&mut args).unwrap_or_else(|e| {
+        eprintln!("ERROR: {}", e);
+        std::process::exit(1);
+    });
+
+    let name = take_name(&mut args).unwrap_or_else(|e| {
+        eprintln!("ERROR: {}", e);
+        std::process::exit(1);
+    });
+    let cosigner_permissions = take_flag_value(&mut args, "--cosigner-permissions").unwrap_or_else(|e| {
+        eprintln!("ERROR: {}", e);
+        std::process::exit(1);
+    });
+    if let Err(e) = reject_unknown_flags(&args) {
+        eprintln!("ERROR: {}", e);
+        eprintln!("Run with --help for usage.");
+        std::process::exit(1);
+    }
 
-    let (rpc_url, treasury_addr, cluster_param) = match network {
-        "mainnet" => (MAINNET_RPC, SQUADS_TREASURY_MAINNET, ""),
-        _ => (DEVNET_RPC, SQUADS_TREASURY_DEVNET, "?cluster=devnet"),
+    if help {
+        println!("Create the Treasury (2-of-3) and Mint (2-of-2) multisigs used for DAO initialization");
+        println!();
+        println!("Usage:");
+        println!("  cargo run --bin create-dao-multisigs -- [options] [mainnet]");
+        println!();
+        println!("Options:");
+        println!("  --json                          - Print both multisigs as JSON");
+        println!("  --quiet                         - Print only the treasury and mint addresses");
+        println!("  --yes                           - Create without the confirmation prompt (required with --json or --quiet)");
+        println!("  --rent-collector <addr|self>    - Who can reclaim rent from closed accounts (default: none)");
+        println!("  --name <string>                 - Name the pair \"<name> Treasury\" / \"<name> Mint\"");
+        println!("  --cosigner-permissions <ive>    - Permissions of the treasury cosigner (default: {})", TREASURY_COSIGNER_PERMISSIONS);
+        return;
+    }
+    if !yes && !output.is_human() {
+        eprintln!("ERROR: --json and --quiet can't prompt for confirmation; pass --yes");
+        std::process::exit(1);
+    }
+
+    let permissions = |value: &str| {
+        parse_permissions(value).unwrap_or_else(|e| {
+            eprintln!("ERROR: {}", e);
+            std::process::exit(1);
+        })
     };
+    let treasury_key_a_permissions = permissions(TREASURY_KEY_A_PERMISSIONS);
+    let treasury_key_b_permissions = permissions(TREASURY_KEY_B_PERMISSIONS);
+    let cosigner_permissions = permissions(cosigner_permissions.as_deref().unwrap_or(TREASURY_COSIGNER_PERMISSIONS));
+    let mint_key_a_permissions = permissions(MINT_KEY_A_PERMISSIONS);
+    let mint_key_b_permissions = permissions(MINT_KEY_B_PERMISSIONS);
+
+    let treasury_name = name.as_ref().map(|name| format!("{} Treasury", name));
+    let mint_name = name.as_ref().map(|name| format!("{} Mint", name));
+
+    let network = Network::from_arg(args.get(1).map(|s| s.as_str()).unwrap_or("devnet"));
+    let cosigner: Pubkey = TREASURY_COSIGNER.parse().unwrap();
 
-    println!("=== Creating DAO Multisigs ({}) ===\n", network.to_uppercase());
-    println!("Cosigner: {}\n", cosigner);
+    say!("=== Creating DAO Multisigs ({}) ===\n", network.name().to_uppercase());
+    say!("Cosigner: {}\n", cosigner);
 
     // Connect to Solana
-    let client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
+    let client = RpcClient::new_with_commitment(network.rpc_url(), CommitmentConfig::confirmed());
+    check_program_or_exit(&client);
 
     // Load creator keypair (pays for transactions)
     let creator = read_keypair_file(CREATOR_KEYPAIR_PATH)
@@ -84,15 +184,8 @@ fn main() {
 
     // Check creator has funds
     let balance = client.get_balance(&creator_pubkey).expect("Failed to get balance");
-    println!("Creator: {}", creator_pubkey);
-    println!("Balance: {} SOL\n", balance as f64 / 1_000_000_000.0);
-
-    if balance < 20_000_000 {
-        // 0.02 SOL minimum (creating 2 multisigs)
-        eprintln!("ERROR: Insufficient balance. Need at least 0.02 SOL for transaction fees.");
-        eprintln!("Fund this wallet: {}", creator_pubkey);
-        std::process::exit(1);
-    }
+    say!("Creator: {}", creator_pubkey);
+    say!("Balance: {} SOL\n", balance as f64 / 1_000_000_000.0);
 
     // Parse protocol keys
     let treasury_config_auth: Pubkey = TREASURY_MULTISIG_CONFIG_AUTH.parse().unwrap();
@@ -102,18 +195,47 @@ fn main() {
     let mint_key_a: Pubkey = MINT_MULTISIG_KEY_A.parse().unwrap();
     let mint_key_b: Pubkey = MINT_MULTISIG_KEY_B.parse().unwrap();
 
-    let squads_treasury: Pubkey = treasury_addr.parse().unwrap();
-    let (program_config_pda, _) = get_program_config_pda(None);
-
-    let all_permissions = Permissions { mask: ALL_PERMISSIONS };
+    let squads_treasury = network
+        .squads_treasury(&client)
+        .expect("Failed to resolve Squads treasury");
+    let (program_config_pda, _) = get_program_config_pda(Some(&program_id()));
+
+    let mut treasury_members = vec![
+        Member { key: treasury_key_a, permissions: treasury_key_a_permissions },
+        Member { key: treasury_key_b, permissions: treasury_key_b_permissions },
+        Member { key: cosigner, permissions: cosigner_permissions },
+    ];
+    let mut mint_members = vec![
+        Member { key: mint_key_a, permissions: mint_key_a_permissions },
+        Member { key: mint_key_b, permissions: mint_key_b_permissions },
+    ];
+
+    // Validate both before sending anything, so a bad mint config can't leave only the
+    // treasury multisig created
+    check_new_multisig_or_exit("Treasury multisig", &mut treasury_members, TREASURY_THRESHOLD);
+    check_new_multisig_or_exit("Mint multisig", &mut mint_members, MINT_THRESHOLD);
+
+    // Both creation fees, rents and network fees, read from the program config and the cluster
+    let priority_fee = send_options.fee_profile.map_or(0, |profile| profile.max_priority_fee());
+    let cost = CreationCost::fetch(&client, &[treasury_members.len(), mint_members.len()], priority_fee)
+        .unwrap_or_else(|e| {
+            eprintln!("ERROR: Failed to read the creation cost: {}", e);
+            std::process::exit(1);
+        });
+    if balance < cost.total() {
+        eprintln!(
+            "ERROR: Insufficient balance. Creating both multisigs costs {} (Squads fees, rent and network fees).",
+            describe_lamports(cost.total())
+        );
+        eprintln!("Fund this wallet: {}", creator_pubkey);
+        std::process::exit(1);
+    }
 
     // ========================================================================
-    // Create Treasury Multisig (2-of-3)
+    // Treasury Multisig (2-of-3)
     // ========================================================================
-    println!("Creating Treasury Multisig (2-of-3)...");
-
     let treasury_create_key = Keypair::new();
-    let (treasury_multisig_pda, _) = get_multisig_pda(&treasury_create_key.pubkey(), None);
+    let (treasury_multisig_pda, _) = get_multisig_pda(&treasury_create_key.pubkey(), Some(&program_id()));
 
     let treasury_accounts = MultisigCreateAccountsV2 {
         program_config: program_config_pda,
@@ -124,46 +246,24 @@ fn main() {
         system_program: system_program::ID,
     };
 
+    let treasury_rent_collector = rent_collector.map(|collector| collector.resolve(&treasury_multisig_pda));
+    let treasury_member_keys: Vec<Pubkey> = treasury_members.iter().map(|m| m.key).collect();
+    let mint_member_keys: Vec<Pubkey> = mint_members.iter().map(|m| m.key).collect();
+
     let treasury_args = MultisigCreateArgsV2 {
         config_authority: Some(treasury_config_auth),
         threshold: TREASURY_THRESHOLD,
-        members: vec![
-            Member { key: treasury_key_a, permissions: all_permissions },
-            Member { key: treasury_key_b, permissions: all_permissions },
-            Member { key: cosigner, permissions: all_permissions },
-        ],
+        members: treasury_members,
         time_lock: 0,
-        rent_collector: None,
-        memo: None,
+        rent_collector: treasury_rent_collector,
+        memo: treasury_name.clone(),
     };
 
-    let treasury_ix = multisig_create_v2(treasury_accounts, treasury_args, None);
-
-    let recent_blockhash = client.get_latest_blockhash().expect("Failed to get blockhash");
-    let treasury_tx = Transaction::new_signed_with_payer(
-        &[treasury_ix],
-        Some(&creator_pubkey),
-        &[&creator, &treasury_create_key],
-        recent_blockhash,
-    );
-
-    let treasury_sig = client
-        .send_and_confirm_transaction(&treasury_tx)
-        .expect("Failed to create treasury multisig");
-
-    let (treasury_vault_pda, _) = get_vault_pda(&treasury_multisig_pda, 0, None);
-
-    println!("  ✓ Treasury Multisig created: {}", treasury_multisig_pda);
-    println!("  ✓ Treasury Vault: {}", treasury_vault_pda);
-    println!("  ✓ Transaction: {}\n", treasury_sig);
-
     // ========================================================================
-    // Create Mint Multisig (2-of-2)
+    // Mint Multisig (2-of-2)
     // ========================================================================
-    println!("Creating Mint Multisig (2-of-2)...");
-
     let mint_create_key = Keypair::new();
-    let (mint_multisig_pda, _) = get_multisig_pda(&mint_create_key.pubkey(), None);
+    let (mint_multisig_pda, _) = get_multisig_pda(&mint_create_key.pubkey(), Some(&program_id()));
 
     let mint_accounts = MultisigCreateAccountsV2 {
         program_config: program_config_pda,
@@ -174,69 +274,151 @@ fn main() {
         system_program: system_program::ID,
     };
 
+    let mint_rent_collector = rent_collector.map(|collector| collector.resolve(&mint_multisig_pda));
     let mint_args = MultisigCreateArgsV2 {
         config_authority: Some(mint_config_auth),
         threshold: MINT_THRESHOLD,
-        members: vec![
-            Member { key: mint_key_a, permissions: all_permissions },
-            Member { key: mint_key_b, permissions: all_permissions },
-        ],
+        members: mint_members,
         time_lock: 0,
-        rent_collector: None,
-        memo: None,
+        rent_collector: mint_rent_collector,
+        memo: mint_name.clone(),
     };
 
-    let mint_ix = multisig_create_v2(mint_accounts, mint_args, None);
+    // Creation can't be undone, so show both configurations and ask before sending either
+    if output.is_human() {
+        print_create_args("Treasury Multisig (2-of-3)", &treasury_multisig_pda, &treasury_args);
+        print_create_args("Mint Multisig (2-of-2)", &mint_multisig_pda, &mint_args);
+        cost.print(&squads_treasury);
+        println!();
+    }
+    if !yes && !confirm("Create both multisigs?") {
+        println!("Aborted; nothing was sent.");
+        return;
+    }
+
+    // ========================================================================
+    // Create Treasury Multisig, then Mint Multisig
+    // ========================================================================
+    say!("Creating Treasury Multisig (2-of-3)...");
+    let treasury_ix = multisig_create_v2(treasury_accounts, treasury_args, Some(program_id()));
+
+    let treasury_sig = build_and_send(&client, &[treasury_ix], &creator_pubkey, &[&creator, &treasury_create_key], &send_options)
+        .expect("Failed to create treasury multisig");
+    record(network, &treasury_multisig_pda, "create treasury multisig", &creator_pubkey, &treasury_sig);
+
+    let (treasury_vault_pda, _) = get_vault_pda(&treasury_multisig_pda, 0, Some(&program_id()));
 
-    let recent_blockhash = client.get_latest_blockhash().expect("Failed to get blockhash");
-    let mint_tx = Transaction::new_signed_with_payer(
-        &[mint_ix],
-        Some(&creator_pubkey),
-        &[&creator, &mint_create_key],
-        recent_blockhash,
-    );
+    say!("  ✓ Treasury Multisig created: {}", treasury_multisig_pda);
+    say!("  ✓ Treasury Vault: {}", treasury_vault_pda);
+    say!("  ✓ Transaction: {}\n", treasury_sig);
 
-    let mint_sig = client
-        .send_and_confirm_transaction(&mint_tx)
+    say!("Creating Mint Multisig (2-of-2)...");
+    let mint_ix = multisig_create_v2(mint_accounts, mint_args, Some(program_id()));
+
+    let mint_sig = build_and_send(&client, &[mint_ix], &creator_pubkey, &[&creator, &mint_create_key], &send_options)
         .expect("Failed to create mint multisig");
+    record(network, &mint_multisig_pda, "create mint multisig", &creator_pubkey, &mint_sig);
+
+    let (mint_vault_pda, _) = get_vault_pda(&mint_multisig_pda, 0, Some(&program_id()));
 
-    let (mint_vault_pda, _) = get_vault_pda(&mint_multisig_pda, 0, None);
+    say!("  ✓ Mint Multisig created: {}", mint_multisig_pda);
+    say!("  ✓ Mint Vault: {}", mint_vault_pda);
+    say!("  ✓ Transaction: {}\n", mint_sig);
 
-    println!("  ✓ Mint Multisig created: {}", mint_multisig_pda);
-    println!("  ✓ Mint Vault: {}", mint_vault_pda);
-    println!("  ✓ Transaction: {}\n", mint_sig);
+    // Both multisigs exist now, so a registry failure is only a warning
+    for (multisig_pda, name) in [(&treasury_multisig_pda, &treasury_name), (&mint_multisig_pda, &mint_name)] {
+        if let Some(name) = name {
+            if let Err(e) = record_name(multisig_pda, name) {
+                eprintln!("WARNING: Could not save '{}' to the local registry: {}", name, e);
+            }
+        }
+    }
 
     // ========================================================================
     // Summary
     // ========================================================================
+    match output {
+        OutputMode::Json => {
+            let mut report = serde_json::json!({
+                "network": network.name(),
+                "treasury": multisig_json(
+                    &treasury_multisig_pda,
+                    &treasury_vault_pda,
+                    Some(&treasury_config_auth),
+                    TREASURY_THRESHOLD,
+                    &treasury_member_keys,
+                    &treasury_sig,
+                ),
+                "mint": multisig_json(
+                    &mint_multisig_pda,
+                    &mint_vault_pda,
+                    Some(&mint_config_auth),
+                    MINT_THRESHOLD,
+                    &mint_member_keys,
+                    &mint_sig,
+                ),
+            });
+            report["treasury"]["rent_collector"] = treasury_rent_collector.map(|key| key.to_string()).into();
+            report["mint"]["rent_collector"] = mint_rent_collector.map(|key| key.to_string()).into();
+            report["treasury"]["name"] = treasury_name.clone().into();
+            report["mint"]["name"] = mint_name.clone().into();
+            println!("{}", serde_json::to_string_pretty(&report).expect("Failed to serialize result"));
+            return;
+        }
+        OutputMode::Quiet => {
+            println!("{}", treasury_multisig_pda);
+            println!("{}", mint_multisig_pda);
+            return;
+        }
+        OutputMode::Human => {}
+    }
+
     println!("========== SUCCESS ==========");
-    println!("Network: {}\n", network.to_uppercase());
+    println!("Network: {}\n", network.name().to_uppercase());
 
     println!("TREASURY MULTISIG (2-of-3):");
+    if let Some(name) = &treasury_name {
+        println!("  Name:    {}", name);
+    }
     println!("  Address: {}", treasury_multisig_pda);
     println!("  Vault:   {} (send funds here)", treasury_vault_pda);
     println!("  Config Authority: {}", treasury_config_auth);
+    println!("  Fault Tolerance: {} lost key(s)", fault_tolerance(TREASURY_THRESHOLD, treasury_member_keys.len()));
+    if let Some(address) = treasury_rent_collector {
+        println!("  Rent Collector: {}", address);
+    }
     println!("  Members:");
-    println!("    1. {} (Protocol Key A)", treasury_key_a);
-    println!("    2. {} (Protocol Key B)", treasury_key_b);
-    println!("    3. {} (Cosigner)", cosigner);
+    println!("    1. {} (Protocol Key A: {})", treasury_key_a, treasury_key_a_permissions.to_string());
+    println!("    2. {} (Protocol Key B: {})", treasury_key_b, treasury_key_b_permissions.to_string());
+    println!("    3. {} (Cosigner: {})", cosigner, cosigner_permissions.to_string());
     println!();
 
     println!("MINT MULTISIG (2-of-2):");
+    if let Some(name) = &mint_name {
+        println!("  Name:    {}", name);
+    }
     println!("  Address: {}", mint_multisig_pda);
     println!("  Vault:   {} (mint authority)", mint_vault_pda);
     println!("  Config Authority: {}", mint_config_auth);
+    println!("  Fault Tolerance: {} lost key(s)", fault_tolerance(MINT_THRESHOLD, mint_member_keys.len()));
+    if let Some(address) = mint_rent_collector {
+        println!("  Rent Collector: {}", address);
+    }
     println!("  Members:");
-    println!("    1. {} (Protocol Key A)", mint_key_a);
-    println!("    2. {} (Protocol Key B)", mint_key_b);
+    println!("    1. {} (Protocol Key A: {})", mint_key_a, mint_key_a_permissions.to_string());
+    println!("    2. {} (Protocol Key B: {})", mint_key_b, mint_key_b_permissions.to_string());
     println!();
 
+    if rent_collector.is_some() {
+        println!("Rent from closed transaction and proposal accounts can be reclaimed to each Rent Collector.");
+        println!();
+    }
     println!("View on Squads App:");
-    println!("  Treasury: https://v4.squads.so/squads/{}/home", treasury_multisig_pda);
-    println!("  Mint:     https://v4.squads.so/squads/{}/home", mint_multisig_pda);
+    println!("  Treasury: {}", squads_app_url(&treasury_multisig_pda));
+    println!("  Mint:     {}", squads_app_url(&mint_multisig_pda));
     println!();
 
     println!("View on Solana Explorer:");
-    println!("  Treasury: https://explorer.solana.com/address/{}{}", treasury_multisig_pda, cluster_param);
-    println!("  Mint:     https://explorer.solana.com/address/{}{}", mint_multisig_pda, cluster_param);
+    println!("  Treasury: {}", network.explorer_address_url(&treasury_multisig_pda));
+    println!("  Mint:     {}", network.explorer_address_url(&mint_multisig_pda));
 }
@@ -12,74 +12,730 @@
 //!
 //!   # Transfer on mainnet
 //!   cargo run --bin create-proposal -- BJbRt... transfer DestPubkey... 100000000 mainnet
+//!
+//!   # The same 0.1 SOL given in SOL (also works for stake and wrap-sol)
+//!   cargo run --bin create-proposal -- BJbRt... transfer DestPubkey... --sol 0.1
+//!
+//!   # Stake 10 SOL from the vault with a validator
+//!   cargo run --bin create-proposal -- BJbRt... stake VoteAccount... 10000000000
+//!
+//!   # Sweep the vault, keeping 0.01 SOL above the rent-exempt minimum
+//!   cargo run --bin create-proposal -- BJbRt... transfer-all DestPubkey... --leave 10000000
+//!
+//!   # Record a governed on-chain note, signed by the vault through the memo program
+//!   cargo run --bin create-proposal -- BJbRt... memo "Q3 budget ratified"
+//!
+//!   # Wrap 1 SOL into the vault's wrapped SOL account, or unwrap it all back
+//!   cargo run --bin create-proposal -- BJbRt... wrap-sol 1000000000
+//!   cargo run --bin create-proposal -- BJbRt... unwrap-sol
+//!
+//!   # Close the vault's empty token accounts, returning their rent to the vault
+//!   cargo run --bin create-proposal -- BJbRt... close-empty-token-accounts
+//!
+//!   # Transfer from vault 1 instead of the default vault 0
+//!   cargo run --bin create-proposal -- BJbRt... transfer DestPubkey... 100000000 --vault 1
+//!
+//!   # Propose with a different member's keypair (must have Initiate permission)
+//!   cargo run --bin create-proposal -- BJbRt... transfer DestPubkey... 100000000 --proposer ../member2.json
+//!
+//!   # Refuse destinations that aren't in a local allowlist file
+//!   cargo run --bin create-proposal -- BJbRt... transfer DestPubkey... 100000000 --recipients-file recipients.txt
+//!
+//!   # Print the compiled vault transaction message for debugging
+//!   cargo run --bin create-proposal -- BJbRt... transfer DestPubkey... 100000000 --dump-message
+//!
+//! If another member creates a proposal between fetching the multisig and sending, the
+//! transaction index is stale and the send fails. The script then re-fetches the multisig and
+//! retries at the next free index, up to `--max-retries <n>` times (default 3).
+//!
+//! `--fee-estimate` prints what the proposal transaction would cost instead of sending it: the
+//! network fee plus rent for the vault transaction and proposal accounts. `--links` prints
+//! explorer and Squads app links for the multisig, new proposal and transaction, and `--open`
+//! opens the new proposal in the Squads app.
+//!
+//! The script also estimates the transaction that will later execute the proposal (the execute
+//! instruction's accounts plus every account of the vault message) and warns when it nears the
+//! 1232-byte packet limit or the account lock limit (`--max-accounts <n>`, default 64), so an
+//! oversized proposal is caught before members spend votes on it.
+//!
+//! Before sending, the script also checks that no transaction account already exists at the
+//! derived index (an RPC node can lag behind another member's proposal) and moves up to the
+//! first free index. `--no-auto-increment` errors instead. `--index <n>` only creates the
+//! proposal at index n, and fails if that is not the next free index: the program always
+//! creates transactions at the multisig's next index, so other indexes cannot be targeted.
+//!
+//! `--ephemeral-signers <n>` reserves n ephemeral signers on the vault transaction. It is refused
+//! below what the command needs (1 for stake, 0 otherwise). Their PDAs are printed, and
+//! execute-proposal passes them as non-signer accounts for the program to sign.
 
+use solana_client::client_error::ClientError;
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::{
     commitment_config::CommitmentConfig,
     instruction::Instruction,
     pubkey::Pubkey,
-    signature::{read_keypair_file, Signer},
+    signature::{read_keypair_file, Keypair, Signer},
+    stake::{
+        self,
+        state::{Authorized, Lockup, StakeStateV2},
+    },
     system_instruction,
-    system_program,
-    transaction::Transaction,
 };
-use squads_multisig::anchor_lang::{AccountDeserialize, AnchorSerialize, InstructionData, ToAccountMetas};
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use spl_token::native_mint;
+use squads_multisig::anchor_lang::AnchorSerialize;
 use squads_multisig::pda::{get_proposal_pda, get_transaction_pda, get_vault_pda};
-use squads_multisig::squads_multisig_program;
-use squads_multisig::state::Multisig;
+use squads_multisig::squads_multisig_program::{self, VaultTransaction};
+use squads_multisig::state::{Permission, Proposal, VaultTransactionMessage};
 use squads_multisig::vault_transaction::VaultTransactionMessageExt;
 use squads_multisig_program::TransactionMessage;
+use squads_rust::addressbook::resolve_pubkey;
+use squads_rust::args::{reject_unknown_flags_or_exit, take_flag, take_flag_value, take_help};
+use squads_rust::audit::record;
+use squads_rust::fee::FeeEstimate;
+use squads_rust::format::{describe_lamports, parse_sol};
+use squads_rust::message::print_compiled_message;
+use squads_rust::multisig::fetch_multisig_or_exit;
+use squads_rust::network::{open_in_browser, squads_app_tx_url, Network};
+use squads_rust::pda::ephemeral_signer_pdas;
+use squads_rust::program::{program_id, take_program_id_or_exit};
+use squads_rust::proposal::{
+    estimate_execute_size, is_index_collision, proposal_approve_ix, proposal_create_ix, vault_transaction_create_ix,
+    ExecuteSize,
+};
+use squads_rust::recipients::{check_recipients, load_recipients};
+use squads_rust::send::{build_and_send, SendOptions, PACKET_DATA_SIZE};
+use squads_rust::token::{derive_ata, fetch_token_accounts, OwnedTokenAccount};
+use squads_rust::vault::parse_vault_index;
 use std::env;
 
-const DEVNET_RPC: &str = "https://api.devnet.solana.com";
-const MAINNET_RPC: &str = "https://api.mainnet-beta.solana.com";
+const DEFAULT_MAX_RETRIES: u32 = 3;
+const DEFAULT_PROPOSER: &str = "../member1.json";
+/// Accounts one transaction may lock
+const DEFAULT_MAX_ACCOUNTS: usize = 64;
+/// Warn once the execute transaction reaches this share of a limit
+const EXECUTE_SIZE_WARN_PERCENT: usize = 90;
+/// The memo program has no length limit of its own, but the memo is stored in the vault
+/// transaction and carried again by the execute transaction, both under 1232 bytes
+const MAX_MEMO_BYTES: usize = 512;
+/// close_account instructions per proposal. Each adds an account key and an instruction to the
+/// vault message, so 18 keeps the create transaction under 1232 bytes even with both token
+/// programs in the message.
+const MAX_CLOSES_PER_PROPOSAL: usize = 18;
+
+/// A vault action parsed from the command line. Instructions are built once the vault
+/// and transaction PDAs are known.
+enum Action {
+    Transfer { destination: Pubkey, amount: u64 },
+    /// Sweep the vault's balance, minus its rent-exempt minimum and `leave`, at creation time
+    TransferAll { destination: Pubkey, leave: u64 },
+    Stake { vote_account: Pubkey, amount: u64 },
+    /// An SPL memo signed by the vault, as an on-chain attestation
+    Memo { text: String },
+    /// Move lamports into the vault's wrapped SOL ATA, creating it if needed
+    WrapSol { amount: u64 },
+    /// Close the vault's wrapped SOL ATA, returning its whole balance to the vault
+    UnwrapSol,
+    /// Close the vault's zero-balance token accounts, returning their rent to the vault
+    CloseEmptyTokenAccounts { include_wsol: bool },
+}
+
+impl Action {
+    /// Addresses that receive funds, for the `--recipients-file` check
+    fn destinations(&self) -> Vec<Pubkey> {
+        match self {
+            Action::Transfer { destination, .. } | Action::TransferAll { destination, .. } => vec![*destination],
+            // Staked and wrapped SOL stays under the vault's authority
+            Action::Stake { .. }
+            | Action::Memo { .. }
+            | Action::WrapSol { .. }
+            | Action::UnwrapSol
+            | Action::CloseEmptyTokenAccounts { .. } => vec![],
+        }
+    }
+
+    /// Number of ephemeral signers the action's inner instructions need
+    fn ephemeral_signers(&self) -> u8 {
+        match self {
+            Action::Transfer { .. }
+            | Action::TransferAll { .. }
+            | Action::Memo { .. }
+            | Action::WrapSol { .. }
+            | Action::UnwrapSol
+            | Action::CloseEmptyTokenAccounts { .. } => 0,
+            // The new stake account is created by, and must sign as, ephemeral signer 0
+            Action::Stake { .. } => 1,
+        }
+    }
+}
 
 fn print_usage() {
     println!("Create a vault transaction proposal for multisig approval");
     println!();
     println!("Usage:");
-    println!("  cargo run --bin create-proposal -- <multisig_address> <command> [args...] [mainnet]");
+    println!("  cargo run --bin create-proposal -- <multisig_address> <command> [args...] [--vault <index>] [--max-retries <n>] [--dump-message] [--proposer <path>] [--recipients-file <path>] [--index <n>] [--no-auto-increment] [--fee-estimate] [--links] [--open] [--max-accounts <n>] [--ephemeral-signers <n>] [mainnet]");
+    println!();
+    println!("Options:");
+    println!("  --vault <index>    Vault to propose from (default 0)");
+    println!("  --dump-message     Print the compiled vault transaction message before sending");
+    println!("  --links            Print explorer and Squads app links for the new proposal");
+    println!("  --open             Open the new proposal in the Squads app (prints the URL if that fails)");
+    println!("  --fee-estimate     Print the network fee and rent the proposal would cost, and exit without sending");
+    println!("  --proposer <path>  Keypair that creates the proposal and pays rent (default {})", DEFAULT_PROPOSER);
+    println!("  --recipients-file <path>");
+    println!("                     Abort unless every destination is listed in this allowlist");
+    println!("  --max-retries <n>  Retry at the next index if another proposal took ours (default {})", DEFAULT_MAX_RETRIES);
+    println!("  --index <n>        Only create the proposal at index n; fail if that index is not the next free one");
+    println!("  --max-accounts <n> Account limit to check the future execute transaction against (default {})", DEFAULT_MAX_ACCOUNTS);
+    println!("  --sol <amount>     Give a transfer/stake/wrap-sol amount in SOL instead of <amount_lamports>");
+    println!("  --ephemeral-signers <n>");
+    println!("                     Ephemeral signers to reserve on the vault transaction (at least what the command needs)");
+    println!("  --no-auto-increment");
+    println!("                     Fail instead of skipping past transaction indexes that are already taken");
     println!();
     println!("Commands:");
     println!("  transfer <destination> <amount_lamports>");
     println!("      Transfer SOL from the vault to a destination address");
     println!();
+    println!("  transfer-all <destination> [--leave <lamports>]");
+    println!("      Transfer the vault's whole balance, keeping it rent-exempt (plus --leave as a");
+    println!("      buffer). The amount is fixed when the proposal is created, so later deposits");
+    println!("      stay in the vault and withdrawals before execution make it fail.");
+    println!();
+    println!("  stake <validator_vote_account> <amount_lamports>");
+    println!("      Create a stake account funded by the vault and delegate it to a validator.");
+    println!("      The vault is the stake and withdraw authority. The rent-exempt reserve is");
+    println!("      added on top of the delegated amount.");
+    println!();
+    println!("  memo <text>");
+    println!("      Record an SPL memo signed by the vault when the proposal executes (at most {} bytes)", MAX_MEMO_BYTES);
+    println!();
+    println!("  wrap-sol <amount_lamports>");
+    println!("      Move SOL into the vault's wrapped SOL token account (created if missing) and sync it");
+    println!();
+    println!("  unwrap-sol");
+    println!("      Close the vault's wrapped SOL token account, returning its balance to the vault as SOL");
+    println!();
+    println!("  close-empty-token-accounts [--include-wsol]");
+    println!("      Close the vault's zero-balance token accounts (SPL Token and Token-2022), returning");
+    println!("      their rent to the vault. At most {} per proposal; rerun after execution for the rest.", MAX_CLOSES_PER_PROPOSAL);
+    println!("      Wrapped SOL accounts are skipped unless --include-wsol, which closes them whatever");
+    println!("      their balance (the wrapped SOL returns to the vault too).");
+    println!();
     println!("Examples:");
     println!("  # Transfer 0.1 SOL (100,000,000 lamports)");
     println!("  cargo run --bin create-proposal -- BJbRt... transfer DestAddr... 100000000");
+    println!("  cargo run --bin create-proposal -- BJbRt... transfer DestAddr... --sol 0.1");
     println!();
     println!("  # Transfer on mainnet");
     println!("  cargo run --bin create-proposal -- BJbRt... transfer DestAddr... 100000000 mainnet");
 }
 
+/// Flags accepted anywhere on the command line
+struct Options {
+    max_retries: u32,
+    vault_index: u8,
+    proposer_path: String,
+    recipients_file: Option<String>,
+    leave: Option<u64>,
+    /// `--sol <amount>` in lamports, in place of the `<amount_lamports>` argument
+    sol: Option<u64>,
+    include_wsol: bool,
+    dump_message: bool,
+    index: Option<u64>,
+    auto_increment: bool,
+    fee_estimate: bool,
+    links: bool,
+    open: bool,
+    max_accounts: usize,
+    /// `--ephemeral-signers <n>`, at least what the action needs
+    ephemeral_signers: Option<u8>,
+}
+
+impl Options {
+    /// Remove the flags from `args`, leaving only the positional arguments
+    fn take_from(args: &mut Vec<String>) -> Result<Self, String> {
+        let dump_message = take_flag(args, "--dump-message");
+        let include_wsol = take_flag(args, "--include-wsol");
+        let fee_estimate = take_flag(args, "--fee-estimate");
+        let links = take_flag(args, "--links");
+        let open = take_flag(args, "--open");
+        let auto_increment = !take_flag(args, "--no-auto-increment");
+        let index = match take_flag_value(args, "--index")? {
+            Some(value) => Some(value.parse().map_err(|_| format!("invalid --index value '{}'", value))?),
+            None => None,
+        };
+        let max_retries = match take_flag_value(args, "--max-retries")? {
+            Some(value) => value
+                .parse()
+                .map_err(|_| format!("invalid --max-retries value '{}'", value))?,
+            None => DEFAULT_MAX_RETRIES,
+        };
+        let vault_index = match take_flag_value(args, "--vault")? {
+            Some(value) => parse_vault_index(&value)?,
+            None => 0,
+        };
+        let proposer_path = take_flag_value(args, "--proposer")?.unwrap_or_else(|| DEFAULT_PROPOSER.to_string());
+        let recipients_file = take_flag_value(args, "--recipients-file")?;
+        let max_accounts = match take_flag_value(args, "--max-accounts")? {
+            Some(value) => value
+                .parse()
+                .map_err(|_| format!("invalid --max-accounts value '{}'", value))?,
+            None => DEFAULT_MAX_ACCOUNTS,
+        };
+        let ephemeral_signers = match take_flag_value(args, "--ephemeral-signers")? {
+            Some(value) => Some(
+                value
+                    .parse()
+                    .map_err(|_| format!("invalid --ephemeral-signers value '{}' (0-255)", value))?,
+            ),
+            None => None,
+        };
+        let leave = match take_flag_value(args, "--leave")? {
+            Some(value) => Some(value.parse().map_err(|_| format!("invalid --leave value '{}'", value))?),
+            None => None,
+        };
+
+        let sol = take_flag_value(args, "--sol")?.map(|value| parse_sol(&value)).transpose()?;
+
+        Ok(Options {
+            max_retries,
+            vault_index,
+            proposer_path,
+            recipients_file,
+            leave,
+            sol,
+            include_wsol,
+            dump_message,
+            index,
+            auto_increment,
+            fee_estimate,
+            links,
+            open,
+            max_accounts,
+            ephemeral_signers,
+        })
+    }
+}
+
+/// The amount of a lamports command: `--sol` if given, else the `<amount_lamports>` argument at
+/// `args[index]`. Also returns the index of the argument after it (the network).
+fn lamports_arg(args: &[String], index: usize, sol: Option<u64>) -> (u64, usize) {
+    match sol {
+        Some(lamports) => (lamports, index),
+        None => (args[index].parse().expect("Invalid amount"), index + 1),
+    }
+}
+
+/// Build the create + propose + approve instructions for `transaction_index`, with the size
+/// of the vault transaction account they create
+fn build_proposal_instructions(
+    client: &RpcClient,
+    creator: &Keypair,
+    multisig_pda: Pubkey,
+    options: &Options,
+    new_transaction_index: u64,
+    action: &Action,
+    auto_approve: bool,
+) -> (Vec<Instruction>, usize) {
+    let vault_index = options.vault_index;
+    let (vault_pda, _) = get_vault_pda(&multisig_pda, vault_index, Some(&program_id()));
+    let (transaction_pda, _) = get_transaction_pda(&multisig_pda, new_transaction_index, Some(&program_id()));
+    let (proposal_pda, _) = get_proposal_pda(&multisig_pda, new_transaction_index, Some(&program_id()));
+
+    println!();
+    println!("Transaction Index: {}", new_transaction_index);
+    println!("Transaction PDA: {}", transaction_pda);
+    println!("Proposal PDA: {}", proposal_pda);
+
+    // Ephemeral signers are PDAs of the transaction that the program signs for during
+    // execution, so inner instructions can create fresh accounts without a keypair
+    let count = options.ephemeral_signers.unwrap_or_else(|| action.ephemeral_signers());
+    let ephemeral_signers = ephemeral_signer_pdas(&transaction_pda, count);
+    for (index, pubkey) in ephemeral_signers.iter().enumerate() {
+        println!("Ephemeral Signer {}: {}", index, pubkey);
+    }
+
+    // Build the inner instructions that will execute from the vault
+    let instructions: Vec<Instruction> = match action {
+        Action::Transfer { destination, amount } => {
+            vec![system_instruction::transfer(&vault_pda, destination, *amount)]
+        }
+        Action::TransferAll { destination, leave } => {
+            // The vault is a system account, so it has to stay rent-exempt
+            let balance = client.get_balance(&vault_pda).expect("Failed to fetch vault balance");
+            let rent_minimum = client
+                .get_minimum_balance_for_rent_exemption(0)
+                .expect("Failed to fetch rent-exempt minimum");
+            let keep = rent_minimum.saturating_add(*leave);
+
+            println!();
+            println!("Vault Balance: {} lamports ({:.9} SOL)", balance, balance as f64 / 1_000_000_000.0);
+            println!("Rent-Exempt Minimum: {} lamports", rent_minimum);
+            println!("Leave: {} lamports", leave);
+
+            let amount = match balance.checked_sub(keep) {
+                Some(amount) if amount > 0 => amount,
+                _ => {
+                    println!("\nError: Vault balance does not exceed the {} lamports it must keep", keep);
+                    std::process::exit(1);
+                }
+            };
+            println!("Transfer Amount: {} lamports ({:.9} SOL)", amount, amount as f64 / 1_000_000_000.0);
+            println!("Note: The amount is fixed now; if the vault balance drops before execution, execution fails.");
+
+            vec![system_instruction::transfer(&vault_pda, destination, amount)]
+        }
+        Action::Stake { vote_account, amount } => {
+            // The stake account is ephemeral signer 0, so the program can sign its creation
+            let stake_account = ephemeral_signers[0];
+            let rent_reserve = client
+                .get_minimum_balance_for_rent_exemption(StakeStateV2::size_of())
+                .expect("Failed to fetch stake account rent");
+            let total_lamports = amount.checked_add(rent_reserve).expect("Stake amount overflows u64");
+
+            println!();
+            println!("Stake Account: {} (ephemeral signer 0)", stake_account);
+            println!("Delegated: {} lamports ({:.9} SOL)", amount, *amount as f64 / 1_000_000_000.0);
+            println!("Rent Reserve: {} lamports", rent_reserve);
+            println!("Total from Vault: {} lamports", total_lamports);
+            println!("Stake/Withdraw Authority: {} (vault)", vault_pda);
+
+            stake::instruction::create_account_and_delegate_stake(
+                &vault_pda,
+                &stake_account,
+                vote_account,
+                &Authorized::auto(&vault_pda),
+                &Lockup::default(),
+                total_lamports,
+            )
+        }
+        Action::Memo { text } => {
+            println!();
+            println!("Memo: {} ({} bytes, signed by vault {})", text, text.len(), vault_pda);
+            vec![spl_memo::build_memo(text.as_bytes(), &[&vault_pda])]
+        }
+        Action::WrapSol { amount } => {
+            let wsol_ata = derive_ata(&vault_pda, &native_mint::ID, &spl_token::ID);
+            println!();
+            println!("Wrapped SOL Account: {} (vault's ATA, created if missing)", wsol_ata);
+            println!("Wrap: {} lamports ({:.9} SOL)", amount, *amount as f64 / 1_000_000_000.0);
+
+            // The vault pays the ATA's rent if it has to be created
+            vec![
+                create_associated_token_account_idempotent(&vault_pda, &vault_pda, &native_mint::ID, &spl_token::ID),
+                system_instruction::transfer(&vault_pda, &wsol_ata, *amount),
+                spl_token::instruction::sync_native(&spl_token::ID, &wsol_ata).expect("Failed to build sync_native"),
+            ]
+        }
+        Action::UnwrapSol => {
+            let wsol_ata = derive_ata(&vault_pda, &native_mint::ID, &spl_token::ID);
+            println!();
+            println!("Wrapped SOL Account: {} (closed on execution)", wsol_ata);
+            match client.get_account(&wsol_ata) {
+                Ok(account) => println!(
+                    "Current Balance: {} lamports, returned to the vault with the account's rent",
+                    account.lamports
+                ),
+                Err(_) => println!("Warning: the account does not exist yet, so execution fails unless it is created first"),
+            }
+
+            vec![spl_token::instruction::close_account(&spl_token::ID, &wsol_ata, &vault_pda, &vault_pda, &[])
+                .expect("Failed to build close_account")]
+        }
+        Action::CloseEmptyTokenAccounts { include_wsol } => {
+            let token_accounts = fetch_token_accounts(client, &vault_pda).unwrap_or_else(|e| {
+                println!("\nError: {}", e);
+                std::process::exit(1);
+            });
+            // The vault can close an account it owns unless someone else holds its close authority
+            let mut closeable: Vec<OwnedTokenAccount> = token_accounts
+                .into_iter()
+                .filter(|account| {
+                    let wsol = account.state.is_native();
+                    (if wsol { *include_wsol } else { account.state.amount == 0 })
+                        && account.state.close_authority.map_or(true, |authority| authority == vault_pda)
+                })
+                .collect();
+            closeable.sort_by_key(|account| account.address);
+            if closeable.is_empty() {
+                println!("\nError: Vault {} has no empty token accounts it can close", vault_pda);
+                std::process::exit(1);
+            }
+            let remaining = closeable.len().saturating_sub(MAX_CLOSES_PER_PROPOSAL);
+            closeable.truncate(MAX_CLOSES_PER_PROPOSAL);
+
+            println!();
+            println!("Token Accounts to Close (rent returns to the vault):");
+            for account in &closeable {
+                let wsol = if account.state.is_native() {
+                    format!(", {} wrapped lamports", account.state.amount)
+                } else {
+                    String::new()
+                };
+                println!("  {} (mint {}, {} lamports rent{})", account.address, account.state.mint, account.lamports, wsol);
+            }
+            let total: u64 = closeable.iter().map(|account| account.lamports).sum();
+            println!("Total Reclaimed: {} lamports ({:.9} SOL)", total, total as f64 / 1_000_000_000.0);
+            if remaining > 0 {
+                println!(
+                    "Note: {} more empty account(s) didn't fit in this proposal; run again once it executes.",
+                    remaining
+                );
+            }
+            println!("Note: An account that receives tokens before execution makes the whole proposal fail.");
+
+            closeable
+                .iter()
+                .map(|account| {
+                    spl_token_2022::instruction::close_account(
+                        &account.token_program,
+                        &account.address,
+                        &vault_pda,
+                        &vault_pda,
+                        &[],
+                    )
+                    .expect("Failed to build close_account")
+                })
+                .collect()
+        }
+    };
+
+    // Compile the transaction message
+    let transaction_message = TransactionMessage::try_compile(&vault_pda, &instructions, &[])
+        .expect("Failed to compile transaction message");
+
+    let message_bytes = transaction_message
+        .try_to_vec()
+        .expect("Failed to serialize message");
+
+    if options.dump_message {
+        println!();
+        print_compiled_message(&message_bytes);
+    }
+    let vault_message = VaultTransactionMessage::try_from(transaction_message).expect("Failed to decode transaction message");
+    let execute_size = estimate_execute_size(
+        &multisig_pda,
+        new_transaction_index,
+        vault_index,
+        ephemeral_signers.len() as u8,
+        &vault_message,
+        &creator.pubkey(),
+    );
+    check_execute_size(&execute_size, options.max_accounts);
+    let transaction_size = VaultTransaction::size(ephemeral_signers.len() as u8, &message_bytes)
+        .expect("Failed to size vault transaction");
+
+    let create_vault_tx_ix = vault_transaction_create_ix(
+        &multisig_pda,
+        new_transaction_index,
+        &creator.pubkey(),
+        vault_index,
+        ephemeral_signers.len() as u8,
+        message_bytes,
+    );
+    let create_proposal_ix = proposal_create_ix(&multisig_pda, new_transaction_index, &creator.pubkey());
+
+    let mut instructions = vec![create_vault_tx_ix, create_proposal_ix];
+    if auto_approve {
+        instructions.push(proposal_approve_ix(&multisig_pda, new_transaction_index, &creator.pubkey()));
+    }
+    (instructions, transaction_size)
+}
+
+/// A memo must carry text and fit in the vault and execute transactions
+fn check_memo(text: &str) -> Result<(), String> {
+    if text.trim().is_empty() {
+        return Err("memo must not be empty".to_string());
+    }
+    if text.len() > MAX_MEMO_BYTES {
+        return Err(format!("memo must be at most {} bytes, got {}", MAX_MEMO_BYTES, text.len()));
+    }
+    Ok(())
+}
+
+/// Warn when the transaction that will execute this proposal nears a size limit
+fn check_execute_size(size: &ExecuteSize, max_accounts: usize) {
+    println!();
+    println!("Execute Transaction (estimate): {} of {} bytes, {} of {} accounts", size.bytes, PACKET_DATA_SIZE, size.accounts, max_accounts);
+    let near = |value: usize, limit: usize| value * 100 >= limit * EXECUTE_SIZE_WARN_PERCENT;
+    if size.bytes > PACKET_DATA_SIZE || size.accounts > max_accounts {
+        println!("WARNING: The proposal can be approved but not executed as a legacy transaction.");
+        println!("  Execute it with --tx-version 0 --lookup-table <address>, with the vault message's accounts in the table.");
+    } else if near(size.bytes, PACKET_DATA_SIZE) || near(size.accounts, max_accounts) {
+        println!("WARNING: The execute transaction is close to the limit; another account or signer may push it over.");
+        println!("  If execution fails, use execute-proposal --tx-version 0 --lookup-table <address>.");
+    }
+}
+
+/// `--fee-estimate` for the proposal transaction: the network fee plus rent for the vault
+/// transaction and proposal accounts it creates (the proposal is sized by the member count)
+#[allow(clippy::result_large_err)]
+fn estimate_fees(
+    client: &RpcClient,
+    instructions: &[Instruction],
+    payer: &Pubkey,
+    transaction_size: usize,
+    member_count: usize,
+) -> Result<FeeEstimate, ClientError> {
+    let mut estimate = FeeEstimate::new(client, instructions, payer)?;
+    estimate.add_rent(client, "vault transaction", transaction_size)?;
+    estimate.add_rent(client, "proposal", Proposal::size(member_count))?;
+    Ok(estimate)
+}
+
+/// The first index from `multisig_index + 1` with no transaction account yet.
+///
+/// A transaction account at `multisig_index + 1` means our multisig read is behind another
+/// member's proposal. Errors with the taken index if `auto_increment` is off.
+fn next_free_index(
+    client: &RpcClient,
+    multisig_pda: &Pubkey,
+    multisig_index: u64,
+    auto_increment: bool,
+) -> Result<u64, String> {
+    let mut index = multisig_index + 1;
+    loop {
+        let (transaction_pda, _) = get_transaction_pda(multisig_pda, index, Some(&program_id()));
+        let taken = client
+            .get_account_with_commitment(&transaction_pda, client.commitment())
+            .map_err(|e| format!("failed to check transaction account {}: {}", transaction_pda, e))?
+            .value
+            .is_some();
+        if !taken {
+            return Ok(index);
+        }
+        if !auto_increment {
+            return Err(format!(
+                "transaction index {} is already in use by another proposal (drop --no-auto-increment to skip past it)",
+                index
+            ));
+        }
+        println!("\nTransaction index {} is already in use, trying {}...", index, index + 1);
+        index += 1;
+    }
+}
+
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    let mut args: Vec<String> = env::args().collect();
+    let help = take_help(&mut args);
+    take_program_id_or_exit(&mut args);
+    let send_options = SendOptions::take_from_or_exit(&mut args);
+
+    let options = match Options::take_from(&mut args) {
+        Ok(options) => options,
+        Err(e) => {
+            println!("Error: {}", e);
+            return;
+        }
+    };
+    reject_unknown_flags_or_exit(&args);
 
-    if args.len() < 3 {
+    if help || args.len() < 3 {
         print_usage();
         return;
     }
 
-    let multisig_pda: Pubkey = args[1].parse().expect("Invalid multisig address");
+    let multisig_pda: Pubkey = resolve_pubkey(&args[1]);
     let command = &args[2];
 
-    // Parse command and build the instruction
-    let (inner_instructions, network, description) = match command.as_str() {
+    // Parse command into the action to propose
+    let (action, network, description) = match command.as_str() {
         "transfer" => {
-            if args.len() < 5 {
-                println!("Error: transfer requires <destination> <amount_lamports>");
+            if args.len() < 5 - options.sol.is_some() as usize {
+                println!("Error: transfer requires <destination> <amount_lamports> (or --sol <amount>)");
                 print_usage();
                 return;
             }
-            let destination: Pubkey = args[3].parse().expect("Invalid destination address");
-            let amount: u64 = args[4].parse().expect("Invalid amount");
-            let network = args.get(5).map(|s| s.as_str()).unwrap_or("devnet");
+            let destination: Pubkey = resolve_pubkey(&args[3]);
+            let (amount, next) = lamports_arg(&args, 4, options.sol);
+            let network = Network::from_arg(args.get(next).map(|s| s.as_str()).unwrap_or("devnet"));
 
             // We'll set the vault PDA as the "from" address later after we derive it
             (
-                vec![("transfer", destination, amount)],
+                Action::Transfer { destination, amount },
                 network,
-                format!("Transfer {} lamports to {}", amount, destination),
+                format!("Transfer {} to {}", describe_lamports(amount), destination),
+            )
+        }
+        "transfer-all" => {
+            if args.len() < 4 {
+                println!("Error: transfer-all requires <destination>");
+                print_usage();
+                return;
+            }
+            let destination: Pubkey = resolve_pubkey(&args[3]);
+            let leave = options.leave.unwrap_or(0);
+            let network = Network::from_arg(args.get(4).map(|s| s.as_str()).unwrap_or("devnet"));
+
+            (
+                Action::TransferAll { destination, leave },
+                network,
+                format!("Transfer entire vault balance (leaving {} lamports above rent) to {}", leave, destination),
+            )
+        }
+        "stake" => {
+            if args.len() < 5 - options.sol.is_some() as usize {
+                println!("Error: stake requires <validator_vote_account> <amount_lamports> (or --sol <amount>)");
+                print_usage();
+                return;
+            }
+            let vote_account: Pubkey = resolve_pubkey(&args[3]);
+            let (amount, next) = lamports_arg(&args, 4, options.sol);
+            let network = Network::from_arg(args.get(next).map(|s| s.as_str()).unwrap_or("devnet"));
+
+            (
+                Action::Stake { vote_account, amount },
+                network,
+                format!("Stake {} with validator {}", describe_lamports(amount), vote_account),
+            )
+        }
+        "memo" => {
+            if args.len() < 4 {
+                println!("Error: memo requires <text>");
+                print_usage();
+                return;
+            }
+            let text = args[3].clone();
+            if let Err(e) = check_memo(&text) {
+                println!("Error: {}", e);
+                return;
+            }
+            let network = Network::from_arg(args.get(4).map(|s| s.as_str()).unwrap_or("devnet"));
+
+            (
+                Action::Memo { text: text.clone() },
+                network,
+                format!("Memo \"{}\" signed by the vault", text),
+            )
+        }
+        "wrap-sol" => {
+            if args.len() < 4 - options.sol.is_some() as usize {
+                println!("Error: wrap-sol requires <amount_lamports> (or --sol <amount>)");
+                print_usage();
+                return;
+            }
+            let (amount, next) = lamports_arg(&args, 3, options.sol);
+            if amount == 0 {
+                println!("Error: wrap-sol amount must be greater than zero");
+                return;
+            }
+            let network = Network::from_arg(args.get(next).map(|s| s.as_str()).unwrap_or("devnet"));
+
+            (Action::WrapSol { amount }, network, format!("Wrap {} into the vault's wrapped SOL account", describe_lamports(amount)))
+        }
+        "unwrap-sol" => {
+            let network = Network::from_arg(args.get(3).map(|s| s.as_str()).unwrap_or("devnet"));
+
+            (Action::UnwrapSol, network, "Unwrap the vault's wrapped SOL account back to SOL".to_string())
+        }
+        "close-empty-token-accounts" => {
+            let network = Network::from_arg(args.get(3).map(|s| s.as_str()).unwrap_or("devnet"));
+
+            (
+                Action::CloseEmptyTokenAccounts { include_wsol: options.include_wsol },
+                network,
+                "Close the vault's empty token accounts, reclaiming their rent".to_string(),
             )
         }
         _ => {
@@ -89,158 +745,175 @@ fn main() {
         }
     };
 
-    let rpc_url = match network {
-        "mainnet" => MAINNET_RPC,
-        _ => DEVNET_RPC,
-    };
+    if let Some(path) = &options.recipients_file {
+        let recipients = load_recipients(path).unwrap_or_else(|e| {
+            println!("Error: {}", e);
+            std::process::exit(1);
+        });
+        if !check_recipients(&recipients, &action.destinations()) {
+            println!("\nAborting: every destination must be listed in {}", path);
+            std::process::exit(1);
+        }
+        println!();
+    }
+
+    if options.leave.is_some() && !matches!(action, Action::TransferAll { .. }) {
+        println!("Error: --leave only applies to transfer-all");
+        return;
+    }
+    if options.sol.is_some() && !matches!(action, Action::Transfer { .. } | Action::Stake { .. } | Action::WrapSol { .. }) {
+        println!("Error: --sol only applies to transfer, stake and wrap-sol");
+        return;
+    }
+    if options.include_wsol && !matches!(action, Action::CloseEmptyTokenAccounts { .. }) {
+        println!("Error: --include-wsol only applies to close-empty-token-accounts");
+        return;
+    }
+    if let Some(count) = options.ephemeral_signers {
+        if count < action.ephemeral_signers() {
+            println!(
+                "Error: --ephemeral-signers {} is fewer than the {} this action needs",
+                count,
+                action.ephemeral_signers()
+            );
+            return;
+        }
+    }
 
-    let client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
-    let creator = read_keypair_file("../member1.json").expect("Failed to read member1.json");
+    let client = RpcClient::new_with_commitment(network.rpc_url(), CommitmentConfig::confirmed());
+    let creator = read_keypair_file(&options.proposer_path)
+        .unwrap_or_else(|e| panic!("Failed to read proposer keypair {}: {}", options.proposer_path, e));
+
+    let (vault_pda, _) = get_vault_pda(&multisig_pda, options.vault_index, Some(&program_id()));
 
     // Fetch multisig to get current transaction index
-    let multisig_account = client
-        .get_account(&multisig_pda)
-        .expect("Failed to fetch multisig account");
-    let multisig = Multisig::try_deserialize(&mut multisig_account.data.as_slice())
-        .expect("Failed to deserialize multisig");
-
-    // The new transaction will be at index + 1
-    let new_transaction_index = multisig.transaction_index + 1;
-    let vault_index: u8 = 0;
-
-    // Derive PDAs
-    let (vault_pda, _) = get_vault_pda(&multisig_pda, vault_index, None);
-    let (transaction_pda, _) = get_transaction_pda(&multisig_pda, new_transaction_index, None);
-    let (proposal_pda, _) = get_proposal_pda(&multisig_pda, new_transaction_index, None);
-
-    println!("=== Create Multisig Proposal ({}) ===\n", network.to_uppercase());
+    let mut multisig = fetch_multisig_or_exit(&client, &multisig_pda);
+
+    // The program rejects proposals from non-members and members without Initiate
+    if !multisig.member_has_permission(creator.pubkey(), Permission::Initiate) {
+        if multisig.is_member(creator.pubkey()).is_none() {
+            println!("Error: Proposer {} is not a member of this multisig", creator.pubkey());
+        } else {
+            println!("Error: Proposer {} does not have Initiate permission", creator.pubkey());
+        }
+        println!("Use --proposer <keypair_path> to propose with a member that has Initiate.");
+        std::process::exit(1);
+    }
+    let auto_approve = multisig.member_has_permission(creator.pubkey(), Permission::Vote);
+
+    println!("=== Create Multisig Proposal ({}) ===\n", network.name().to_uppercase());
     println!("Multisig: {}", multisig_pda);
     println!("Vault: {}", vault_pda);
     println!("Creator: {}", creator.pubkey());
+    if !auto_approve {
+        println!("Note: Creator has no Vote permission, so the proposal will not be auto-approved.");
+    }
     println!("Threshold: {} of {}", multisig.threshold, multisig.members.len());
     println!();
-    println!("Transaction Index: {}", new_transaction_index);
-    println!("Transaction PDA: {}", transaction_pda);
-    println!("Proposal PDA: {}", proposal_pda);
-    println!();
     println!("Action: {}", description);
 
-    // Build the inner instructions that will execute from the vault
-    let instructions: Vec<Instruction> = inner_instructions
-        .iter()
-        .map(|(cmd, dest, amount)| match *cmd {
-            "transfer" => system_instruction::transfer(&vault_pda, dest, *amount),
-            _ => panic!("Unknown command"),
-        })
-        .collect();
-
-    // Compile the transaction message
-    let transaction_message = TransactionMessage::try_compile(&vault_pda, &instructions, &[])
-        .expect("Failed to compile transaction message");
-
-    let message_bytes = transaction_message
-        .try_to_vec()
-        .expect("Failed to serialize message");
-
-    // === Instruction 1: Create Vault Transaction ===
-    let vault_tx_accounts = squads_multisig_program::accounts::VaultTransactionCreate {
-        multisig: multisig_pda,
-        transaction: transaction_pda,
-        creator: creator.pubkey(),
-        rent_payer: creator.pubkey(),
-        system_program: system_program::ID,
-    };
-
-    let vault_tx_data = squads_multisig_program::instruction::VaultTransactionCreate {
-        args: squads_multisig_program::instructions::VaultTransactionCreateArgs {
-            vault_index,
-            ephemeral_signers: 0,
-            transaction_message: message_bytes,
-            memo: None,
-        },
-    };
-
-    let create_vault_tx_ix = Instruction {
-        program_id: squads_multisig_program::ID,
-        accounts: vault_tx_accounts.to_account_metas(Some(false)),
-        data: vault_tx_data.data(),
-    };
-
-    // === Instruction 2: Create Proposal ===
-    let proposal_accounts = squads_multisig_program::accounts::ProposalCreate {
-        multisig: multisig_pda,
-        proposal: proposal_pda,
-        creator: creator.pubkey(),
-        rent_payer: creator.pubkey(),
-        system_program: system_program::ID,
-    };
-
-    let proposal_data = squads_multisig_program::instruction::ProposalCreate {
-        args: squads_multisig_program::instructions::ProposalCreateArgs {
-            transaction_index: new_transaction_index,
-            draft: false, // Active immediately so members can vote
-        },
-    };
-
-    let create_proposal_ix = Instruction {
-        program_id: squads_multisig_program::ID,
-        accounts: proposal_accounts.to_account_metas(Some(false)),
-        data: proposal_data.data(),
-    };
-
-    // === Instruction 3: Creator auto-approves ===
-    let approve_accounts = squads_multisig_program::accounts::ProposalVote {
-        multisig: multisig_pda,
-        proposal: proposal_pda,
-        member: creator.pubkey(),
-    };
-
-    let approve_data = squads_multisig_program::instruction::ProposalApprove {
-        args: squads_multisig_program::instructions::ProposalVoteArgs { memo: None },
-    };
-
-    let approve_ix = Instruction {
-        program_id: squads_multisig_program::ID,
-        accounts: approve_accounts.to_account_metas(Some(false)),
-        data: approve_data.data(),
-    };
+    let mut retries = 0;
+    loop {
+        // The new transaction will be at index + 1, unless that's already taken
+        let new_transaction_index =
+            next_free_index(&client, &multisig_pda, multisig.transaction_index, options.auto_increment)
+                .unwrap_or_else(|e| {
+                    println!("\nError: {}", e);
+                    std::process::exit(1);
+                });
+        if let Some(index) = options.index {
+            if index != new_transaction_index {
+                println!(
+                    "\nError: --index {} is not available; the next free transaction index is {}",
+                    index, new_transaction_index
+                );
+                std::process::exit(1);
+            }
+        }
+        let (proposal_pda, _) = get_proposal_pda(&multisig_pda, new_transaction_index, Some(&program_id()));
+        let (instructions, transaction_size) = build_proposal_instructions(
+            &client,
+            &creator,
+            multisig_pda,
+            &options,
+            new_transaction_index,
+            &action,
+            auto_approve,
+        );
 
-    println!("\nCreating proposal...");
+        if options.fee_estimate {
+            let mut estimate = estimate_fees(
+                &client,
+                &instructions,
+                &creator.pubkey(),
+                transaction_size,
+                multisig.members.len(),
+            )
+            .unwrap_or_else(|e| {
+                println!("\nError: Failed to estimate fees: {}", e);
+                std::process::exit(1);
+            });
+            if let Some(profile) = send_options.fee_profile {
+                estimate.priority_fee = profile.max_priority_fee();
+            }
+            println!();
+            estimate.print();
+            println!("\nNothing was sent (--fee-estimate).");
+            return;
+        }
 
-    let recent_blockhash = client.get_latest_blockhash().expect("Failed to get blockhash");
-    let transaction = Transaction::new_signed_with_payer(
-        &[create_vault_tx_ix, create_proposal_ix, approve_ix],
-        Some(&creator.pubkey()),
-        &[&creator],
-        recent_blockhash,
-    );
+        println!("\nCreating proposal...");
 
-    match client.send_and_confirm_transaction(&transaction) {
-        Ok(sig) => {
-            println!("\nProposal created successfully!");
-            println!("Transaction: {}", sig);
-            println!();
-            println!("=== Proposal Details ===");
-            println!("Proposal Index: {}", new_transaction_index);
-            println!("Proposal Address: {}", proposal_pda);
-            println!("Status: Active (awaiting {} more approval(s))", multisig.threshold - 1);
-            println!();
-            println!("Share this with other members to approve:");
-            println!("  cargo run --bin approve-proposal -- {} {} [mainnet]",
-                     multisig_pda, new_transaction_index);
-            println!();
-            println!("After threshold is met, execute with:");
-            println!("  cargo run --bin execute-proposal -- {} {} [mainnet]",
-                     multisig_pda, new_transaction_index);
+        match build_and_send(&client, &instructions, &creator.pubkey(), &[&creator], &send_options) {
+            Ok(sig) => {
+                record(network, &multisig_pda, &format!("propose {}: {}", new_transaction_index, description), &creator.pubkey(), &sig);
+                println!("\nProposal created successfully!");
+                println!("Transaction: {}", sig);
+                println!();
+                println!("=== Proposal Details ===");
+                println!("Proposal Index: {}", new_transaction_index);
+                println!("Proposal Address: {}", proposal_pda);
+                let approvals = if auto_approve { 1 } else { 0 };
+                println!("Status: Active (awaiting {} more approval(s))", multisig.threshold - approvals);
+                println!();
+                println!("Share this with other members to approve:");
+                println!("  cargo run --bin approve-proposal -- {} {} [mainnet]",
+                         multisig_pda, new_transaction_index);
+                println!();
+                println!("After threshold is met, execute with:");
+                println!("  cargo run --bin execute-proposal -- {} {} [mainnet]",
+                         multisig_pda, new_transaction_index);
 
-            let cluster_param = if network == "mainnet" { "" } else { "?cluster=devnet" };
-            println!("\nView on Solana Explorer:");
-            println!("https://explorer.solana.com/tx/{}{}", sig, cluster_param);
-            println!("\nView on Squads UI:");
-            println!("https://v4.squads.so/squads/{}/tx/{}", multisig_pda, new_transaction_index);
-        }
-        Err(e) => {
-            println!("\nFailed to create proposal: {}", e);
+                println!("\nView on Solana Explorer:");
+                println!("{}", network.explorer_tx_url(&sig));
+                println!("\nView on Squads UI:");
+                println!("{}", squads_app_tx_url(&multisig_pda, new_transaction_index));
+                if options.links {
+                    network.print_links(&multisig_pda, Some(new_transaction_index), Some(&sig));
+                }
+                if options.open {
+                    open_in_browser(&squads_app_tx_url(&multisig_pda, new_transaction_index));
+                }
+                return;
+            }
+            // A pinned --index can't move, so a collision there is a plain failure
+            Err(e) if options.index.is_none() && retries < options.max_retries => {
+                let current = fetch_multisig_or_exit(&client, &multisig_pda);
+                if !is_index_collision(&e, new_transaction_index, current.transaction_index) {
+                    println!("\nFailed to create proposal: {}", e);
+                    return;
+                }
+                retries += 1;
+                println!(
+                    "\nTransaction index {} was taken by another proposal, retrying ({}/{})...",
+                    new_transaction_index, retries, options.max_retries
+                );
+                multisig = current;
+            }
+            Err(e) => {
+                println!("\nFailed to create proposal: {}", e);
+                return;
+            }
         }
     }
 }
@@ -0,0 +1,68 @@
+//! Decode raw instruction data into its instruction name and arguments
+//!
+//! Offline: nothing is fetched or sent. Useful for checking what an instruction copied from an
+//! explorer, a wallet prompt or another tool's output actually does before approving it.
+//!
+//! Squads instructions are matched on their Anchor discriminator and their args decoded with the
+//! program crate's types; vault transaction messages inside `vault_transaction_create` and
+//! `batch_add_transaction` are printed in full. System, SPL Token, Token-2022, associated token
+//! account, memo and compute budget instructions are decoded from their known layouts.
+//!
+//! Usage:
+//!   cargo run --bin decode-instruction -- <program_id> <base64_data> [--program-id <id>]
+//!
+//! `<program_id>` is the program the instruction is addressed to, and accepts address book names.
+//! The global `--program-id` sets which program is treated as Squads (default: the mainnet
+//! deployment).
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use squads_rust::addressbook::resolve_pubkey;
+use squads_rust::args::{reject_unknown_flags_or_exit, take_help};
+use squads_rust::decode::decode_instruction;
+use squads_rust::message::print_compiled_message;
+use squads_rust::program::take_program_id_or_exit;
+use std::env;
+
+fn main() {
+    let mut args: Vec<String> = env::args().collect();
+    let help = take_help(&mut args);
+    take_program_id_or_exit(&mut args);
+    reject_unknown_flags_or_exit(&args);
+
+    if help || args.len() < 3 {
+        println!("Decode raw instruction data into its instruction name and arguments");
+        println!();
+        println!("Usage:");
+        println!("  cargo run --bin decode-instruction -- <program_id> <base64_data> [--program-id <id>]");
+        println!();
+        println!("Decodes Squads, System, SPL Token, Token-2022, ATA, Memo and Compute Budget");
+        println!("instructions. Offline: fetches and sends nothing.");
+        return;
+    }
+
+    let program = resolve_pubkey(&args[1]);
+    let data = STANDARD.decode(args[2].trim()).unwrap_or_else(|e| {
+        println!("Error: Instruction data is not valid base64: {}", e);
+        std::process::exit(1);
+    });
+
+    let decoded = decode_instruction(&program, &data).unwrap_or_else(|e| {
+        println!("Error: {}", e);
+        std::process::exit(1);
+    });
+
+    println!("=== Decoded Instruction ===");
+    println!("Program: {} ({})", decoded.program, program);
+    println!("Instruction: {}", decoded.name);
+    println!("Data: {} bytes", data.len());
+    if !decoded.fields.is_empty() {
+        println!();
+        for (name, value) in &decoded.fields {
+            println!("  {}: {}", name, value);
+        }
+    }
+    if let Some(message) = &decoded.transaction_message {
+        println!();
+        print_compiled_message(message);
+    }
+}
@@ -0,0 +1,268 @@
+//! End-to-end demo of the full multisig lifecycle on localnet
+//!
+//! Runs every step the other scripts cover, with throwaway keypairs generated in memory:
+//! 1. Airdrop SOL to three new members
+//! 2. Create a 2-of-3 multisig
+//! 3. Fund the vault
+//! 4. Create a transfer proposal (member 1 proposes and approves)
+//! 5. Approve from member 2
+//! 6. Execute from member 1
+//! 7. Inspect the proposal and destination balance
+//!
+//! Requires a local `solana-test-validator` with the Squads program and an initialized
+//! program config (see init-program-config). Useful as a smoke test of the whole toolkit.
+//!
+//! Usage:
+//!   cargo run --bin demo [--program-id <address>]
+
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::{AccountMeta, Instruction},
+    signature::{Keypair, Signature, Signer},
+    system_instruction,
+    system_program,
+};
+use squads_multisig::anchor_lang::{AccountDeserialize, AnchorSerialize, InstructionData, ToAccountMetas};
+use squads_multisig::client::{multisig_create_v2, MultisigCreateAccountsV2, MultisigCreateArgsV2};
+use squads_multisig::pda::{
+    get_multisig_pda, get_program_config_pda, get_proposal_pda, get_transaction_pda, get_vault_pda,
+};
+use squads_multisig::squads_multisig_program;
+use squads_multisig::state::{Member, Permissions, Proposal, ProposalStatus};
+use squads_multisig::vault_transaction::VaultTransactionMessageExt;
+use squads_multisig_program::TransactionMessage;
+use squads_rust::airdrop::airdrop;
+use squads_rust::args::{reject_unknown_flags_or_exit, take_help};
+use squads_rust::network::Network;
+use squads_rust::permissions::PermissionsExt;
+use squads_rust::program::{check_program_or_exit, program_id, take_program_id_or_exit};
+use squads_rust::send::{build_and_send, SendOptions};
+use std::env;
+
+const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+const AIRDROP_LAMPORTS: u64 = 2 * LAMPORTS_PER_SOL;
+const VAULT_FUNDING_LAMPORTS: u64 = LAMPORTS_PER_SOL;
+const TRANSFER_LAMPORTS: u64 = LAMPORTS_PER_SOL / 10;
+const THRESHOLD: u16 = 2;
+
+/// Sign and send `instructions`, printing the signature for the step. Exits on failure.
+fn send(client: &RpcClient, step: &str, instructions: &[Instruction], payer: &Keypair, signers: &[&Keypair]) -> Signature {
+    match build_and_send(client, instructions, &payer.pubkey(), signers, &SendOptions::default()) {
+        Ok(sig) => {
+            println!("  {}: {}", step, sig);
+            sig
+        }
+        Err(e) => {
+            println!("\nFailed at step '{}': {}", step, e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn main() {
+    let mut args: Vec<String> = env::args().collect();
+    let help = take_help(&mut args);
+    take_program_id_or_exit(&mut args);
+    reject_unknown_flags_or_exit(&args);
+
+    if help {
+        println!("End-to-end demo of the full multisig lifecycle, with throwaway keypairs on localnet");
+        println!();
+        println!("Usage:");
+        println!("  cargo run --bin demo [--program-id <address>]");
+        println!();
+        println!("Requires a local solana-test-validator with the Squads program and an initialized program config.");
+        return;
+    }
+
+    let network = Network::Localnet;
+    let client = RpcClient::new_with_commitment(network.rpc_url(), CommitmentConfig::confirmed());
+    check_program_or_exit(&client);
+
+    println!("=== Squads Lifecycle Demo ({}) ===\n", network.name().to_uppercase());
+
+    // Step 1: Generate and fund members
+    let members: Vec<Keypair> = (0..3).map(|_| Keypair::new()).collect();
+    println!("Step 1: Airdrop to members");
+    for (i, member) in members.iter().enumerate() {
+        let sig = airdrop(&client, network, &member.pubkey(), AIRDROP_LAMPORTS).unwrap_or_else(|e| {
+            println!("Error: {}. Is solana-test-validator running?", e);
+            std::process::exit(1);
+        });
+        println!("  Member {} {}: {}", i + 1, member.pubkey(), sig);
+    }
+    let member1 = &members[0];
+    let member2 = &members[1];
+
+    // Step 2: Create a 2-of-3 multisig with no config authority
+    println!("\nStep 2: Create {}-of-{} multisig", THRESHOLD, members.len());
+    let create_key = Keypair::new();
+    let (multisig_pda, _) = get_multisig_pda(&create_key.pubkey(), Some(&program_id()));
+    let (program_config_pda, _) = get_program_config_pda(Some(&program_id()));
+    let treasury = network.squads_treasury(&client).unwrap_or_else(|e| {
+        println!("Error: {}", e);
+        println!("Run init-program-config against the local validator first.");
+        std::process::exit(1);
+    });
+
+    let all_permissions = Permissions::all();
+    let create_ix = multisig_create_v2(
+        MultisigCreateAccountsV2 {
+            program_config: program_config_pda,
+            treasury,
+            multisig: multisig_pda,
+            create_key: create_key.pubkey(),
+            creator: member1.pubkey(),
+            system_program: system_program::ID,
+        },
+        MultisigCreateArgsV2 {
+            config_authority: None,
+            threshold: THRESHOLD,
+            members: members
+                .iter()
+                .map(|m| Member { key: m.pubkey(), permissions: all_permissions })
+                .collect(),
+            time_lock: 0,
+            rent_collector: None,
+            memo: None,
+        },
+        Some(program_id()),
+    );
+    send(&client, "Create multisig", &[create_ix], member1, &[member1, &create_key]);
+    println!("  Multisig: {}", multisig_pda);
+
+    // Step 3: Fund the vault
+    println!("\nStep 3: Fund vault");
+    let vault_index: u8 = 0;
+    let (vault_pda, _) = get_vault_pda(&multisig_pda, vault_index, Some(&program_id()));
+    let fund_ix = system_instruction::transfer(&member1.pubkey(), &vault_pda, VAULT_FUNDING_LAMPORTS);
+    send(&client, "Fund vault", &[fund_ix], member1, &[member1]);
+    println!("  Vault: {}", vault_pda);
+
+    // Step 4: Create a transfer proposal; member 1 proposes and approves
+    println!("\nStep 4: Create transfer proposal");
+    let destination = Keypair::new().pubkey();
+    let transaction_index: u64 = 1;
+    let (transaction_pda, _) = get_transaction_pda(&multisig_pda, transaction_index, Some(&program_id()));
+    let (proposal_pda, _) = get_proposal_pda(&multisig_pda, transaction_index, Some(&program_id()));
+
+    let transfer_ix = system_instruction::transfer(&vault_pda, &destination, TRANSFER_LAMPORTS);
+    let transaction_message = TransactionMessage::try_compile(&vault_pda, &[transfer_ix], &[])
+        .expect("Failed to compile transaction message");
+    let message_bytes = transaction_message.try_to_vec().expect("Failed to serialize message");
+
+    let create_vault_tx_ix = Instruction {
+        program_id: program_id(),
+        accounts: squads_multisig_program::accounts::VaultTransactionCreate {
+            multisig: multisig_pda,
+            transaction: transaction_pda,
+            creator: member1.pubkey(),
+            rent_payer: member1.pubkey(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(Some(false)),
+        data: squads_multisig_program::instruction::VaultTransactionCreate {
+            args: squads_multisig_program::instructions::VaultTransactionCreateArgs {
+                vault_index,
+                ephemeral_signers: 0,
+                transaction_message: message_bytes,
+                memo: None,
+            },
+        }
+        .data(),
+    };
+    let create_proposal_ix = Instruction {
+        program_id: program_id(),
+        accounts: squads_multisig_program::accounts::ProposalCreate {
+            multisig: multisig_pda,
+            proposal: proposal_pda,
+            creator: member1.pubkey(),
+            rent_payer: member1.pubkey(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(Some(false)),
+        data: squads_multisig_program::instruction::ProposalCreate {
+            args: squads_multisig_program::instructions::ProposalCreateArgs {
+                transaction_index,
+                draft: false,
+            },
+        }
+        .data(),
+    };
+    let approve_ix = |member: &Keypair| Instruction {
+        program_id: program_id(),
+        accounts: squads_multisig_program::accounts::ProposalVote {
+            multisig: multisig_pda,
+            proposal: proposal_pda,
+            member: member.pubkey(),
+        }
+        .to_account_metas(Some(false)),
+        data: squads_multisig_program::instruction::ProposalApprove {
+            args: squads_multisig_program::instructions::ProposalVoteArgs { memo: None },
+        }
+        .data(),
+    };
+    send(
+        &client,
+        "Create proposal",
+        &[create_vault_tx_ix, create_proposal_ix, approve_ix(member1)],
+        member1,
+        &[member1],
+    );
+    println!("  Proposal: {} (transfer {} lamports to {})", proposal_pda, TRANSFER_LAMPORTS, destination);
+
+    // Step 5: Second approval reaches the threshold
+    println!("\nStep 5: Approve from member 2");
+    send(&client, "Approve", &[approve_ix(member2)], member2, &[member2]);
+
+    // Step 6: Execute. Remaining accounts are the message's static keys; the vault signs via CPI.
+    println!("\nStep 6: Execute proposal");
+    let transaction_account = client
+        .get_account(&transaction_pda)
+        .expect("Failed to fetch transaction account");
+    let vault_transaction =
+        squads_multisig_program::VaultTransaction::try_deserialize(&mut transaction_account.data.as_slice())
+            .expect("Failed to deserialize vault transaction");
+    let message = &vault_transaction.message;
+
+    let mut account_metas = squads_multisig_program::accounts::VaultTransactionExecute {
+        multisig: multisig_pda,
+        proposal: proposal_pda,
+        transaction: transaction_pda,
+        member: member1.pubkey(),
+    }
+    .to_account_metas(Some(false));
+    account_metas.extend(message.account_keys.iter().enumerate().map(|(index, pubkey)| AccountMeta {
+        pubkey: *pubkey,
+        is_signer: message.is_signer_index(index) && *pubkey != vault_pda,
+        is_writable: message.is_static_writable_index(index),
+    }));
+    let execute_ix = Instruction {
+        program_id: program_id(),
+        accounts: account_metas,
+        data: squads_multisig_program::instruction::VaultTransactionExecute {}.data(),
+    };
+    send(&client, "Execute", &[execute_ix], member1, &[member1]);
+
+    // Step 7: Inspect the result
+    println!("\nStep 7: Inspect result");
+    let proposal_account = client.get_account(&proposal_pda).expect("Failed to fetch proposal account");
+    let proposal = Proposal::try_deserialize(&mut proposal_account.data.as_slice())
+        .expect("Failed to deserialize proposal");
+    let executed = matches!(proposal.status, ProposalStatus::Executed { .. });
+    let destination_balance = client.get_balance(&destination).expect("Failed to get balance");
+    let vault_balance = client.get_balance(&vault_pda).expect("Failed to get balance");
+
+    println!("  Proposal status: {}", if executed { "Executed" } else { "NOT executed" });
+    println!("  Approvals: {}", proposal.approved.len());
+    println!("  Destination balance: {} lamports", destination_balance);
+    println!("  Vault balance: {} lamports", vault_balance);
+
+    if !executed || destination_balance != TRANSFER_LAMPORTS {
+        println!("\nDemo FAILED: expected an executed proposal and {} lamports at the destination", TRANSFER_LAMPORTS);
+        std::process::exit(1);
+    }
+
+    println!("\nDemo complete: the full lifecycle succeeded.");
+}
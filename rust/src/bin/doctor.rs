@@ -0,0 +1,231 @@
+//! Check that the environment is ready to run the other scripts
+//!
+//! Read-only: nothing is sent. Prints a checklist with a fix for every failure:
+//!
+//! - the member keypair file exists and is a valid keypair
+//! - the cluster's RPC is reachable and healthy
+//! - the Squads program (or `--program-id`) is deployed on that cluster
+//! - the signer has SOL for fees (a zero balance fails, under 0.01 SOL warns)
+//! - with `--multisig`, the multisig exists on that cluster and the signer is a member
+//!
+//! Exits 1 if any check fails, so it can gate other scripts.
+//!
+//! Usage:
+//!   cargo run --bin doctor -- [--keypair <path>] [--multisig <address>] [mainnet|localnet]
+//!
+//! Options:
+//!   --keypair <path>       - Keypair to check (default: ../member1.json, as the other scripts use)
+//!   --multisig <address>   - Also check the multisig exists and the signer is a member
+
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Signer},
+};
+use squads_rust::addressbook::resolve_pubkey;
+use squads_rust::args::{reject_unknown_flags_or_exit, take_flag_value, take_help};
+use squads_rust::format::{format_amount, Units, SOL_DECIMALS};
+use squads_rust::multisig::fetch_multisig;
+use squads_rust::permissions::PermissionsExt;
+use squads_rust::network::Network;
+use squads_rust::program::{program_id, take_program_id_or_exit};
+use std::env;
+
+const DEFAULT_KEYPAIR_PATH: &str = "../member1.json";
+/// Below this the signer can still pay fees, but not for long or for any account rent
+const LOW_BALANCE_LAMPORTS: u64 = 10_000_000;
+
+enum Status {
+    Pass,
+    Warn,
+    Fail,
+    Skip,
+}
+
+struct Checklist {
+    failed: bool,
+}
+
+impl Checklist {
+    fn report(&mut self, status: Status, check: &str, detail: &str, fix: Option<&str>) {
+        let tag = match status {
+            Status::Pass => "PASS",
+            Status::Warn => "WARN",
+            Status::Fail => {
+                self.failed = true;
+                "FAIL"
+            }
+            Status::Skip => "SKIP",
+        };
+        println!("[{}] {}: {}", tag, check, detail);
+        if let Some(fix) = fix {
+            println!("       Fix: {}", fix);
+        }
+    }
+}
+
+fn sol(lamports: u64) -> String {
+    format!("{} SOL", format_amount(lamports, SOL_DECIMALS, Units::Ui))
+}
+
+fn main() {
+    let mut args: Vec<String> = env::args().collect();
+    let help = take_help(&mut args);
+    take_program_id_or_exit(&mut args);
+
+    if help {
+        println!("Check that the environment is ready to run the other scripts");
+        println!();
+        println!("Usage:");
+        println!("  cargo run --bin doctor -- [--keypair <path>] [--multisig <address>] [mainnet|localnet]");
+        println!();
+        println!("Options:");
+        println!("  --keypair <path>      - Keypair to check (default: {})", DEFAULT_KEYPAIR_PATH);
+        println!("  --multisig <address>  - Also check the multisig exists and the signer is a member");
+        println!();
+        println!("Read-only. Exits 1 if any check fails.");
+        return;
+    }
+
+    let (keypair_path, multisig_arg) = match (
+        take_flag_value(&mut args, "--keypair"),
+        take_flag_value(&mut args, "--multisig"),
+    ) {
+        (Ok(keypair), Ok(multisig)) => (keypair.unwrap_or_else(|| DEFAULT_KEYPAIR_PATH.to_string()), multisig),
+        (Err(e), _) | (_, Err(e)) => {
+            println!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    reject_unknown_flags_or_exit(&args);
+    let multisig_pda: Option<Pubkey> = multisig_arg.map(|value| resolve_pubkey(&value));
+    let network = Network::from_arg(args.get(1).map(|s| s.as_str()).unwrap_or("devnet"));
+
+    println!("=== Environment Check ({}) ===\n", network.name().to_uppercase());
+    let mut checklist = Checklist { failed: false };
+
+    let signer = match read_keypair_file(&keypair_path) {
+        Ok(keypair) => {
+            checklist.report(Status::Pass, "Keypair", &format!("{} ({})", keypair.pubkey(), keypair_path), None);
+            Some(keypair.pubkey())
+        }
+        Err(e) => {
+            let fix = format!(
+                "solana-keygen new -o {} (or copy an existing 64-byte keypair array there)",
+                keypair_path
+            );
+            checklist.report(Status::Fail, "Keypair", &format!("{}: {}", keypair_path, e), Some(&fix));
+            None
+        }
+    };
+
+    let client = RpcClient::new_with_commitment(network.rpc_url(), CommitmentConfig::confirmed());
+    let rpc_ok = match client.get_health() {
+        Ok(()) => {
+            let version = client.get_version().map(|v| v.solana_core).unwrap_or_else(|_| "unknown".to_string());
+            checklist.report(Status::Pass, "RPC", &format!("{} healthy (solana-core {})", network.rpc_url(), version), None);
+            true
+        }
+        Err(e) => {
+            let fix = match network {
+                Network::Localnet => "start solana-test-validator with the Squads program loaded".to_string(),
+                _ => format!("check your connection; {} may be down or rate-limiting", network.rpc_url()),
+            };
+            checklist.report(Status::Fail, "RPC", &format!("{}: {}", network.rpc_url(), e), Some(&fix));
+            false
+        }
+    };
+    if !rpc_ok {
+        checklist.report(Status::Skip, "Squads program", "RPC unreachable", None);
+        checklist.report(Status::Skip, "Balance", "RPC unreachable", None);
+        if multisig_pda.is_some() {
+            checklist.report(Status::Skip, "Multisig", "RPC unreachable", None);
+        }
+        finish(&checklist);
+    }
+
+    let id = program_id();
+    match client.get_account_with_commitment(&id, client.commitment()).map(|response| response.value) {
+        Ok(Some(account)) if account.executable => {
+            checklist.report(Status::Pass, "Squads program", &format!("{} deployed", id), None)
+        }
+        Ok(Some(account)) => checklist.report(
+            Status::Fail,
+            "Squads program",
+            &format!("{} is not executable (owner: {})", id, account.owner),
+            Some("pass the address of a deployed Squads program with --program-id"),
+        ),
+        Ok(None) => checklist.report(
+            Status::Fail,
+            "Squads program",
+            &format!("{} not found on {}", id, network.name()),
+            Some("load the program into your validator or pass its address with --program-id"),
+        ),
+        Err(e) => checklist.report(Status::Fail, "Squads program", &format!("failed to fetch {}: {}", id, e), None),
+    }
+
+    match signer {
+        Some(signer) => match client.get_balance(&signer) {
+            Ok(lamports) => {
+                let fix = match network {
+                    Network::Mainnet => format!("send SOL to {} from an exchange or wallet", signer),
+                    _ => format!("solana airdrop 1 {} --url {}", signer, network.rpc_url()),
+                };
+                if lamports == 0 {
+                    checklist.report(Status::Fail, "Balance", "0 SOL, can't pay transaction fees", Some(&fix));
+                } else if lamports < LOW_BALANCE_LAMPORTS {
+                    let detail = format!("{} (low; ~{} covers fees)", sol(lamports), sol(LOW_BALANCE_LAMPORTS));
+                    checklist.report(Status::Warn, "Balance", &detail, Some(&fix));
+                } else {
+                    checklist.report(Status::Pass, "Balance", &sol(lamports), None);
+                }
+            }
+            Err(e) => checklist.report(Status::Fail, "Balance", &format!("failed to fetch: {}", e), None),
+        },
+        None => checklist.report(Status::Skip, "Balance", "no keypair", None),
+    }
+
+    if let Some(multisig_pda) = multisig_pda {
+        match fetch_multisig(&client, &multisig_pda) {
+            Ok(multisig) => {
+                let detail = format!("{} ({}/{} threshold)", multisig_pda, multisig.threshold, multisig.members.len());
+                checklist.report(Status::Pass, "Multisig", &detail, None);
+                match signer {
+                    Some(signer) => match multisig.members.iter().find(|member| member.key == signer) {
+                        Some(member) => checklist.report(
+                            Status::Pass,
+                            "Membership",
+                            &format!("signer is a member ({})", member.permissions.to_string()),
+                            None,
+                        ),
+                        None => checklist.report(
+                            Status::Fail,
+                            "Membership",
+                            &format!("{} is not a member of {}", signer, multisig_pda),
+                            Some("use a member's keypair with --keypair, or propose adding this key with add-member"),
+                        ),
+                    },
+                    None => checklist.report(Status::Skip, "Membership", "no keypair", None),
+                }
+            }
+            Err(e) => {
+                // fetch_multisig's error already names the other cluster when that's the cause
+                let detail = e.replace('\n', " ");
+                checklist.report(Status::Fail, "Multisig", &detail, Some("check the address and the network argument"));
+            }
+        }
+    }
+
+    finish(&checklist);
+}
+
+fn finish(checklist: &Checklist) -> ! {
+    println!();
+    if checklist.failed {
+        println!("Some checks failed; fix them before running the other scripts.");
+        std::process::exit(1);
+    }
+    println!("All critical checks passed.");
+    std::process::exit(0);
+}
@@ -3,8 +3,41 @@
 //! Once a proposal has reached the required threshold of approvals,
 //! any member with Execute permission can execute it.
 //!
+//! member1 pays the fee and executes by default. `--executor <path>` executes with another
+//! member's keypair (which must have Execute) while member1 still pays. If the executor lacks
+//! Execute, the script lists the members who have it instead of sending.
+//!
+//! Vault transactions created with ephemeral signers (e.g. create-proposal's stake command)
+//! reference PDAs derived from the transaction account with `ephemeral_signer_pdas`. Those
+//! PDAs are passed as remaining accounts but NOT marked as transaction signers: like the vault,
+//! the program signs for them via CPI during execution.
+//!
+//! Irreversible inner actions (an SPL Token `SetAuthority`, e.g. from
+//! transfer-mint-authority-proposal) default to waiting for `finalized` after the transaction
+//! lands, so a rare rollback of a `confirmed` block can't be mistaken for success. Downgrade with
+//! `--commitment confirmed --yes`; `--commitment finalized` waits for any proposal.
+//!
+//! A vault transaction touching many accounts can push the execute transaction past the
+//! 1232-byte limit. `--tx-version 0 --lookup-table <address>` builds it as a v0 transaction that
+//! loads accounts from existing address lookup tables (comma-separated for several), e.g. one
+//! holding the Squads program, multisig and the inner transaction's accounts.
+//!
+//! A vault transaction compiled with address lookup tables (e.g. a swap-proposal route) loads
+//! some inner accounts from them. Those tables are fetched and their addresses passed as remaining
+//! accounts; with `--tx-version 0` they are also used by the execute transaction itself, on top
+//! of any `--lookup-table`. `--compute-unit-limit <n>` raises the execute transaction's compute
+//! budget for inner instructions heavier than the 200k-unit default.
+//!
+//! `--show-accounts` (or its alias `--dump-accounts`) prints the execute instruction's ordered
+//! account list before sending: pubkey, signer and writable flags, and who signs each signer (the
+//! transaction, or the program via CPI for the vault and ephemeral signers). The fixed
+//! VaultTransactionExecute accounts are listed apart from the remaining accounts appended after them.
+//!
+//! `--links` prints explorer and Squads app links for the multisig, proposal and transaction
+//! after executing, and `--open` opens the proposal in the Squads app.
+//!
 //! Usage:
-//!   cargo run --bin execute-proposal -- <multisig_address> <proposal_index> [mainnet]
+//!   cargo run --bin execute-proposal -- <multisig_address> <proposal_index> [--executor <path>] [--commitment confirmed|finalized [--yes]] [--tx-version 0 --lookup-table <address>] [--compute-unit-limit <n>] [--show-accounts|--dump-accounts] [--links] [--open] [mainnet]
 //!
 //! Example:
 //!   cargo run --bin execute-proposal -- BJbRt... 1 mainnet
@@ -12,61 +45,208 @@
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::{
     commitment_config::CommitmentConfig,
-    instruction::{AccountMeta, Instruction},
+    compute_budget::ComputeBudgetInstruction,
+    instruction::Instruction,
     pubkey::Pubkey,
-    signature::{read_keypair_file, Signer},
-    transaction::Transaction,
+    signature::{read_keypair_file, Keypair, Signer},
 };
-use squads_multisig::anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use squads_multisig::anchor_lang::AccountDeserialize;
 use squads_multisig::pda::{get_proposal_pda, get_transaction_pda, get_vault_pda};
 use squads_multisig::squads_multisig_program;
-use squads_multisig::state::{Multisig, Proposal, ProposalStatus};
+use squads_multisig::state::Permission;
 use squads_multisig_program::VaultTransaction;
+use squads_rust::addressbook::resolve_pubkey;
+use squads_rust::args::{reject_unknown_flags_or_exit, take_flag, take_flag_value, take_help};
+use squads_rust::audit::record;
+use squads_rust::multisig::fetch_multisig_or_exit;
+use squads_rust::network::{open_in_browser, squads_app_tx_url, Network};
+use squads_rust::pda::ephemeral_signer_pdas;
+use squads_rust::permissions::PermissionsExt;
+use squads_rust::program::{program_id, take_program_id_or_exit};
+use squads_rust::proposal::{fetch_proposal_state, irreversible_actions, vault_transaction_execute_ix, ProposalState};
+use squads_rust::send::{
+    fetch_lookup_tables, send_and_track, wait_for_finalized, SendError, SendOptions, TxVersion, PACKET_DATA_SIZE,
+};
 use std::env;
 
-const DEVNET_RPC: &str = "https://api.devnet.solana.com";
-const MAINNET_RPC: &str = "https://api.mainnet-beta.solana.com";
+/// Print the execute instruction's account metas in order. The first four are the
+/// VaultTransactionExecute accounts; the rest come from the inner message: its lookup tables, its
+/// static accounts, then the accounts it loads from those tables. A signer among the static
+/// accounts is either the vault or an ephemeral signer (both signed by the program via CPI) or
+/// must sign the outer transaction.
+fn print_execute_accounts(
+    instruction: &Instruction,
+    vault_transaction: &VaultTransaction,
+    vault_pda: &Pubkey,
+    transaction_pda: &Pubkey,
+) {
+    const FIXED: [&str; 4] = ["multisig", "proposal", "transaction", "member (executor)"];
+    let ephemeral_signers =
+        ephemeral_signer_pdas(transaction_pda, vault_transaction.ephemeral_signer_bumps.len() as u8);
+    let message = &vault_transaction.message;
+    let num_lookups = message.address_table_lookups.len();
+    let num_static = message.account_keys.len();
+
+    println!("\nExecute instruction accounts ({}):", instruction.accounts.len());
+    println!("  {:<3} {:<44} {:<6} {:<8} Role", "#", "Pubkey", "Signer", "Writable");
+    for (index, meta) in instruction.accounts.iter().enumerate() {
+        if index == 0 {
+            println!("  -- VaultTransactionExecute accounts --");
+        } else if index == FIXED.len() {
+            println!("  -- remaining accounts (from the vault message) --");
+        }
+        let role = match FIXED.get(index) {
+            Some(name) => name.to_string(),
+            None => {
+                let message_index = index - FIXED.len();
+                if message_index < num_lookups {
+                    "address lookup table".to_string()
+                } else if message_index >= num_lookups + num_static {
+                    "inner account (loaded from a lookup table)".to_string()
+                } else if meta.pubkey == *vault_pda {
+                    "vault PDA - signs via CPI".to_string()
+                } else if let Some(n) = ephemeral_signers.iter().position(|key| *key == meta.pubkey) {
+                    format!("ephemeral signer {} - signs via CPI", n)
+                } else if message.is_signer_index(message_index - num_lookups) {
+                    "inner signer - must sign the transaction".to_string()
+                } else {
+                    "inner account".to_string()
+                }
+            }
+        };
+        println!(
+            "  {:<3} {:<44} {:<6} {:<8} {}",
+            index,
+            meta.pubkey.to_string(),
+            if meta.is_signer { "yes" } else { "no" },
+            if meta.is_writable { "yes" } else { "no" },
+            role
+        );
+    }
+    match instruction.accounts.iter().position(|meta| meta.pubkey == *vault_pda) {
+        Some(index) => println!("Vault PDA: {} (#{}, signer flag suppressed; signed via CPI)", vault_pda, index),
+        None => println!("Vault PDA: {} (not referenced by the vault message)", vault_pda),
+    }
+}
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    let mut args: Vec<String> = env::args().collect();
+    let help = take_help(&mut args);
+    take_program_id_or_exit(&mut args);
+    let send_options = SendOptions::take_from_or_exit(&mut args);
+    let lookup_table_arg = take_flag_value(&mut args, "--lookup-table").unwrap_or_else(|e| {
+        println!("Error: {}", e);
+        std::process::exit(1);
+    });
+    let executor_path = take_flag_value(&mut args, "--executor").unwrap_or_else(|e| {
+        println!("Error: {}", e);
+        std::process::exit(1);
+    });
+    let commitment_arg = take_flag_value(&mut args, "--commitment").unwrap_or_else(|e| {
+        println!("Error: {}", e);
+        std::process::exit(1);
+    });
+    let compute_unit_limit_arg = take_flag_value(&mut args, "--compute-unit-limit").unwrap_or_else(|e| {
+        println!("Error: {}", e);
+        std::process::exit(1);
+    });
+    let compute_unit_limit: Option<u32> = compute_unit_limit_arg.map(|value| {
+        value.parse().unwrap_or_else(|_| {
+            println!("Error: invalid --compute-unit-limit value '{}'", value);
+            std::process::exit(1);
+        })
+    });
+    let yes = take_flag(&mut args, "--yes");
+    let show_accounts = take_flag(&mut args, "--show-accounts") | take_flag(&mut args, "--dump-accounts");
+    let links = take_flag(&mut args, "--links");
+    let open = take_flag(&mut args, "--open");
+    let wait_finalized_arg = match commitment_arg.as_deref() {
+        None => None,
+        Some("confirmed") => Some(false),
+        Some("finalized") => Some(true),
+        Some(other) => {
+            println!("Error: invalid --commitment '{}' (expected confirmed or finalized)", other);
+            std::process::exit(1);
+        }
+    };
+    let lookup_table_keys: Vec<Pubkey> = lookup_table_arg
+        .iter()
+        .flat_map(|list| list.split(','))
+        .map(|address| resolve_pubkey(address.trim()))
+        .collect();
+    if !lookup_table_keys.is_empty() && send_options.tx_version != TxVersion::V0 {
+        println!("Error: --lookup-table requires --tx-version 0");
+        std::process::exit(1);
+    }
+    reject_unknown_flags_or_exit(&args);
 
-    if args.len() < 3 {
+    if help || args.len() < 3 {
         println!("Usage: cargo run --bin execute-proposal -- <multisig_address> <proposal_index> [mainnet]");
         println!();
+        println!("Options:");
+        println!("  --executor <path>         - Member keypair with Execute permission (default: member1, who always pays the fee)");
+        println!("  --commitment <level>      - confirmed or finalized; irreversible actions default to finalized");
+        println!("  --yes                     - Accept --commitment confirmed for an irreversible action");
+        println!("  --tx-version legacy|0     - Outer transaction format (default: legacy)");
+        println!("  --lookup-table <address>  - Address lookup table(s) for a v0 transaction, comma-separated");
+        println!("  --compute-unit-limit <n>  - Compute unit limit for the execute transaction (default: 200000)");
+        println!("  --show-accounts           - Print the execute instruction's accounts (signer/writable, CPI signers) before sending");
+        println!("  --dump-accounts           - Alias for --show-accounts");
+        println!("  --links                   - Print explorer and Squads app links after executing");
+        println!("  --open                    - Open the Squads app page in the browser (prints the URL if that fails)");
+        println!();
         println!("Example:");
         println!("  cargo run --bin execute-proposal -- BJbRt... 1 mainnet");
         return;
     }
 
-    let multisig_pda: Pubkey = args[1].parse().expect("Invalid multisig address");
+    let multisig_pda: Pubkey = resolve_pubkey(&args[1]);
     let proposal_index: u64 = args[2].parse().expect("Invalid proposal index");
-    let network = args.get(3).map(|s| s.as_str()).unwrap_or("devnet");
-
-    let rpc_url = match network {
-        "mainnet" => MAINNET_RPC,
-        _ => DEVNET_RPC,
-    };
+    let network = Network::from_arg(args.get(3).map(|s| s.as_str()).unwrap_or("devnet"));
 
-    let client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
-    let member = read_keypair_file("../member1.json").expect("Failed to read member1.json");
+    let client = RpcClient::new_with_commitment(network.rpc_url(), CommitmentConfig::confirmed());
+    let payer = read_keypair_file("../member1.json").expect("Failed to read member1.json");
+    let executor_keypair: Option<Keypair> = executor_path.map(|path| {
+        read_keypair_file(&path).unwrap_or_else(|e| panic!("Failed to read executor keypair {}: {}", path, e))
+    });
+    let executor = executor_keypair.as_ref().unwrap_or(&payer);
 
     // Derive PDAs
-    let (transaction_pda, _) = get_transaction_pda(&multisig_pda, proposal_index, None);
-    let (proposal_pda, _) = get_proposal_pda(&multisig_pda, proposal_index, None);
+    let (transaction_pda, _) = get_transaction_pda(&multisig_pda, proposal_index, Some(&program_id()));
+    let (proposal_pda, _) = get_proposal_pda(&multisig_pda, proposal_index, Some(&program_id()));
 
     // Fetch multisig
-    let multisig_account = client
-        .get_account(&multisig_pda)
-        .expect("Failed to fetch multisig account");
-    let multisig = Multisig::try_deserialize(&mut multisig_account.data.as_slice())
-        .expect("Failed to deserialize multisig");
+    let multisig = fetch_multisig_or_exit(&client, &multisig_pda);
+
+    // The program rejects executors without Execute, so route the user to someone who has it
+    if !multisig.member_has_permission(executor.pubkey(), Permission::Execute) {
+        if multisig.is_member(executor.pubkey()).is_none() {
+            println!("Error: Executor {} is not a member of this multisig", executor.pubkey());
+        } else {
+            println!("Error: Executor {} does not have Execute permission", executor.pubkey());
+        }
+        let executors: Vec<_> = multisig
+            .members
+            .iter()
+            .filter(|m| m.permissions.contains(Permission::Execute))
+            .collect();
+        if executors.is_empty() {
+            println!("No member of this multisig has Execute permission, so no one can execute proposals.");
+        } else {
+            println!("Members with Execute permission:");
+            for m in executors {
+                println!("  - {}", m.key);
+            }
+            println!("Have one of them run this command, or pass their keypair with --executor <path>.");
+        }
+        std::process::exit(1);
+    }
 
     // Fetch proposal
-    let proposal_account = client
-        .get_account(&proposal_pda)
-        .expect("Failed to fetch proposal account");
-    let proposal = Proposal::try_deserialize(&mut proposal_account.data.as_slice())
-        .expect("Failed to deserialize proposal");
+    let (proposal, state) = fetch_proposal_state(&client, &proposal_pda).unwrap_or_else(|e| {
+        println!("Error: {}", e);
+        std::process::exit(1);
+    });
 
     // Fetch vault transaction
     let transaction_account = client
@@ -76,100 +256,160 @@ fn main() {
         .expect("Failed to deserialize vault transaction");
 
     // Derive vault PDA
-    let (vault_pda, _) = get_vault_pda(&multisig_pda, vault_transaction.vault_index, None);
+    let (vault_pda, _) = get_vault_pda(&multisig_pda, vault_transaction.vault_index, Some(&program_id()));
 
-    println!("=== Execute Proposal ({}) ===\n", network.to_uppercase());
+    // Irreversible actions wait for finalized unless explicitly downgraded
+    let irreversible = irreversible_actions(&vault_transaction.message);
+    let wait_finalized = match wait_finalized_arg {
+        Some(false) if !irreversible.is_empty() && !yes => {
+            println!("Error: This proposal contains an irreversible action:");
+            for action in &irreversible {
+                println!("  - {}", action);
+            }
+            println!("Pass --yes along with --commitment confirmed to skip waiting for finalization.");
+            std::process::exit(1);
+        }
+        Some(wait) => wait,
+        None => !irreversible.is_empty(),
+    };
+
+    println!("=== Execute Proposal ({}) ===\n", network.name().to_uppercase());
     println!("Multisig: {}", multisig_pda);
-    println!("Executor: {}", member.pubkey());
+    println!("Executor: {}", executor.pubkey());
+    if executor.pubkey() != payer.pubkey() {
+        println!("Fee Payer: {}", payer.pubkey());
+    }
     println!();
     println!("Proposal Index: {}", proposal_index);
     println!("Proposal Address: {}", proposal_pda);
     println!("Transaction Address: {}", transaction_pda);
     println!("Vault: {}", vault_pda);
+    for action in &irreversible {
+        println!("Irreversible: {}", action);
+    }
+    println!("Confirmation: {}", if wait_finalized { "finalized" } else { "confirmed" });
 
     // Check proposal status
-    let status_str = match &proposal.status {
-        ProposalStatus::Draft { .. } => "Draft",
-        ProposalStatus::Active { .. } => "Active",
-        ProposalStatus::Rejected { .. } => "Rejected",
-        ProposalStatus::Approved { .. } => "Approved",
-        ProposalStatus::Executed { .. } => "Executed",
-        ProposalStatus::Cancelled { .. } => "Cancelled",
-        _ => "Unknown",
-    };
-    println!("Status: {}", status_str);
+    println!("Status: {}", state.label());
     println!("Approvals: {} of {} required", proposal.approved.len(), multisig.threshold);
 
+    // Executing is only set inside an execute transaction by older program versions, so seeing
+    // it means another executor's transaction is landing; it isn't an error to wait out
+    if state == ProposalState::Executing {
+        println!("\nProposal is mid-execution (status Executing); another execute transaction is in flight.");
+        println!("Wait a few seconds and rerun this command to see the final status.");
+        return;
+    }
+
     // Check if proposal is approved
-    if !matches!(proposal.status, ProposalStatus::Approved { .. }) {
-        println!("\nError: Proposal is not approved. Current status: {}", status_str);
-        if matches!(proposal.status, ProposalStatus::Active { .. }) {
+    if !state.is_executable() {
+        println!("\nError: Proposal is not approved. Current status: {}", state.name());
+        if state.is_votable() {
             let remaining = multisig.threshold as usize - proposal.approved.len();
             println!("  {} more approval(s) needed.", remaining);
         }
         return;
     }
 
-    // Build remaining accounts from the transaction message
-    let message = &vault_transaction.message;
-
-    // The remaining accounts need to include:
-    // 1. AddressLookupTable accounts (none for simple transactions)
-    // 2. Static account keys from the message
-    // 3. Loaded accounts from address table lookups (none for simple transactions)
-
-    let mut remaining_accounts: Vec<AccountMeta> = Vec::new();
+    // Accounts of the inner message become remaining accounts; the vault and any ephemeral
+    // signers (e.g. a new stake account) are signed by the program during execution
+    let message_table_keys: Vec<Pubkey> =
+        vault_transaction.message.address_table_lookups.iter().map(|lookup| lookup.account_key).collect();
+    let message_tables = fetch_lookup_tables(&client, &message_table_keys).unwrap_or_else(|e| {
+        println!("Error: {}", e);
+        std::process::exit(1);
+    });
+    let instruction = vault_transaction_execute_ix(
+        &multisig_pda,
+        proposal_index,
+        &vault_transaction,
+        &message_tables,
+        &executor.pubkey(),
+    )
+    .unwrap_or_else(|e| {
+        println!("Error: {}", e);
+        std::process::exit(1);
+    });
 
-    // Add static accounts from the message
-    for (index, pubkey) in message.account_keys.iter().enumerate() {
-        let is_signer = message.is_signer_index(index);
-        let is_writable = message.is_static_writable_index(index);
-
-        // Vault PDA signs via CPI, so we don't mark it as signer here
-        let actual_is_signer = is_signer && pubkey != &vault_pda;
-
-        remaining_accounts.push(AccountMeta {
-            pubkey: *pubkey,
-            is_signer: actual_is_signer,
-            is_writable,
-        });
+    if show_accounts {
+        print_execute_accounts(&instruction, &vault_transaction, &vault_pda, &transaction_pda);
     }
 
-    // Build the execute instruction
-    let accounts = squads_multisig_program::accounts::VaultTransactionExecute {
-        multisig: multisig_pda,
-        proposal: proposal_pda,
-        transaction: transaction_pda,
-        member: member.pubkey(),
-    };
-
-    let mut account_metas = accounts.to_account_metas(Some(false));
-    account_metas.extend(remaining_accounts);
+    println!("\nExecuting proposal...");
 
-    let instruction = Instruction {
-        program_id: squads_multisig_program::ID,
-        accounts: account_metas,
-        data: squads_multisig_program::instruction::VaultTransactionExecute {}.data(),
+    let (recent_blockhash, last_valid_block_height) = client
+        .get_latest_blockhash_with_commitment(client.commitment())
+        .expect("Failed to get blockhash");
+    let mut lookup_tables = fetch_lookup_tables(&client, &lookup_table_keys).unwrap_or_else(|e| {
+        println!("Error: {}", e);
+        std::process::exit(1);
+    });
+    if send_options.tx_version == TxVersion::V0 {
+        for table in message_tables {
+            if !lookup_tables.iter().any(|t| t.key == table.key) {
+                lookup_tables.push(table);
+            }
+        }
+    }
+    let mut instructions = Vec::new();
+    if let Some(units) = compute_unit_limit {
+        instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(units));
+    }
+    instructions.push(instruction);
+    let signers: Vec<&Keypair> = if executor.pubkey() == payer.pubkey() {
+        vec![&payer]
+    } else {
+        vec![&payer, executor]
     };
+    let transaction = send_options
+        .build_transaction_with_lookup_tables(&instructions, &payer.pubkey(), &signers, recent_blockhash, &lookup_tables)
+        .expect("Failed to build transaction");
 
-    println!("\nExecuting proposal...");
-
-    let recent_blockhash = client.get_latest_blockhash().expect("Failed to get blockhash");
-    let transaction = Transaction::new_signed_with_payer(
-        &[instruction],
-        Some(&member.pubkey()),
-        &[&member],
-        recent_blockhash,
-    );
+    // Compact-u16 signature count (one byte here), the signatures, then the message
+    let size = 1 + 64 * transaction.signatures.len() + transaction.message.serialize().len();
+    println!("Transaction size: {} of {} bytes", size, PACKET_DATA_SIZE);
+    if size > PACKET_DATA_SIZE {
+        println!("\nError: The execute transaction is too large to send.");
+        if send_options.tx_version == TxVersion::Legacy {
+            println!("Rerun with --tx-version 0 --lookup-table <address> to load accounts from a lookup table.");
+        } else {
+            println!("Add the remaining accounts to a lookup table passed with --lookup-table.");
+        }
+        std::process::exit(1);
+    }
 
-    match client.send_and_confirm_transaction(&transaction) {
-        Ok(sig) => {
+    match send_and_track(&client, &transaction, &send_options, last_valid_block_height) {
+        Ok(landed) => {
+            let sig = landed.signature;
+            if wait_finalized {
+                println!("\nLanded in slot {} (confirmed); waiting for finalization...", landed.slot);
+                if let Err(e) = wait_for_finalized(&client, &sig) {
+                    println!("\nNot finalized: {}", e);
+                    println!("{}", network.explorer_tx_url(&sig));
+                    std::process::exit(1);
+                }
+                println!("Finalized.");
+            }
+            record(network, &multisig_pda, &format!("execute proposal {}", proposal_index), &executor.pubkey(), &sig);
             println!("\nProposal executed successfully!");
             println!("Transaction: {}", sig);
+            println!("Slot: {}", landed.slot);
 
-            let cluster_param = if network == "mainnet" { "" } else { "?cluster=devnet" };
             println!("\nView on Solana Explorer:");
-            println!("https://explorer.solana.com/tx/{}{}", sig, cluster_param);
+            println!("{}", network.explorer_tx_url(&sig));
+            if links {
+                network.print_links(&multisig_pda, Some(proposal_index), Some(&sig));
+            }
+            if open {
+                open_in_browser(&squads_app_tx_url(&multisig_pda, proposal_index));
+            }
+        }
+        Err(e @ SendError::Unknown { signature, .. }) => {
+            println!("\n{}", e);
+            println!("{}", network.explorer_tx_url(&signature));
+        }
+        Err(e @ SendError::Dropped { .. }) => {
+            println!("\nFailed to execute proposal: {}", e);
         }
         Err(e) => {
             println!("\nFailed to execute proposal: {}", e);
@@ -0,0 +1,134 @@
+//! Inspect a batch transaction and the progress of its execution
+//!
+//! A batch executes its inner vault transactions one at a time, in order, so a long batch can
+//! be left half-executed. This prints the batch's size and `executed_transaction_index`, the
+//! status of its proposal, and each inner `VaultBatchTransaction` with whether it has executed.
+//!
+//! Usage:
+//!   cargo run --bin inspect-batch -- <multisig_address> <batch_index> [--instructions] [mainnet]
+//!
+//! Options:
+//!   --instructions  - Also list each inner transaction's instructions (program, accounts, data size)
+//!
+//! Example:
+//!   cargo run --bin inspect-batch -- BJbRt... 7 --instructions mainnet
+
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
+use squads_multisig::anchor_lang::AccountDeserialize;
+use squads_multisig::pda::{get_proposal_pda, get_transaction_pda};
+use squads_multisig::squads_multisig_program::VaultBatchTransaction;
+use squads_multisig::state::{Batch, Proposal};
+use squads_rust::addressbook::resolve_pubkey;
+use squads_rust::args::{reject_unknown_flags_or_exit, take_flag, take_help};
+use squads_rust::multisig::{fetch_accounts, fetch_multisig_or_exit};
+use squads_rust::network::Network;
+use squads_rust::pda::get_batch_transaction_pda;
+use squads_rust::program::{program_id, take_program_id_or_exit};
+use squads_rust::proposal::ProposalState;
+use std::env;
+
+fn exit_with(message: String) -> ! {
+    println!("Error: {}", message);
+    std::process::exit(1);
+}
+
+/// One line per instruction of an inner transaction
+fn print_instructions(transaction: &VaultBatchTransaction) {
+    let message = &transaction.message;
+    for (i, ix) in message.instructions.iter().enumerate() {
+        let program = message
+            .account_keys
+            .get(usize::from(ix.program_id_index))
+            .map(|k| k.to_string())
+            .unwrap_or_else(|| "<address table lookup>".to_string());
+        println!(
+            "      #{}: program {} ({} accounts, {} bytes of data)",
+            i,
+            program,
+            ix.account_indexes.len(),
+            ix.data.len()
+        );
+    }
+}
+
+fn main() {
+    let mut args: Vec<String> = env::args().collect();
+    let help = take_help(&mut args);
+    take_program_id_or_exit(&mut args);
+    let show_instructions = take_flag(&mut args, "--instructions");
+    reject_unknown_flags_or_exit(&args);
+
+    if help || args.len() < 3 {
+        println!("Inspect a batch transaction and the progress of its execution");
+        println!();
+        println!("Usage:");
+        println!("  cargo run --bin inspect-batch -- <multisig_address> <batch_index> [--instructions] [mainnet]");
+        println!();
+        println!("Options:");
+        println!("  --instructions  - Also list each inner transaction's instructions");
+        return;
+    }
+
+    let multisig_pda: Pubkey = resolve_pubkey(&args[1]);
+    let batch_index: u64 = args[2].parse().expect("Invalid batch index");
+    let network = Network::from_arg(args.get(3).map(|s| s.as_str()).unwrap_or("devnet"));
+
+    let client = RpcClient::new_with_commitment(network.rpc_url(), CommitmentConfig::confirmed());
+    fetch_multisig_or_exit(&client, &multisig_pda);
+
+    let (batch_pda, _) = get_transaction_pda(&multisig_pda, batch_index, Some(&program_id()));
+    let (proposal_pda, _) = get_proposal_pda(&multisig_pda, batch_index, Some(&program_id()));
+    let accounts = fetch_accounts(&client, &[batch_pda, proposal_pda])
+        .unwrap_or_else(|e| exit_with(format!("failed to fetch batch accounts: {}", e)));
+
+    let batch_account = accounts[0]
+        .as_ref()
+        .unwrap_or_else(|| exit_with(format!("no transaction account at index {} ({}); it was never created or has been closed", batch_index, batch_pda)));
+    let batch = Batch::try_deserialize(&mut batch_account.data.as_slice())
+        .unwrap_or_else(|e| exit_with(format!("transaction {} is not a batch: {}", batch_index, e)));
+
+    println!("=== Inspect Batch ({}) ===\n", network.name().to_uppercase());
+    println!("Multisig: {}", multisig_pda);
+    println!("Batch Index: {}", batch_index);
+    println!("Batch Address: {}", batch_pda);
+    println!("Creator: {}", batch.creator);
+    println!("Vault Index: {}", batch.vault_index);
+    println!("Size: {} transaction(s)", batch.size);
+    println!("Executed: {} of {}", batch.executed_transaction_index, batch.size);
+    match accounts[1].as_ref().map(|account| Proposal::try_deserialize(&mut account.data.as_slice())) {
+        Some(Ok(proposal)) => println!("Proposal: {} ({})", proposal_pda, ProposalState::of(&proposal).label()),
+        Some(Err(e)) => println!("Proposal: {} (could not be decoded: {})", proposal_pda, e),
+        None => println!("Proposal: {} (not found)", proposal_pda),
+    }
+
+    let keys: Vec<Pubkey> = (1..=batch.size)
+        .map(|n| get_batch_transaction_pda(&multisig_pda, batch_index, n))
+        .collect();
+    let transactions = fetch_accounts(&client, &keys)
+        .unwrap_or_else(|e| exit_with(format!("failed to fetch batch transactions: {}", e)));
+
+    println!();
+    println!("Transactions:");
+    for (n, (key, account)) in (1..=batch.size).zip(keys.iter().zip(transactions)) {
+        let state = if n <= batch.executed_transaction_index { "executed" } else { "pending" };
+        let Some(account) = account else {
+            println!("  {}. {} ({}, account closed)", n, key, state);
+            continue;
+        };
+        match VaultBatchTransaction::try_deserialize(&mut account.data.as_slice()) {
+            Ok(transaction) => {
+                println!("  {}. {} ({}, {} instruction(s))", n, key, state, transaction.message.instructions.len());
+                if show_instructions {
+                    print_instructions(&transaction);
+                }
+            }
+            Err(e) => println!("  {}. {} ({}, could not be decoded: {})", n, key, state, e),
+        }
+    }
+
+    if batch.executed_transaction_index < batch.size {
+        println!();
+        println!("Next to execute: transaction {} of {}", batch.executed_transaction_index + 1, batch.size);
+    }
+}
@@ -7,21 +7,54 @@
 //!   # Derive and inspect spending limit for a multisig (uses 'combinator' create_key)
 //!   cargo run --bin inspect-spending-limit -- --multisig <multisig_address> [mainnet]
 //!
+//!   # Summarize every spending limit of a multisig, whatever its create_key
+//!   cargo run --bin inspect-spending-limit -- --list <multisig_address> [--verbose] [mainnet]
+//!
+//! `--list` scans the program with getProgramAccounts (mainnet needs a dedicated RPC), filtered to
+//! the multisig and sliced to the fixed fields the summary needs, so the member and destination
+//! lists are never downloaded. Each account is folded into a summary row as it is read.
+//!
+//! `--count-only` (with `--list` or `--multisig`) runs the same scan but prints only how many
+//! limits the multisig has, whatever their create_key, and the SOL limits' combined amount and
+//! remaining; no per-limit rows and no mint lookups. For monitoring.
+//!
+//! With `--multisig` or `--list` the address is checked to be a multisig first. Each mint's
+//! decimals are fetched once per run, however many limits share the mint.
+//!
+//! Options:
+//!   --dump <path>  - Save the spending limit as a JSON snapshot (for use-spending-limit --snapshot)
+//!   --alert-threshold <pct>  - Print a WARNING and exit 1 if usage exceeds pct (for cron/monitoring)
+//!   --units raw|ui  - Print amounts as integer smallest units, or as UI amounts using the mint's decimals
+//!   --verbose  - With --list, print the bytes transferred versus a full-data scan
+//!   --count-only  - With --list or --multisig, print only the limit count and SOL totals
+//!
 //! Examples:
 //!   cargo run --bin inspect-spending-limit -- SpendingLimitPDA...
 //!   cargo run --bin inspect-spending-limit -- SpendingLimitPDA... mainnet
 //!   cargo run --bin inspect-spending-limit -- --multisig MultisigPDA... mainnet
+//!   cargo run --bin inspect-spending-limit -- SpendingLimitPDA... --dump limit.json
+//!   cargo run --bin inspect-spending-limit -- --multisig MultisigPDA... --alert-threshold 80 mainnet
+//!   cargo run --bin inspect-spending-limit -- --list MultisigPDA... --verbose mainnet
+//!   cargo run --bin inspect-spending-limit -- --multisig MultisigPDA... --count-only mainnet
 
+use solana_account_decoder::{UiAccountEncoding, UiDataSliceConfig};
 use solana_client::rpc_client::RpcClient;
-use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
-use squads_multisig::anchor_lang::AccountDeserialize;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, rent::Rent};
+use squads_multisig::anchor_lang::{AccountDeserialize, Discriminator};
 use squads_multisig::pda::get_spending_limit_pda;
 use squads_multisig::squads_multisig_program;
-use squads_multisig::state::SpendingLimit;
+use squads_multisig::state::{Period, SpendingLimit};
+use squads_rust::addressbook::try_resolve_pubkey;
+use squads_rust::args::{reject_unknown_flags_or_exit, take_flag, take_flag_value, take_help};
+use squads_rust::cache::AccountCache;
+use squads_rust::format::{format_amount, usage_percent, Units, SOL_DECIMALS};
+use squads_rust::program::{check_program_or_exit, program_id, take_program_id_or_exit};
+use squads_rust::snapshot::write_spending_limit_snapshot;
+use squads_rust::network::Network;
 use std::env;
 
-const DEVNET_RPC: &str = "https://api.devnet.solana.com";
-const MAINNET_RPC: &str = "https://api.mainnet-beta.solana.com";
 const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
 
 fn format_period(period: &squads_multisig::state::Period) -> &'static str {
@@ -33,7 +66,41 @@ fn format_period(period: &squads_multisig::state::Period) -> &'static str {
     }
 }
 
-fn print_spending_limit(pubkey: &Pubkey, limit: &SpendingLimit, index: Option<usize>, network: &str) {
+/// Parse an `--alert-threshold` percentage, which must be within 0–100
+fn parse_alert_threshold(value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(pct) if (0.0..=100.0).contains(&pct) => Ok(pct),
+        _ => Err(format!("--alert-threshold must be a percentage between 0 and 100, got {}", value)),
+    }
+}
+
+/// Decimals for a limit's mint, fetched only when UI amounts were requested for a token.
+/// Falls back to raw units if the mint can't be read.
+fn resolve_units(cache: &mut AccountCache, mint: &Pubkey, units: Option<Units>) -> Option<(Units, u8)> {
+    let units = units?;
+    if *mint == Pubkey::default() {
+        return Some((units, SOL_DECIMALS));
+    }
+    if units == Units::Raw {
+        return Some((Units::Raw, 0));
+    }
+
+    match cache.mint_decimals(mint) {
+        Ok(decimals) => Some((Units::Ui, decimals)),
+        Err(e) => {
+            println!("Note: could not read mint decimals ({}); showing raw units", e);
+            Some((Units::Raw, 0))
+        }
+    }
+}
+
+fn print_spending_limit(
+    pubkey: &Pubkey,
+    limit: &SpendingLimit,
+    index: Option<usize>,
+    network: Network,
+    units: Option<(Units, u8)>,
+) {
     let is_sol = limit.mint == Pubkey::default();
 
     if let Some(i) = index {
@@ -44,7 +111,20 @@ fn print_spending_limit(pubkey: &Pubkey, limit: &SpendingLimit, index: Option<us
     println!();
 
     // Token info
-    if is_sol {
+    if let Some((units, decimals)) = units {
+        let label = match (is_sol, units) {
+            (true, Units::Raw) => " lamports",
+            (true, Units::Ui) => " SOL",
+            (false, _) => "",
+        };
+        if is_sol {
+            println!("Token:       SOL (Native)");
+        } else {
+            println!("Mint:        {}", limit.mint);
+        }
+        println!("Amount:      {}{}", format_amount(limit.amount, decimals, units), label);
+        println!("Remaining:   {}{}", format_amount(limit.remaining_amount, decimals, units), label);
+    } else if is_sol {
         println!("Token:       SOL (Native)");
         println!(
             "Amount:      {:.9} SOL ({} lamports)",
@@ -63,13 +143,7 @@ fn print_spending_limit(pubkey: &Pubkey, limit: &SpendingLimit, index: Option<us
     }
 
     // Usage stats
-    let used = limit.amount.saturating_sub(limit.remaining_amount);
-    let usage_pct = if limit.amount > 0 {
-        (used as f64 / limit.amount as f64) * 100.0
-    } else {
-        0.0
-    };
-    println!("Used:        {:.1}%", usage_pct);
+    println!("Used:        {:.1}%", usage_percent(limit.amount, limit.remaining_amount));
 
     println!("Period:      {}", format_period(&limit.period));
     println!("Vault Index: {}", limit.vault_index);
@@ -98,40 +172,78 @@ fn print_spending_limit(pubkey: &Pubkey, limit: &SpendingLimit, index: Option<us
     }
 
     // Explorer link
-    let cluster_param = if network == "mainnet" { "" } else { "?cluster=devnet" };
     println!();
-    println!(
-        "Explorer: https://explorer.solana.com/address/{}{}",
-        pubkey, cluster_param
-    );
+    println!("Explorer: {}", network.explorer_address_url(pubkey));
 }
 
-fn inspect_single(client: &RpcClient, spending_limit_pda: Pubkey, network: &str) {
-    println!("=== Spending Limit Details ({}) ===\n", network.to_uppercase());
-
-    match client.get_account(&spending_limit_pda) {
-        Ok(account) => {
-            match SpendingLimit::try_deserialize(&mut account.data.as_slice()) {
-                Ok(limit) => {
-                    print_spending_limit(&spending_limit_pda, &limit, None, network);
-                }
-                Err(e) => {
-                    println!("Error: Failed to deserialize spending limit account");
-                    println!("Details: {}", e);
-                    println!();
-                    println!("This may not be a valid Squads spending limit account.");
-                }
+/// Output flags shared by both inspection modes
+struct DisplayOptions {
+    dump: Option<String>,
+    units: Option<Units>,
+}
+
+fn dump_snapshot(path: &str, pubkey: &Pubkey, limit: &SpendingLimit) {
+    match write_spending_limit_snapshot(path, pubkey, limit) {
+        Ok(()) => println!("\nSnapshot saved to {}", path),
+        Err(e) => println!("\nError: Failed to save snapshot: {}", e),
+    }
+}
+
+/// Deserialize fetched account data once, then print and optionally snapshot it.
+/// Returns the limit so the caller can run threshold checks, or None if it failed to decode.
+fn show_spending_limit(
+    cache: &mut AccountCache,
+    pubkey: &Pubkey,
+    data: &[u8],
+    network: Network,
+    options: &DisplayOptions,
+) -> Option<SpendingLimit> {
+    match SpendingLimit::try_deserialize(&mut &data[..]) {
+        Ok(limit) => {
+            let units = resolve_units(cache, &limit.mint, options.units);
+            print_spending_limit(pubkey, &limit, None, network, units);
+            if let Some(path) = options.dump.as_deref() {
+                dump_snapshot(path, pubkey, &limit);
             }
+            Some(limit)
         }
+        Err(e) => {
+            println!("Error: Failed to deserialize spending limit account");
+            println!("Details: {}", e);
+            println!();
+            println!("This may not be a valid Squads spending limit account.");
+            None
+        }
+    }
+}
+
+fn inspect_single(
+    cache: &mut AccountCache,
+    spending_limit_pda: Pubkey,
+    network: Network,
+    options: &DisplayOptions,
+) -> Vec<(Pubkey, SpendingLimit)> {
+    println!("=== Spending Limit Details ({}) ===\n", network.name().to_uppercase());
+
+    match cache.client().get_account(&spending_limit_pda) {
+        Ok(account) => show_spending_limit(cache, &spending_limit_pda, &account.data, network, options)
+            .map(|limit| vec![(spending_limit_pda, limit)])
+            .unwrap_or_default(),
         Err(e) => {
             println!("Error: Failed to fetch account");
             println!("Details: {}", e);
+            Vec::new()
         }
     }
 }
 
-fn inspect_multisig(client: &RpcClient, multisig_pda: Pubkey, network: &str) {
-    println!("=== Spending Limit for Multisig ({}) ===\n", network.to_uppercase());
+fn inspect_multisig(
+    cache: &mut AccountCache,
+    multisig_pda: Pubkey,
+    network: Network,
+    options: &DisplayOptions,
+) -> Vec<(Pubkey, SpendingLimit)> {
+    println!("=== Spending Limit for Multisig ({}) ===\n", network.name().to_uppercase());
     println!("Multisig: {}", multisig_pda);
 
     // Derive the spending limit PDA using the deterministic "combinator" create_key
@@ -139,26 +251,16 @@ fn inspect_multisig(client: &RpcClient, multisig_pda: Pubkey, network: &str) {
         &[b"combinator"],
         &squads_multisig_program::ID,
     );
-    let (spending_limit_pda, _) = get_spending_limit_pda(&multisig_pda, &create_key, None);
+    let (spending_limit_pda, _) = get_spending_limit_pda(&multisig_pda, &create_key, Some(&program_id()));
 
     println!("Create Key: {} (derived from 'combinator')", create_key);
     println!("Spending Limit PDA: {}", spending_limit_pda);
     println!();
 
-    match client.get_account(&spending_limit_pda) {
-        Ok(account) => {
-            match SpendingLimit::try_deserialize(&mut account.data.as_slice()) {
-                Ok(limit) => {
-                    print_spending_limit(&spending_limit_pda, &limit, None, network);
-                }
-                Err(e) => {
-                    println!("Error: Failed to deserialize spending limit account");
-                    println!("Details: {}", e);
-                    println!();
-                    println!("This may not be a valid Squads spending limit account.");
-                }
-            }
-        }
+    match cache.client().get_account(&spending_limit_pda) {
+        Ok(account) => show_spending_limit(cache, &spending_limit_pda, &account.data, network, options)
+            .map(|limit| vec![(spending_limit_pda, limit)])
+            .unwrap_or_default(),
         Err(e) => {
             println!("No spending limit found for this multisig.");
             println!();
@@ -170,14 +272,278 @@ fn inspect_multisig(client: &RpcClient, multisig_pda: Pubkey, network: &str) {
             println!("  cargo run --bin add-spending-limit -- {} <amount> <period> [mainnet]", multisig_pda);
             println!();
             println!("RPC error: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Offset of the fixed fields `--list` needs, after the discriminator, multisig and create_key
+const SUMMARY_SLICE_OFFSET: usize = 8 + 32 + 32;
+/// vault_index (1), mint (32), amount (8), period (1), remaining_amount (8), last_reset (8)
+const SUMMARY_SLICE_LEN: usize = 1 + 32 + 8 + 1 + 8 + 8;
+
+/// The fixed fields of a spending limit, decoded from a `SUMMARY_SLICE_*` data slice
+struct LimitSummary {
+    address: Pubkey,
+    vault_index: u8,
+    mint: Pubkey,
+    amount: u64,
+    period: Period,
+    remaining_amount: u64,
+    last_reset: i64,
+}
+
+impl LimitSummary {
+    fn parse(address: Pubkey, data: &[u8]) -> Option<Self> {
+        if data.len() < SUMMARY_SLICE_LEN {
+            return None;
+        }
+        let u64_at = |offset: usize| u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+        let period = match data[41] {
+            0 => Period::OneTime,
+            1 => Period::Day,
+            2 => Period::Week,
+            3 => Period::Month,
+            _ => return None,
+        };
+        Some(LimitSummary {
+            address,
+            vault_index: data[0],
+            mint: Pubkey::try_from(&data[1..33]).ok()?,
+            amount: u64_at(33),
+            period,
+            remaining_amount: u64_at(42),
+            last_reset: u64_at(50) as i64,
+        })
+    }
+}
+
+/// Bytes a `--list` scan moved, for `--verbose`
+#[derive(Default)]
+struct ScanStats {
+    accounts: usize,
+    sliced_bytes: usize,
+    /// Data bytes a full-data scan would have downloaded, inferred from each account's rent
+    full_bytes: usize,
+}
+
+fn format_limit_amount(value: u64, mint: &Pubkey, units: Option<(Units, u8)>) -> String {
+    let is_sol = *mint == Pubkey::default();
+    match units {
+        Some((units, decimals)) => {
+            let label = match (is_sol, units) {
+                (true, Units::Raw) => " lamports",
+                (true, Units::Ui) => " SOL",
+                (false, _) => "",
+            };
+            format!("{}{}", format_amount(value, decimals, units), label)
+        }
+        None if is_sol => format!("{:.9} SOL", value as f64 / LAMPORTS_PER_SOL),
+        None => value.to_string(),
+    }
+}
+
+/// Every spending limit of `multisig_pda` as summary rows, from a sliced getProgramAccounts
+/// scan. None (after printing why) if the scan failed.
+fn scan_spending_limits(client: &RpcClient, multisig_pda: Pubkey, network: Network) -> Option<(Vec<LimitSummary>, ScanStats)> {
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, SpendingLimit::DISCRIMINATOR.to_vec())),
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(8, multisig_pda.to_bytes().to_vec())),
+        ]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            data_slice: Some(UiDataSliceConfig { offset: SUMMARY_SLICE_OFFSET, length: SUMMARY_SLICE_LEN }),
+            commitment: Some(client.commitment()),
+            min_context_slot: None,
+        },
+        with_context: None,
+    };
+    let accounts = match client.get_program_accounts_with_config(&program_id(), config) {
+        Ok(accounts) => accounts,
+        Err(e) => {
+            println!("Error: getProgramAccounts failed: {}", e);
+            if network == Network::Mainnet {
+                println!("The public mainnet RPC disables this call; use a dedicated RPC.");
+            }
+            return None;
+        }
+    };
+
+    // Fold each sliced account into a summary row as it is consumed
+    let rent = Rent::default();
+    let per_byte = (rent.lamports_per_byte_year as f64 * rent.exemption_threshold) as u64;
+    let mut stats = ScanStats::default();
+    let mut summaries: Vec<LimitSummary> = Vec::new();
+    for (address, account) in accounts {
+        stats.accounts += 1;
+        stats.sliced_bytes += account.data.len();
+        stats.full_bytes += (account.lamports / per_byte).saturating_sub(solana_sdk::rent::ACCOUNT_STORAGE_OVERHEAD) as usize;
+        match LimitSummary::parse(address, &account.data) {
+            Some(summary) => summaries.push(summary),
+            None => println!("Warning: could not decode spending limit {}", address),
+        }
+    }
+    Some((summaries, stats))
+}
+
+/// Each limit's usage percentage, for threshold checks
+fn usages(summaries: &[LimitSummary]) -> Vec<(Pubkey, f64)> {
+    summaries
+        .iter()
+        .map(|summary| (summary.address, usage_percent(summary.amount, summary.remaining_amount)))
+        .collect()
+}
+
+/// Summarize every spending limit of `multisig_pda`. Returns each limit's usage percentage.
+fn list_spending_limits(
+    cache: &mut AccountCache,
+    multisig_pda: Pubkey,
+    network: Network,
+    units: Option<Units>,
+    verbose: bool,
+) -> Vec<(Pubkey, f64)> {
+    println!("=== Spending Limits for Multisig ({}) ===\n", network.name().to_uppercase());
+    println!("Multisig: {}", multisig_pda);
+    println!();
+
+    let Some((summaries, stats)) = scan_spending_limits(cache.client(), multisig_pda, network) else {
+        return Vec::new();
+    };
+    if summaries.is_empty() {
+        println!("No spending limits found for this multisig.");
+    } else {
+        println!(
+            "  {:<44} {:>5} {:<10} {:>28} {:>28} {:>6}",
+            "Address", "Vault", "Period", "Remaining", "Amount", "Used"
+        );
+        for summary in &summaries {
+            let units = resolve_units(cache, &summary.mint, units);
+            println!(
+                "  {:<44} {:>5} {:<10} {:>28} {:>28} {:>5.1}%",
+                summary.address.to_string(),
+                summary.vault_index,
+                format_period(&summary.period),
+                format_limit_amount(summary.remaining_amount, &summary.mint, units),
+                format_limit_amount(summary.amount, &summary.mint, units),
+                usage_percent(summary.amount, summary.remaining_amount)
+            );
+            if summary.mint != Pubkey::default() {
+                println!("    mint {}", summary.mint);
+            }
+            if verbose {
+                println!("    last reset: slot {}", summary.last_reset);
+            }
         }
+        println!();
+        println!("Inspect one in full with:");
+        println!("  cargo run --bin inspect-spending-limit -- <spending_limit_address> {}", network.cli_arg());
+    }
+
+    if verbose {
+        println!();
+        println!("Scan: {} account(s), {} bytes of account data transferred", stats.accounts, stats.sliced_bytes);
+        println!("A full-data scan would have transferred ~{} bytes (estimated from rent)", stats.full_bytes);
+    }
+
+    usages(&summaries)
+}
+
+/// `--count-only`: the number of spending limits of `multisig_pda` and the SOL limits' totals.
+/// Returns each limit's usage percentage.
+fn count_spending_limits(
+    cache: &mut AccountCache,
+    multisig_pda: Pubkey,
+    network: Network,
+    units: Option<Units>,
+) -> Vec<(Pubkey, f64)> {
+    println!("=== Spending Limit Count ({}) ===\n", network.name().to_uppercase());
+    println!("Multisig: {}", multisig_pda);
+
+    let Some((summaries, _)) = scan_spending_limits(cache.client(), multisig_pda, network) else {
+        return Vec::new();
+    };
+    let sol: Vec<&LimitSummary> = summaries.iter().filter(|summary| summary.mint == Pubkey::default()).collect();
+    let sol_amount = sol.iter().fold(0u64, |total, summary| total.saturating_add(summary.amount));
+    let sol_remaining = sol.iter().fold(0u64, |total, summary| total.saturating_add(summary.remaining_amount));
+
+    println!("Spending Limits: {} ({} SOL, {} token)", summaries.len(), sol.len(), summaries.len() - sol.len());
+    if !sol.is_empty() {
+        let units = resolve_units(cache, &Pubkey::default(), units);
+        println!("SOL Amount (all limits): {}", format_limit_amount(sol_amount, &Pubkey::default(), units));
+        println!("SOL Remaining (all limits): {}", format_limit_amount(sol_remaining, &Pubkey::default(), units));
+    }
+    usages(&summaries)
+}
+
+/// Print a WARNING for every limit whose usage exceeds `threshold` percent.
+/// Returns true if any limit is over the threshold.
+fn check_alert_threshold(usages: &[(Pubkey, f64)], threshold: f64) -> bool {
+    let over: Vec<_> = usages.iter().filter(|(_, usage)| *usage > threshold).collect();
+
+    println!();
+    if over.is_empty() {
+        println!("OK: all spending limits are at or below {}% usage", threshold);
+        return false;
+    }
+    for (pubkey, usage) in over {
+        println!(
+            "WARNING: spending limit {} is {:.1}% used (alert threshold {}%)",
+            pubkey, usage, threshold
+        );
     }
+    true
 }
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    let mut args: Vec<String> = env::args().collect();
+    let help = take_help(&mut args);
+    take_program_id_or_exit(&mut args);
 
-    if args.len() < 2 {
+    let mut take = |flag: &str| {
+        take_flag_value(&mut args, flag).unwrap_or_else(|e| {
+            println!("Error: {}", e);
+            std::process::exit(1);
+        })
+    };
+    let dump = take("--dump");
+    let multisig_arg = take("--multisig");
+    let list_arg = take("--list");
+
+    let units = match Units::take_from(&mut args) {
+        Ok(units) => units,
+        Err(e) => {
+            println!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let alert_threshold = match take_flag_value(&mut args, "--alert-threshold").and_then(|value| {
+        value.map(|v| parse_alert_threshold(&v)).transpose()
+    }) {
+        Ok(threshold) => threshold,
+        Err(e) => {
+            println!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let verbose = take_flag(&mut args, "--verbose");
+    let count_only = take_flag(&mut args, "--count-only");
+    reject_unknown_flags_or_exit(&args);
+
+    // --multisig and --list replace the spending limit address, shifting the network left
+    let mode = match (multisig_arg, list_arg) {
+        (Some(_), Some(_)) => {
+            println!("Error: --multisig and --list can't be combined");
+            std::process::exit(1);
+        }
+        (Some(address), None) => Some(("--multisig", address)),
+        (None, Some(address)) => Some(("--list", address)),
+        (None, None) => None,
+    };
+
+    if help || (mode.is_none() && args.len() < 2) {
         println!("Usage:");
         println!("  # Inspect a specific spending limit");
         println!("  cargo run --bin inspect-spending-limit -- <spending_limit_address> [mainnet]");
@@ -185,43 +551,88 @@ fn main() {
         println!("  # List all spending limits for a multisig");
         println!("  cargo run --bin inspect-spending-limit -- --multisig <multisig_address> [mainnet]");
         println!();
+        println!("  # Summarize every spending limit of a multisig (any create_key)");
+        println!("  cargo run --bin inspect-spending-limit -- --list <multisig_address> [--verbose] [mainnet]");
+        println!();
+        println!("Options:");
+        println!("  --dump <path>  - Save the spending limit as a JSON snapshot (for use-spending-limit --snapshot)");
+        println!("  --alert-threshold <pct>  - Print a WARNING and exit 1 if usage exceeds pct (for cron/monitoring)");
+        println!("  --units raw|ui  - Print amounts as integer smallest units, or as UI amounts using the mint's decimals");
+        println!("  --verbose  - With --list, print the bytes transferred versus a full-data scan");
+        println!("  --count-only  - With --list or --multisig, print only the limit count and SOL totals (any create_key)");
+        println!();
         println!("Examples:");
         println!("  cargo run --bin inspect-spending-limit -- SpendingLimitPDA...");
         println!("  cargo run --bin inspect-spending-limit -- --multisig MultisigPDA... mainnet");
         return;
     }
 
-    // Parse arguments
-    let is_multisig_mode = args.get(1).map(|s| s == "--multisig").unwrap_or(false);
+    let is_multisig_mode = matches!(mode, Some(("--multisig", _)));
+    let is_list_mode = matches!(mode, Some(("--list", _)));
+    if count_only && mode.is_none() {
+        println!("Error: --count-only needs --list or --multisig");
+        std::process::exit(1);
+    }
+    if count_only && dump.is_some() {
+        println!("Error: --dump needs a single spending limit; it cannot be combined with --count-only");
+        std::process::exit(1);
+    }
+    if is_list_mode && dump.is_some() {
+        println!("Error: --dump needs a single spending limit; it cannot be combined with --list");
+        std::process::exit(1);
+    }
 
-    let (address_str, network) = if is_multisig_mode {
-        if args.len() < 3 {
-            println!("Error: --multisig requires an address");
-            return;
-        }
-        (args[2].as_str(), args.get(3).map(|s| s.as_str()).unwrap_or("devnet"))
-    } else {
-        (args[1].as_str(), args.get(2).map(|s| s.as_str()).unwrap_or("devnet"))
+    let (address_str, network) = match &mode {
+        Some((_, address)) => (address.as_str(), Network::from_arg(args.get(1).map(|s| s.as_str()).unwrap_or("devnet"))),
+        None => (args[1].as_str(), Network::from_arg(args.get(2).map(|s| s.as_str()).unwrap_or("devnet"))),
     };
 
-    let address: Pubkey = match address_str.parse() {
+    let address = match try_resolve_pubkey(address_str) {
         Ok(pk) => pk,
-        Err(_) => {
-            println!("Error: Invalid address: {}", address_str);
+        Err(e) => {
+            println!("Error: {}", e);
             return;
         }
     };
 
-    let rpc_url = match network {
-        "mainnet" => MAINNET_RPC,
-        _ => DEVNET_RPC,
-    };
+    let client = RpcClient::new_with_commitment(network.rpc_url(), CommitmentConfig::confirmed());
 
-    let client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
+    check_program_or_exit(&client);
 
-    if is_multisig_mode {
-        inspect_multisig(&client, address, network);
+    // Accounts and mint decimals are fetched once, however many limits share them
+    let mut cache = AccountCache::new(&client);
+    if mode.is_some() {
+        if let Err(e) = cache.multisig(&address) {
+            println!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+
+    let options = DisplayOptions { dump, units };
+    let usages: Vec<(Pubkey, f64)> = if count_only {
+        count_spending_limits(&mut cache, address, network, units)
+    } else if is_list_mode {
+        list_spending_limits(&mut cache, address, network, units, verbose)
     } else {
-        inspect_single(&client, address, network);
+        let limits = if is_multisig_mode {
+            inspect_multisig(&mut cache, address, network, &options)
+        } else {
+            inspect_single(&mut cache, address, network, &options)
+        };
+        limits
+            .iter()
+            .map(|(pubkey, limit)| (*pubkey, usage_percent(limit.amount, limit.remaining_amount)))
+            .collect()
+    };
+
+    if let Some(threshold) = alert_threshold {
+        // A monitoring check must not pass silently when nothing could be read
+        if usages.is_empty() {
+            println!("\nError: no spending limit could be read; cannot check --alert-threshold");
+            std::process::exit(1);
+        }
+        if check_alert_threshold(&usages, threshold) {
+            std::process::exit(1);
+        }
     }
 }
@@ -1,76 +1,224 @@
-use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient};
 use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
-use squads_multisig::anchor_lang::AccountDeserialize;
 use squads_multisig::pda::get_vault_pda;
-use squads_multisig::state::Multisig;
+use squads_multisig::state::{Multisig, Permission};
+use squads_rust::addressbook::resolve_pubkey;
+use squads_rust::args::{reject_unknown_flags_or_exit, take_flag, take_flag_value, take_help};
+use squads_rust::format::{format_amount, format_unix_timestamp, Units, SOL_DECIMALS};
+use squads_rust::multisig::fetch_multisig;
+use squads_rust::network::{open_in_browser, squads_app_url, Network};
+use squads_rust::permissions::PermissionsExt;
+use squads_rust::program::{program_id, take_program_id_or_exit};
+use squads_rust::registry::lookup_name;
 use std::env;
 
-const DEVNET_RPC: &str = "https://api.devnet.solana.com";
-const MAINNET_RPC: &str = "https://api.mainnet-beta.solana.com";
+fn yes_no(value: bool) -> &'static str {
+    if value { "yes" } else { "no" }
+}
+
+/// Print a permission audit of the multisig's members. Returns true if there is a critical
+/// finding, i.e. the multisig can no longer pass or execute proposals.
+fn audit(multisig: &Multisig) -> bool {
+    println!("\n=== Permission Audit ===\n");
+    println!("  {:<44} {:<9} {:<5} {:<7}", "Member", "Initiate", "Vote", "Execute");
+    for member in &multisig.members {
+        println!(
+            "  {:<44} {:<9} {:<5} {:<7}",
+            member.key.to_string(),
+            yes_no(member.permissions.contains(Permission::Initiate)),
+            yes_no(member.permissions.contains(Permission::Vote)),
+            yes_no(member.permissions.contains(Permission::Execute)),
+        );
+    }
+
+    let mut critical = false;
+    let mut findings = 0;
+    println!("\nFindings:");
+
+    for member in &multisig.members {
+        if member.permissions.contains(Permission::Execute) && !member.permissions.contains(Permission::Vote) {
+            findings += 1;
+            println!("  WARNING: {} can execute but not vote", member.key);
+        }
+    }
+
+    let voters = multisig
+        .members
+        .iter()
+        .filter(|m| m.permissions.contains(Permission::Vote))
+        .count();
+    if usize::from(multisig.threshold) > voters {
+        findings += 1;
+        critical = true;
+        println!(
+            "  CRITICAL: Threshold {} exceeds the {} member(s) with Vote permission. No proposal can be approved.",
+            multisig.threshold, voters
+        );
+    }
+
+    if !multisig.members.iter().any(|m| m.permissions.contains(Permission::Execute)) {
+        findings += 1;
+        critical = true;
+        println!("  CRITICAL: No member has Execute permission. Approved proposals can never be executed.");
+    }
+
+    if multisig.config_authority != Pubkey::default() && multisig.is_member(multisig.config_authority).is_some() {
+        findings += 1;
+        println!(
+            "  WARNING: Config authority {} is also a member and can change the multisig without a vote",
+            multisig.config_authority
+        );
+    }
+
+    if findings == 0 {
+        println!("  None");
+    }
+
+    critical
+}
+
+/// Most signatures `get_signatures_for_address` returns in one call
+const MAX_HISTORY: usize = 1000;
+
+/// Print the last `limit` transactions that touched the multisig account, newest first
+fn print_history(client: &RpcClient, multisig_pda: &Pubkey, limit: usize) {
+    println!("\n=== Recent Activity (last {}) ===\n", limit);
+    let config = GetConfirmedSignaturesForAddress2Config {
+        limit: Some(limit),
+        ..GetConfirmedSignaturesForAddress2Config::default()
+    };
+    let signatures = match client.get_signatures_for_address_with_config(multisig_pda, config) {
+        Ok(signatures) => signatures,
+        Err(e) => {
+            println!("  Unavailable: {}", e);
+            return;
+        }
+    };
+    if signatures.is_empty() {
+        println!("  No transactions found for this multisig yet.");
+        return;
+    }
+    for entry in &signatures {
+        let time = entry
+            .block_time
+            .map(format_unix_timestamp)
+            .unwrap_or_else(|| "time unknown".to_string());
+        let status = match &entry.err {
+            None => "ok".to_string(),
+            Some(e) => format!("FAILED ({})", e),
+        };
+        println!("  {} slot {} {} {}", time, entry.slot, status, entry.signature);
+    }
+}
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    let mut args: Vec<String> = env::args().collect();
+    let help = take_help(&mut args);
+    take_program_id_or_exit(&mut args);
+
+    let run_audit = take_flag(&mut args, "--audit");
+    let links = take_flag(&mut args, "--links");
+    let open = take_flag(&mut args, "--open");
 
-    if args.len() < 2 {
-        println!("Usage: cargo run --bin inspect_multisig -- <multisig_address> [mainnet]");
+    let units = Units::take_from(&mut args).unwrap_or_else(|e| {
+        println!("Error: {}", e);
+        std::process::exit(1);
+    });
+
+    let history = take_flag_value(&mut args, "--history")
+        .and_then(|value| {
+            value
+                .map(|v| match v.parse::<usize>() {
+                    Ok(n) if (1..=MAX_HISTORY).contains(&n) => Ok(n),
+                    _ => Err(format!("--history must be a count between 1 and {}, got {}", MAX_HISTORY, v)),
+                })
+                .transpose()
+        })
+        .unwrap_or_else(|e| {
+            println!("Error: {}", e);
+            std::process::exit(1);
+        });
+    reject_unknown_flags_or_exit(&args);
+
+    if help || args.len() < 2 {
+        println!("Usage: cargo run --bin inspect_multisig -- <multisig_address> [--audit] [--units raw|ui] [--history <n>] [--links] [--open] [mainnet]");
         println!("Example: cargo run --bin inspect_multisig -- BJbRtXM8wecvRrJNbbpNLfuG8FTSoU6zPYW1NFrMH6Q3 mainnet");
+        println!();
+        println!("  --audit  Report member permissions and misconfigurations; exits 1 on a critical finding");
+        println!("  --units raw|ui  Also show the vault balance, in lamports (raw) or SOL (ui)");
+        println!("  --history <n>  Show the last n transactions on the multisig (slot, time, status)");
+        println!("  --links  Print explorer and Squads app links for the multisig");
+        println!("  --open   Open the Squads app page for the multisig in the browser (prints the URL if that fails)");
         return;
     }
 
-    let multisig_pda: Pubkey = args[1].parse().expect("Invalid multisig address");
-    let network = args.get(2).map(|s| s.as_str()).unwrap_or("devnet");
+    let multisig_pda: Pubkey = resolve_pubkey(&args[1]);
+    let network = Network::from_arg(args.get(2).map(|s| s.as_str()).unwrap_or("devnet"));
 
-    let rpc_url = match network {
-        "mainnet" => MAINNET_RPC,
-        _ => DEVNET_RPC,
-    };
+    let client = RpcClient::new_with_commitment(network.rpc_url(), CommitmentConfig::confirmed());
 
-    let client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
-
-    println!("=== Multisig Info ({}) ===\n", network.to_uppercase());
-
-    match client.get_account(&multisig_pda) {
-        Ok(account) => {
-            match Multisig::try_deserialize(&mut account.data.as_slice()) {
-                Ok(multisig) => {
-                    println!("Multisig Address: {}", multisig_pda);
-                    let (vault_pda, _) = get_vault_pda(&multisig_pda, 0, None);
-                    println!("Vault Address: {} (send funds here!)", vault_pda);
-                    println!("Threshold: {} of {}", multisig.threshold, multisig.members.len());
-                    println!("Time Lock: {} seconds", multisig.time_lock);
-
-                    // Config authority - check if it's the default (all zeros = None)
-                    let config_auth = multisig.config_authority;
-                    if config_auth == Pubkey::default() {
-                        println!("Config Authority: None (autonomous)");
-                    } else {
-                        println!("Config Authority: {}", config_auth);
-                    }
-
-                    // Rent collector
-                    match multisig.rent_collector {
-                        Some(rc) => println!("Rent Collector: {}", rc),
-                        None => println!("Rent Collector: None"),
-                    }
-
-                    println!("\nMembers:");
-                    for (i, member) in multisig.members.iter().enumerate() {
-                        let perms = member.permissions.mask;
-                        let perm_str = format!(
-                            "{}{}{}",
-                            if perms & 1 != 0 { "Initiate " } else { "" },
-                            if perms & 2 != 0 { "Vote " } else { "" },
-                            if perms & 4 != 0 { "Execute" } else { "" }
-                        );
-                        println!("  {}. {} [{}]", i + 1, member.key, perm_str.trim());
-                    }
-
-                    println!("\nTransaction Index: {}", multisig.transaction_index);
-                    println!("Stale Transaction Index: {}", multisig.stale_transaction_index);
+    println!("=== Multisig Info ({}) ===\n", network.name().to_uppercase());
+
+    match fetch_multisig(&client, &multisig_pda) {
+        Ok(multisig) => {
+            match lookup_name(&multisig_pda) {
+                Ok(Some(name)) => println!("Name: {} (local registry)", name),
+                Ok(None) => {}
+                Err(e) => println!("Name: unavailable ({})", e),
+            }
+            println!("Multisig Address: {}", multisig_pda);
+            let (vault_pda, _) = get_vault_pda(&multisig_pda, 0, Some(&program_id()));
+            println!("Vault Address: {} (send funds here!)", vault_pda);
+            if let Some(units) = units {
+                match client.get_balance(&vault_pda) {
+                    Ok(lamports) => println!("Vault Balance: {}", format_amount(lamports, SOL_DECIMALS, units)),
+                    Err(e) => println!("Vault Balance: unavailable ({})", e),
                 }
-                Err(e) => println!("Failed to deserialize multisig: {}", e),
+            }
+            println!("Threshold: {} of {}", multisig.threshold, multisig.members.len());
+            println!("Time Lock: {} seconds", multisig.time_lock);
+
+            // Config authority - check if it's the default (all zeros = None)
+            let config_auth = multisig.config_authority;
+            if config_auth == Pubkey::default() {
+                println!("Config Authority: None (autonomous)");
+            } else {
+                println!("Config Authority: {}", config_auth);
+            }
+
+            // Rent collector
+            match multisig.rent_collector {
+                Some(rc) => println!("Rent Collector: {}", rc),
+                None => println!("Rent Collector: None"),
+            }
+
+            println!("\nMembers:");
+            for (i, member) in multisig.members.iter().enumerate() {
+                println!("  {}. {} [{}]", i + 1, member.key, member.permissions.to_string());
+            }
+
+            println!("\nTransaction Index: {}", multisig.transaction_index);
+            println!("Stale Transaction Index: {}", multisig.stale_transaction_index);
+
+            if let Some(limit) = history {
+                print_history(&client, &multisig_pda, limit);
+            }
+            if links {
+                network.print_links(&multisig_pda, None, None);
+            }
+            if open {
+                open_in_browser(&squads_app_url(&multisig_pda));
+            }
+
+            if run_audit {
+                std::process::exit(if audit(&multisig) { 1 } else { 0 });
             }
         }
-        Err(e) => println!("Error fetching account: {}", e),
+        Err(e) => println!("Error: {}", e),
+    }
+
+    // Reaching here means the multisig couldn't be read; don't let an audit pass silently
+    if run_audit {
+        std::process::exit(1);
     }
 }
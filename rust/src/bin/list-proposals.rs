@@ -0,0 +1,165 @@
+//! List a multisig's proposals, optionally filtered to a date range for audits
+//!
+//! Read-only: nothing is sent. Lists every proposal still on-chain with its status and votes.
+//!
+//! With `--since` and/or `--before`, each proposal's creation time is also resolved (the block
+//! time of the oldest signature on the proposal account, one or more extra RPC calls per
+//! proposal), and only proposals created or last changing status within the range are listed.
+//! The status time is the one stored on the proposal, e.g. when it was approved or executed.
+//! Without the date flags no extra calls are made.
+//!
+//! Closed proposals no longer exist on-chain, so they can't be listed.
+//!
+//! Usage:
+//!   cargo run --bin list-proposals -- <multisig_address> [--since <date>] [--before <date>] [mainnet]
+//!
+//! Options:
+//!   --since <date>   - Only proposals created or changing status at or after this UTC date
+//!   --before <date>  - Only proposals created or changing status before this UTC date
+//!
+//! Dates are `YYYY-MM-DD` or `YYYY-MM-DDTHH:MM:SS`, in UTC.
+
+use solana_client::rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient};
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature};
+use squads_rust::addressbook::resolve_pubkey;
+use squads_rust::args::{reject_unknown_flags_or_exit, take_flag_value, take_help};
+use squads_rust::format::{format_unix_timestamp, parse_date};
+use squads_rust::multisig::{fetch_multisig_or_exit, fetch_proposals};
+use squads_rust::network::Network;
+use squads_rust::program::take_program_id_or_exit;
+use squads_rust::proposal::ProposalState;
+use std::env;
+use std::str::FromStr;
+
+/// Page size of getSignaturesForAddress
+const SIGNATURE_PAGE: usize = 1_000;
+
+/// Block time of the oldest signature touching `address`, i.e. when the account was created
+fn creation_time(client: &RpcClient, address: &Pubkey) -> Result<Option<i64>, String> {
+    let mut before: Option<Signature> = None;
+    let mut oldest = None;
+    loop {
+        let config = GetConfirmedSignaturesForAddress2Config {
+            before,
+            until: None,
+            limit: Some(SIGNATURE_PAGE),
+            commitment: Some(client.commitment()),
+        };
+        let page = client
+            .get_signatures_for_address_with_config(address, config)
+            .map_err(|e| format!("failed to fetch signatures for {}: {}", address, e))?;
+        let Some(last) = page.last() else { break };
+        oldest = last.block_time;
+        before = Some(Signature::from_str(&last.signature).map_err(|e| format!("invalid signature: {}", e))?);
+        if page.len() < SIGNATURE_PAGE {
+            break;
+        }
+    }
+    Ok(oldest)
+}
+
+fn take_date(args: &mut Vec<String>, flag: &str) -> Option<i64> {
+    let value = take_flag_value(args, flag).unwrap_or_else(|e| {
+        println!("Error: {}", e);
+        std::process::exit(1);
+    })?;
+    Some(parse_date(&value).unwrap_or_else(|e| {
+        println!("Error: {} {}", flag, e);
+        std::process::exit(1);
+    }))
+}
+
+fn main() {
+    let mut args: Vec<String> = env::args().collect();
+    let help = take_help(&mut args);
+    take_program_id_or_exit(&mut args);
+    let since = take_date(&mut args, "--since");
+    let before = take_date(&mut args, "--before");
+    reject_unknown_flags_or_exit(&args);
+
+    if help || args.len() < 2 {
+        println!("List a multisig's proposals, optionally filtered to a date range");
+        println!();
+        println!("Usage:");
+        println!("  cargo run --bin list-proposals -- <multisig_address> [--since <date>] [--before <date>] [mainnet]");
+        println!();
+        println!("Options:");
+        println!("  --since <date>   - Only proposals created or changing status at or after this UTC date");
+        println!("  --before <date>  - Only proposals created or changing status before this UTC date");
+        println!();
+        println!("Dates are YYYY-MM-DD or YYYY-MM-DDTHH:MM:SS (UTC). The date flags fetch each proposal's");
+        println!("creation time, costing extra RPC calls per proposal.");
+        return;
+    }
+    if let (Some(since), Some(before)) = (since, before) {
+        if since >= before {
+            println!("Error: --since must be earlier than --before");
+            std::process::exit(1);
+        }
+    }
+
+    let multisig_pda: Pubkey = resolve_pubkey(&args[1]);
+    let network = Network::from_arg(args.get(2).map(|s| s.as_str()).unwrap_or("devnet"));
+
+    let client = RpcClient::new_with_commitment(network.rpc_url(), CommitmentConfig::confirmed());
+    let multisig = fetch_multisig_or_exit(&client, &multisig_pda);
+    let proposals = fetch_proposals(&client, &multisig_pda, &multisig).unwrap_or_else(|e| {
+        println!("Error: {}", e);
+        std::process::exit(1);
+    });
+
+    println!("=== Proposals ({}) ===\n", network.name().to_uppercase());
+    println!("Multisig: {}", multisig_pda);
+    println!("Threshold: {}/{}", multisig.threshold, multisig.members.len());
+    if since.is_some() || before.is_some() {
+        println!(
+            "Range: {} to {}",
+            since.map_or("any".to_string(), format_unix_timestamp),
+            before.map_or("any".to_string(), format_unix_timestamp)
+        );
+    }
+    println!();
+
+    let filtered = since.is_some() || before.is_some();
+    let in_range = |time: Option<i64>| {
+        time.is_some_and(|time| since.is_none_or(|since| time >= since) && before.is_none_or(|before| time < before))
+    };
+    let mut listed = 0;
+    for (index, address, proposal) in &proposals {
+        let created = if filtered {
+            match creation_time(&client, address) {
+                Ok(created) => created,
+                Err(e) => {
+                    println!("Warning: proposal {}: {}", index, e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        let state = ProposalState::of(proposal);
+        if filtered && !in_range(created) && !in_range(state.timestamp()) {
+            continue;
+        }
+        listed += 1;
+
+        println!("Proposal {} ({})", index, address);
+        println!("  Status: {}", state.label());
+        if filtered {
+            println!("  Created: {}", created.map_or("unknown".to_string(), format_unix_timestamp));
+        }
+        println!(
+            "  Votes: {} approved, {} rejected, {} cancelled (threshold {})",
+            proposal.approved.len(),
+            proposal.rejected.len(),
+            proposal.cancelled.len(),
+            multisig.threshold
+        );
+    }
+
+    if filtered {
+        println!("\n{} of {} on-chain proposal(s) in range", listed, proposals.len());
+    } else {
+        println!("{} on-chain proposal(s)", proposals.len());
+    }
+}
@@ -0,0 +1,169 @@
+//! List a multisig's vaults with their SOL and token balances
+//!
+//! SOL balances for all vaults come from one `get_multiple_accounts` call; token balances come
+//! from `get_token_accounts_by_owner` (jsonParsed) per vault, for both SPL Token and Token-2022.
+//!
+//! `--json` prints only a JSON array for portfolio and accounting tools:
+//!
+//!   [{"vault_index": 0, "address": "...", "sol_lamports": "...",
+//!     "tokens": [{"mint": "...", "account": "...", "amount": "...", "decimals": 6, "ui_amount": "..."}]}]
+//!
+//! Amounts are strings so large u64 values survive JSON parsers that use doubles.
+//!
+//! Usage:
+//!   cargo run --bin list-vaults-with-balances -- <multisig_address> [--vaults <count>] [--json] [mainnet]
+//!
+//! Options:
+//!   --vaults <count>  - Number of vaults to list, starting at index 0 (default 1)
+//!   --json            - Print the JSON array only
+
+use serde_json::{json, Value};
+use solana_account_decoder::UiAccountData;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_request::TokenAccountsFilter;
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
+use squads_multisig::pda::get_vault_pda;
+use squads_rust::addressbook::resolve_pubkey;
+use squads_rust::args::{reject_unknown_flags_or_exit, take_flag, take_flag_value, take_help};
+use squads_rust::multisig::{fetch_accounts, fetch_multisig_or_exit};
+use squads_rust::network::Network;
+use squads_rust::program::{program_id, take_program_id_or_exit};
+use std::env;
+
+/// One token account held by a vault
+struct TokenBalance {
+    mint: String,
+    account: String,
+    amount: String,
+    decimals: u64,
+    ui_amount: String,
+}
+
+fn exit_with(message: String) -> ! {
+    println!("Error: {}", message);
+    std::process::exit(1);
+}
+
+/// Token accounts owned by `owner` under both token programs, from jsonParsed data
+fn fetch_token_balances(client: &RpcClient, owner: &Pubkey) -> Result<Vec<TokenBalance>, String> {
+    let mut balances = Vec::new();
+    for program_id in [spl_token::ID, spl_token_2022::ID] {
+        let accounts = client
+            .get_token_accounts_by_owner(owner, TokenAccountsFilter::ProgramId(program_id))
+            .map_err(|e| format!("failed to fetch token accounts of {}: {}", owner, e))?;
+        for keyed in accounts {
+            let UiAccountData::Json(parsed) = keyed.account.data else {
+                return Err(format!("token account {} was not returned as jsonParsed", keyed.pubkey));
+            };
+            let info = &parsed.parsed["info"];
+            let token_amount = &info["tokenAmount"];
+            let field = |value: &Value, name: &str| {
+                value[name]
+                    .as_str()
+                    .map(str::to_string)
+                    .ok_or_else(|| format!("token account {} has no {}", keyed.pubkey, name))
+            };
+            balances.push(TokenBalance {
+                mint: field(info, "mint")?,
+                account: keyed.pubkey.clone(),
+                amount: field(token_amount, "amount")?,
+                decimals: token_amount["decimals"]
+                    .as_u64()
+                    .ok_or_else(|| format!("token account {} has no decimals", keyed.pubkey))?,
+                ui_amount: field(token_amount, "uiAmountString")?,
+            });
+        }
+    }
+    Ok(balances)
+}
+
+fn main() {
+    let mut args: Vec<String> = env::args().collect();
+    let help = take_help(&mut args);
+    take_program_id_or_exit(&mut args);
+    let json_output = take_flag(&mut args, "--json");
+    let vault_count: u16 = match take_flag_value(&mut args, "--vaults").unwrap_or_else(|e| exit_with(e)) {
+        Some(value) => match value.parse::<u16>() {
+            Ok(count) if (1..=256).contains(&count) => count,
+            _ => exit_with(format!("--vaults must be a count between 1 and 256, got {}", value)),
+        },
+        None => 1,
+    };
+    reject_unknown_flags_or_exit(&args);
+
+    if help || args.len() < 2 {
+        println!("List a multisig's vaults with their SOL and token balances");
+        println!();
+        println!("Usage:");
+        println!("  cargo run --bin list-vaults-with-balances -- <multisig_address> [--vaults <count>] [--json] [mainnet]");
+        println!();
+        println!("Options:");
+        println!("  --vaults <count>  - Number of vaults to list, starting at index 0 (default 1)");
+        println!("  --json            - Print only a JSON array (amounts as strings)");
+        return;
+    }
+
+    let multisig_pda: Pubkey = resolve_pubkey(&args[1]);
+    let network = Network::from_arg(args.get(2).map(|s| s.as_str()).unwrap_or("devnet"));
+
+    let client = RpcClient::new_with_commitment(network.rpc_url(), CommitmentConfig::confirmed());
+    // Fail early (with the wrong-cluster hint) if this isn't a multisig
+    fetch_multisig_or_exit(&client, &multisig_pda);
+
+    // Vault PDAs are plain system accounts; one that was never funded doesn't exist yet
+    let vaults: Vec<(u8, Pubkey)> = (0..vault_count)
+        .map(|index| {
+            let index = index as u8;
+            (index, get_vault_pda(&multisig_pda, index, Some(&program_id())).0)
+        })
+        .collect();
+    let keys: Vec<Pubkey> = vaults.iter().map(|(_, address)| *address).collect();
+    let accounts = fetch_accounts(&client, &keys)
+        .unwrap_or_else(|e| exit_with(format!("failed to fetch vault accounts: {}", e)));
+
+    let mut report = Vec::with_capacity(vaults.len());
+    for ((index, address), account) in vaults.iter().zip(accounts) {
+        let lamports = account.map_or(0, |account| account.lamports);
+        let tokens = fetch_token_balances(&client, address).unwrap_or_else(|e| exit_with(e));
+        report.push((*index, *address, lamports, tokens));
+    }
+
+    if json_output {
+        let vaults: Vec<Value> = report
+            .iter()
+            .map(|(index, address, lamports, tokens)| {
+                json!({
+                    "vault_index": index,
+                    "address": address.to_string(),
+                    "sol_lamports": lamports.to_string(),
+                    "tokens": tokens
+                        .iter()
+                        .map(|token| json!({
+                            "mint": token.mint,
+                            "account": token.account,
+                            "amount": token.amount,
+                            "decimals": token.decimals,
+                            "ui_amount": token.ui_amount,
+                        }))
+                        .collect::<Vec<_>>(),
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&vaults).expect("Failed to serialize vaults"));
+        return;
+    }
+
+    println!("=== Vault Balances ({}) ===\n", network.name().to_uppercase());
+    println!("Multisig: {}", multisig_pda);
+    for (index, address, lamports, tokens) in &report {
+        println!();
+        println!("Vault {}: {}", index, address);
+        println!("  SOL: {:.9} ({} lamports)", *lamports as f64 / 1_000_000_000.0, lamports);
+        if tokens.is_empty() {
+            println!("  Tokens: none");
+        }
+        for token in tokens {
+            println!("  Token {}: {} (account {})", token.mint, token.ui_amount, token.account);
+        }
+    }
+}
@@ -0,0 +1,218 @@
+//! Bring a spending limit's members in line with the multisig's current roster (config authority only)
+//!
+//! Removing a member from the multisig does not remove them from its spending limits, and
+//! `SpendingLimitUse` only checks the limit's own member list, so a departed member can keep
+//! spending. This diffs the limit's members against the current roster, prints the diff, and after
+//! confirmation replaces the limit in one transaction: remove it, then re-add it with the same
+//! create_key (so its address is unchanged) and the new member list.
+//!
+//! Re-adding resets the limit, so the remaining amount goes back to the full amount.
+//!
+//! Usage:
+//!   cargo run --bin migrate-spending-limit-members -- <spending_limit_address> [--roster intersection|current] [--yes] [mainnet]
+//!
+//! Options:
+//!   --roster intersection  - Keep only limit members still in the multisig (default)
+//!   --roster current       - Grant the limit to every current multisig member
+//!   --yes                  - Skip the confirmation prompt
+
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Signer},
+    system_program,
+};
+use squads_multisig::anchor_lang::{AccountDeserialize, InstructionData};
+use squads_multisig::squads_multisig_program;
+use squads_multisig::state::SpendingLimit;
+use squads_rust::addressbook::resolve_pubkey;
+use squads_rust::args::{reject_unknown_flags_or_exit, take_flag, take_flag_value, take_help};
+use squads_rust::audit::record;
+use squads_rust::confirm::confirm;
+use squads_rust::multisig::{fetch_multisig_or_exit, normalize_members};
+use squads_rust::network::Network;
+use squads_rust::program::{program_id, take_program_id_or_exit};
+use squads_rust::send::{build_and_send, SendOptions};
+use std::env;
+
+fn exit_with(message: String) -> ! {
+    println!("Error: {}", message);
+    std::process::exit(1);
+}
+
+fn main() {
+    let mut args: Vec<String> = env::args().collect();
+    let help = take_help(&mut args);
+    take_program_id_or_exit(&mut args);
+    let send_options = SendOptions::take_from_or_exit(&mut args);
+    let yes = take_flag(&mut args, "--yes");
+    let full_roster = match take_flag_value(&mut args, "--roster").unwrap_or_else(|e| exit_with(e)).as_deref() {
+        None | Some("intersection") => false,
+        Some("current") => true,
+        Some(other) => exit_with(format!("invalid --roster '{}' (expected intersection or current)", other)),
+    };
+    reject_unknown_flags_or_exit(&args);
+
+    if help || args.len() < 2 {
+        println!("Bring a spending limit's members in line with the multisig's current roster");
+        println!();
+        println!("Usage:");
+        println!("  cargo run --bin migrate-spending-limit-members -- <spending_limit_address> [--roster intersection|current] [--yes] [mainnet]");
+        println!();
+        println!("Options:");
+        println!("  --roster intersection  - Keep only limit members still in the multisig (default)");
+        println!("  --roster current       - Grant the limit to every current multisig member");
+        println!("  --yes                  - Skip the confirmation prompt");
+        return;
+    }
+
+    let spending_limit_pda: Pubkey = resolve_pubkey(&args[1]);
+    let network = Network::from_arg(args.get(2).map(|s| s.as_str()).unwrap_or("devnet"));
+
+    let client = RpcClient::new_with_commitment(network.rpc_url(), CommitmentConfig::confirmed());
+    let config_authority = read_keypair_file("../member1.json").expect("Failed to read member1.json");
+
+    let account = client
+        .get_account(&spending_limit_pda)
+        .unwrap_or_else(|e| exit_with(format!("Failed to fetch spending limit {}: {}", spending_limit_pda, e)));
+    let limit = SpendingLimit::try_deserialize(&mut account.data.as_slice())
+        .unwrap_or_else(|e| exit_with(format!("{} is not a spending limit: {}", spending_limit_pda, e)));
+    let multisig_pda = limit.multisig;
+    let multisig = fetch_multisig_or_exit(&client, &multisig_pda);
+
+    println!("=== Migrate Spending Limit Members ({}) ===\n", network.name().to_uppercase());
+    println!("Spending Limit: {}", spending_limit_pda);
+    println!("Multisig: {}", multisig_pda);
+    println!("Config Authority: {}", config_authority.pubkey());
+    println!();
+
+    // Diff the limit's members against the roster
+    let stale: Vec<Pubkey> = limit
+        .members
+        .iter()
+        .filter(|key| multisig.is_member(**key).is_none())
+        .copied()
+        .collect();
+    let missing: Vec<Pubkey> = multisig
+        .members
+        .iter()
+        .map(|m| m.key)
+        .filter(|key| !limit.members.contains(key))
+        .collect();
+
+    println!("Limit members ({}):", limit.members.len());
+    for key in &limit.members {
+        let note = if stale.contains(key) { "  STALE (no longer a multisig member)" } else { "" };
+        println!("  - {}{}", key, note);
+    }
+    if !missing.is_empty() {
+        println!("Multisig members not on the limit ({}):", missing.len());
+        for key in &missing {
+            println!("  + {}", key);
+        }
+    }
+    println!();
+
+    let mut new_members: Vec<Pubkey> = if full_roster {
+        multisig.members.iter().map(|m| m.key).collect()
+    } else {
+        limit.members.iter().filter(|key| !stale.contains(key)).copied().collect()
+    };
+    if new_members.is_empty() {
+        exit_with(
+            "no limit member is still in the multisig; use --roster current to grant it to the current roster, or remove the limit".to_string(),
+        );
+    }
+    normalize_members(&mut new_members, |key| *key).unwrap_or_else(|e| exit_with(e));
+
+    if new_members == limit.members {
+        println!("Nothing to migrate: the limit's members already match.");
+        return;
+    }
+    if multisig.config_authority != config_authority.pubkey() {
+        exit_with(format!(
+            "member1 ({}) is not the config authority ({}); an autonomous multisig must change spending limits through a config transaction",
+            config_authority.pubkey(),
+            multisig.config_authority
+        ));
+    }
+
+    println!("New members ({}, {}):", new_members.len(), if full_roster { "current roster" } else { "intersection" });
+    for key in &new_members {
+        println!("  - {}", key);
+    }
+    println!();
+    println!("The limit will be removed and re-created at the same address with these members.");
+    println!(
+        "Its remaining amount resets from {} to {} (smallest units).",
+        limit.remaining_amount, limit.amount
+    );
+
+    if !yes && !confirm("\nReplace the spending limit?") {
+        println!("Aborted.");
+        std::process::exit(1);
+    }
+
+    // Account order from MultisigRemoveSpendingLimit: multisig, config_authority,
+    // spending_limit (close), rent_collector
+    let remove_ix = Instruction {
+        program_id: program_id(),
+        accounts: vec![
+            AccountMeta::new_readonly(multisig_pda, false),
+            AccountMeta::new_readonly(config_authority.pubkey(), true),
+            AccountMeta::new(spending_limit_pda, false),
+            AccountMeta::new(config_authority.pubkey(), false),
+        ],
+        data: squads_multisig_program::instruction::MultisigRemoveSpendingLimit {
+            args: squads_multisig_program::MultisigRemoveSpendingLimitArgs { memo: None },
+        }
+        .data(),
+    };
+
+    // Account order from MultisigAddSpendingLimit: multisig, config_authority,
+    // spending_limit (init), rent_payer, system_program
+    let add_ix = Instruction {
+        program_id: program_id(),
+        accounts: vec![
+            AccountMeta::new_readonly(multisig_pda, false),
+            AccountMeta::new_readonly(config_authority.pubkey(), true),
+            AccountMeta::new(spending_limit_pda, false),
+            AccountMeta::new(config_authority.pubkey(), true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: squads_multisig_program::instruction::MultisigAddSpendingLimit {
+            args: squads_multisig_program::MultisigAddSpendingLimitArgs {
+                create_key: limit.create_key,
+                vault_index: limit.vault_index,
+                mint: limit.mint,
+                amount: limit.amount,
+                period: limit.period,
+                members: new_members,
+                destinations: limit.destinations.clone(),
+                memo: None,
+            },
+        }
+        .data(),
+    };
+
+    println!("\nReplacing spending limit...");
+
+    match build_and_send(&client, &[remove_ix, add_ix], &config_authority.pubkey(), &[&config_authority], &send_options) {
+        Ok(sig) => {
+            record(network, &multisig_pda, &format!("migrate spending limit {} members", spending_limit_pda), &config_authority.pubkey(), &sig);
+            println!("\nSpending limit members migrated successfully!");
+            println!("Transaction: {}", sig);
+            println!("\nInspect with:");
+            println!("  cargo run --bin inspect-spending-limit -- {} {}", spending_limit_pda, network.cli_arg());
+
+            println!("\nView on Solana Explorer:");
+            println!("{}", network.explorer_tx_url(&sig));
+        }
+        Err(e) => {
+            println!("\nFailed to migrate spending limit: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
@@ -1,97 +1,134 @@
 //! Create a proposal to mint tokens from a mint the multisig controls
 //!
 //! Usage:
-//!   cargo run --bin mint-tokens-proposal -- <multisig_address> <mint> <destination_wallet> <amount> [mainnet]
+//!   cargo run --bin mint-tokens-proposal -- <multisig_address> <mint> <destination_wallet> <amount> [--vault <index>] [--dump-message] [mainnet]
+//!
+//! Pass `--dump-message` to print the compiled vault transaction message before sending.
 //!
 //! Example:
 //!   # Mint 10,000 tokens (with 9 decimals = 10000 * 10^9 = 10_000_000_000_000)
 //!   cargo run --bin mint-tokens-proposal -- BJbRt... E7xkt... DestWallet... 10000000000000 mainnet
 //!
+//!   # Mint authority held by vault 1 instead of vault 0
+//!   cargo run --bin mint-tokens-proposal -- BJbRt... E7xkt... DestWallet... 10000000000000 --vault 1
+//!
 //! This script now derives the destination ATA from <destination_wallet> and adds an
 //! idempotent ATA creation instruction before minting, so the ATA can be absent.
+//! The token program (SPL Token or Token-2022) is read from the mint's owner.
 
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::{
     commitment_config::CommitmentConfig,
     instruction::Instruction,
+    program_option::COption,
     pubkey::Pubkey,
     signature::{read_keypair_file, Signer},
     system_program,
-    transaction::Transaction,
-};
-use spl_associated_token_account::{
-    get_associated_token_address, instruction::create_associated_token_account_idempotent,
 };
-use spl_token::instruction::mint_to;
-use squads_multisig::anchor_lang::{AccountDeserialize, AnchorSerialize, InstructionData, ToAccountMetas};
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use spl_token_2022::instruction::mint_to;
+use squads_multisig::anchor_lang::{AnchorSerialize, InstructionData, ToAccountMetas};
 use squads_multisig::pda::{get_proposal_pda, get_transaction_pda, get_vault_pda};
 use squads_multisig::squads_multisig_program;
-use squads_multisig::state::Multisig;
 use squads_multisig::vault_transaction::VaultTransactionMessageExt;
 use squads_multisig_program::TransactionMessage;
+use squads_rust::addressbook::resolve_pubkey;
+use squads_rust::args::{reject_unknown_flags_or_exit, take_flag, take_flag_value, take_help};
+use squads_rust::audit::record;
+use squads_rust::message::print_compiled_message;
+use squads_rust::multisig::fetch_multisig_or_exit;
+use squads_rust::network::Network;
+use squads_rust::program::{program_id, take_program_id_or_exit};
+use squads_rust::send::{build_and_send, SendOptions};
+use squads_rust::token::{derive_ata, is_token_program, unpack_mint};
+use squads_rust::vault::parse_vault_index;
 use std::env;
 
-const DEVNET_RPC: &str = "https://api.devnet.solana.com";
-const MAINNET_RPC: &str = "https://api.mainnet-beta.solana.com";
-
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    let mut args: Vec<String> = env::args().collect();
+    let help = take_help(&mut args);
+    take_program_id_or_exit(&mut args);
+    let send_options = SendOptions::take_from_or_exit(&mut args);
+
+    let dump_message = take_flag(&mut args, "--dump-message");
 
-    if args.len() < 5 {
+    let vault_index = match take_flag_value(&mut args, "--vault")
+        .and_then(|value| value.map(|value| parse_vault_index(&value)).transpose())
+    {
+        Ok(index) => index.unwrap_or(0),
+        Err(e) => {
+            println!("Error: {}", e);
+            return;
+        }
+    };
+    reject_unknown_flags_or_exit(&args);
+
+    if help || args.len() < 5 {
         println!("Create a proposal to mint tokens from a mint the multisig controls");
         println!();
         println!("Usage:");
-        println!("  cargo run --bin mint-tokens-proposal -- <multisig_address> <mint> <destination_wallet> <amount> [mainnet]");
+        println!("  cargo run --bin mint-tokens-proposal -- <multisig_address> <mint> <destination_wallet> <amount> [--vault <index>] [--dump-message] [mainnet]");
         println!();
         println!("Arguments:");
         println!("  multisig_address   - The multisig PDA");
         println!("  mint               - The token mint address");
         println!("  destination_wallet - Recipient wallet pubkey (ATA will be derived/created idempotently)");
         println!("  amount             - Amount in smallest units (e.g., for 9 decimals: 10000 tokens = 10000000000000)");
+        println!("  --vault <index>    - Vault holding the mint authority (default: 0)");
         println!();
         println!("Example:");
         println!("  cargo run --bin mint-tokens-proposal -- BJbRt... E7xkt... DestWallet... 10000000000000 mainnet");
         return;
     }
 
-    let multisig_pda: Pubkey = args[1].parse().expect("Invalid multisig address");
-    let mint: Pubkey = args[2].parse().expect("Invalid mint address");
-    let destination_wallet: Pubkey = args[3].parse().expect("Invalid destination wallet address");
+    let multisig_pda: Pubkey = resolve_pubkey(&args[1]);
+    let mint: Pubkey = resolve_pubkey(&args[2]);
+    let destination_wallet: Pubkey = resolve_pubkey(&args[3]);
     let amount: u64 = args[4].parse().expect("Invalid amount");
-    let network = args.get(5).map(|s| s.as_str()).unwrap_or("devnet");
-
-    let rpc_url = match network {
-        "mainnet" => MAINNET_RPC,
-        _ => DEVNET_RPC,
-    };
+    let network = Network::from_arg(args.get(5).map(|s| s.as_str()).unwrap_or("devnet"));
 
-    let client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
+    let client = RpcClient::new_with_commitment(network.rpc_url(), CommitmentConfig::confirmed());
     let creator = read_keypair_file("../member1.json").expect("Failed to read member1.json");
 
     // Fetch multisig
-    let multisig_account = client
-        .get_account(&multisig_pda)
-        .expect("Failed to fetch multisig account");
-    let multisig = Multisig::try_deserialize(&mut multisig_account.data.as_slice())
-        .expect("Failed to deserialize multisig");
+    let multisig = fetch_multisig_or_exit(&client, &multisig_pda);
 
     let new_transaction_index = multisig.transaction_index + 1;
-    let vault_index: u8 = 0;
 
     // Derive PDAs
-    let (vault_pda, _) = get_vault_pda(&multisig_pda, vault_index, None);
-    let (transaction_pda, _) = get_transaction_pda(&multisig_pda, new_transaction_index, None);
-    let (proposal_pda, _) = get_proposal_pda(&multisig_pda, new_transaction_index, None);
+    let (vault_pda, _) = get_vault_pda(&multisig_pda, vault_index, Some(&program_id()));
+    let (transaction_pda, _) = get_transaction_pda(&multisig_pda, new_transaction_index, Some(&program_id()));
+    let (proposal_pda, _) = get_proposal_pda(&multisig_pda, new_transaction_index, Some(&program_id()));
 
-    let destination_ata = get_associated_token_address(&destination_wallet, &mint);
+    // The mint's owner is its token program (SPL Token or Token-2022)
+    let mint_account = client.get_account(&mint).expect("Failed to fetch mint account");
+    let token_program = mint_account.owner;
+    if !is_token_program(&token_program) {
+        println!("Error: {} is not a token mint (owned by {})", mint, token_program);
+        std::process::exit(1);
+    }
+
+    let destination_ata = derive_ata(&destination_wallet, &mint, &token_program);
+
+    // The proposal can only execute if this vault is the mint authority, so check before creating it
+    let mint_state = unpack_mint(&mint_account.data).expect("Failed to deserialize mint");
+    if mint_state.mint_authority != COption::Some(vault_pda) {
+        println!("Error: Vault {} (index {}) is not the mint authority of {}", vault_pda, vault_index, mint);
+        match mint_state.mint_authority {
+            COption::Some(authority) => println!("Current mint authority: {}", authority),
+            COption::None => println!("The mint has no mint authority (supply is fixed)"),
+        }
+        std::process::exit(1);
+    }
 
-    println!("=== Create Mint Tokens Proposal ({}) ===\n", network.to_uppercase());
+    println!("=== Create Mint Tokens Proposal ({}) ===\n", network.name().to_uppercase());
     println!("Multisig: {}", multisig_pda);
-    println!("Vault (mint authority / tx payer on execute): {}", vault_pda);
+    println!("Vault {} (mint authority / tx payer on execute): {}", vault_index, vault_pda);
     println!("Creator: {}", creator.pubkey());
     println!("Threshold: {} of {}", multisig.threshold, multisig.members.len());
     println!();
     println!("Mint: {}", mint);
+    println!("Token Program: {}", token_program);
     println!("Destination Wallet: {}", destination_wallet);
     println!("Destination ATA: {}", destination_ata);
     println!("Amount: {} (smallest units)", amount);
@@ -105,12 +142,12 @@ fn main() {
         &vault_pda,
         &destination_wallet,
         &mint,
-        &spl_token::ID,
+        &token_program,
     );
 
     // Create the mint_to instruction. The vault PDA is the mint authority and signs via Squads CPI.
     let mint_ix = mint_to(
-        &spl_token::ID,
+        &token_program,
         &mint,
         &destination_ata,
         &vault_pda,
@@ -127,6 +164,11 @@ fn main() {
         .try_to_vec()
         .expect("Failed to serialize message");
 
+    if dump_message {
+        println!();
+        print_compiled_message(&message_bytes);
+    }
+
     // === Instruction 1: Create Vault Transaction ===
     let vault_tx_accounts = squads_multisig_program::accounts::VaultTransactionCreate {
         multisig: multisig_pda,
@@ -146,7 +188,7 @@ fn main() {
     };
 
     let create_vault_tx_ix = Instruction {
-        program_id: squads_multisig_program::ID,
+        program_id: program_id(),
         accounts: vault_tx_accounts.to_account_metas(Some(false)),
         data: vault_tx_data.data(),
     };
@@ -168,7 +210,7 @@ fn main() {
     };
 
     let create_proposal_ix = Instruction {
-        program_id: squads_multisig_program::ID,
+        program_id: program_id(),
         accounts: proposal_accounts.to_account_metas(Some(false)),
         data: proposal_data.data(),
     };
@@ -185,23 +227,16 @@ fn main() {
     };
 
     let approve_ix = Instruction {
-        program_id: squads_multisig_program::ID,
+        program_id: program_id(),
         accounts: approve_accounts.to_account_metas(Some(false)),
         data: approve_data.data(),
     };
 
     println!("\nCreating mint proposal...");
 
-    let recent_blockhash = client.get_latest_blockhash().expect("Failed to get blockhash");
-    let transaction = Transaction::new_signed_with_payer(
-        &[create_vault_tx_ix, create_proposal_ix, approve_ix],
-        Some(&creator.pubkey()),
-        &[&creator],
-        recent_blockhash,
-    );
-
-    match client.send_and_confirm_transaction(&transaction) {
+    match build_and_send(&client, &[create_vault_tx_ix, create_proposal_ix, approve_ix], &creator.pubkey(), &[&creator], &send_options) {
         Ok(sig) => {
+            record(network, &multisig_pda, &format!("propose {}: mint {} of {} to {}", new_transaction_index, amount, mint, destination_wallet), &creator.pubkey(), &sig);
             println!("\nProposal created successfully!");
             println!("Transaction: {}", sig);
             println!();
@@ -215,7 +250,7 @@ fn main() {
                 "  cargo run --bin approve-proposal -- {} {} {}",
                 multisig_pda,
                 new_transaction_index,
-                if network == "mainnet" { "mainnet" } else { "" }
+                network.cli_arg()
             );
             println!();
             println!("After threshold is met, execute with:");
@@ -223,12 +258,11 @@ fn main() {
                 "  cargo run --bin execute-proposal -- {} {} {}",
                 multisig_pda,
                 new_transaction_index,
-                if network == "mainnet" { "mainnet" } else { "" }
+                network.cli_arg()
             );
 
-            let cluster_param = if network == "mainnet" { "" } else { "?cluster=devnet" };
             println!("\nView on Solana Explorer:");
-            println!("https://explorer.solana.com/tx/{}{}", sig, cluster_param);
+            println!("{}", network.explorer_tx_url(&sig));
         }
         Err(e) => {
             println!("\nFailed to create proposal: {}", e);
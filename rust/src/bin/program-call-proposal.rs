@@ -0,0 +1,276 @@
+//! Create a proposal that calls an Anchor program method, encoded from its IDL
+//!
+//! Usage:
+//!   cargo run --bin program-call-proposal -- <multisig_address> --idl <path> --method <name> --accounts <json> [--args <json>] [--program-id <address>] [--squads-program-id <address>] [--vault <index>] [--dump-message] [mainnet]
+//!
+//! The instruction data is the method's discriminator followed by the Borsh encoding of
+//! `--args`, checked against the IDL types before anything is sent. `--accounts` maps each
+//! IDL account name to an address; the vault is the only account that can sign.
+//!
+//! JSON conventions: structs are objects, tuples and vecs are arrays, `null` is None, unit
+//! enum variants are strings and other variants are `{"Variant": fields}`. 64/128-bit
+//! integers may be passed as strings to avoid JSON precision loss.
+//!
+//! Example:
+//!   cargo run --bin program-call-proposal -- BJbRt... --idl target/idl/my_program.json \
+//!     --method setFee --args '{"fee": 25}' --accounts '{"config": "Config...", "admin": "Vault..."}'
+
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Signer},
+    system_program,
+};
+use serde_json::Value;
+use squads_multisig::anchor_lang::{AnchorSerialize, InstructionData, ToAccountMetas};
+use squads_multisig::pda::{get_proposal_pda, get_transaction_pda, get_vault_pda};
+use squads_multisig::squads_multisig_program;
+use squads_multisig::vault_transaction::VaultTransactionMessageExt;
+use squads_multisig_program::TransactionMessage;
+use squads_rust::addressbook::resolve_pubkey;
+use squads_rust::args::{reject_unknown_flags_or_exit, take_flag, take_flag_value, take_help};
+use squads_rust::audit::record;
+use squads_rust::idl::Idl;
+use squads_rust::message::print_compiled_message;
+use squads_rust::multisig::fetch_multisig_or_exit;
+use squads_rust::network::Network;
+use squads_rust::program::{program_id as squads_program_id, take_program_id_flag};
+use squads_rust::send::{build_and_send, SendOptions};
+use squads_rust::vault::parse_vault_index;
+use std::env;
+
+fn exit_with(message: String) -> ! {
+    println!("Error: {}", message);
+    std::process::exit(1);
+}
+
+fn parse_json(flag: &str, text: &str) -> Value {
+    serde_json::from_str(text).unwrap_or_else(|e| exit_with(format!("{} is not valid JSON: {}", flag, e)))
+}
+
+fn print_usage() {
+    println!("Create a proposal that calls an Anchor program method, encoded from its IDL");
+    println!();
+    println!("Usage:");
+    println!("  cargo run --bin program-call-proposal -- <multisig_address> --idl <path> --method <name> --accounts <json> [--args <json>] [--program-id <address>] [--squads-program-id <address>] [--vault <index>] [--dump-message] [mainnet]");
+    println!();
+    println!("Options:");
+    println!("  --idl <path>            - Anchor IDL JSON of the target program (legacy or 0.30+ format)");
+    println!("  --method <name>         - Instruction to call (camelCase or snake_case)");
+    println!("  --accounts <json>       - Object of IDL account name -> address");
+    println!("  --args <json>           - Object of IDL arg name -> value (default: {{}})");
+    println!("  --program-id <address>  - Target program (default: the address recorded in the IDL)");
+    println!("  --squads-program-id <address>  - Squads program for a local or forked deployment");
+    println!("  --vault <index>         - Vault that signs the call (default: 0)");
+    println!("  --dump-message          - Print the compiled vault transaction message before sending");
+    println!();
+    println!("Example:");
+    println!("  cargo run --bin program-call-proposal -- BJbRt... --idl my_program.json --method setFee \\");
+    println!("    --args '{{\"fee\": 25}}' --accounts '{{\"config\": \"Config...\", \"admin\": \"Vault...\"}}'");
+}
+
+fn main() {
+    let mut args: Vec<String> = env::args().collect();
+    let help = take_help(&mut args);
+    take_program_id_flag(&mut args, "--squads-program-id").unwrap_or_else(|e| exit_with(e));
+    let send_options = SendOptions::take_from_or_exit(&mut args);
+
+    let dump_message = take_flag(&mut args, "--dump-message");
+    let mut take = |flag: &str| take_flag_value(&mut args, flag).unwrap_or_else(|e| exit_with(e));
+    let idl_path = take("--idl");
+    let method = take("--method");
+    let accounts_json = take("--accounts");
+    let args_json = take("--args");
+    let program_id_arg = take("--program-id");
+    let vault_arg = take("--vault");
+    reject_unknown_flags_or_exit(&args);
+
+    let (idl_path, method, accounts_json) = match (idl_path, method, accounts_json) {
+        (Some(idl), Some(method), Some(accounts)) if !help && args.len() >= 2 => (idl, method, accounts),
+        _ => {
+            print_usage();
+            return;
+        }
+    };
+
+    let multisig_pda: Pubkey = resolve_pubkey(&args[1]);
+    let network = Network::from_arg(args.get(2).map(|s| s.as_str()).unwrap_or("devnet"));
+    let vault_index = match vault_arg {
+        Some(value) => parse_vault_index(&value).unwrap_or_else(|e| exit_with(e)),
+        None => 0,
+    };
+    let (vault_pda, _) = get_vault_pda(&multisig_pda, vault_index, Some(&squads_program_id()));
+
+    // Build and validate the instruction before touching the network
+    let idl = Idl::load(&idl_path).unwrap_or_else(|e| exit_with(e));
+    let program_id: Pubkey = match program_id_arg {
+        Some(address) => resolve_pubkey(&address),
+        None => idl
+            .program_id()
+            .unwrap_or_else(|| exit_with("IDL has no program address; pass --program-id".to_string())),
+    };
+    let instruction = idl.instruction(&method).unwrap_or_else(|e| exit_with(e));
+    let call_args = parse_json("--args", args_json.as_deref().unwrap_or("{}"));
+    let data = idl
+        .encode_instruction_data(&instruction, &call_args)
+        .unwrap_or_else(|e| exit_with(format!("Invalid --args: {}", e)));
+    let accounts = instruction
+        .account_metas(&parse_json("--accounts", &accounts_json), &program_id)
+        .unwrap_or_else(|e| exit_with(format!("Invalid --accounts: {}", e)));
+
+    // Only the vault can sign inside a vault transaction (via Squads CPI)
+    if let Some((account, meta)) = instruction
+        .accounts
+        .iter()
+        .zip(&accounts)
+        .find(|(_, meta)| meta.is_signer && meta.pubkey != vault_pda)
+    {
+        exit_with(format!(
+            "Account '{}' ({}) must sign, but only vault {} ({}) can sign a vault transaction",
+            account.name, meta.pubkey, vault_index, vault_pda
+        ));
+    }
+
+    let call_ix = Instruction { program_id, accounts, data };
+
+    let client = RpcClient::new_with_commitment(network.rpc_url(), CommitmentConfig::confirmed());
+    let creator = read_keypair_file("../member1.json").expect("Failed to read member1.json");
+
+    // Fetch multisig
+    let multisig = fetch_multisig_or_exit(&client, &multisig_pda);
+
+    let new_transaction_index = multisig.transaction_index + 1;
+
+    // Derive PDAs
+    let (transaction_pda, _) = get_transaction_pda(&multisig_pda, new_transaction_index, Some(&squads_program_id()));
+    let (proposal_pda, _) = get_proposal_pda(&multisig_pda, new_transaction_index, Some(&squads_program_id()));
+
+    println!("=== Create Program Call Proposal ({}) ===\n", network.name().to_uppercase());
+    println!("Multisig: {}", multisig_pda);
+    println!("Vault {} (signer): {}", vault_index, vault_pda);
+    println!("Creator: {}", creator.pubkey());
+    println!("Threshold: {} of {}", multisig.threshold, multisig.members.len());
+    println!();
+    println!("Program: {}", program_id);
+    println!("Method: {}", instruction.name);
+    println!("Args: {}", call_args);
+    println!("Accounts:");
+    for (account, meta) in instruction.accounts.iter().zip(&call_ix.accounts) {
+        println!(
+            "  {:<24} {} {}{}",
+            account.name,
+            meta.pubkey,
+            if meta.is_writable { "[writable]" } else { "" },
+            if meta.is_signer { "[signer]" } else { "" }
+        );
+    }
+    println!();
+    println!("Transaction Index: {}", new_transaction_index);
+
+    // Compile the transaction message
+    let transaction_message = TransactionMessage::try_compile(&vault_pda, &[call_ix], &[])
+        .expect("Failed to compile transaction message");
+
+    let message_bytes = transaction_message
+        .try_to_vec()
+        .expect("Failed to serialize message");
+
+    if dump_message {
+        println!();
+        print_compiled_message(&message_bytes);
+    }
+
+    // === Instruction 1: Create Vault Transaction ===
+    let vault_tx_accounts = squads_multisig_program::accounts::VaultTransactionCreate {
+        multisig: multisig_pda,
+        transaction: transaction_pda,
+        creator: creator.pubkey(),
+        rent_payer: creator.pubkey(),
+        system_program: system_program::ID,
+    };
+
+    let vault_tx_data = squads_multisig_program::instruction::VaultTransactionCreate {
+        args: squads_multisig_program::instructions::VaultTransactionCreateArgs {
+            vault_index,
+            ephemeral_signers: 0,
+            transaction_message: message_bytes,
+            memo: None,
+        },
+    };
+
+    let create_vault_tx_ix = Instruction {
+        program_id: squads_program_id(),
+        accounts: vault_tx_accounts.to_account_metas(Some(false)),
+        data: vault_tx_data.data(),
+    };
+
+    // === Instruction 2: Create Proposal ===
+    let proposal_accounts = squads_multisig_program::accounts::ProposalCreate {
+        multisig: multisig_pda,
+        proposal: proposal_pda,
+        creator: creator.pubkey(),
+        rent_payer: creator.pubkey(),
+        system_program: system_program::ID,
+    };
+
+    let proposal_data = squads_multisig_program::instruction::ProposalCreate {
+        args: squads_multisig_program::instructions::ProposalCreateArgs {
+            transaction_index: new_transaction_index,
+            draft: false,
+        },
+    };
+
+    let create_proposal_ix = Instruction {
+        program_id: squads_program_id(),
+        accounts: proposal_accounts.to_account_metas(Some(false)),
+        data: proposal_data.data(),
+    };
+
+    // === Instruction 3: Creator auto-approves ===
+    let approve_accounts = squads_multisig_program::accounts::ProposalVote {
+        multisig: multisig_pda,
+        proposal: proposal_pda,
+        member: creator.pubkey(),
+    };
+
+    let approve_data = squads_multisig_program::instruction::ProposalApprove {
+        args: squads_multisig_program::instructions::ProposalVoteArgs { memo: None },
+    };
+
+    let approve_ix = Instruction {
+        program_id: squads_program_id(),
+        accounts: approve_accounts.to_account_metas(Some(false)),
+        data: approve_data.data(),
+    };
+
+    println!("\nCreating program call proposal...");
+
+    match build_and_send(&client, &[create_vault_tx_ix, create_proposal_ix, approve_ix], &creator.pubkey(), &[&creator], &send_options) {
+        Ok(sig) => {
+            record(network, &multisig_pda, &format!("propose {}: call {}", new_transaction_index, program_id), &creator.pubkey(), &sig);
+            println!("\nProposal created successfully!");
+            println!("Transaction: {}", sig);
+            println!();
+            println!("=== Proposal Details ===");
+            println!("Proposal Index: {}", new_transaction_index);
+            println!("Proposal Address: {}", proposal_pda);
+            println!("Status: Active (awaiting {} more approval(s))", multisig.threshold - 1);
+            println!();
+            println!("Share this with other members to approve:");
+            println!("  cargo run --bin approve-proposal -- {} {} {}",
+                     multisig_pda, new_transaction_index, network.cli_arg());
+            println!();
+            println!("After threshold is met, execute with:");
+            println!("  cargo run --bin execute-proposal -- {} {} {}",
+                     multisig_pda, new_transaction_index, network.cli_arg());
+
+            println!("\nView on Solana Explorer:");
+            println!("{}", network.explorer_tx_url(&sig));
+        }
+        Err(e) => {
+            println!("\nFailed to create proposal: {}", e);
+        }
+    }
+}
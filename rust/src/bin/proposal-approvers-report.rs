@@ -0,0 +1,149 @@
+//! Report per-member voting participation across a multisig's proposals
+//!
+//! Walks every proposal still on-chain and tallies, per member, how many they approved,
+//! rejected and voted to cancel, plus the share of proposals they voted on at all. Surfaces
+//! disengaged signers before they become a threshold problem.
+//!
+//! Closed proposals no longer exist on-chain, so they can't be counted.
+//!
+//! Usage:
+//!   cargo run --bin proposal-approvers-report -- <multisig_address> [--json] [mainnet]
+
+use serde_json::json;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
+use squads_rust::addressbook::resolve_pubkey;
+use squads_rust::args::{reject_unknown_flags_or_exit, take_flag, take_help};
+use squads_rust::multisig::{fetch_multisig_or_exit, fetch_proposals};
+use squads_rust::network::Network;
+use squads_rust::program::take_program_id_or_exit;
+use std::env;
+
+#[derive(Default)]
+struct Participation {
+    approvals: usize,
+    rejections: usize,
+    cancellations: usize,
+    /// Proposals with an approval or rejection from this member
+    voted: usize,
+}
+
+/// The tally for `key`, adding a row for a voter who is no longer a member
+fn entry<'a>(voters: &'a mut Vec<(Pubkey, Participation)>, key: &Pubkey) -> &'a mut Participation {
+    let i = match voters.iter().position(|(voter, _)| voter == key) {
+        Some(i) => i,
+        None => {
+            voters.push((*key, Participation::default()));
+            voters.len() - 1
+        }
+    };
+    &mut voters[i].1
+}
+
+fn percent(part: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 / total as f64 * 100.0
+    }
+}
+
+fn main() {
+    let mut args: Vec<String> = env::args().collect();
+    let help = take_help(&mut args);
+    take_program_id_or_exit(&mut args);
+    let json_output = take_flag(&mut args, "--json");
+    reject_unknown_flags_or_exit(&args);
+
+    if help || args.len() < 2 {
+        println!("Report per-member voting participation across a multisig's proposals");
+        println!();
+        println!("Usage:");
+        println!("  cargo run --bin proposal-approvers-report -- <multisig_address> [--json] [mainnet]");
+        println!();
+        println!("Options:");
+        println!("  --json  - Print the report as JSON");
+        return;
+    }
+
+    let multisig_pda: Pubkey = resolve_pubkey(&args[1]);
+    let network = Network::from_arg(args.get(2).map(|s| s.as_str()).unwrap_or("devnet"));
+
+    let client = RpcClient::new_with_commitment(network.rpc_url(), CommitmentConfig::confirmed());
+    let multisig = fetch_multisig_or_exit(&client, &multisig_pda);
+    let proposals = fetch_proposals(&client, &multisig_pda, &multisig).unwrap_or_else(|e| {
+        println!("Error: {}", e);
+        std::process::exit(1);
+    });
+
+    // Current members first, in multisig order; anyone else who voted was removed since
+    let mut voters: Vec<(Pubkey, Participation)> =
+        multisig.members.iter().map(|m| (m.key, Participation::default())).collect();
+    for (_, _, proposal) in &proposals {
+        for key in &proposal.approved {
+            let p = entry(&mut voters, key);
+            p.approvals += 1;
+            p.voted += 1;
+        }
+        for key in &proposal.rejected {
+            let p = entry(&mut voters, key);
+            p.rejections += 1;
+            p.voted += 1;
+        }
+        for key in &proposal.cancelled {
+            entry(&mut voters, key).cancellations += 1;
+        }
+    }
+
+    let total = proposals.len();
+    let is_member = |key: &Pubkey| multisig.is_member(*key).is_some();
+
+    if json_output {
+        let report = json!({
+            "multisig": multisig_pda.to_string(),
+            "network": network.name(),
+            "proposals": total,
+            "members": voters.iter().map(|(key, p)| json!({
+                "member": key.to_string(),
+                "current_member": is_member(key),
+                "approvals": p.approvals,
+                "rejections": p.rejections,
+                "cancellations": p.cancellations,
+                "voted": p.voted,
+                "participation_pct": percent(p.voted, total),
+            })).collect::<Vec<_>>(),
+        });
+        println!("{}", serde_json::to_string_pretty(&report).expect("Failed to serialize report"));
+        return;
+    }
+
+    println!("=== Proposal Participation ({}) ===\n", network.name().to_uppercase());
+    println!("Multisig: {}", multisig_pda);
+    println!("Proposals on-chain: {} (of {} created; closed ones can't be counted)", total, multisig.transaction_index);
+    println!();
+    println!("  {:<44} {:>9} {:>10} {:>8} {:>7}", "Member", "Approved", "Rejected", "Cancel", "Voted");
+    for (key, p) in &voters {
+        println!(
+            "  {:<44} {:>9} {:>10} {:>8} {:>6.1}%{}",
+            key.to_string(),
+            p.approvals,
+            p.rejections,
+            p.cancellations,
+            percent(p.voted, total),
+            if is_member(key) { "" } else { "  (former member)" }
+        );
+    }
+
+    let silent: Vec<&Pubkey> = voters
+        .iter()
+        .filter(|(key, p)| p.voted == 0 && is_member(key))
+        .map(|(key, _)| key)
+        .collect();
+    if total > 0 && !silent.is_empty() {
+        println!();
+        println!("WARNING: {} current member(s) have not voted on any on-chain proposal:", silent.len());
+        for key in silent {
+            println!("  - {}", key);
+        }
+    }
+}
@@ -0,0 +1,177 @@
+//! Create a SOL transfer proposal and, if the creator alone satisfies the threshold, execute it
+//!
+//! For single-signer test flows: member1 creates the proposal and approves it in one
+//! transaction. Once that confirms, and only if the threshold is 1, the time lock is 0 and member1
+//! has Execute, it sends the execute transaction. Otherwise it stops after creation and prints
+//! the pending index with the approve/execute commands, just like create-proposal.
+//!
+//! Usage:
+//!   cargo run --bin propose-and-execute -- <multisig_address> transfer <destination> <amount_lamports> [mainnet]
+//!
+//! Example:
+//!   cargo run --bin propose-and-execute -- BJbRt... transfer DestPubkey... 100000000
+
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Signer},
+    system_instruction,
+};
+use squads_multisig::anchor_lang::{AccountDeserialize, AnchorSerialize};
+use squads_multisig::pda::{get_proposal_pda, get_transaction_pda, get_vault_pda};
+use squads_multisig::squads_multisig_program::{TransactionMessage, VaultTransaction};
+use squads_multisig::state::Permission;
+use squads_multisig::vault_transaction::VaultTransactionMessageExt;
+use squads_rust::addressbook::resolve_pubkey;
+use squads_rust::args::{reject_unknown_flags_or_exit, take_help};
+use squads_rust::audit::record;
+use squads_rust::multisig::fetch_multisig_or_exit;
+use squads_rust::network::Network;
+use squads_rust::program::{program_id, take_program_id_or_exit};
+use squads_rust::proposal::{
+    proposal_approve_ix, proposal_create_ix, vault_transaction_create_ix, vault_transaction_execute_ix,
+};
+use squads_rust::send::{build_and_send, send_and_track, SendError, SendOptions};
+use std::env;
+
+fn main() {
+    let mut args: Vec<String> = env::args().collect();
+    let help = take_help(&mut args);
+    take_program_id_or_exit(&mut args);
+    let send_options = SendOptions::take_from_or_exit(&mut args);
+    reject_unknown_flags_or_exit(&args);
+
+    if help || args.len() < 5 || args[2] != "transfer" {
+        println!("Create a SOL transfer proposal and execute it right away if the creator alone meets the threshold");
+        println!();
+        println!("Usage:");
+        println!("  cargo run --bin propose-and-execute -- <multisig_address> transfer <destination> <amount_lamports> [mainnet]");
+        println!();
+        println!("With threshold > 1 (or a time lock, or no Execute permission) it stops after creating");
+        println!("the proposal and prints the pending index.");
+        return;
+    }
+
+    let multisig_pda: Pubkey = resolve_pubkey(&args[1]);
+    let destination: Pubkey = resolve_pubkey(&args[3]);
+    let amount: u64 = args[4].parse().expect("Invalid amount");
+    let network = Network::from_arg(args.get(5).map(|s| s.as_str()).unwrap_or("devnet"));
+
+    let client = RpcClient::new_with_commitment(network.rpc_url(), CommitmentConfig::confirmed());
+    let creator = read_keypair_file("../member1.json").expect("Failed to read member1.json");
+
+    let multisig = fetch_multisig_or_exit(&client, &multisig_pda);
+    if !multisig.member_has_permission(creator.pubkey(), Permission::Initiate) {
+        println!("Error: {} cannot propose (not a member, or no Initiate permission)", creator.pubkey());
+        std::process::exit(1);
+    }
+    let can_approve = multisig.member_has_permission(creator.pubkey(), Permission::Vote);
+    let can_execute = multisig.member_has_permission(creator.pubkey(), Permission::Execute);
+
+    let vault_index: u8 = 0;
+    let transaction_index = multisig.transaction_index + 1;
+    let (vault_pda, _) = get_vault_pda(&multisig_pda, vault_index, Some(&program_id()));
+    let (transaction_pda, _) = get_transaction_pda(&multisig_pda, transaction_index, Some(&program_id()));
+    let (proposal_pda, _) = get_proposal_pda(&multisig_pda, transaction_index, Some(&program_id()));
+
+    println!("=== Propose and Execute ({}) ===\n", network.name().to_uppercase());
+    println!("Multisig: {}", multisig_pda);
+    println!("Vault: {}", vault_pda);
+    println!("Creator: {}", creator.pubkey());
+    println!("Threshold: {} of {}", multisig.threshold, multisig.members.len());
+    println!("Time Lock: {} seconds", multisig.time_lock);
+    println!();
+    println!("Action: Transfer {} lamports ({:.9} SOL) to {}", amount, amount as f64 / 1_000_000_000.0, destination);
+    println!("Transaction Index: {}", transaction_index);
+    println!("Proposal PDA: {}", proposal_pda);
+
+    // === Step 1: create the vault transaction and proposal, and approve it ===
+    let transfer_ix = system_instruction::transfer(&vault_pda, &destination, amount);
+    let message_bytes = TransactionMessage::try_compile(&vault_pda, &[transfer_ix], &[])
+        .expect("Failed to compile transaction message")
+        .try_to_vec()
+        .expect("Failed to serialize message");
+
+    let mut instructions = vec![
+        vault_transaction_create_ix(&multisig_pda, transaction_index, &creator.pubkey(), vault_index, 0, message_bytes),
+        proposal_create_ix(&multisig_pda, transaction_index, &creator.pubkey()),
+    ];
+    if can_approve {
+        instructions.push(proposal_approve_ix(&multisig_pda, transaction_index, &creator.pubkey()));
+    }
+
+    println!("\n[1/2] Creating proposal...");
+
+    match build_and_send(&client, &instructions, &creator.pubkey(), &[&creator], &send_options) {
+        Ok(sig) => {
+            record(network, &multisig_pda, &format!("propose {}: transfer {} lamports to {}", transaction_index, amount, destination), &creator.pubkey(), &sig);
+            println!("Proposal created{}: {}", if can_approve { " and approved" } else { "" }, sig);
+        }
+        Err(e) => {
+            println!("\nFailed to create proposal: {}", e);
+            std::process::exit(1);
+        }
+    }
+
+    // === Step 2: execute, if nothing else is needed ===
+    let approvals = u16::from(can_approve);
+    let blocker = if multisig.threshold > approvals {
+        Some(format!("awaiting {} more approval(s)", multisig.threshold - approvals))
+    } else if multisig.time_lock > 0 {
+        Some(format!("the {} second time lock must pass before execution", multisig.time_lock))
+    } else if !can_execute {
+        Some(format!("{} has no Execute permission", creator.pubkey()))
+    } else {
+        None
+    };
+    if let Some(reason) = blocker {
+        println!("\nProposal {} is pending: {}.", transaction_index, reason);
+        println!();
+        println!("Approve with:");
+        println!("  cargo run --bin approve-proposal -- {} {} {}", multisig_pda, transaction_index, network.cli_arg());
+        println!("Execute with:");
+        println!("  cargo run --bin execute-proposal -- {} {} {}", multisig_pda, transaction_index, network.cli_arg());
+        return;
+    }
+
+    println!("\n[2/2] Threshold met by the creator; executing...");
+
+    // Read the vault transaction back from the chain, as execute-proposal does
+    let transaction_account = client
+        .get_account(&transaction_pda)
+        .expect("Failed to fetch transaction account");
+    let vault_transaction = VaultTransaction::try_deserialize(&mut transaction_account.data.as_slice())
+        .expect("Failed to deserialize vault transaction");
+    let execute_ix = vault_transaction_execute_ix(&multisig_pda, transaction_index, &vault_transaction, &[], &creator.pubkey())
+        .expect("Failed to build execute instruction");
+
+    let (recent_blockhash, last_valid_block_height) = client
+        .get_latest_blockhash_with_commitment(client.commitment())
+        .expect("Failed to get blockhash");
+    let transaction = send_options
+        .build_transaction(&[execute_ix], &creator.pubkey(), &[&creator], recent_blockhash)
+        .expect("Failed to build transaction");
+
+    match send_and_track(&client, &transaction, &send_options, last_valid_block_height) {
+        Ok(landed) => {
+            record(network, &multisig_pda, &format!("execute proposal {}", transaction_index), &creator.pubkey(), &landed.signature);
+            println!("\nProposal {} executed successfully!", transaction_index);
+            println!("Transaction: {}", landed.signature);
+            println!("Slot: {}", landed.slot);
+
+            println!("\nView on Solana Explorer:");
+            println!("{}", network.explorer_tx_url(&landed.signature));
+        }
+        Err(e @ SendError::Unknown { signature, .. }) => {
+            println!("\n{}", e);
+            println!("{}", network.explorer_tx_url(&signature));
+        }
+        Err(e) => {
+            println!("\nFailed to execute proposal: {}", e);
+            println!("The proposal was created and approved; retry with:");
+            println!("  cargo run --bin execute-proposal -- {} {} {}", multisig_pda, transaction_index, network.cli_arg());
+            std::process::exit(1);
+        }
+    }
+}
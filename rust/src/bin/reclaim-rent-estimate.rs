@@ -0,0 +1,207 @@
+//! Estimate the SOL recoverable by closing a multisig's transaction and proposal accounts
+//!
+//! Read-only: nothing is sent. Walks every transaction index of the multisig, reads the actual
+//! lamports of each transaction, proposal and batch-transaction account (sizes vary, and large
+//! batches hold much more rent than a simple transfer), and reports which ones the Squads
+//! program would let you close today:
+//!
+//! - Executed, Rejected and Cancelled proposals can always be closed.
+//! - Draft and Active proposals can be closed once stale (a config change invalidated them).
+//! - Approved vault/batch transactions can't be closed, since they can still execute;
+//!   approved config transactions can once stale.
+//!
+//! Closing requires the multisig to have a rent collector, which receives the rent.
+//!
+//! Usage:
+//!   cargo run --bin reclaim-rent-estimate -- <multisig_address> [--all] [mainnet]
+//!
+//! Options:
+//!   --all  - Also list accounts that can't be closed yet (default: closeable only)
+
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{account::Account, commitment_config::CommitmentConfig, pubkey::Pubkey};
+use squads_multisig::anchor_lang::{AccountDeserialize, Discriminator};
+use squads_multisig::pda::{get_proposal_pda, get_transaction_pda};
+use squads_multisig::squads_multisig_program::VaultTransaction;
+use squads_multisig::state::{Batch, ConfigTransaction, Proposal};
+use squads_rust::addressbook::resolve_pubkey;
+use squads_rust::args::{reject_unknown_flags_or_exit, take_flag, take_help};
+use squads_rust::format::{format_amount, Units, SOL_DECIMALS};
+use squads_rust::multisig::{fetch_accounts, fetch_multisig_or_exit};
+use squads_rust::network::Network;
+use squads_rust::pda::get_batch_transaction_pda;
+use squads_rust::program::{program_id, take_program_id_or_exit};
+use squads_rust::proposal::ProposalState;
+use std::env;
+
+#[derive(Clone, Copy, PartialEq)]
+enum TransactionKind {
+    Vault,
+    Config,
+    Batch,
+    Unknown,
+}
+
+impl TransactionKind {
+    fn of(account: &Account) -> Self {
+        if account.data.starts_with(&VaultTransaction::DISCRIMINATOR) {
+            TransactionKind::Vault
+        } else if account.data.starts_with(&ConfigTransaction::DISCRIMINATOR) {
+            TransactionKind::Config
+        } else if account.data.starts_with(&Batch::DISCRIMINATOR) {
+            TransactionKind::Batch
+        } else {
+            TransactionKind::Unknown
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            TransactionKind::Vault => "vault tx",
+            TransactionKind::Config => "config tx",
+            TransactionKind::Batch => "batch",
+            TransactionKind::Unknown => "unknown",
+        }
+    }
+}
+
+fn sol(lamports: u64) -> String {
+    format!("{} SOL", format_amount(lamports, SOL_DECIMALS, Units::Ui))
+}
+
+/// Whether the program would allow closing this index's accounts, mirroring its close checks
+fn can_close(kind: TransactionKind, state: Option<ProposalState>, is_stale: bool) -> bool {
+    match state {
+        Some(state) if state.is_terminal() => true,
+        Some(ProposalState::Draft { .. } | ProposalState::Active { .. }) => is_stale,
+        // Stale config transactions can no longer execute; vault and batch ones still can
+        Some(state) if state.is_executable() => is_stale && kind == TransactionKind::Config,
+        // Mid-execution (deprecated Executing state), or a status this version doesn't know
+        Some(_) => false,
+        // A transaction whose proposal was never created can be closed once stale
+        None => is_stale,
+    }
+}
+
+fn main() {
+    let mut args: Vec<String> = env::args().collect();
+    let help = take_help(&mut args);
+    take_program_id_or_exit(&mut args);
+    let show_all = take_flag(&mut args, "--all");
+    reject_unknown_flags_or_exit(&args);
+
+    if help || args.len() < 2 {
+        println!("Estimate the SOL recoverable by closing a multisig's transaction and proposal accounts");
+        println!();
+        println!("Usage:");
+        println!("  cargo run --bin reclaim-rent-estimate -- <multisig_address> [--all] [mainnet]");
+        println!();
+        println!("Options:");
+        println!("  --all  - Also list accounts that can't be closed yet");
+        println!();
+        println!("Read-only: fetches account balances and sends nothing.");
+        return;
+    }
+
+    let multisig_pda: Pubkey = resolve_pubkey(&args[1]);
+    let network = Network::from_arg(args.get(2).map(|s| s.as_str()).unwrap_or("devnet"));
+
+    let client = RpcClient::new_with_commitment(network.rpc_url(), CommitmentConfig::confirmed());
+    let multisig = fetch_multisig_or_exit(&client, &multisig_pda);
+
+    println!("=== Reclaimable Rent Estimate ({}) ===\n", network.name().to_uppercase());
+    println!("Multisig: {}", multisig_pda);
+    println!("Transaction Index: {}", multisig.transaction_index);
+    println!("Stale Transaction Index: {}", multisig.stale_transaction_index);
+    match multisig.rent_collector {
+        Some(collector) => println!("Rent Collector: {}", collector),
+        None => println!("Rent Collector: None"),
+    }
+    println!();
+
+    // Transaction and proposal PDAs for every index, fetched in bulk
+    let indices: Vec<u64> = (1..=multisig.transaction_index).collect();
+    let keys: Vec<Pubkey> = indices
+        .iter()
+        .flat_map(|&index| {
+            [
+                get_transaction_pda(&multisig_pda, index, Some(&program_id())).0,
+                get_proposal_pda(&multisig_pda, index, Some(&program_id())).0,
+            ]
+        })
+        .collect();
+    let accounts = fetch_accounts(&client, &keys).expect("Failed to fetch accounts");
+
+    let mut closeable_total: u64 = 0;
+    let mut closeable_count = 0;
+    let mut held_total: u64 = 0;
+
+    for (i, &index) in indices.iter().enumerate() {
+        let (transaction_key, proposal_key) = (keys[2 * i], keys[2 * i + 1]);
+        let (transaction, proposal) = (&accounts[2 * i], &accounts[2 * i + 1]);
+        if transaction.is_none() && proposal.is_none() {
+            continue; // Already closed, or never created
+        }
+
+        let kind = transaction.as_ref().map(TransactionKind::of).unwrap_or(TransactionKind::Unknown);
+        let state = proposal
+            .as_ref()
+            .and_then(|account| Proposal::try_deserialize(&mut account.data.as_slice()).ok())
+            .map(|proposal| ProposalState::of(&proposal));
+        let is_stale = index <= multisig.stale_transaction_index;
+
+        // (label, address, lamports) for every account closed along with this index
+        let mut entries: Vec<(String, Pubkey, u64)> = Vec::new();
+        if let Some(account) = transaction {
+            entries.push((kind.name().to_string(), transaction_key, account.lamports));
+        }
+        if let Some(account) = proposal {
+            entries.push(("proposal".to_string(), proposal_key, account.lamports));
+        }
+        // A batch's inner transactions are separate accounts closed one by one with it
+        if kind == TransactionKind::Batch {
+            let batch = Batch::try_deserialize(&mut transaction.as_ref().expect("batch exists").data.as_slice())
+                .expect("Failed to deserialize batch");
+            let batch_keys: Vec<Pubkey> = (1..=batch.size)
+                .map(|n| get_batch_transaction_pda(&multisig_pda, index, n))
+                .collect();
+            for (n, (key, account)) in batch_keys.iter().zip(fetch_accounts(&client, &batch_keys).expect("Failed to fetch accounts")).enumerate() {
+                if let Some(account) = account {
+                    entries.push((format!("batch tx {}", n + 1), *key, account.lamports));
+                }
+            }
+        }
+
+        let lamports: u64 = entries.iter().map(|(_, _, lamports)| lamports).sum();
+        let closeable = can_close(kind, state, is_stale);
+        held_total += lamports;
+        if closeable {
+            closeable_total += lamports;
+            closeable_count += 1;
+        } else if !show_all {
+            continue;
+        }
+
+        println!(
+            "#{} {} [{}{}] {}: {}",
+            index,
+            kind.name(),
+            state.map_or("no proposal", |state| state.name()),
+            if is_stale { ", stale" } else { "" },
+            if closeable { "closeable" } else { "not closeable" },
+            sol(lamports)
+        );
+        for (label, key, lamports) in &entries {
+            println!("    {:<12} {} {}", label, key, sol(*lamports));
+        }
+    }
+
+    println!();
+    println!("Closeable: {} transaction(s), {}", closeable_count, sol(closeable_total));
+    println!("Held in all transaction accounts: {}", sol(held_total));
+    if multisig.rent_collector.is_none() && closeable_total > 0 {
+        println!();
+        println!("NOTE: This multisig has no rent collector, so the program can't close these accounts.");
+        println!("A config transaction must set one before any of this rent can be reclaimed.");
+    }
+}
@@ -0,0 +1,176 @@
+//! Reject a multisig proposal
+//!
+//! Members use this to vote "reject" on an active proposal. Rejecting is not the mirror of
+//! approving: approval needs `threshold` votes, but a proposal is rejected as soon as enough
+//! voters reject that the threshold can no longer be reached. That cutoff is
+//! `voters - threshold + 1`, where voters are the members with Vote permission; e.g. a 2-of-3
+//! is approved by 2 approvals and rejected by 2 rejections, but a 2-of-5 needs 4 rejections.
+//! The script prints both counts before voting and says when the proposal flips to Rejected.
+//!
+//! Usage:
+//!   cargo run --bin reject-proposal -- <multisig_address> <proposal_index> [--links] [--open] [mainnet]
+//!
+//! `--links` prints explorer and Squads app links for the multisig, proposal and transaction, and
+//! `--open` opens the proposal in the Squads app.
+//!
+//! Example:
+//!   cargo run --bin reject-proposal -- BJbRt... 1 mainnet
+
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Signer},
+};
+use squads_multisig::pda::get_proposal_pda;
+use squads_multisig::state::{Multisig, Permission};
+use squads_rust::addressbook::resolve_pubkey;
+use squads_rust::args::{reject_unknown_flags_or_exit, take_flag, take_help};
+use squads_rust::audit::record;
+use squads_rust::format::progress_bar;
+use squads_rust::multisig::{fetch_multisig_or_exit, pending_voters};
+use squads_rust::network::{open_in_browser, squads_app_tx_url, Network};
+use squads_rust::program::{program_id, take_program_id_or_exit};
+use squads_rust::proposal::{fetch_proposal_state, proposal_reject_ix, ProposalState};
+use squads_rust::send::{build_and_send, SendOptions};
+use std::env;
+
+fn main() {
+    let mut args: Vec<String> = env::args().collect();
+    let help = take_help(&mut args);
+    take_program_id_or_exit(&mut args);
+    let send_options = SendOptions::take_from_or_exit(&mut args);
+    let links = take_flag(&mut args, "--links");
+    let open = take_flag(&mut args, "--open");
+    reject_unknown_flags_or_exit(&args);
+
+    if help || args.len() < 3 {
+        println!("Usage: cargo run --bin reject-proposal -- <multisig_address> <proposal_index> [--links] [--open] [mainnet]");
+        println!();
+        println!("  --links  Print explorer and Squads app links after voting");
+        println!("  --open   Open the Squads app page in the browser (prints the URL if that fails)");
+        println!();
+        println!("Example:");
+        println!("  cargo run --bin reject-proposal -- BJbRt... 1 mainnet");
+        return;
+    }
+
+    let multisig_pda: Pubkey = resolve_pubkey(&args[1]);
+    let proposal_index: u64 = args[2].parse().expect("Invalid proposal index");
+    let network = Network::from_arg(args.get(3).map(|s| s.as_str()).unwrap_or("devnet"));
+
+    let client = RpcClient::new_with_commitment(network.rpc_url(), CommitmentConfig::confirmed());
+    let member = read_keypair_file("../member1.json").expect("Failed to read member1.json");
+
+    // Derive proposal PDA
+    let (proposal_pda, _) = get_proposal_pda(&multisig_pda, proposal_index, Some(&program_id()));
+
+    // Fetch multisig info
+    let multisig = fetch_multisig_or_exit(&client, &multisig_pda);
+
+    // Fetch proposal info
+    let (proposal, state) = fetch_proposal_state(&client, &proposal_pda).unwrap_or_else(|e| {
+        println!("Error: {}", e);
+        std::process::exit(1);
+    });
+
+    println!("=== Reject Proposal ({}) ===\n", network.name().to_uppercase());
+    println!("Multisig: {}", multisig_pda);
+    println!("Member: {}", member.pubkey());
+    println!();
+    println!("Proposal Index: {}", proposal_index);
+    println!("Proposal Address: {}", proposal_pda);
+
+    // Check proposal status
+    println!("Status: {}", state.label());
+    println!();
+
+    // Approval and rejection need different counts; spell both out
+    let threshold = usize::from(multisig.threshold);
+    let cutoff = multisig.cutoff();
+    let voters = Multisig::num_voters(&multisig.members);
+    println!("Approval needs {} of {} voters.", threshold, voters);
+    println!(
+        "Rejection needs {} of {} voters: after {} rejection(s), only {} voter(s) are left to approve, fewer than {}.",
+        cutoff,
+        voters,
+        cutoff,
+        voters - cutoff,
+        threshold
+    );
+    println!();
+    println!("Approvals:  {}", progress_bar(proposal.approved.len(), threshold));
+    println!("Rejections: {}", progress_bar(proposal.rejected.len(), cutoff));
+    for voter in &proposal.rejected {
+        println!("  - {}", voter);
+    }
+    let voted: Vec<Pubkey> = proposal.approved.iter().chain(&proposal.rejected).copied().collect();
+    let not_voted = pending_voters(&multisig, &voted);
+    if !not_voted.is_empty() {
+        println!("Not yet voted:");
+        for key in not_voted {
+            println!("  - {}", key);
+        }
+    }
+
+    // Check if member already rejected
+    if proposal.rejected.contains(&member.pubkey()) {
+        println!("\nYou have already rejected this proposal!");
+        return;
+    }
+
+    // Check if proposal is active
+    if !state.is_votable() {
+        println!("\nError: Proposal is not active. Current status: {}", state.name());
+        return;
+    }
+
+    // Check if member can vote
+    if !multisig.member_has_permission(member.pubkey(), Permission::Vote) {
+        println!("\nError: {} is not a member of this multisig with Vote permission", member.pubkey());
+        return;
+    }
+    if proposal.approved.contains(&member.pubkey()) {
+        println!("\nNote: you approved this proposal; rejecting replaces that approval.");
+    }
+
+    let instruction = proposal_reject_ix(&multisig_pda, proposal_index, &member.pubkey());
+
+    println!("\nRejecting proposal...");
+
+    match build_and_send(&client, &[instruction], &member.pubkey(), &[&member], &send_options) {
+        Ok(sig) => {
+            record(network, &multisig_pda, &format!("reject proposal {}", proposal_index), &member.pubkey(), &sig);
+            let new_rejection_count = proposal.rejected.len() + 1;
+            println!("\nRejection recorded!");
+            println!("Transaction: {}", sig);
+            println!();
+            println!("Rejections: {}", progress_bar(new_rejection_count, cutoff));
+
+            // Read the status back rather than assuming the program counted the same way
+            match fetch_proposal_state(&client, &proposal_pda) {
+                Ok((_, ProposalState::Rejected { .. })) => {
+                    println!("\nCutoff reached! The proposal is now Rejected and can never be approved or executed.");
+                }
+                Ok((_, state)) => {
+                    let remaining = cutoff.saturating_sub(new_rejection_count);
+                    println!("\nStatus: {}", state.label());
+                    println!("{} more rejection(s) needed to reject the proposal.", remaining);
+                }
+                Err(e) => println!("\nWarning: could not re-read the proposal status: {}", e),
+            }
+
+            println!("\nView on Solana Explorer:");
+            println!("{}", network.explorer_tx_url(&sig));
+            if links {
+                network.print_links(&multisig_pda, Some(proposal_index), Some(&sig));
+            }
+            if open {
+                open_in_browser(&squads_app_tx_url(&multisig_pda, proposal_index));
+            }
+        }
+        Err(e) => {
+            println!("\nFailed to reject proposal: {}", e);
+        }
+    }
+}
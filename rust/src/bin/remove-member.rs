@@ -1,7 +1,12 @@
 //! Remove a member from a Squads v4 Multisig (config authority only)
 //!
+//! Safe to re-run: an address that is no longer a member is reported and exits 0. Removals that
+//! would leave fewer Vote-capable members than the threshold are refused locally with a hint to
+//! lower the threshold first; `--force` skips both checks and sends anyway (to see the program's
+//! own error).
+//!
 //! Usage:
-//!   cargo run --bin remove_member -- <multisig_address> <member_to_remove> [mainnet]
+//!   cargo run --bin remove_member -- <multisig_address> <member_to_remove> [--force] [mainnet]
 //!
 //! Example:
 //!   cargo run --bin remove_member -- BJbRtXM8wecvRrJNbbpNLfuG8FTSoU6zPYW1NFrMH6Q3 53Sb8FiUTRJbqs6SC5KgbMLqfwT98qPPTVroodLJKQ9m mainnet
@@ -12,41 +17,71 @@ use solana_sdk::{
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
     signature::{read_keypair_file, Signer},
-    transaction::Transaction,
 };
 use squads_multisig::anchor_lang::InstructionData;
 use squads_multisig::squads_multisig_program;
+use squads_rust::addressbook::resolve_pubkey;
+use squads_rust::args::{reject_unknown_flags_or_exit, take_flag, take_help};
+use squads_rust::audit::record;
+use squads_rust::multisig::{check_voters, fetch_multisig_or_exit};
+use squads_rust::network::Network;
+use squads_rust::program::{program_id, take_program_id_or_exit};
+use squads_rust::send::{build_and_send, SendOptions};
 use std::env;
 
-const DEVNET_RPC: &str = "https://api.devnet.solana.com";
-const MAINNET_RPC: &str = "https://api.mainnet-beta.solana.com";
-
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    let mut args: Vec<String> = env::args().collect();
+    let help = take_help(&mut args);
+    take_program_id_or_exit(&mut args);
+    let send_options = SendOptions::take_from_or_exit(&mut args);
+    let force = take_flag(&mut args, "--force");
+    reject_unknown_flags_or_exit(&args);
 
-    if args.len() < 3 {
-        println!("Usage: cargo run --bin remove_member -- <multisig_address> <member_to_remove> [mainnet]");
+    if help || args.len() < 3 {
+        println!("Usage: cargo run --bin remove_member -- <multisig_address> <member_to_remove> [--force] [mainnet]");
         println!("Example: cargo run --bin remove_member -- BJbRtXM8wecvRrJNbbpNLfuG8FTSoU6zPYW1NFrMH6Q3 53Sb8FiUTRJbqs6SC5KgbMLqfwT98qPPTVroodLJKQ9m mainnet");
         return;
     }
 
-    let multisig_pda: Pubkey = args[1].parse().expect("Invalid multisig address");
-    let member_to_remove: Pubkey = args[2].parse().expect("Invalid member address");
-    let network = args.get(3).map(|s| s.as_str()).unwrap_or("devnet");
+    let multisig_pda: Pubkey = resolve_pubkey(&args[1]);
+    let member_to_remove: Pubkey = resolve_pubkey(&args[2]);
+    let network = Network::from_arg(args.get(3).map(|s| s.as_str()).unwrap_or("devnet"));
 
-    let rpc_url = match network {
-        "mainnet" => MAINNET_RPC,
-        _ => DEVNET_RPC,
-    };
-
-    let client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
+    let client = RpcClient::new_with_commitment(network.rpc_url(), CommitmentConfig::confirmed());
     let config_authority = read_keypair_file("../member1.json").expect("Failed to read member1.json");
 
-    println!("=== Remove Member from Multisig ({}) ===\n", network.to_uppercase());
+    println!("=== Remove Member from Multisig ({}) ===\n", network.name().to_uppercase());
     println!("Multisig: {}", multisig_pda);
     println!("Config Authority: {}", config_authority.pubkey());
     println!("Member to Remove: {}", member_to_remove);
 
+    // Prechecks, so a re-run or an impossible removal doesn't fail on-chain
+    let multisig = fetch_multisig_or_exit(&client, &multisig_pda);
+    if force {
+        println!("\n--force: skipping the membership and threshold checks");
+    } else {
+        if multisig.is_member(member_to_remove).is_none() {
+            println!("\n{} is not a member, nothing to do", member_to_remove);
+            return;
+        }
+        let remaining: Vec<_> = multisig
+            .members
+            .iter()
+            .filter(|m| m.key != member_to_remove)
+            .cloned()
+            .collect();
+        if let Err(e) = check_voters(&remaining, multisig.threshold) {
+            println!("\nError: Removing this member would break the threshold: {}", e);
+            println!("Lower the threshold first:");
+            println!(
+                "  cargo run --bin change_threshold -- {} <new_threshold> {}",
+                multisig_pda,
+                network.cli_arg()
+            );
+            std::process::exit(1);
+        }
+    }
+
     let instruction_data = squads_multisig_program::instruction::MultisigRemoveMember {
         args: squads_multisig_program::MultisigRemoveMemberArgs {
             old_member: member_to_remove,
@@ -57,37 +92,31 @@ fn main() {
     let accounts = vec![
         AccountMeta::new(multisig_pda, false),
         AccountMeta::new_readonly(config_authority.pubkey(), true),
-        AccountMeta::new_readonly(squads_multisig_program::ID, false), // rent_payer (None)
-        AccountMeta::new_readonly(squads_multisig_program::ID, false), // system_program (None)
+        AccountMeta::new_readonly(program_id(), false), // rent_payer (None)
+        AccountMeta::new_readonly(program_id(), false), // system_program (None)
     ];
 
     let instruction = Instruction {
-        program_id: squads_multisig_program::ID,
+        program_id: program_id(),
         accounts,
         data: instruction_data.data(),
     };
 
     println!("\nRemoving member...");
 
-    let recent_blockhash = client.get_latest_blockhash().expect("Failed to get blockhash");
-    let transaction = Transaction::new_signed_with_payer(
-        &[instruction],
-        Some(&config_authority.pubkey()),
-        &[&config_authority],
-        recent_blockhash,
-    );
-
-    match client.send_and_confirm_transaction(&transaction) {
+    match build_and_send(&client, &[instruction], &config_authority.pubkey(), &[&config_authority], &send_options) {
         Ok(sig) => {
+            record(network, &multisig_pda, &format!("remove member {}", member_to_remove), &config_authority.pubkey(), &sig);
             println!("\nMember removed successfully!");
             println!("Transaction: {}", sig);
+            println!("\nSpending limits may still list this member; resync them with migrate-spending-limit-members.");
 
-            let cluster_param = if network == "mainnet" { "" } else { "?cluster=devnet" };
             println!("\nView on Solana Explorer:");
-            println!("https://explorer.solana.com/tx/{}{}", sig, cluster_param);
+            println!("{}", network.explorer_tx_url(&sig));
         }
         Err(e) => {
             println!("\nFailed to remove member: {}", e);
+            std::process::exit(1);
         }
     }
 }
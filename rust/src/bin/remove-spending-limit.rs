@@ -16,20 +16,26 @@ use solana_sdk::{
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
     signature::{read_keypair_file, Signer},
-    transaction::Transaction,
 };
 use squads_multisig::anchor_lang::{AccountDeserialize, InstructionData};
 use squads_multisig::squads_multisig_program;
 use squads_multisig::state::SpendingLimit;
+use squads_rust::addressbook::resolve_pubkey;
+use squads_rust::args::{reject_unknown_flags_or_exit, take_help};
+use squads_rust::audit::record;
+use squads_rust::network::Network;
+use squads_rust::program::{check_program_or_exit, program_id, take_program_id_or_exit};
+use squads_rust::send::{build_and_send, SendOptions};
 use std::env;
 
-const DEVNET_RPC: &str = "https://api.devnet.solana.com";
-const MAINNET_RPC: &str = "https://api.mainnet-beta.solana.com";
-
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    let mut args: Vec<String> = env::args().collect();
+    let help = take_help(&mut args);
+    take_program_id_or_exit(&mut args);
+    let send_options = SendOptions::take_from_or_exit(&mut args);
+    reject_unknown_flags_or_exit(&args);
 
-    if args.len() < 3 {
+    if help || args.len() < 3 {
         println!("Usage: cargo run --bin remove-spending-limit -- <multisig_address> <spending_limit_address> [mainnet]");
         println!();
         println!("Arguments:");
@@ -41,23 +47,20 @@ fn main() {
         return;
     }
 
-    let multisig_pda: Pubkey = args[1].parse().expect("Invalid multisig address");
-    let spending_limit_pda: Pubkey = args[2].parse().expect("Invalid spending limit address");
-    let network = args.get(3).map(|s| s.as_str()).unwrap_or("devnet");
+    let multisig_pda: Pubkey = resolve_pubkey(&args[1]);
+    let spending_limit_pda: Pubkey = resolve_pubkey(&args[2]);
+    let network = Network::from_arg(args.get(3).map(|s| s.as_str()).unwrap_or("devnet"));
 
-    let rpc_url = match network {
-        "mainnet" => MAINNET_RPC,
-        _ => DEVNET_RPC,
-    };
+    let client = RpcClient::new_with_commitment(network.rpc_url(), CommitmentConfig::confirmed());
 
-    let client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
+    check_program_or_exit(&client);
     let config_authority = read_keypair_file("../member1.json").expect("Failed to read member1.json");
 
     // Fetch and display spending limit info before removal
     match client.get_account(&spending_limit_pda) {
         Ok(account) => {
             if let Ok(spending_limit) = SpendingLimit::try_deserialize(&mut account.data.as_slice()) {
-                println!("=== Remove Spending Limit ({}) ===\n", network.to_uppercase());
+                println!("=== Remove Spending Limit ({}) ===\n", network.name().to_uppercase());
                 println!("Multisig: {}", multisig_pda);
                 println!("Config Authority: {}", config_authority.pubkey());
                 println!("Spending Limit: {}", spending_limit_pda);
@@ -104,30 +107,22 @@ fn main() {
     ];
 
     let instruction = Instruction {
-        program_id: squads_multisig_program::ID,
+        program_id: program_id(),
         accounts,
         data: instruction_data.data(),
     };
 
     println!("\nRemoving spending limit...");
 
-    let recent_blockhash = client.get_latest_blockhash().expect("Failed to get blockhash");
-    let transaction = Transaction::new_signed_with_payer(
-        &[instruction],
-        Some(&config_authority.pubkey()),
-        &[&config_authority],
-        recent_blockhash,
-    );
-
-    match client.send_and_confirm_transaction(&transaction) {
+    match build_and_send(&client, &[instruction], &config_authority.pubkey(), &[&config_authority], &send_options) {
         Ok(sig) => {
+            record(network, &multisig_pda, &format!("remove spending limit {}", spending_limit_pda), &config_authority.pubkey(), &sig);
             println!("\nSpending limit removed successfully!");
             println!("Transaction: {}", sig);
             println!("Rent has been returned to: {}", config_authority.pubkey());
 
-            let cluster_param = if network == "mainnet" { "" } else { "?cluster=devnet" };
             println!("\nView on Solana Explorer:");
-            println!("https://explorer.solana.com/tx/{}{}", sig, cluster_param);
+            println!("{}", network.explorer_tx_url(&sig));
         }
         Err(e) => {
             println!("\nFailed to remove spending limit: {}", e);
@@ -0,0 +1,343 @@
+//! Interactively review a multisig's open proposals, then vote or execute
+//!
+//! Lists every Active and Approved proposal with its votes, marking those still waiting on the
+//! member's vote, and shows vault 0's balance. Commands at the prompt:
+//!
+//! - `v <n>` - show proposal n's actions: vault transaction instructions decoded as in
+//!   decode-instruction, or config transaction actions
+//! - `a <n>` / `r <n>` - approve / reject proposal n
+//! - `e <n>` - execute approved proposal n (irreversible actions ask for confirmation and wait
+//!   for finalized, as in execute-proposal)
+//! - Enter - refresh from chain; `q` - quit
+//!
+//! State is refetched after every vote or execution, so proposals other members act on show up
+//! on the next refresh.
+//!
+//! Usage:
+//!   cargo run --bin review-proposals -- <multisig_address> [--member <keypair_path>] [mainnet]
+//!
+//! Options:
+//!   --member <path>  - Keypair that votes, executes and pays fees (default: ../member1.json)
+
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair, Signature, Signer},
+};
+use squads_multisig::anchor_lang::{AccountDeserialize, Discriminator};
+use squads_multisig::pda::{get_transaction_pda, get_vault_pda};
+use squads_multisig::squads_multisig_program::{ConfigTransaction, VaultTransaction};
+use squads_multisig::state::{Batch, Member, Multisig, Permission, Proposal};
+use squads_rust::addressbook::resolve_pubkey;
+use squads_rust::args::{reject_unknown_flags_or_exit, take_flag_value, take_help};
+use squads_rust::audit::record;
+use squads_rust::confirm::confirm;
+use squads_rust::decode::{decode_instruction, format_config_action};
+use squads_rust::format::{format_amount, progress_bar, Units, SOL_DECIMALS};
+use squads_rust::multisig::{fetch_multisig, fetch_proposals};
+use squads_rust::permissions::PermissionsExt;
+use squads_rust::network::Network;
+use squads_rust::program::{program_id, take_program_id_or_exit};
+use squads_rust::proposal::{
+    config_transaction_execute_ix, irreversible_actions, proposal_approve_ix, proposal_reject_ix,
+    vault_transaction_execute_ix, ProposalState,
+};
+use squads_rust::send::{build_and_send, fetch_lookup_tables, wait_for_finalized, SendOptions};
+use std::env;
+use std::io::{self, BufRead, Write};
+
+const DEFAULT_MEMBER_PATH: &str = "../member1.json";
+
+fn sol(lamports: u64) -> String {
+    format!("{} SOL", format_amount(lamports, SOL_DECIMALS, Units::Ui))
+}
+
+/// The transaction behind a proposal, by account type
+enum Transaction {
+    Vault(VaultTransaction),
+    Config(ConfigTransaction),
+    Batch(Batch),
+}
+
+struct Review {
+    client: RpcClient,
+    network: Network,
+    multisig_pda: Pubkey,
+    member: Keypair,
+    send_options: SendOptions,
+}
+
+impl Review {
+    /// The multisig and its Active and Approved proposals
+    fn fetch(&self) -> Result<(Multisig, Vec<(u64, Proposal)>), String> {
+        let multisig = fetch_multisig(&self.client, &self.multisig_pda)?;
+        let open = fetch_proposals(&self.client, &self.multisig_pda, &multisig)?
+            .into_iter()
+            .filter(|(_, _, proposal)| {
+                let state = ProposalState::of(proposal);
+                state.is_votable() || state.is_executable()
+            })
+            .map(|(index, _, proposal)| (index, proposal))
+            .collect();
+        Ok((multisig, open))
+    }
+
+    fn membership<'a>(&self, multisig: &'a Multisig) -> Option<&'a Member> {
+        multisig.members.iter().find(|member| member.key == self.member.pubkey())
+    }
+
+    fn needs_vote(&self, multisig: &Multisig, proposal: &Proposal) -> bool {
+        let me = self.member.pubkey();
+        ProposalState::of(proposal).is_votable()
+            && self.membership(multisig).is_some_and(|member| member.permissions.contains(Permission::Vote))
+            && !proposal.approved.contains(&me)
+            && !proposal.rejected.contains(&me)
+    }
+
+    fn print_overview(&self, multisig: &Multisig, open: &[(u64, Proposal)]) {
+        let (vault_pda, _) = get_vault_pda(&self.multisig_pda, 0, Some(&program_id()));
+        let balance = self.client.get_balance(&vault_pda).map(sol).unwrap_or_else(|e| format!("unavailable ({})", e));
+
+        println!("\n=== Review Proposals ({}) ===\n", self.network.name().to_uppercase());
+        println!("Multisig: {}", self.multisig_pda);
+        println!("Threshold: {} of {}", multisig.threshold, multisig.members.len());
+        match self.membership(multisig) {
+            Some(member) => println!("Member: {} ({})", member.key, member.permissions.to_string()),
+            None => println!("Member: {} (NOT a member; can only view)", self.member.pubkey()),
+        }
+        println!("Vault 0: {} ({})", vault_pda, balance);
+        println!();
+
+        if open.is_empty() {
+            println!("No Active or Approved proposals.");
+            return;
+        }
+        for (index, proposal) in open {
+            let marker = if self.needs_vote(multisig, proposal) { "*" } else { " " };
+            let state = ProposalState::of(proposal);
+            let note = if state.is_executable() {
+                "ready to execute"
+            } else if self.needs_vote(multisig, proposal) {
+                "needs your vote"
+            } else {
+                ""
+            };
+            println!(
+                "{} #{:<4} {}  {}  {}",
+                marker,
+                index,
+                state.label(),
+                progress_bar(proposal.approved.len(), usize::from(multisig.threshold)),
+                note
+            );
+        }
+        println!("\n* waiting on your vote");
+    }
+
+    fn fetch_transaction(&self, index: u64) -> Result<Transaction, String> {
+        let (transaction_pda, _) = get_transaction_pda(&self.multisig_pda, index, Some(&program_id()));
+        let account = self
+            .client
+            .get_account(&transaction_pda)
+            .map_err(|e| format!("failed to fetch transaction {}: {}", transaction_pda, e))?;
+        let data = account.data.as_slice();
+        let deserialize_error = |e| format!("failed to deserialize transaction {}: {}", transaction_pda, e);
+        if data.starts_with(&VaultTransaction::DISCRIMINATOR) {
+            VaultTransaction::try_deserialize(&mut &data[..]).map(Transaction::Vault).map_err(deserialize_error)
+        } else if data.starts_with(&ConfigTransaction::DISCRIMINATOR) {
+            ConfigTransaction::try_deserialize(&mut &data[..]).map(Transaction::Config).map_err(deserialize_error)
+        } else if data.starts_with(&Batch::DISCRIMINATOR) {
+            Batch::try_deserialize(&mut &data[..]).map(Transaction::Batch).map_err(deserialize_error)
+        } else {
+            Err(format!("unrecognized transaction account {}", transaction_pda))
+        }
+    }
+
+    fn view(&self, index: u64) -> Result<(), String> {
+        println!();
+        match self.fetch_transaction(index)? {
+            Transaction::Vault(transaction) => {
+                let message = &transaction.message;
+                println!("Proposal #{}: vault {} transaction, {} instruction(s)", index, transaction.vault_index, message.instructions.len());
+                for (i, ix) in message.instructions.iter().enumerate() {
+                    let Some(program) = message.account_keys.get(usize::from(ix.program_id_index)) else {
+                        println!("  {}. program loaded from an address lookup table, {} bytes of data", i + 1, ix.data.len());
+                        continue;
+                    };
+                    match decode_instruction(program, &ix.data) {
+                        Ok(decoded) => {
+                            println!("  {}. {} {}", i + 1, decoded.program, decoded.name);
+                            for (name, value) in &decoded.fields {
+                                println!("       {}: {}", name, value);
+                            }
+                        }
+                        Err(_) => println!("  {}. program {}, {} bytes of data (not decoded)", i + 1, program, ix.data.len()),
+                    }
+                }
+                for action in irreversible_actions(message) {
+                    println!("  Irreversible: {}", action);
+                }
+            }
+            Transaction::Config(transaction) => {
+                println!("Proposal #{}: config transaction, {} action(s)", index, transaction.actions.len());
+                for (i, action) in transaction.actions.iter().enumerate() {
+                    println!("  {}. {}", i + 1, format_config_action(action));
+                }
+            }
+            Transaction::Batch(batch) => {
+                println!("Proposal #{}: batch of {} transaction(s) on vault {}", index, batch.size, batch.vault_index);
+                println!("  See: cargo run --bin inspect-batch -- {} {} --instructions {}", self.multisig_pda, index, self.network.cli_arg());
+            }
+        }
+        Ok(())
+    }
+
+    fn vote(&self, multisig: &Multisig, index: u64, approve: bool) -> Result<Signature, String> {
+        if !self.membership(multisig).is_some_and(|member| member.permissions.contains(Permission::Vote)) {
+            return Err("you don't have Vote permission on this multisig".to_string());
+        }
+        let me = self.member.pubkey();
+        let (instruction, action) = if approve {
+            (proposal_approve_ix(&self.multisig_pda, index, &me), "approve")
+        } else {
+            (proposal_reject_ix(&self.multisig_pda, index, &me), "reject")
+        };
+        let signature = build_and_send(&self.client, &[instruction], &me, &[&self.member], &self.send_options)
+            .map_err(|e| e.to_string())?;
+        record(self.network, &self.multisig_pda, &format!("{} proposal {}", action, index), &me, &signature);
+        Ok(signature)
+    }
+
+    fn execute(&self, multisig: &Multisig, index: u64) -> Result<Signature, String> {
+        if !self.membership(multisig).is_some_and(|member| member.permissions.contains(Permission::Execute)) {
+            return Err("you don't have Execute permission on this multisig".to_string());
+        }
+        let me = self.member.pubkey();
+        let (instruction, wait_finalized) = match self.fetch_transaction(index)? {
+            Transaction::Vault(transaction) => {
+                let irreversible = irreversible_actions(&transaction.message);
+                if !irreversible.is_empty() {
+                    for action in &irreversible {
+                        println!("Irreversible: {}", action);
+                    }
+                    if !confirm("This can't be undone.") {
+                        return Err("not executed".to_string());
+                    }
+                }
+                let table_keys: Vec<Pubkey> =
+                    transaction.message.address_table_lookups.iter().map(|lookup| lookup.account_key).collect();
+                let lookup_tables = fetch_lookup_tables(&self.client, &table_keys)?;
+                let instruction = vault_transaction_execute_ix(&self.multisig_pda, index, &transaction, &lookup_tables, &me)?;
+                (instruction, !irreversible.is_empty())
+            }
+            Transaction::Config(transaction) => {
+                (config_transaction_execute_ix(&self.multisig_pda, index, &transaction, &me), false)
+            }
+            Transaction::Batch(_) => {
+                return Err("batches execute one inner transaction at a time; see inspect-batch".to_string())
+            }
+        };
+        let signature = build_and_send(&self.client, &[instruction], &me, &[&self.member], &self.send_options)
+            .map_err(|e| e.to_string())?;
+        record(self.network, &self.multisig_pda, &format!("execute proposal {}", index), &me, &signature);
+        if wait_finalized {
+            println!("Waiting for finalized...");
+            wait_for_finalized(&self.client, &signature).map_err(|e| format!("landed as {} but not finalized: {}", signature, e))?;
+        }
+        Ok(signature)
+    }
+}
+
+fn prompt() -> Option<String> {
+    print!("\n[v]iew <n>  [a]pprove <n>  [r]eject <n>  [e]xecute <n>  Enter: refresh  [q]uit > ");
+    io::stdout().flush().expect("Failed to flush stdout");
+    let mut line = String::new();
+    match io::stdin().lock().read_line(&mut line) {
+        Ok(0) | Err(_) => None,
+        Ok(_) => Some(line.trim().to_string()),
+    }
+}
+
+fn main() {
+    let mut args: Vec<String> = env::args().collect();
+    let help = take_help(&mut args);
+    take_program_id_or_exit(&mut args);
+    let send_options = SendOptions::take_from_or_exit(&mut args);
+    let member_path = match take_flag_value(&mut args, "--member") {
+        Ok(path) => path.unwrap_or_else(|| DEFAULT_MEMBER_PATH.to_string()),
+        Err(e) => {
+            println!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    reject_unknown_flags_or_exit(&args);
+
+    if help || args.len() < 2 {
+        println!("Interactively review a multisig's open proposals, then vote or execute");
+        println!();
+        println!("Usage:");
+        println!("  cargo run --bin review-proposals -- <multisig_address> [--member <keypair_path>] [mainnet]");
+        println!();
+        println!("Options:");
+        println!("  --member <path>  - Keypair that votes, executes and pays fees (default: {})", DEFAULT_MEMBER_PATH);
+        println!();
+        println!("At the prompt: v <n> views, a <n> approves, r <n> rejects, e <n> executes,");
+        println!("Enter refreshes and q quits.");
+        return;
+    }
+
+    let member = read_keypair_file(&member_path).unwrap_or_else(|e| {
+        println!("Error: Failed to read {}: {}", member_path, e);
+        std::process::exit(1);
+    });
+    let network = Network::from_arg(args.get(2).map(|s| s.as_str()).unwrap_or("devnet"));
+    let review = Review {
+        client: RpcClient::new_with_commitment(network.rpc_url(), CommitmentConfig::confirmed()),
+        network,
+        multisig_pda: resolve_pubkey(&args[1]),
+        member,
+        send_options,
+    };
+
+    let (mut multisig, mut open) = review.fetch().unwrap_or_else(|e| {
+        println!("Error: {}", e);
+        std::process::exit(1);
+    });
+    review.print_overview(&multisig, &open);
+
+    while let Some(line) = prompt() {
+        let mut words = line.split_whitespace();
+        let command = words.next().unwrap_or("");
+        let index = words.next().map(|value| value.trim_start_matches('#').parse::<u64>());
+        let result = match (command, index) {
+            ("q" | "quit", _) => break,
+            ("", _) => Ok(None),
+            (_, None) => Err(format!("'{}' needs a proposal number", command)),
+            (_, Some(Err(_))) => Err("invalid proposal number".to_string()),
+            ("v" | "view", Some(Ok(index))) => review.view(index).map(|_| None),
+            ("a" | "approve", Some(Ok(index))) => review.vote(&multisig, index, true).map(Some),
+            ("r" | "reject", Some(Ok(index))) => review.vote(&multisig, index, false).map(Some),
+            ("e" | "execute", Some(Ok(index))) => review.execute(&multisig, index).map(Some),
+            _ => Err(format!("unknown command '{}'", command)),
+        };
+        match result {
+            // Viewing doesn't change anything on-chain, so keep the listing as it is
+            Ok(None) if !command.is_empty() => continue,
+            Ok(Some(signature)) => println!("Sent: {}", review.network.explorer_tx_url(&signature)),
+            Ok(None) => {}
+            Err(e) => {
+                println!("Error: {}", e);
+                continue;
+            }
+        }
+        match review.fetch() {
+            Ok((fetched_multisig, fetched_open)) => {
+                multisig = fetched_multisig;
+                open = fetched_open;
+                review.print_overview(&multisig, &open);
+            }
+            Err(e) => println!("Error: Failed to refresh: {}", e),
+        }
+    }
+}
@@ -0,0 +1,95 @@
+//! Suggest a threshold for a multisig with N members
+//!
+//! Purely advisory: prints every threshold from 1 to N with how many keys it can lose
+//! (fault tolerance, `n - threshold`) and how many members must collude to move funds, and
+//! recommends a strict majority. Nothing is read from or sent to the chain.
+//!
+//! Usage:
+//!   cargo run --bin suggest-threshold -- <member_count> [--threshold <n>]
+//!
+//! Options:
+//!   --threshold <n>  - Check one threshold instead of listing all of them
+//!
+//! Example:
+//!   cargo run --bin suggest-threshold -- 5
+//!   cargo run --bin suggest-threshold -- 5 --threshold 2
+
+use squads_rust::args::{reject_unknown_flags_or_exit, take_flag_value, take_help};
+use squads_rust::multisig::{check_threshold, fault_tolerance, majority_threshold};
+use std::env;
+
+/// Upper bound on the member count, matching the program's u16 threshold
+const MAX_MEMBERS: usize = u16::MAX as usize;
+
+fn exit_with(message: String) -> ! {
+    println!("Error: {}", message);
+    std::process::exit(1);
+}
+
+/// Print the advice for `threshold` of `member_count`
+fn print_assessment(threshold: u16, member_count: usize) {
+    println!("Threshold: {} of {}", threshold, member_count);
+    println!("  Fault Tolerance: {} lost key(s)", fault_tolerance(threshold, member_count));
+    println!("  Collusion Needed: {} member(s) to move funds", threshold);
+    match check_threshold(threshold, member_count) {
+        Ok(Some(warning)) => println!("  WARNING: {}", warning),
+        Ok(None) => println!("  OK"),
+        Err(e) => println!("  ERROR: {}", e),
+    }
+}
+
+fn main() {
+    let mut args: Vec<String> = env::args().collect();
+    let help = take_help(&mut args);
+    let threshold: Option<u16> = take_flag_value(&mut args, "--threshold")
+        .unwrap_or_else(|e| exit_with(e))
+        .map(|value| {
+            value
+                .parse()
+                .unwrap_or_else(|_| exit_with(format!("invalid --threshold value '{}'", value)))
+        });
+    reject_unknown_flags_or_exit(&args);
+
+    if help || args.len() < 2 {
+        println!("Suggest a threshold for a multisig with N members (advisory only, no on-chain effect)");
+        println!();
+        println!("Usage:");
+        println!("  cargo run --bin suggest-threshold -- <member_count> [--threshold <n>]");
+        println!();
+        println!("Options:");
+        println!("  --threshold <n>  - Check one threshold instead of listing all of them");
+        return;
+    }
+
+    let member_count: usize = match args[1].parse() {
+        Ok(count) if (1..=MAX_MEMBERS).contains(&count) => count,
+        _ => exit_with(format!("member count must be between 1 and {}, got {}", MAX_MEMBERS, args[1])),
+    };
+
+    if let Some(threshold) = threshold {
+        print_assessment(threshold, member_count);
+        return;
+    }
+
+    let majority = majority_threshold(member_count);
+    println!("=== Threshold Suggestions for {} Member(s) ===\n", member_count);
+    println!("{:>9}  {:>15}  {:>16}  Notes", "Threshold", "Fault Tolerance", "Collusion Needed");
+    for threshold in 1..=member_count {
+        let note = match check_threshold(threshold as u16, member_count) {
+            Ok(Some(warning)) => warning,
+            _ if threshold == majority => "recommended (majority)".to_string(),
+            _ => String::new(),
+        };
+        let row = format!(
+            "{:>9}  {:>15}  {:>16}  {}",
+            format!("{} of {}", threshold, member_count),
+            fault_tolerance(threshold as u16, member_count),
+            threshold,
+            note
+        );
+        println!("{}", row.trim_end());
+    }
+    println!();
+    println!("Recommended: {} of {} (a strict majority, floor(n/2)+1)", majority, member_count);
+    println!("  It tolerates {} lost key(s) and no minority can move funds.", fault_tolerance(majority as u16, member_count));
+}
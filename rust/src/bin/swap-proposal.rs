@@ -0,0 +1,438 @@
+//! Create a proposal to swap vault tokens through a Jupiter route
+//!
+//! Fetches a quote from Jupiter's swap API for `--input-mint`/`--output-mint`/`--amount`, prints
+//! the quoted output amount, then asks Jupiter for the route's instructions with the vault as the
+//! user. The setup, swap and cleanup instructions (ATA creation, SOL wrapping, the swap itself)
+//! are compiled into a vault transaction message together with the address lookup tables the
+//! route references, and proposed like any other vault transaction.
+//!
+//! Jupiter's compute budget instructions are left out: they only apply to a top-level
+//! instruction, not to the CPI the vault executes. The script prints the compute unit limit to
+//! execute with instead. Execute the proposal with execute-proposal, which loads the route's
+//! lookup tables; `--tx-version 0` lets the execute transaction use them as well, since the
+//! route's accounts rarely fit a legacy transaction.
+//!
+//! The route is fixed when the proposal is created, and `--slippage-bps` bounds how far the
+//! output may fall before the swap instruction fails. A proposal that waits long for approvals
+//! can fail on execution; propose a fresh route then. `--max-accounts` (default 40) asks Jupiter
+//! for a route small enough to execute in one transaction.
+//!
+//! Jupiter only routes mainnet liquidity. `--jupiter-api <url>` points at another deployment of
+//! the API (default: https://lite-api.jup.ag/swap/v1).
+//!
+//! Usage:
+//!   cargo run --bin swap-proposal -- <multisig_address> --input-mint <mint> --output-mint <mint> --amount <amount> [--slippage-bps <n>] [--max-accounts <n>] [--vault <index>] [--jupiter-api <url>] [--dump-message] mainnet
+//!
+//! Example:
+//!   # Swap 100 USDC (6 decimals) from vault 0 to SOL
+//!   cargo run --bin swap-proposal -- BJbRt... --input-mint EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v --output-mint So11111111111111111111111111111111111111112 --amount 100000000 mainnet
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::Deserialize;
+use serde_json::Value;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::{AccountMeta, Instruction},
+    message::Message,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Signer},
+};
+use squads_multisig::anchor_lang::AnchorSerialize;
+use squads_multisig::pda::{get_proposal_pda, get_transaction_pda, get_vault_pda};
+use squads_multisig::squads_multisig_program::TransactionMessage;
+use squads_multisig::vault_transaction::VaultTransactionMessageExt;
+use squads_rust::addressbook::resolve_pubkey;
+use squads_rust::args::{reject_unknown_flags_or_exit, take_flag, take_flag_value, take_help};
+use squads_rust::audit::record;
+use squads_rust::format::{format_amount, Units};
+use squads_rust::message::print_compiled_message;
+use squads_rust::multisig::fetch_multisig_or_exit;
+use squads_rust::network::Network;
+use squads_rust::program::{program_id, take_program_id_or_exit};
+use squads_rust::proposal::{proposal_approve_ix, proposal_create_ix, vault_transaction_create_ix};
+use squads_rust::send::{build_and_send, fetch_lookup_tables, SendOptions, PACKET_DATA_SIZE};
+use squads_rust::token::{derive_ata, is_token_program, unpack_mint, unpack_token_account};
+use squads_rust::vault::parse_vault_index;
+use std::env;
+
+const DEFAULT_JUPITER_API: &str = "https://lite-api.jup.ag/swap/v1";
+const DEFAULT_SLIPPAGE_BPS: u16 = 50;
+const DEFAULT_MAX_ACCOUNTS: u8 = 40;
+/// Compute units the Squads execute instruction uses on top of the inner swap
+const EXECUTE_OVERHEAD_UNITS: u32 = 100_000;
+const MAX_COMPUTE_UNITS: u32 = 1_400_000;
+/// `ComputeBudgetInstruction::SetComputeUnitLimit`'s tag
+const SET_COMPUTE_UNIT_LIMIT_TAG: u8 = 2;
+
+fn exit_with(message: String) -> ! {
+    println!("Error: {}", message);
+    std::process::exit(1);
+}
+
+/// `/swap-instructions` response; the fields this script uses
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SwapInstructions {
+    #[serde(default)]
+    compute_budget_instructions: Vec<JupiterInstruction>,
+    #[serde(default)]
+    setup_instructions: Vec<JupiterInstruction>,
+    swap_instruction: JupiterInstruction,
+    cleanup_instruction: Option<JupiterInstruction>,
+    #[serde(default)]
+    address_lookup_table_addresses: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JupiterInstruction {
+    program_id: String,
+    accounts: Vec<JupiterAccount>,
+    /// Base64
+    data: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JupiterAccount {
+    pubkey: String,
+    is_signer: bool,
+    is_writable: bool,
+}
+
+impl JupiterInstruction {
+    fn to_instruction(&self) -> Result<Instruction, String> {
+        let parse = |value: &str| value.parse::<Pubkey>().map_err(|e| format!("invalid pubkey '{}' from Jupiter: {}", value, e));
+        let accounts = self
+            .accounts
+            .iter()
+            .map(|account| {
+                Ok(AccountMeta {
+                    pubkey: parse(&account.pubkey)?,
+                    is_signer: account.is_signer,
+                    is_writable: account.is_writable,
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        let data = STANDARD
+            .decode(&self.data)
+            .map_err(|e| format!("invalid instruction data from Jupiter: {}", e))?;
+        Ok(Instruction { program_id: parse(&self.program_id)?, accounts, data })
+    }
+}
+
+/// A JSON field holding an integer, which Jupiter sends as a string
+fn amount_field(value: &Value, field: &str) -> Result<u64, String> {
+    value[field]
+        .as_str()
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| format!("Jupiter quote has no valid '{}'", field))
+}
+
+/// Error body of a failed Jupiter request, or the HTTP status
+fn jupiter_error(response: reqwest::blocking::Response) -> String {
+    let status = response.status();
+    match response.json::<Value>() {
+        Ok(body) => match body["error"].as_str() {
+            Some(error) => format!("Jupiter returned {}: {}", status, error),
+            None => format!("Jupiter returned {}: {}", status, body),
+        },
+        Err(_) => format!("Jupiter returned {}", status),
+    }
+}
+
+/// `amount` in the mint's UI units when its decimals are known
+fn describe_amount(amount: u64, decimals: Option<u8>) -> String {
+    match decimals {
+        Some(decimals) => format!("{} ({} raw)", format_amount(amount, decimals, Units::Ui), amount),
+        None => format!("{} (raw)", amount),
+    }
+}
+
+fn main() {
+    let mut args: Vec<String> = env::args().collect();
+    let help = take_help(&mut args);
+    take_program_id_or_exit(&mut args);
+    let send_options = SendOptions::take_from_or_exit(&mut args);
+    let mut flag = |name: &str| take_flag_value(&mut args, name).unwrap_or_else(|e| exit_with(e));
+    let input_mint = flag("--input-mint");
+    let output_mint = flag("--output-mint");
+    let amount = flag("--amount");
+    let slippage_bps = flag("--slippage-bps");
+    let max_accounts = flag("--max-accounts");
+    let vault = flag("--vault");
+    let jupiter_api = flag("--jupiter-api").unwrap_or_else(|| DEFAULT_JUPITER_API.to_string());
+    let dump_message = take_flag(&mut args, "--dump-message");
+    reject_unknown_flags_or_exit(&args);
+
+    let (false, Some(input_mint), Some(output_mint), Some(amount)) = (help, input_mint, output_mint, amount) else {
+        println!("Create a proposal to swap vault tokens through a Jupiter route");
+        println!();
+        println!("Usage:");
+        println!("  cargo run --bin swap-proposal -- <multisig_address> --input-mint <mint> --output-mint <mint> --amount <amount> [--slippage-bps <n>] [--max-accounts <n>] [--vault <index>] [--jupiter-api <url>] [--dump-message] mainnet");
+        println!();
+        println!("Options:");
+        println!("  --input-mint <mint>   - Mint the vault sells (So11111111111111111111111111111111111111112 for SOL)");
+        println!("  --output-mint <mint>  - Mint the vault buys");
+        println!("  --amount <amount>     - Input amount in smallest units (lamports for SOL)");
+        println!("  --slippage-bps <n>    - Slippage tolerance in basis points (default: {})", DEFAULT_SLIPPAGE_BPS);
+        println!("  --max-accounts <n>    - Largest route Jupiter may return, in accounts (default: {})", DEFAULT_MAX_ACCOUNTS);
+        println!("  --vault <index>       - Vault that swaps (default: 0)");
+        println!("  --jupiter-api <url>   - Jupiter swap API base URL (default: {})", DEFAULT_JUPITER_API);
+        println!("  --dump-message        - Print the compiled vault transaction message before sending");
+        println!();
+        println!("Example:");
+        println!("  cargo run --bin swap-proposal -- BJbRt... --input-mint EPjFW... --output-mint So111... --amount 100000000 mainnet");
+        return;
+    };
+    if args.len() < 2 {
+        exit_with("missing <multisig_address>".to_string());
+    }
+
+    let multisig_pda = resolve_pubkey(&args[1]);
+    let input_mint = resolve_pubkey(&input_mint);
+    let output_mint = resolve_pubkey(&output_mint);
+    if input_mint == output_mint {
+        exit_with("--input-mint and --output-mint are the same mint".to_string());
+    }
+    let amount: u64 = amount.parse().unwrap_or_else(|_| exit_with(format!("invalid --amount '{}'", amount)));
+    if amount == 0 {
+        exit_with("--amount must be greater than zero".to_string());
+    }
+    let slippage_bps: u16 = match slippage_bps {
+        Some(value) => match value.parse() {
+            Ok(bps) if bps <= 10_000 => bps,
+            _ => exit_with(format!("invalid --slippage-bps '{}' (expected 0-10000)", value)),
+        },
+        None => DEFAULT_SLIPPAGE_BPS,
+    };
+    let max_accounts: u8 = match max_accounts {
+        Some(value) => value.parse().unwrap_or_else(|_| exit_with(format!("invalid --max-accounts '{}'", value))),
+        None => DEFAULT_MAX_ACCOUNTS,
+    };
+    let vault_index = match vault {
+        Some(value) => parse_vault_index(&value).unwrap_or_else(|e| exit_with(e)),
+        None => 0,
+    };
+    let network = Network::from_arg(args.get(2).map(|s| s.as_str()).unwrap_or("devnet"));
+    if network != Network::Mainnet {
+        exit_with(format!("Jupiter only routes mainnet liquidity; pass mainnet instead of {}", network.name()));
+    }
+
+    let client = RpcClient::new_with_commitment(network.rpc_url(), CommitmentConfig::confirmed());
+    let creator = read_keypair_file("../member1.json").expect("Failed to read member1.json");
+    let multisig = fetch_multisig_or_exit(&client, &multisig_pda);
+    let new_transaction_index = multisig.transaction_index + 1;
+    let (vault_pda, _) = get_vault_pda(&multisig_pda, vault_index, Some(&program_id()));
+    let (proposal_pda, _) = get_proposal_pda(&multisig_pda, new_transaction_index, Some(&program_id()));
+    let (transaction_pda, _) = get_transaction_pda(&multisig_pda, new_transaction_index, Some(&program_id()));
+
+    // Decimals are only for display; the vault's input balance is a warning, since it may be
+    // funded before execution
+    let mint_accounts = client
+        .get_multiple_accounts(&[input_mint, output_mint])
+        .unwrap_or_else(|e| exit_with(format!("failed to fetch mints: {}", e)));
+    let decimals: Vec<Option<u8>> = mint_accounts
+        .iter()
+        .map(|account| {
+            account
+                .as_ref()
+                .filter(|a| is_token_program(&a.owner))
+                .and_then(|a| unpack_mint(&a.data).ok())
+                .map(|mint| mint.decimals)
+        })
+        .collect();
+    let input_balance = if input_mint == spl_token::native_mint::ID {
+        client.get_balance(&vault_pda).ok()
+    } else {
+        mint_accounts[0].as_ref().and_then(|mint| {
+            let ata = derive_ata(&vault_pda, &input_mint, &mint.owner);
+            match client.get_account(&ata) {
+                Ok(account) => unpack_token_account(&account.data).ok().map(|token| token.amount),
+                Err(_) => Some(0),
+            }
+        })
+    };
+
+    println!("=== Create Swap Proposal ({}) ===\n", network.name().to_uppercase());
+    println!("Multisig: {}", multisig_pda);
+    println!("Vault {}: {}", vault_index, vault_pda);
+    println!("Creator: {}", creator.pubkey());
+    println!("Threshold: {} of {}", multisig.threshold, multisig.members.len());
+    println!();
+    println!("Input Mint: {}", input_mint);
+    println!("Output Mint: {}", output_mint);
+    println!("Amount In: {}", describe_amount(amount, decimals[0]));
+    if let Some(balance) = input_balance {
+        if balance < amount {
+            println!("Warning: the vault holds {} of the input mint; fund it before executing", describe_amount(balance, decimals[0]));
+        }
+    }
+
+    // === Quote ===
+    let http = reqwest::blocking::Client::new();
+    println!("\nFetching Jupiter quote...");
+    let response = http
+        .get(format!("{}/quote", jupiter_api))
+        .query(&[
+            ("inputMint", input_mint.to_string()),
+            ("outputMint", output_mint.to_string()),
+            ("amount", amount.to_string()),
+            ("slippageBps", slippage_bps.to_string()),
+            ("maxAccounts", max_accounts.to_string()),
+        ])
+        .send()
+        .unwrap_or_else(|e| exit_with(format!("failed to reach Jupiter: {}", e)));
+    if !response.status().is_success() {
+        exit_with(jupiter_error(response));
+    }
+    let quote: Value = response
+        .json()
+        .unwrap_or_else(|e| exit_with(format!("invalid Jupiter quote: {}", e)));
+    let out_amount = amount_field(&quote, "outAmount").unwrap_or_else(|e| exit_with(e));
+    let minimum_out = amount_field(&quote, "otherAmountThreshold").unwrap_or_else(|e| exit_with(e));
+    let route: Vec<&str> = quote["routePlan"]
+        .as_array()
+        .map(|plan| plan.iter().filter_map(|step| step["swapInfo"]["label"].as_str()).collect())
+        .unwrap_or_default();
+
+    println!("Quoted Out: {}", describe_amount(out_amount, decimals[1]));
+    println!("Minimum Out: {} ({} bps slippage)", describe_amount(minimum_out, decimals[1]), slippage_bps);
+    if let Some(impact) = quote["priceImpactPct"].as_str() {
+        println!("Price Impact: {}%", impact);
+    }
+    if !route.is_empty() {
+        println!("Route: {}", route.join(" -> "));
+    }
+
+    // === Swap instructions, with the vault as the user ===
+    let response = http
+        .post(format!("{}/swap-instructions", jupiter_api))
+        .json(&serde_json::json!({
+            "quoteResponse": quote,
+            "userPublicKey": vault_pda.to_string(),
+            "wrapAndUnwrapSol": true,
+            "dynamicComputeUnitLimit": true,
+        }))
+        .send()
+        .unwrap_or_else(|e| exit_with(format!("failed to reach Jupiter: {}", e)));
+    if !response.status().is_success() {
+        exit_with(jupiter_error(response));
+    }
+    let swap: SwapInstructions = response
+        .json()
+        .unwrap_or_else(|e| exit_with(format!("invalid Jupiter swap instructions: {}", e)));
+
+    let instructions: Vec<Instruction> = swap
+        .setup_instructions
+        .iter()
+        .chain(Some(&swap.swap_instruction))
+        .chain(swap.cleanup_instruction.as_ref())
+        .map(JupiterInstruction::to_instruction)
+        .collect::<Result<_, _>>()
+        .unwrap_or_else(|e| exit_with(e));
+    // The program signs for the vault only; a route that needs another signer can't be proposed
+    for instruction in &instructions {
+        if let Some(signer) = instruction.accounts.iter().find(|meta| meta.is_signer && meta.pubkey != vault_pda) {
+            exit_with(format!("the route needs {} to sign, which the vault cannot do", signer.pubkey));
+        }
+    }
+    let swap_compute_units = swap
+        .compute_budget_instructions
+        .iter()
+        .filter_map(|ix| STANDARD.decode(&ix.data).ok())
+        .find(|data| data.len() >= 5 && data[0] == SET_COMPUTE_UNIT_LIMIT_TAG)
+        .map(|data| u32::from_le_bytes([data[1], data[2], data[3], data[4]]));
+
+    let table_keys: Vec<Pubkey> = swap
+        .address_lookup_table_addresses
+        .iter()
+        .map(|address| address.parse().unwrap_or_else(|_| exit_with(format!("invalid lookup table '{}' from Jupiter", address))))
+        .collect();
+    let lookup_tables = fetch_lookup_tables(&client, &table_keys).unwrap_or_else(|e| exit_with(e));
+
+    let transaction_message = TransactionMessage::try_compile(&vault_pda, &instructions, &lookup_tables)
+        .unwrap_or_else(|e| exit_with(format!("failed to compile the route: {}", e)));
+    let message_bytes = transaction_message.try_to_vec().expect("Failed to serialize message");
+
+    println!();
+    println!("Instructions: {} ({} setup, swap{})", instructions.len(), swap.setup_instructions.len(), if swap.cleanup_instruction.is_some() { ", cleanup" } else { "" });
+    println!("Lookup Tables: {}", table_keys.len());
+    println!("Transaction Index: {}", new_transaction_index);
+    if dump_message {
+        println!();
+        print_compiled_message(&message_bytes);
+    }
+
+    let create_vault_tx_ix = vault_transaction_create_ix(
+        &multisig_pda,
+        new_transaction_index,
+        &creator.pubkey(),
+        vault_index,
+        0,
+        message_bytes,
+    );
+    let create_proposal_ix = proposal_create_ix(&multisig_pda, new_transaction_index, &creator.pubkey());
+    let approve_ix = proposal_approve_ix(&multisig_pda, new_transaction_index, &creator.pubkey());
+
+    // A long route can leave no room for the proposal instructions; send those separately then
+    let mut sends = vec![vec![create_vault_tx_ix, create_proposal_ix, approve_ix]];
+    let budgeted = send_options.fee_profile.map_or_else(|| sends[0].clone(), |profile| profile.apply(&sends[0]));
+    let size = 1 + 64 + Message::new(&budgeted, Some(&creator.pubkey())).serialize().len();
+    if size > PACKET_DATA_SIZE {
+        let proposal_ixs = sends[0].split_off(1);
+        sends.push(proposal_ixs);
+    }
+
+    println!("\nCreating swap proposal...");
+    let mut signature = None;
+    for instructions in &sends {
+        match build_and_send(&client, instructions, &creator.pubkey(), &[&creator], &send_options) {
+            Ok(sig) => signature = Some(sig),
+            Err(e) => {
+                if signature.is_some() {
+                    println!("\nThe vault transaction {} was created, but its proposal was not.", transaction_pda);
+                }
+                println!("\nFailed to create proposal: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+    let sig = signature.expect("at least one transaction is sent");
+    record(
+        network,
+        &multisig_pda,
+        &format!("propose {}: swap {} of {} for {}", new_transaction_index, amount, input_mint, output_mint),
+        &creator.pubkey(),
+        &sig,
+    );
+
+    println!("\nProposal created successfully!");
+    println!("Transaction: {}", sig);
+    println!();
+    println!("=== Proposal Details ===");
+    println!("Proposal Index: {}", new_transaction_index);
+    println!("Proposal Address: {}", proposal_pda);
+    println!("Status: Active (awaiting {} more approval(s))", multisig.threshold.saturating_sub(1));
+    println!();
+    println!("Share this with other members to approve:");
+    println!("  cargo run --bin approve-proposal -- {} {} {}", multisig_pda, new_transaction_index, network.cli_arg());
+    println!();
+    println!("After threshold is met, execute with:");
+    let compute_unit_limit = swap_compute_units
+        .map(|units| units.saturating_add(EXECUTE_OVERHEAD_UNITS).min(MAX_COMPUTE_UNITS))
+        .unwrap_or(MAX_COMPUTE_UNITS);
+    let compute_unit_limit = send_options.fee_profile.map_or(compute_unit_limit, |profile| profile.cap(compute_unit_limit));
+    println!(
+        "  cargo run --bin execute-proposal -- {} {} --tx-version 0 --compute-unit-limit {} {}",
+        multisig_pda,
+        new_transaction_index,
+        compute_unit_limit,
+        network.cli_arg()
+    );
+    println!("The quote moves with the market: below {} out, the swap fails on execution.", describe_amount(minimum_out, decimals[1]));
+
+    println!("\nView on Solana Explorer:");
+    println!("{}", network.explorer_tx_url(&sig));
+}
@@ -3,97 +3,321 @@
 //! Usage:
 //!   cargo run --bin use-spending-limit -- <spending_limit_address> <destination> <amount> [mainnet]
 //!   cargo run --bin use-spending-limit -- --multisig <multisig_address> <destination> <amount> [mainnet]
+//!   cargo run --bin use-spending-limit -- --snapshot <snapshot_path> <destination> <amount>
 //!
 //! Arguments:
 //!   spending_limit_address  - The spending limit PDA (or use --multisig to derive it)
+//!   snapshot_path           - JSON snapshot from `inspect-spending-limit --dump`; validates
+//!                             the transfer offline and reports go/no-go without sending
 //!   destination             - Destination wallet address
 //!   amount                  - Amount in lamports (for SOL) or smallest unit (for tokens)
 //!
+//! Options:
+//!   --sol <amount>           - For a SOL spending limit, the amount in SOL in place of <amount>
+//!                              (e.g. `--sol 0.1`). Amounts are echoed as lamports and SOL either way
+//!   --recipients-file <path> - Client-side allowlist (one address per line, optional label);
+//!                              aborts unless the destination is listed
+//!   --token-program <pubkey> - Token program for the transfer and ATA derivation; defaults to
+//!                              the mint's owner (SPL Token or Token-2022)
+//!   --force                  - Skip the local checks: member, destination, remaining limit and
+//!                              the vault's balance of the limit's mint
+//!   --simulate               - Build the exact transaction and simulate it instead of sending.
+//!                              The program re-checks the limit (including period resets) against
+//!                              current chain state, catching changes since the local check.
+//!                              Combine with --force to skip the local check entirely
+//!
 //! Examples:
 //!   # Transfer 0.1 SOL using spending limit PDA directly
 //!   cargo run --bin use-spending-limit -- SpendingLimitPDA... DestWallet... 100000000
+//!   cargo run --bin use-spending-limit -- SpendingLimitPDA... DestWallet... --sol 0.1
 //!
 //!   # Transfer using multisig address (derives spending limit via 'combinator')
 //!   cargo run --bin use-spending-limit -- --multisig MultisigPDA... DestWallet... 100000000 mainnet
+//!
+//!   # Check a transfer against a saved snapshot (no RPC access needed)
+//!   cargo run --bin use-spending-limit -- --snapshot limit.json DestWallet... 100000000
 
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::{
+    account::from_account,
+    clock::Clock,
     commitment_config::CommitmentConfig,
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
     signature::{read_keypair_file, Signer},
     system_program,
-    transaction::Transaction,
-};
-use spl_associated_token_account::{
-    get_associated_token_address,
-    instruction::create_associated_token_account_idempotent,
+    sysvar,
 };
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
 use squads_multisig::anchor_lang::{AccountDeserialize, InstructionData};
 use squads_multisig::pda::{get_spending_limit_pda, get_vault_pda};
 use squads_multisig::squads_multisig_program;
 use squads_multisig::state::SpendingLimit;
+use squads_rust::addressbook::resolve_pubkey;
+use squads_rust::args::{reject_unknown_flags_or_exit, take_flag, take_flag_value, take_help};
+use squads_rust::audit::record;
+use squads_rust::format::{describe_lamports, format_amount, parse_sol, Units};
+use squads_rust::network::Network;
+use squads_rust::program::{check_program_or_exit, program_id, take_program_id_or_exit};
+use squads_rust::recipients::{check_recipients, load_recipients};
+use squads_rust::send::{send_and_track, SendError, SendOptions};
+use squads_rust::snapshot::read_spending_limit_snapshot;
+use squads_rust::token::{derive_ata, mint_program_and_decimals, unpack_token_account};
 use std::env;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Amount the program will actually allow at `now`.
+///
+/// The program only refreshes `remaining_amount` when the limit is next used, so once a full
+/// period has passed since `last_reset` the stored value is stale and the full `amount` applies.
+fn available_amount(spending_limit: &SpendingLimit, now: i64) -> u64 {
+    match spending_limit.period.to_seconds() {
+        Some(period) if now.saturating_sub(spending_limit.last_reset) > period => {
+            println!(
+                "Note: The {:?} period has elapsed since the last reset; the limit resets to {} on-chain when used.",
+                spending_limit.period, spending_limit.amount
+            );
+            spending_limit.amount
+        }
+        _ => spending_limit.remaining_amount,
+    }
+}
+
+/// Run the local checks (member authorized, destination allowed, amount within remaining).
+/// Prints the reason and returns false if the transfer would be rejected.
+fn validate_transfer(
+    spending_limit: &SpendingLimit,
+    member: &Pubkey,
+    destination: &Pubkey,
+    amount: u64,
+    now: i64,
+) -> bool {
+    let is_sol = spending_limit.mint == Pubkey::default();
+
+    if !spending_limit.members.contains(member) {
+        println!("Error: Your wallet {} is not authorized to use this spending limit", member);
+        println!();
+        println!("Authorized members:");
+        for m in &spending_limit.members {
+            println!("  - {}", m);
+        }
+        return false;
+    }
+
+    if !spending_limit.destinations.is_empty() && !spending_limit.destinations.contains(destination) {
+        println!("Error: Destination {} is not in the allowed destinations list", destination);
+        println!();
+        println!("Allowed destinations:");
+        for d in &spending_limit.destinations {
+            println!("  - {}", d);
+        }
+        return false;
+    }
+
+    let available = available_amount(spending_limit, now);
+    if amount > available {
+        println!("Error: Requested amount {} exceeds remaining limit {}", amount, available);
+        if is_sol {
+            println!("  Requested: {}", describe_lamports(amount));
+            println!("  Remaining: {}", describe_lamports(available));
+        }
+        return false;
+    }
 
-const DEVNET_RPC: &str = "https://api.devnet.solana.com";
-const MAINNET_RPC: &str = "https://api.mainnet-beta.solana.com";
-const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
+    true
+}
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    let mut args: Vec<String> = env::args().collect();
+    let help = take_help(&mut args);
+    take_program_id_or_exit(&mut args);
+    let send_options = SendOptions::take_from_or_exit(&mut args);
 
-    if args.len() < 4 {
+    if help || args.len() < 4 {
         println!("Usage: cargo run --bin use-spending-limit -- <spending_limit_address> <destination> <amount> [mainnet]");
         println!("       cargo run --bin use-spending-limit -- --multisig <multisig_address> <destination> <amount> [mainnet]");
+        println!("       cargo run --bin use-spending-limit -- --snapshot <snapshot_path> <destination> <amount>");
         println!();
         println!("Arguments:");
         println!("  spending_limit_address  - The spending limit PDA (or use --multisig to derive it)");
+        println!("  snapshot_path           - Snapshot from inspect-spending-limit --dump (offline go/no-go check)");
         println!("  destination             - Destination wallet address");
         println!("  amount                  - Amount in lamports (for SOL) or smallest unit (for tokens)");
         println!();
+        println!("Options:");
+        println!("  --sol <amount>           - SOL limits only: the amount in SOL instead of <amount> in lamports");
+        println!("  --recipients-file <path> - Abort unless the destination is in this allowlist (one address per line)");
+        println!("  --token-program <pubkey> - Override the token program (default: the mint's owner)");
+        println!("  --simulate               - Simulate the transfer on-chain and report go/no-go without sending");
+        println!();
         println!("Examples:");
         println!("  cargo run --bin use-spending-limit -- SpendingLimitPDA... DestWallet... 100000000");
+        println!("  cargo run --bin use-spending-limit -- SpendingLimitPDA... DestWallet... --sol 0.1");
         println!("  cargo run --bin use-spending-limit -- --multisig MultisigPDA... DestWallet... 100000000 mainnet");
         return;
     }
 
-    // Check for --force flag anywhere in args
-    let force = args.iter().any(|a| a == "--force");
-    let args: Vec<String> = args.into_iter().filter(|a| a != "--force").collect();
+    let force = take_flag(&mut args, "--force");
+    let simulate = take_flag(&mut args, "--simulate");
+
+    // Optional client-side allowlist of destinations
+    let recipients = match take_flag_value(&mut args, "--recipients-file") {
+        Ok(Some(path)) => match load_recipients(&path) {
+            Ok(recipients) => Some((path, recipients)),
+            Err(e) => {
+                println!("Error: {}", e);
+                std::process::exit(1);
+            }
+        },
+        Ok(None) => None,
+        Err(e) => {
+            println!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    // Optional token program override; by default the mint's owner is used
+    let token_program_override: Option<Pubkey> = match take_flag_value(&mut args, "--token-program") {
+        Ok(value) => value.map(|v| resolve_pubkey(&v)),
+        Err(e) => {
+            println!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    // --sol replaces the amount argument, shifting the network left
+    let sol = match take_flag_value(&mut args, "--sol").and_then(|value| value.map(|v| parse_sol(&v)).transpose()) {
+        Ok(sol) => sol,
+        Err(e) => {
+            println!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let amount_at = |args: &[String], index: usize| -> (u64, usize) {
+        match sol {
+            Some(lamports) => (lamports, index),
+            None => (args[index].parse().expect("Invalid amount"), index + 1),
+        }
+    };
+    let check_sol_limit = |mint: &Pubkey| {
+        if sol.is_some() && *mint != Pubkey::default() {
+            println!("Error: --sol only applies to SOL spending limits; this one is for mint {}", mint);
+            std::process::exit(1);
+        }
+    };
+    let amount_args = if sol.is_some() { 1 } else { 2 };
+
+    // --multisig and --snapshot replace the spending limit address, shifting the rest left
+    let (multisig_arg, snapshot_arg) = match (
+        take_flag_value(&mut args, "--multisig"),
+        take_flag_value(&mut args, "--snapshot"),
+    ) {
+        (Ok(multisig), Ok(snapshot)) => (multisig, snapshot),
+        (Err(e), _) | (_, Err(e)) => {
+            println!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    reject_unknown_flags_or_exit(&args);
+    if multisig_arg.is_some() && snapshot_arg.is_some() {
+        println!("Error: --multisig and --snapshot can't be combined");
+        std::process::exit(1);
+    }
+
+    let check_allowlist = |destination: &Pubkey| {
+        if let Some((path, recipients)) = &recipients {
+            if !check_recipients(recipients, &[*destination]) {
+                println!("\nAborting: destination must be listed in {}", path);
+                std::process::exit(1);
+            }
+            println!();
+        }
+    };
+
+    // Offline mode: validate against a saved snapshot and report go/no-go without RPC
+    if let Some(snapshot_path) = snapshot_arg {
+        if args.len() < 1 + amount_args {
+            println!("Error: --snapshot requires: <snapshot_path> <destination> <amount>");
+            return;
+        }
+        let (spending_limit_pda, spending_limit) = match read_spending_limit_snapshot(&snapshot_path) {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                println!("Error: {}", e);
+                std::process::exit(1);
+            }
+        };
+        let destination: Pubkey = resolve_pubkey(&args[1]);
+        let (amount, _) = amount_at(&args, 2);
+        check_sol_limit(&spending_limit.mint);
+        check_allowlist(&destination);
+        let member = read_keypair_file("../member1.json").expect("Failed to read member1.json");
+
+        println!("=== Validate Spending Limit Use (snapshot) ===\n");
+        println!("Snapshot: {}", snapshot_path);
+        println!("Spending Limit: {}", spending_limit_pda);
+        println!("Multisig: {}", spending_limit.multisig);
+        println!("Member: {}", member.pubkey());
+        println!("Destination: {}", destination);
+        if spending_limit.mint == Pubkey::default() {
+            println!("Amount: {}", describe_lamports(amount));
+        } else {
+            println!("Amount: {}", amount);
+        }
+        println!();
+
+        // No cluster clock offline; local time is close enough for a period boundary check
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("System clock is before the Unix epoch")
+            .as_secs() as i64;
+
+        if validate_transfer(&spending_limit, &member.pubkey(), &destination, amount, now) {
+            println!("GO: Transfer passes all local checks against the snapshot.");
+            println!("Note: On-chain state may have changed since the snapshot was taken.");
+        } else {
+            println!();
+            println!("NO-GO: Transfer would be rejected.");
+            std::process::exit(1);
+        }
+        return;
+    }
 
     // Parse arguments - handle --multisig flag
-    let (spending_limit_pda, destination, amount, network) = if args.get(1).map(|s| s.as_str()) == Some("--multisig") {
-        if args.len() < 5 {
+    let (spending_limit_pda, destination, amount, network) = if let Some(multisig_arg) = multisig_arg {
+        if args.len() < 1 + amount_args {
             println!("Error: --multisig requires: <multisig_address> <destination> <amount> [mainnet]");
             return;
         }
-        let multisig_pda: Pubkey = args[2].parse().expect("Invalid multisig address");
-        let dest: Pubkey = args[3].parse().expect("Invalid destination address");
-        let amt: u64 = args[4].parse().expect("Invalid amount");
-        let net = args.get(5).map(|s| s.as_str()).unwrap_or("devnet");
+        let multisig_pda: Pubkey = resolve_pubkey(&multisig_arg);
+        let dest: Pubkey = resolve_pubkey(&args[1]);
+        let (amt, next) = amount_at(&args, 2);
+        let net = Network::from_arg(args.get(next).map(|s| s.as_str()).unwrap_or("devnet"));
 
         // Derive spending limit PDA using "combinator" createKey
         let (create_key, _) = Pubkey::find_program_address(
             &[b"combinator"],
             &squads_multisig_program::ID,
         );
-        let (spending_limit, _) = get_spending_limit_pda(&multisig_pda, &create_key, None);
+        let (spending_limit, _) = get_spending_limit_pda(&multisig_pda, &create_key, Some(&program_id()));
         println!("Derived spending limit PDA: {}", spending_limit);
         (spending_limit, dest, amt, net)
     } else {
-        let spending_limit: Pubkey = args[1].parse().expect("Invalid spending limit address");
-        let dest: Pubkey = args[2].parse().expect("Invalid destination address");
-        let amt: u64 = args[3].parse().expect("Invalid amount");
-        let net = args.get(4).map(|s| s.as_str()).unwrap_or("devnet");
+        if args.len() < 2 + amount_args {
+            println!("Error: requires <spending_limit_address> <destination> <amount>");
+            return;
+        }
+        let spending_limit: Pubkey = resolve_pubkey(&args[1]);
+        let dest: Pubkey = resolve_pubkey(&args[2]);
+        let (amt, next) = amount_at(&args, 3);
+        let net = Network::from_arg(args.get(next).map(|s| s.as_str()).unwrap_or("devnet"));
         (spending_limit, dest, amt, net)
     };
 
-    let rpc_url = match network {
-        "mainnet" => MAINNET_RPC,
-        _ => DEVNET_RPC,
-    };
+    check_allowlist(&destination);
+
+    let client = RpcClient::new_with_commitment(network.rpc_url(), CommitmentConfig::confirmed());
 
-    let client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
+    check_program_or_exit(&client);
     let member = read_keypair_file("../member1.json").expect("Failed to read member1.json");
 
     // Fetch the spending limit to get multisig, vault_index, mint, and validate member
@@ -107,36 +331,16 @@ fn main() {
     let vault_index = spending_limit.vault_index;
     let mint = spending_limit.mint;
     let is_sol = mint == Pubkey::default();
+    check_sol_limit(&mint);
 
-    // Validate member is authorized (skip with --force)
-    if !force && !spending_limit.members.contains(&member.pubkey()) {
-        println!("Error: Your wallet {} is not authorized to use this spending limit", member.pubkey());
-        println!();
-        println!("Authorized members:");
-        for m in &spending_limit.members {
-            println!("  - {}", m);
-        }
-        return;
-    }
-
-    // Validate destination if restricted (skip with --force)
-    if !force && !spending_limit.destinations.is_empty() && !spending_limit.destinations.contains(&destination) {
-        println!("Error: Destination {} is not in the allowed destinations list", destination);
-        println!();
-        println!("Allowed destinations:");
-        for d in &spending_limit.destinations {
-            println!("  - {}", d);
-        }
-        return;
-    }
+    // Validate member, destination, and remaining amount (skip with --force to test on-chain validation)
+    // The program resets the limit against the cluster clock, not local time
+    let clock_account = client
+        .get_account(&sysvar::clock::ID)
+        .expect("Failed to fetch clock sysvar");
+    let clock: Clock = from_account(&clock_account).expect("Failed to deserialize clock sysvar");
 
-    // Check remaining amount (skip with --force to test on-chain validation)
-    if !force && amount > spending_limit.remaining_amount {
-        println!("Error: Requested amount {} exceeds remaining limit {}", amount, spending_limit.remaining_amount);
-        if is_sol {
-            println!("  Requested: {:.9} SOL", amount as f64 / LAMPORTS_PER_SOL);
-            println!("  Remaining: {:.9} SOL", spending_limit.remaining_amount as f64 / LAMPORTS_PER_SOL);
-        }
+    if !force && !validate_transfer(&spending_limit, &member.pubkey(), &destination, amount, clock.unix_timestamp) {
         return;
     }
 
@@ -145,9 +349,9 @@ fn main() {
     }
 
     // Derive vault PDA
-    let (vault_pda, _) = get_vault_pda(&multisig_pda, vault_index, None);
+    let (vault_pda, _) = get_vault_pda(&multisig_pda, vault_index, Some(&program_id()));
 
-    println!("=== Use Spending Limit ({}) ===\n", network.to_uppercase());
+    println!("=== Use Spending Limit ({}) ===\n", network.name().to_uppercase());
     println!("Spending Limit: {}", spending_limit_pda);
     println!("Multisig: {}", multisig_pda);
     println!("Vault: {}", vault_pda);
@@ -155,28 +359,91 @@ fn main() {
     println!();
     if is_sol {
         println!("Token: SOL (Native)");
-        println!("Amount: {} lamports ({:.9} SOL)", amount, amount as f64 / LAMPORTS_PER_SOL);
-        let remaining_after = spending_limit.remaining_amount.saturating_sub(amount);
-        println!("Remaining after: {} lamports ({:.9} SOL)",
-            remaining_after,
-            remaining_after as f64 / LAMPORTS_PER_SOL
-        );
+        println!("Amount: {}", describe_lamports(amount));
+        let remaining = available_amount(&spending_limit, clock.unix_timestamp);
+        println!("Remaining: {}", describe_lamports(remaining));
+        println!("Remaining after: {}", describe_lamports(remaining.saturating_sub(amount)));
     } else {
         println!("Mint: {}", mint);
         println!("Amount: {}", amount);
-        println!("Remaining after: {}", spending_limit.remaining_amount.saturating_sub(amount));
+        let remaining = available_amount(&spending_limit, clock.unix_timestamp);
+        println!("Remaining after: {}", remaining.saturating_sub(amount));
     }
     println!("Destination: {}", destination);
     println!("Period: {:?}", spending_limit.period);
 
     // Build the instruction
-    let decimals = if is_sol { 9 } else {
+    // Token mints may belong to SPL Token or Token-2022; ATAs and transfers use the owner
+    if is_sol && token_program_override.is_some() {
+        println!("Error: --token-program only applies to token spending limits, not SOL");
+        std::process::exit(1);
+    }
+    let (decimals, token_program) = if is_sol { (9, spl_token::ID) } else {
         // Fetch mint to get decimals
         let mint_account = client.get_account(&mint).expect("Failed to fetch mint account");
-        // SPL Token mint has decimals at offset 44
-        mint_account.data[44]
+        if let Some(program) = token_program_override {
+            if program != mint_account.owner {
+                println!("WARNING: --token-program {} differs from the mint's owner {}", program, mint_account.owner);
+            }
+        }
+        let (token_program, decimals) =
+            mint_program_and_decimals(&mint, &mint_account.owner, &mint_account.data, token_program_override)
+                .unwrap_or_else(|e| {
+                    println!("Error: {}", e);
+                    std::process::exit(1);
+                });
+        (decimals, token_program)
     };
 
+    // The limit only caps what may be spent; the vault still has to hold it (skip with --force)
+    if !force {
+        let shortfall = if is_sol {
+            let balance = client.get_balance(&vault_pda).expect("Failed to fetch vault balance");
+            println!("Vault Balance: {}", describe_lamports(balance));
+            // The vault is a system account: it can be emptied, but not left below rent-exempt
+            let rent_minimum = client
+                .get_minimum_balance_for_rent_exemption(0)
+                .expect("Failed to fetch rent-exempt minimum");
+            match balance.checked_sub(amount) {
+                None => Some(format!("short by {}", describe_lamports(amount - balance))),
+                Some(left) if left > 0 && left < rent_minimum => Some(format!(
+                    "it would leave {} in the vault, below the rent-exempt minimum of {}; send at most {} or all of it",
+                    describe_lamports(left),
+                    describe_lamports(rent_minimum),
+                    describe_lamports(balance - rent_minimum)
+                )),
+                Some(_) => None,
+            }
+        } else {
+            let vault_token_account = derive_ata(&vault_pda, &mint, &token_program);
+            let balance = match client
+                .get_account_with_commitment(&vault_token_account, client.commitment())
+                .expect("Failed to fetch vault token account")
+                .value
+            {
+                Some(account) => unpack_token_account(&account.data)
+                    .unwrap_or_else(|e| {
+                        println!("Error: could not read vault token account {}: {}", vault_token_account, e);
+                        std::process::exit(1);
+                    })
+                    .amount,
+                None => {
+                    println!("Vault token account {} does not exist", vault_token_account);
+                    0
+                }
+            };
+            println!("Vault Balance: {} ({})", format_amount(balance, decimals, Units::Ui), balance);
+            amount.checked_sub(balance).filter(|short| *short > 0).map(|short| {
+                format!("short by {} ({})", format_amount(short, decimals, Units::Ui), short)
+            })
+        };
+        if let Some(shortfall) = shortfall {
+            println!("\nError: Vault {} can't cover this transfer: {}", vault_pda, shortfall);
+            println!("The spending limit allows it, but the program would reject it on-chain.");
+            std::process::exit(1);
+        }
+    }
+
     let instruction_data = squads_multisig_program::instruction::SpendingLimitUse {
         args: squads_multisig_program::SpendingLimitUseArgs {
             amount,
@@ -198,8 +465,8 @@ fn main() {
         ]
     } else {
         // SPL token transfer accounts
-        let vault_token_account = get_associated_token_address(&vault_pda, &mint);
-        let destination_token_account = get_associated_token_address(&destination, &mint);
+        let vault_token_account = derive_ata(&vault_pda, &mint, &token_program);
+        let destination_token_account = derive_ata(&destination, &mint, &token_program);
 
         vec![
             AccountMeta::new_readonly(multisig_pda, false),
@@ -211,12 +478,12 @@ fn main() {
             AccountMeta::new_readonly(mint, false),
             AccountMeta::new(vault_token_account, false),
             AccountMeta::new(destination_token_account, false),
-            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(token_program, false),
         ]
     };
 
     let spending_limit_ix = Instruction {
-        program_id: squads_multisig_program::ID,
+        program_id: program_id(),
         accounts,
         data: instruction_data.data(),
     };
@@ -225,35 +492,67 @@ fn main() {
     let instructions = if is_sol {
         vec![spending_limit_ix]
     } else {
-        let destination_token_account = get_associated_token_address(&destination, &mint);
+        let destination_token_account = derive_ata(&destination, &mint, &token_program);
         let create_ata_ix = create_associated_token_account_idempotent(
             &member.pubkey(),
             &destination,
             &mint,
-            &spl_token::ID,
+            &token_program,
         );
         println!("Will create destination ATA if needed: {}", destination_token_account);
         vec![create_ata_ix, spending_limit_ix]
     };
 
-    println!("\nExecuting transfer...");
+    println!("\n{}", if simulate { "Simulating transfer..." } else { "Executing transfer..." });
+
+    let (recent_blockhash, last_valid_block_height) = client
+        .get_latest_blockhash_with_commitment(client.commitment())
+        .expect("Failed to get blockhash");
+    let transaction = send_options
+        .build_transaction(&instructions, &member.pubkey(), &[&member], recent_blockhash)
+        .expect("Failed to build transaction");
 
-    let recent_blockhash = client.get_latest_blockhash().expect("Failed to get blockhash");
-    let transaction = Transaction::new_signed_with_payer(
-        &instructions,
-        Some(&member.pubkey()),
-        &[&member],
-        recent_blockhash,
-    );
+    // The program applies period resets and the remaining amount against current chain state,
+    // so this catches a concurrent use or reset since the spending limit was fetched above
+    if simulate {
+        let result = client.simulate_transaction(&transaction).expect("Failed to simulate transaction").value;
+        match result.err {
+            None => {
+                println!("\nGO: The program would accept this transfer.");
+                if let Some(units) = result.units_consumed {
+                    println!("Compute units: {}", units);
+                }
+                println!("Nothing was sent. Rerun without --simulate to transfer.");
+            }
+            Some(e) => {
+                println!("\nNO-GO: The program would reject this transfer: {}", e);
+                println!("\nProgram logs:");
+                for line in result.logs.unwrap_or_default() {
+                    println!("  {}", line);
+                }
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
 
-    match client.send_and_confirm_transaction(&transaction) {
-        Ok(sig) => {
+    match send_and_track(&client, &transaction, &send_options, last_valid_block_height) {
+        Ok(landed) => {
+            let sig = landed.signature;
+            record(network, &multisig_pda, &format!("use spending limit {}: {} to {}", spending_limit_pda, amount, destination), &member.pubkey(), &sig);
             println!("\nTransfer successful!");
             println!("Transaction: {}", sig);
+            println!("Slot: {}", landed.slot);
 
-            let cluster_param = if network == "mainnet" { "" } else { "?cluster=devnet" };
             println!("\nView on Solana Explorer:");
-            println!("https://explorer.solana.com/tx/{}{}", sig, cluster_param);
+            println!("{}", network.explorer_tx_url(&sig));
+        }
+        Err(e @ SendError::Unknown { signature, .. }) => {
+            println!("\n{}", e);
+            println!("{}", network.explorer_tx_url(&signature));
+        }
+        Err(e @ SendError::Dropped { .. }) => {
+            println!("\nTransfer failed: {}", e);
         }
         Err(e) => {
             println!("\nTransfer failed: {}", e);
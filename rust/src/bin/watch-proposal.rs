@@ -0,0 +1,193 @@
+//! Watch a multisig proposal and print every vote as it lands
+//!
+//! Subscribes to the proposal account over WebSocket instead of polling, printing
+//! the approval/rejection counts and status each time the account changes.
+//! Exits once the proposal reaches a terminal state (Rejected, Executed, Cancelled) or its
+//! account is closed.
+//!
+//! Usage:
+//!   cargo run --bin watch-proposal -- <multisig_address> <proposal_index> [mainnet]
+//!
+//! Example:
+//!   cargo run --bin watch-proposal -- BJbRt... 1 mainnet
+
+use futures::StreamExt;
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::nonblocking::{pubsub_client::PubsubClient, rpc_client::RpcClient};
+use solana_client::rpc_config::RpcAccountInfoConfig;
+use solana_sdk::{account::Account, commitment_config::CommitmentConfig, pubkey::Pubkey};
+use squads_multisig::anchor_lang::AccountDeserialize;
+use squads_multisig::pda::get_proposal_pda;
+use squads_multisig::state::{Multisig, Proposal};
+use squads_rust::addressbook::resolve_pubkey;
+use squads_rust::args::{reject_unknown_flags_or_exit, take_help};
+use squads_rust::multisig::parse_multisig;
+use squads_rust::network::Network;
+use squads_rust::program::{check_program_account, is_overridden, program_id, take_program_id_or_exit};
+use squads_rust::proposal::ProposalState;
+use std::env;
+use std::time::Duration;
+
+/// Longest wait between reconnection attempts after the WebSocket drops
+const MAX_RECONNECT_DELAY_SECS: u64 = 30;
+
+/// Print the proposal's current vote counts. Returns true once the proposal is terminal.
+fn report(proposal: &Proposal, multisig: &Multisig, slot: Option<u64>) -> bool {
+    let state = ProposalState::of(proposal);
+    let slot_str = slot.map(|s| format!("[slot {}] ", s)).unwrap_or_default();
+    println!(
+        "{}Approvals: {}/{} | Rejections: {}/{} | Cancels: {} | Status: {}",
+        slot_str,
+        proposal.approved.len(),
+        multisig.threshold,
+        proposal.rejected.len(),
+        multisig.cutoff(),
+        proposal.cancelled.len(),
+        state.label()
+    );
+    state.is_terminal()
+}
+
+fn decode_proposal(account: &Account) -> Option<Proposal> {
+    Proposal::try_deserialize(&mut account.data.as_slice()).ok()
+}
+
+#[tokio::main]
+async fn main() {
+    let mut args: Vec<String> = env::args().collect();
+    let help = take_help(&mut args);
+    take_program_id_or_exit(&mut args);
+    reject_unknown_flags_or_exit(&args);
+
+    if help || args.len() < 3 {
+        println!("Usage: cargo run --bin watch-proposal -- <multisig_address> <proposal_index> [mainnet]");
+        println!();
+        println!("Example:");
+        println!("  cargo run --bin watch-proposal -- BJbRt... 1 mainnet");
+        return;
+    }
+
+    let multisig_pda: Pubkey = resolve_pubkey(&args[1]);
+    let proposal_index: u64 = args[2].parse().expect("Invalid proposal index");
+    let network = Network::from_arg(args.get(3).map(|s| s.as_str()).unwrap_or("devnet"));
+
+    let client = RpcClient::new_with_commitment(network.rpc_url().to_string(), CommitmentConfig::confirmed());
+    if is_overridden() {
+        let account = client
+            .get_account_with_commitment(&program_id(), client.commitment())
+            .await
+            .expect("Failed to fetch --program-id account")
+            .value;
+        if let Err(e) = check_program_account(account.as_ref()) {
+            println!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+
+    let (proposal_pda, _) = get_proposal_pda(&multisig_pda, proposal_index, Some(&program_id()));
+
+    let multisig_account = client
+        .get_account(&multisig_pda)
+        .await
+        .expect("Failed to fetch multisig account");
+    let multisig = parse_multisig(&multisig_pda, &multisig_account).unwrap_or_else(|e| {
+        println!("Error: {}", e);
+        std::process::exit(1);
+    });
+
+    println!("=== Watch Proposal ({}) ===\n", network.name().to_uppercase());
+    println!("Multisig: {}", multisig_pda);
+    println!("Proposal Index: {}", proposal_index);
+    println!("Proposal Address: {}", proposal_pda);
+    println!("Threshold: {} of {}", multisig.threshold, multisig.members.len());
+    println!();
+
+    let config = RpcAccountInfoConfig {
+        encoding: Some(UiAccountEncoding::Base64),
+        commitment: Some(CommitmentConfig::confirmed()),
+        ..RpcAccountInfoConfig::default()
+    };
+
+    let mut reconnect_delay = 1;
+    // Set once the proposal has been seen, after which its account disappearing ends the watch
+    let mut watched = false;
+
+    loop {
+        // (Re)read the current state so updates missed while disconnected aren't lost
+        match client.get_account_with_commitment(&proposal_pda, client.commitment()).await {
+            Ok(response) => match response.value.as_ref().map(decode_proposal) {
+                Some(Some(proposal)) => {
+                    if report(&proposal, &multisig, None) {
+                        println!("\nProposal reached a terminal state.");
+                        return;
+                    }
+                }
+                Some(None) if watched => {
+                    println!("\nProposal account was closed or replaced; nothing left to watch.");
+                    return;
+                }
+                Some(None) => {
+                    println!("Error: Account {} is not a proposal", proposal_pda);
+                    std::process::exit(1);
+                }
+                None if watched => {
+                    println!("\nProposal account was closed; nothing left to watch.");
+                    return;
+                }
+                None => {
+                    println!("Error: Proposal account {} not found. Does this proposal exist?", proposal_pda);
+                    std::process::exit(1);
+                }
+            },
+            Err(e) => {
+                println!("Error: Failed to fetch proposal account {}", proposal_pda);
+                println!("Details: {}", e);
+                std::process::exit(1);
+            }
+        }
+        watched = true;
+
+        let pubsub = match PubsubClient::new(network.ws_url()).await {
+            Ok(pubsub) => pubsub,
+            Err(e) => {
+                println!("WebSocket connection failed: {} (retrying in {}s)", e, reconnect_delay);
+                tokio::time::sleep(Duration::from_secs(reconnect_delay)).await;
+                reconnect_delay = (reconnect_delay * 2).min(MAX_RECONNECT_DELAY_SECS);
+                continue;
+            }
+        };
+
+        let (mut updates, unsubscribe) = match pubsub.account_subscribe(&proposal_pda, Some(config.clone())).await {
+            Ok(subscription) => subscription,
+            Err(e) => {
+                println!("Subscription failed: {} (retrying in {}s)", e, reconnect_delay);
+                tokio::time::sleep(Duration::from_secs(reconnect_delay)).await;
+                reconnect_delay = (reconnect_delay * 2).min(MAX_RECONNECT_DELAY_SECS);
+                continue;
+            }
+        };
+
+        println!("Subscribed. Waiting for votes...");
+        reconnect_delay = 1;
+
+        while let Some(update) = updates.next().await {
+            // A closed account arrives as an empty, system-owned account, which doesn't decode
+            let Some(proposal) = update.value.decode::<Account>().as_ref().and_then(decode_proposal) else {
+                println!("[slot {}] Proposal account was closed; nothing left to watch.", update.context.slot);
+                unsubscribe().await;
+                return;
+            };
+
+            if report(&proposal, &multisig, Some(update.context.slot)) {
+                println!("\nProposal reached a terminal state.");
+                unsubscribe().await;
+                return;
+            }
+        }
+
+        // The stream ends when the WebSocket drops
+        println!("WebSocket disconnected (retrying in {}s)", reconnect_delay);
+        tokio::time::sleep(Duration::from_secs(reconnect_delay)).await;
+        reconnect_delay = (reconnect_delay * 2).min(MAX_RECONNECT_DELAY_SECS);
+    }
+}
@@ -0,0 +1,54 @@
+//! In-run memoization of fetched accounts
+//!
+//! A listing that touches the same multisig or mint once per row would otherwise fetch and
+//! deserialize it each time. `AccountCache` fetches each address at most once per run and keeps
+//! what it parsed. A failure is kept too, so a missing mint isn't refetched for every row.
+
+use crate::multisig::fetch_multisig;
+use crate::token::unpack_mint;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use squads_multisig::state::Multisig;
+use std::collections::HashMap;
+
+/// Accounts fetched through `client` during this run, by address
+pub struct AccountCache<'a> {
+    client: &'a RpcClient,
+    multisigs: HashMap<Pubkey, Result<Multisig, String>>,
+    mint_decimals: HashMap<Pubkey, Result<u8, String>>,
+}
+
+impl<'a> AccountCache<'a> {
+    pub fn new(client: &'a RpcClient) -> Self {
+        AccountCache { client, multisigs: HashMap::new(), mint_decimals: HashMap::new() }
+    }
+
+    pub fn client(&self) -> &'a RpcClient {
+        self.client
+    }
+
+    /// The multisig at `address`, fetched and validated with `fetch_multisig` on first use
+    pub fn multisig(&mut self, address: &Pubkey) -> Result<&Multisig, String> {
+        let client = self.client;
+        self.multisigs
+            .entry(*address)
+            .or_insert_with(|| fetch_multisig(client, address))
+            .as_ref()
+            .map_err(Clone::clone)
+    }
+
+    /// Decimals of `mint` (either token program), fetched on first use
+    pub fn mint_decimals(&mut self, mint: &Pubkey) -> Result<u8, String> {
+        let client = self.client;
+        self.mint_decimals
+            .entry(*mint)
+            .or_insert_with(|| {
+                client
+                    .get_account(mint)
+                    .map_err(|e| e.to_string())
+                    .and_then(|account| unpack_mint(&account.data))
+                    .map(|mint| mint.decimals)
+            })
+            .clone()
+    }
+}
@@ -0,0 +1,14 @@
+//! Interactive confirmation before an action that can't be undone
+//!
+//! Scripts that prompt also take `--yes` to skip the prompt, for scripted runs.
+
+use std::io::{self, BufRead, Write};
+
+/// Ask on stdin; only an explicit "yes" continues
+pub fn confirm(prompt: &str) -> bool {
+    print!("{} Type 'yes' to continue: ", prompt);
+    io::stdout().flush().expect("Failed to flush stdout");
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer).expect("Failed to read confirmation");
+    answer.trim() == "yes"
+}
@@ -0,0 +1,393 @@
+//! Offline decoding of raw instruction data
+//!
+//! Squads instructions are matched on their 8-byte Anchor discriminator and their args
+//! Borsh-deserialized with the program crate's own instruction types, so the decoder follows the
+//! squads-multisig version in Cargo.toml. System, SPL Token / Token-2022, associated token
+//! account, memo and compute budget instructions are decoded from their well-known layouts.
+//! Anything else is reported as unknown rather than guessed at.
+
+use crate::permissions::PermissionsExt;
+use solana_sdk::{
+    compute_budget, program_utils::limited_deserialize, pubkey::Pubkey, system_instruction::SystemInstruction,
+    system_program,
+};
+use spl_token_2022::instruction::{AuthorityType, TokenInstruction};
+use squads_multisig::anchor_lang::{AnchorDeserialize, Discriminator};
+use squads_multisig::squads_multisig_program::instruction as ix;
+use squads_multisig::state::{ConfigAction, Member, Period};
+use std::fmt::Display;
+
+/// A decoded instruction: which program, which instruction, and its arguments in order
+pub struct DecodedInstruction {
+    pub program: &'static str,
+    pub name: String,
+    pub fields: Vec<(&'static str, String)>,
+    /// The serialized vault transaction message carried by `vault_transaction_create` and
+    /// `batch_add_transaction`, for `message::print_compiled_message`
+    pub transaction_message: Option<Vec<u8>>,
+}
+
+impl DecodedInstruction {
+    fn new(program: &'static str, name: impl Into<String>, fields: Vec<(&'static str, String)>) -> Self {
+        DecodedInstruction { program, name: name.into(), fields, transaction_message: None }
+    }
+}
+
+/// Decode `data` as an instruction of `program_id`
+pub fn decode_instruction(program_id: &Pubkey, data: &[u8]) -> Result<DecodedInstruction, String> {
+    if *program_id == crate::program::program_id() {
+        decode_squads(data)
+    } else if *program_id == system_program::ID {
+        decode_system(data)
+    } else if *program_id == spl_token::ID {
+        decode_token("SPL Token", data)
+    } else if *program_id == spl_token_2022::ID {
+        decode_token("Token-2022", data)
+    } else if *program_id == spl_associated_token_account::ID {
+        decode_associated_token_account(data)
+    } else if *program_id == spl_memo::ID || *program_id == spl_memo::v1::ID {
+        let text = std::str::from_utf8(data).map_err(|e| format!("memo is not valid UTF-8: {}", e))?;
+        Ok(DecodedInstruction::new("SPL Memo", "memo", vec![("text", text.to_string())]))
+    } else if *program_id == compute_budget::ID {
+        decode_compute_budget(data)
+    } else {
+        Err(format!("unknown program {}; only Squads, System, SPL Token, Token-2022, ATA, Memo and Compute Budget are decoded", program_id))
+    }
+}
+
+fn optional<T: Display>(value: &Option<T>) -> String {
+    match value {
+        Some(value) => value.to_string(),
+        None => "none".to_string(),
+    }
+}
+
+fn list<T: Display>(values: &[T]) -> String {
+    if values.is_empty() {
+        return "[]".to_string();
+    }
+    let items: Vec<String> = values.iter().map(|value| value.to_string()).collect();
+    format!("[{}]", items.join(", "))
+}
+
+fn member(member: &Member) -> String {
+    format!("{} ({})", member.key, member.permissions.to_string())
+}
+
+fn members(members: &[Member]) -> String {
+    list(&members.iter().map(member).collect::<Vec<_>>())
+}
+
+fn period(period: &Period) -> &'static str {
+    match period {
+        Period::OneTime => "OneTime",
+        Period::Day => "Day",
+        Period::Week => "Week",
+        Period::Month => "Month",
+    }
+}
+
+/// One-line description of a config transaction action
+pub fn format_config_action(action: &ConfigAction) -> String {
+    match action {
+        ConfigAction::AddMember { new_member } => format!("AddMember {}", member(new_member)),
+        ConfigAction::RemoveMember { old_member } => format!("RemoveMember {}", old_member),
+        ConfigAction::ChangeThreshold { new_threshold } => format!("ChangeThreshold {}", new_threshold),
+        ConfigAction::SetTimeLock { new_time_lock } => format!("SetTimeLock {} seconds", new_time_lock),
+        ConfigAction::AddSpendingLimit { create_key, vault_index, mint, amount, period: p, members, destinations } => {
+            format!(
+                "AddSpendingLimit create_key={} vault_index={} mint={} amount={} period={} members={} destinations={}",
+                create_key,
+                vault_index,
+                mint,
+                amount,
+                period(p),
+                list(members),
+                list(destinations)
+            )
+        }
+        ConfigAction::RemoveSpendingLimit { spending_limit } => format!("RemoveSpendingLimit {}", spending_limit),
+        ConfigAction::SetRentCollector { new_rent_collector } => {
+            format!("SetRentCollector {}", optional(new_rent_collector))
+        }
+        _ => "unrecognized config action".to_string(),
+    }
+}
+
+/// Deserialize Anchor instruction `T` from the bytes after the discriminator. Trailing bytes are
+/// an error, since they mean the data isn't what the discriminator says.
+fn args<T: AnchorDeserialize>(name: &str, mut data: &[u8]) -> Result<T, String> {
+    let value = T::deserialize(&mut data).map_err(|e| format!("{}: failed to deserialize args: {}", name, e))?;
+    if !data.is_empty() {
+        return Err(format!("{}: {} unexpected trailing byte(s)", name, data.len()));
+    }
+    Ok(value)
+}
+
+fn decode_squads(data: &[u8]) -> Result<DecodedInstruction, String> {
+    if data.len() < 8 {
+        return Err(format!("Squads instruction data is {} byte(s), shorter than the 8-byte discriminator", data.len()));
+    }
+    let (discriminator, rest) = data.split_at(8);
+    let squads = |name: &str, fields| DecodedInstruction::new("Squads Multisig", name, fields);
+    // Decode `$ty` when its discriminator matches, binding its args for the fields expression
+    macro_rules! try_squads {
+        ($ty:ident, $name:literal, |$a:pat_param| $fields:expr) => {
+            if discriminator == ix::$ty::DISCRIMINATOR {
+                let $a = args::<ix::$ty>($name, rest)?;
+                return Ok(squads($name, $fields));
+            }
+        };
+    }
+
+    try_squads!(ProgramConfigInit, "program_config_init", |ix::ProgramConfigInit { args }| vec![
+        ("authority", args.authority.to_string()),
+        ("multisig_creation_fee", args.multisig_creation_fee.to_string()),
+        ("treasury", args.treasury.to_string()),
+    ]);
+    try_squads!(ProgramConfigSetAuthority, "program_config_set_authority", |ix::ProgramConfigSetAuthority { args }| vec![
+        ("new_authority", args.new_authority.to_string()),
+    ]);
+    try_squads!(
+        ProgramConfigSetMultisigCreationFee,
+        "program_config_set_multisig_creation_fee",
+        |ix::ProgramConfigSetMultisigCreationFee { args }| vec![(
+            "new_multisig_creation_fee",
+            args.new_multisig_creation_fee.to_string()
+        )]
+    );
+    try_squads!(ProgramConfigSetTreasury, "program_config_set_treasury", |ix::ProgramConfigSetTreasury { args }| vec![
+        ("new_treasury", args.new_treasury.to_string()),
+    ]);
+    try_squads!(MultisigCreate, "multisig_create", |ix::MultisigCreate { args }| vec![
+        ("config_authority", optional(&args.config_authority)),
+        ("threshold", args.threshold.to_string()),
+        ("members", members(&args.members)),
+        ("time_lock", args.time_lock.to_string()),
+        ("memo", optional(&args.memo)),
+    ]);
+    try_squads!(MultisigCreateV2, "multisig_create_v2", |ix::MultisigCreateV2 { args }| vec![
+        ("config_authority", optional(&args.config_authority)),
+        ("threshold", args.threshold.to_string()),
+        ("members", members(&args.members)),
+        ("time_lock", args.time_lock.to_string()),
+        ("rent_collector", optional(&args.rent_collector)),
+        ("memo", optional(&args.memo)),
+    ]);
+    try_squads!(MultisigAddMember, "multisig_add_member", |ix::MultisigAddMember { args }| vec![
+        ("new_member", member(&args.new_member)),
+        ("memo", optional(&args.memo)),
+    ]);
+    try_squads!(MultisigRemoveMember, "multisig_remove_member", |ix::MultisigRemoveMember { args }| vec![
+        ("old_member", args.old_member.to_string()),
+        ("memo", optional(&args.memo)),
+    ]);
+    try_squads!(MultisigSetTimeLock, "multisig_set_time_lock", |ix::MultisigSetTimeLock { args }| vec![
+        ("time_lock", args.time_lock.to_string()),
+        ("memo", optional(&args.memo)),
+    ]);
+    try_squads!(MultisigChangeThreshold, "multisig_change_threshold", |ix::MultisigChangeThreshold { args }| vec![
+        ("new_threshold", args.new_threshold.to_string()),
+        ("memo", optional(&args.memo)),
+    ]);
+    try_squads!(MultisigSetConfigAuthority, "multisig_set_config_authority", |ix::MultisigSetConfigAuthority {
+        args,
+    }| vec![
+        ("config_authority", args.config_authority.to_string()),
+        ("memo", optional(&args.memo)),
+    ]);
+    try_squads!(MultisigSetRentCollector, "multisig_set_rent_collector", |ix::MultisigSetRentCollector { args }| vec![
+        ("rent_collector", optional(&args.rent_collector)),
+        ("memo", optional(&args.memo)),
+    ]);
+    try_squads!(MultisigAddSpendingLimit, "multisig_add_spending_limit", |ix::MultisigAddSpendingLimit { args }| vec![
+        ("create_key", args.create_key.to_string()),
+        ("vault_index", args.vault_index.to_string()),
+        ("mint", args.mint.to_string()),
+        ("amount", args.amount.to_string()),
+        ("period", period(&args.period).to_string()),
+        ("members", list(&args.members)),
+        ("destinations", list(&args.destinations)),
+        ("memo", optional(&args.memo)),
+    ]);
+    try_squads!(MultisigRemoveSpendingLimit, "multisig_remove_spending_limit", |ix::MultisigRemoveSpendingLimit {
+        args,
+    }| vec![("memo", optional(&args.memo))]);
+    try_squads!(ConfigTransactionCreate, "config_transaction_create", |ix::ConfigTransactionCreate { args }| {
+        let mut fields: Vec<(&'static str, String)> =
+            args.actions.iter().map(|action| ("action", format_config_action(action))).collect();
+        fields.push(("memo", optional(&args.memo)));
+        fields
+    });
+    try_squads!(ConfigTransactionExecute, "config_transaction_execute", |ix::ConfigTransactionExecute {}| vec![]);
+    if discriminator == ix::VaultTransactionCreate::DISCRIMINATOR {
+        let ix::VaultTransactionCreate { args } = args::<ix::VaultTransactionCreate>("vault_transaction_create", rest)?;
+        let mut decoded = squads(
+            "vault_transaction_create",
+            vec![
+                ("vault_index", args.vault_index.to_string()),
+                ("ephemeral_signers", args.ephemeral_signers.to_string()),
+                ("transaction_message", format!("{} bytes", args.transaction_message.len())),
+                ("memo", optional(&args.memo)),
+            ],
+        );
+        decoded.transaction_message = Some(args.transaction_message);
+        return Ok(decoded);
+    }
+    try_squads!(VaultTransactionExecute, "vault_transaction_execute", |ix::VaultTransactionExecute {}| vec![]);
+    try_squads!(BatchCreate, "batch_create", |ix::BatchCreate { args }| vec![
+        ("vault_index", args.vault_index.to_string()),
+        ("memo", optional(&args.memo)),
+    ]);
+    if discriminator == ix::BatchAddTransaction::DISCRIMINATOR {
+        let ix::BatchAddTransaction { args } = args::<ix::BatchAddTransaction>("batch_add_transaction", rest)?;
+        let mut decoded = squads(
+            "batch_add_transaction",
+            vec![
+                ("ephemeral_signers", args.ephemeral_signers.to_string()),
+                ("transaction_message", format!("{} bytes", args.transaction_message.len())),
+            ],
+        );
+        decoded.transaction_message = Some(args.transaction_message);
+        return Ok(decoded);
+    }
+    try_squads!(BatchExecuteTransaction, "batch_execute_transaction", |ix::BatchExecuteTransaction {}| vec![]);
+    try_squads!(ProposalCreate, "proposal_create", |ix::ProposalCreate { args }| vec![
+        ("transaction_index", args.transaction_index.to_string()),
+        ("draft", args.draft.to_string()),
+    ]);
+    try_squads!(ProposalActivate, "proposal_activate", |ix::ProposalActivate {}| vec![]);
+    try_squads!(ProposalApprove, "proposal_approve", |ix::ProposalApprove { args }| vec![
+        ("memo", optional(&args.memo)),
+    ]);
+    try_squads!(ProposalReject, "proposal_reject", |ix::ProposalReject { args }| vec![("memo", optional(&args.memo))]);
+    try_squads!(ProposalCancel, "proposal_cancel", |ix::ProposalCancel { args }| vec![("memo", optional(&args.memo))]);
+    try_squads!(SpendingLimitUse, "spending_limit_use", |ix::SpendingLimitUse { args }| vec![
+        ("amount", args.amount.to_string()),
+        ("decimals", args.decimals.to_string()),
+        ("memo", optional(&args.memo)),
+    ]);
+    try_squads!(ConfigTransactionAccountsClose, "config_transaction_accounts_close", |ix::ConfigTransactionAccountsClose {}| vec![]);
+    try_squads!(VaultTransactionAccountsClose, "vault_transaction_accounts_close", |ix::VaultTransactionAccountsClose {}| vec![]);
+    try_squads!(
+        VaultBatchTransactionAccountClose,
+        "vault_batch_transaction_account_close",
+        |ix::VaultBatchTransactionAccountClose {}| vec![]
+    );
+    try_squads!(BatchAccountsClose, "batch_accounts_close", |ix::BatchAccountsClose {}| vec![]);
+
+    Err(format!("discriminator {:?} matches no Squads instruction", discriminator))
+}
+
+fn decode_system(data: &[u8]) -> Result<DecodedInstruction, String> {
+    let instruction: SystemInstruction = limited_deserialize(data)
+        .map_err(|e| format!("not a System instruction: {}", e))?;
+    let system = |name: &str, fields| DecodedInstruction::new("System", name, fields);
+    Ok(match instruction {
+        SystemInstruction::CreateAccount { lamports, space, owner } => system(
+            "create_account",
+            vec![("lamports", lamports.to_string()), ("space", space.to_string()), ("owner", owner.to_string())],
+        ),
+        SystemInstruction::Assign { owner } => system("assign", vec![("owner", owner.to_string())]),
+        SystemInstruction::Transfer { lamports } => system("transfer", vec![("lamports", lamports.to_string())]),
+        SystemInstruction::CreateAccountWithSeed { base, seed, lamports, space, owner } => system(
+            "create_account_with_seed",
+            vec![
+                ("base", base.to_string()),
+                ("seed", seed),
+                ("lamports", lamports.to_string()),
+                ("space", space.to_string()),
+                ("owner", owner.to_string()),
+            ],
+        ),
+        SystemInstruction::Allocate { space } => system("allocate", vec![("space", space.to_string())]),
+        SystemInstruction::TransferWithSeed { lamports, from_seed, from_owner } => system(
+            "transfer_with_seed",
+            vec![
+                ("lamports", lamports.to_string()),
+                ("from_seed", from_seed),
+                ("from_owner", from_owner.to_string()),
+            ],
+        ),
+        other => system(&format!("{:?}", other), vec![]),
+    })
+}
+
+fn authority_type(authority_type: &AuthorityType) -> String {
+    format!("{:?}", authority_type)
+}
+
+// Plain `Transfer` is deprecated in favour of `TransferChecked`, but still decoded
+#[allow(deprecated)]
+fn decode_token(program: &'static str, data: &[u8]) -> Result<DecodedInstruction, String> {
+    let instruction =
+        TokenInstruction::unpack(data).map_err(|e| format!("not a {} instruction: {}", program, e))?;
+    let token = |name: &str, fields| DecodedInstruction::new(program, name, fields);
+    Ok(match instruction {
+        TokenInstruction::Transfer { amount } => token("transfer", vec![("amount", amount.to_string())]),
+        TokenInstruction::TransferChecked { amount, decimals } => token(
+            "transfer_checked",
+            vec![("amount", amount.to_string()), ("decimals", decimals.to_string())],
+        ),
+        TokenInstruction::MintTo { amount } => token("mint_to", vec![("amount", amount.to_string())]),
+        TokenInstruction::MintToChecked { amount, decimals } => token(
+            "mint_to_checked",
+            vec![("amount", amount.to_string()), ("decimals", decimals.to_string())],
+        ),
+        TokenInstruction::Burn { amount } => token("burn", vec![("amount", amount.to_string())]),
+        TokenInstruction::BurnChecked { amount, decimals } => token(
+            "burn_checked",
+            vec![("amount", amount.to_string()), ("decimals", decimals.to_string())],
+        ),
+        TokenInstruction::Approve { amount } => token("approve", vec![("amount", amount.to_string())]),
+        TokenInstruction::ApproveChecked { amount, decimals } => token(
+            "approve_checked",
+            vec![("amount", amount.to_string()), ("decimals", decimals.to_string())],
+        ),
+        TokenInstruction::Revoke => token("revoke", vec![]),
+        TokenInstruction::SetAuthority { authority_type: kind, new_authority } => token(
+            "set_authority",
+            vec![
+                ("authority_type", authority_type(&kind)),
+                ("new_authority", Option::<Pubkey>::from(new_authority).map_or("none".to_string(), |key| key.to_string())),
+            ],
+        ),
+        TokenInstruction::CloseAccount => token("close_account", vec![]),
+        TokenInstruction::FreezeAccount => token("freeze_account", vec![]),
+        TokenInstruction::ThawAccount => token("thaw_account", vec![]),
+        TokenInstruction::SyncNative => token("sync_native", vec![]),
+        TokenInstruction::InitializeAccount => token("initialize_account", vec![]),
+        TokenInstruction::InitializeAccount3 { owner } => {
+            token("initialize_account3", vec![("owner", owner.to_string())])
+        }
+        other => token(&format!("{:?}", other), vec![]),
+    })
+}
+
+fn decode_associated_token_account(data: &[u8]) -> Result<DecodedInstruction, String> {
+    let name = match data {
+        // The original instruction had no data at all
+        [] | [0] => "create",
+        [1] => "create_idempotent",
+        [2] => "recover_nested",
+        _ => return Err(format!("unknown associated token account instruction {:?}", data)),
+    };
+    Ok(DecodedInstruction::new("Associated Token Account", name, vec![]))
+}
+
+fn decode_compute_budget(data: &[u8]) -> Result<DecodedInstruction, String> {
+    let budget = |name: &str, fields| DecodedInstruction::new("Compute Budget", name, fields);
+    let u32_at = |data: &[u8]| data.get(1..5).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]));
+    Ok(match data.first() {
+        Some(1) if data.len() == 5 => budget("request_heap_frame", vec![("bytes", u32_at(data).unwrap().to_string())]),
+        Some(2) if data.len() == 5 => budget("set_compute_unit_limit", vec![("units", u32_at(data).unwrap().to_string())]),
+        Some(3) if data.len() == 9 => {
+            let price = u64::from_le_bytes(data[1..9].try_into().unwrap());
+            budget("set_compute_unit_price", vec![("micro_lamports", price.to_string())])
+        }
+        Some(4) if data.len() == 5 => {
+            budget("set_loaded_accounts_data_size_limit", vec![("bytes", u32_at(data).unwrap().to_string())])
+        }
+        _ => return Err(format!("unknown compute budget instruction {:?}", data)),
+    })
+}
@@ -0,0 +1,203 @@
+//! Checking a vault transaction against what an approving member expects it to do
+//!
+//! approve-proposal's `--expect-destination` / `--expect-amount` only mean something if every
+//! inner instruction is accounted for: a matching transfer next to a SetAuthority or an arbitrary
+//! program call must not pass. Each instruction is classified as a transfer, as idempotent ATA
+//! creation for one of the transfers, or as something else, and anything else is a mismatch.
+
+use crate::decode::decode_instruction;
+use crate::token::{derive_ata, is_token_program};
+use solana_sdk::{
+    program_utils::limited_deserialize, pubkey::Pubkey, system_instruction::SystemInstruction, system_program,
+};
+use spl_token::instruction::TokenInstruction;
+use squads_multisig::state::VaultTransactionMessage;
+
+/// A value-moving instruction: a SOL transfer or an SPL token transfer/mint
+pub struct DecodedTransfer {
+    pub description: String,
+    pub destination: Pubkey,
+    pub amount: u64,
+    /// Token mint, when the instruction names it (lets a wallet match its ATA)
+    pub mint: Option<Pubkey>,
+    /// Program that executes the transfer; ATAs are derived with it
+    pub token_program: Pubkey,
+}
+
+/// One inner instruction of a vault transaction, as far as `--expect` checks are concerned
+pub enum InnerAction {
+    Transfer(DecodedTransfer),
+    /// Associated token account creation; allowed only for an account a transfer pays into
+    CreateAta { description: String, ata: Pubkey },
+    /// Anything else, which an expectation on transfers can't vouch for
+    Other { description: String },
+}
+
+impl InnerAction {
+    pub fn description(&self) -> &str {
+        match self {
+            InnerAction::Transfer(transfer) => &transfer.description,
+            InnerAction::CreateAta { description, .. } | InnerAction::Other { description } => description,
+        }
+    }
+}
+
+/// Classify every instruction in `message`, in order.
+///
+/// An instruction whose program or accounts come from an address lookup table can't be read
+/// from the message alone, so it is classified as `Other`.
+pub fn decode_actions(message: &VaultTransactionMessage) -> Vec<InnerAction> {
+    message
+        .instructions
+        .iter()
+        .map(|ix| {
+            let key = |index: u8| message.account_keys.get(usize::from(index)).copied();
+            let Some(program_id) = key(ix.program_id_index) else {
+                return InnerAction::Other {
+                    description: "instruction for a program loaded from an address lookup table".to_string(),
+                };
+            };
+            let accounts: Option<Vec<Pubkey>> = ix.account_indexes.iter().map(|&index| key(index)).collect();
+            let Some(accounts) = accounts else {
+                return InnerAction::Other {
+                    description: format!("instruction for {} using accounts from an address lookup table", program_id),
+                };
+            };
+            classify(&program_id, &accounts, &ix.data)
+        })
+        .collect()
+}
+
+fn classify(program_id: &Pubkey, accounts: &[Pubkey], data: &[u8]) -> InnerAction {
+    let account = |position: usize| accounts.get(position).copied();
+
+    if *program_id == system_program::ID {
+        if let (Ok(SystemInstruction::Transfer { lamports }), Some(from), Some(to)) =
+            (limited_deserialize::<SystemInstruction>(data), account(0), account(1))
+        {
+            return InnerAction::Transfer(DecodedTransfer {
+                description: format!("System transfer of {} lamports from {} to {}", lamports, from, to),
+                destination: to,
+                amount: lamports,
+                mint: None,
+                token_program: system_program::ID,
+            });
+        }
+    } else if is_token_program(program_id) {
+        // Token-2022 shares the legacy instruction layout for these instructions
+        match (TokenInstruction::unpack(data), account(0), account(1), account(2)) {
+            (Ok(TokenInstruction::Transfer { amount }), Some(source), Some(destination), _) => {
+                return InnerAction::Transfer(DecodedTransfer {
+                    description: format!("Token transfer of {} from {} to {}", amount, source, destination),
+                    destination,
+                    amount,
+                    mint: None,
+                    token_program: *program_id,
+                })
+            }
+            (Ok(TokenInstruction::TransferChecked { amount, .. }), Some(source), Some(mint), Some(destination)) => {
+                return InnerAction::Transfer(DecodedTransfer {
+                    description: format!("Token transfer of {} (mint {}) from {} to {}", amount, mint, source, destination),
+                    destination,
+                    amount,
+                    mint: Some(mint),
+                    token_program: *program_id,
+                })
+            }
+            (
+                Ok(TokenInstruction::MintTo { amount } | TokenInstruction::MintToChecked { amount, .. }),
+                Some(mint),
+                Some(destination),
+                _,
+            ) => {
+                return InnerAction::Transfer(DecodedTransfer {
+                    description: format!("Mint {} of {} to {}", amount, mint, destination),
+                    destination,
+                    amount,
+                    mint: Some(mint),
+                    token_program: *program_id,
+                })
+            }
+            _ => {}
+        }
+    } else if *program_id == spl_associated_token_account::ID {
+        // Create (empty data or 0) and CreateIdempotent (1): [payer, ata, wallet, mint, ...]
+        if let (true, Some(ata), Some(wallet)) = (matches!(data, [] | [0] | [1]), account(1), account(2)) {
+            return InnerAction::CreateAta {
+                description: format!("Create associated token account {} for {}", ata, wallet),
+                ata,
+            };
+        }
+    }
+
+    let description = match decode_instruction(program_id, data) {
+        Ok(decoded) => format!("{} {} ({})", decoded.program, decoded.name, program_id),
+        Err(_) => format!("unrecognized instruction for program {}", program_id),
+    };
+    InnerAction::Other { description }
+}
+
+/// Describe why a decoded transfer doesn't match the member's expectation, if it doesn't
+fn transfer_mismatch(
+    transfer: &DecodedTransfer,
+    expect_destination: Option<Pubkey>,
+    expect_amount: Option<u64>,
+) -> Option<String> {
+    if let Some(expected) = expect_destination {
+        // A token transfer to the expected wallet lands in that wallet's ATA
+        let matches_ata = transfer
+            .mint
+            .map(|mint| derive_ata(&expected, &mint, &transfer.token_program) == transfer.destination)
+            .unwrap_or(false);
+        if transfer.destination != expected && !matches_ata {
+            return Some(format!("destination is {}, expected {}", transfer.destination, expected));
+        }
+    }
+    if let Some(expected) = expect_amount {
+        if transfer.amount != expected {
+            return Some(format!("amount is {}, expected {}", transfer.amount, expected));
+        }
+    }
+    None
+}
+
+/// Every reason `actions` don't match the expectation; empty means approving is safe.
+///
+/// There must be at least one transfer, every transfer must match, ATA creation must be for an
+/// account one of the transfers pays into, and any other instruction is refused.
+pub fn expectation_mismatches(
+    actions: &[InnerAction],
+    expect_destination: Option<Pubkey>,
+    expect_amount: Option<u64>,
+) -> Vec<String> {
+    let destinations: Vec<Pubkey> = actions
+        .iter()
+        .filter_map(|action| match action {
+            InnerAction::Transfer(transfer) => Some(transfer.destination),
+            _ => None,
+        })
+        .collect();
+
+    let mut mismatches = Vec::new();
+    if destinations.is_empty() {
+        mismatches.push("proposal contains no transfer to verify".to_string());
+    }
+    for (i, action) in actions.iter().enumerate() {
+        match action {
+            InnerAction::Transfer(transfer) => {
+                if let Some(mismatch) = transfer_mismatch(transfer, expect_destination, expect_amount) {
+                    mismatches.push(format!("instruction #{}: {}", i, mismatch));
+                }
+            }
+            InnerAction::CreateAta { ata, .. } => {
+                if !destinations.contains(ata) {
+                    mismatches.push(format!("instruction #{}: creates {}, which no transfer pays into", i, ata));
+                }
+            }
+            InnerAction::Other { description } => {
+                mismatches.push(format!("instruction #{}: {} is not a transfer", i, description));
+            }
+        }
+    }
+    mismatches
+}
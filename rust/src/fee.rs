@@ -0,0 +1,59 @@
+//! `--fee-estimate`: the lamports a transaction would cost, printed instead of sending it
+//!
+//! The network fee comes from `getFeeForMessage`, so it reflects the signature count and the
+//! cluster's current fee. There is no priority fee unless a `--fee-profile` sets a compute unit
+//! price; then it's the most that profile can cost (its price on its whole limit). Rent
+//! for accounts the transaction creates is added per account; the rent payer gets it back when
+//! those accounts are closed.
+
+use solana_client::client_error::ClientError;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{instruction::Instruction, message::Message, pubkey::Pubkey};
+
+/// Cost breakdown of one transaction
+pub struct FeeEstimate {
+    pub signatures: u8,
+    pub network_fee: u64,
+    /// Upper bound of the `--fee-profile` priority fee, 0 without one
+    pub priority_fee: u64,
+    /// (label, account size, rent-exempt lamports) per created account
+    pub rent: Vec<(String, usize, u64)>,
+}
+
+impl FeeEstimate {
+    /// Estimate the network fee for sending `instructions` with `payer`
+    #[allow(clippy::result_large_err)]
+    pub fn new(client: &RpcClient, instructions: &[Instruction], payer: &Pubkey) -> Result<Self, ClientError> {
+        let blockhash = client.get_latest_blockhash()?;
+        let message = Message::new_with_blockhash(instructions, Some(payer), &blockhash);
+        let network_fee = client.get_fee_for_message(&message)?;
+        Ok(FeeEstimate { signatures: message.header.num_required_signatures, network_fee, priority_fee: 0, rent: Vec::new() })
+    }
+
+    /// Add the rent for a new account of `size` bytes
+    #[allow(clippy::result_large_err)]
+    pub fn add_rent(&mut self, client: &RpcClient, label: &str, size: usize) -> Result<(), ClientError> {
+        let lamports = client.get_minimum_balance_for_rent_exemption(size)?;
+        self.rent.push((label.to_string(), size, lamports));
+        Ok(())
+    }
+
+    pub fn total(&self) -> u64 {
+        self.network_fee + self.priority_fee + self.rent.iter().map(|(_, _, lamports)| lamports).sum::<u64>()
+    }
+
+    pub fn print(&self) {
+        println!("=== Fee Estimate ===");
+        println!("Network Fee: {} lamports ({} signature(s))", self.network_fee, self.signatures);
+        if self.priority_fee == 0 {
+            println!("Priority Fee: 0 lamports (no compute unit price is set)");
+        } else {
+            println!("Priority Fee: up to {} lamports (--fee-profile)", self.priority_fee);
+        }
+        for (label, size, lamports) in &self.rent {
+            println!("Rent, {}: {} lamports ({} bytes, refunded when closed)", label, lamports, size);
+        }
+        let total = self.total();
+        println!("Total: {} lamports ({:.9} SOL)", total, total as f64 / 1_000_000_000.0);
+    }
+}
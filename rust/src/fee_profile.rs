@@ -0,0 +1,157 @@
+//! `--fee-profile <path>` / `--profile <name>`: shared compute budget presets
+//!
+//! The file is JSON mapping a profile name (`low`, `normal` or `turbo`) to a compute unit limit
+//! and price (micro-lamports per unit), so a team can keep one file and every operator picks a
+//! profile by name instead of passing raw numbers:
+//!
+//! ```json
+//! {
+//!   "low":    { "cu_limit": 200000, "cu_price": 1000 },
+//!   "normal": { "cu_limit": 200000, "cu_price": 50000 },
+//!   "turbo":  { "cu_limit": 400000, "cu_price": 1000000 }
+//! }
+//! ```
+//!
+//! Not every name has to be defined, but the selected one does; `--profile` defaults to `normal`.
+//! The flags are taken with the send options, and the selected profile's `SetComputeUnitLimit`
+//! and `SetComputeUnitPrice` are prepended to every transaction the script sends. A limit the
+//! script computes itself (e.g. execute-proposal's `--compute-unit-limit`) is kept, but capped
+//! at the profile's limit.
+
+use crate::args::take_flag_value;
+use serde::Deserialize;
+use solana_sdk::{
+    compute_budget::{self, ComputeBudgetInstruction},
+    instruction::Instruction,
+};
+use std::collections::BTreeMap;
+
+/// Profile names a file may define
+pub const PROFILE_NAMES: [&str; 3] = ["low", "normal", "turbo"];
+const DEFAULT_PROFILE: &str = "normal";
+/// `ComputeBudgetInstruction` tags of SetComputeUnitLimit and SetComputeUnitPrice
+const SET_COMPUTE_UNIT_LIMIT_TAG: u8 = 2;
+const SET_COMPUTE_UNIT_PRICE_TAG: u8 = 3;
+/// Largest compute unit limit a transaction can request
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+/// One named compute budget
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct FeeProfile {
+    pub cu_limit: u32,
+    /// Micro-lamports per compute unit
+    pub cu_price: u64,
+}
+
+impl FeeProfile {
+    /// Most the priority fee can cost, in lamports (the price is charged on the requested limit)
+    pub fn max_priority_fee(&self) -> u64 {
+        (self.cu_limit as u128 * self.cu_price as u128).div_ceil(1_000_000) as u64
+    }
+
+    /// A limit the script computed itself, capped at this profile's limit
+    pub fn cap(&self, units: u32) -> u32 {
+        units.min(self.cu_limit)
+    }
+
+    /// `instructions` with this profile's compute budget put first. A limit or price the script
+    /// already set is kept (a transaction can't set either twice), with the limit capped.
+    pub fn apply(&self, instructions: &[Instruction]) -> Vec<Instruction> {
+        let tag = |ix: &Instruction| (ix.program_id == compute_budget::ID).then(|| ix.data.first().copied()).flatten();
+        let sets = |wanted: u8| instructions.iter().any(|ix| tag(ix) == Some(wanted));
+
+        let mut applied = Vec::with_capacity(instructions.len() + 2);
+        if !sets(SET_COMPUTE_UNIT_LIMIT_TAG) {
+            applied.push(ComputeBudgetInstruction::set_compute_unit_limit(self.cu_limit));
+        }
+        if !sets(SET_COMPUTE_UNIT_PRICE_TAG) {
+            applied.push(ComputeBudgetInstruction::set_compute_unit_price(self.cu_price));
+        }
+        for ix in instructions {
+            match (tag(ix), ix.data.get(1..5)) {
+                (Some(SET_COMPUTE_UNIT_LIMIT_TAG), Some(units)) => {
+                    let units = u32::from_le_bytes(units.try_into().unwrap());
+                    applied.push(ComputeBudgetInstruction::set_compute_unit_limit(self.cap(units)));
+                }
+                _ => applied.push(ix.clone()),
+            }
+        }
+        applied
+    }
+}
+
+/// Parse a fee profile file and select `name` from it
+pub fn parse_fee_profile(json: &str, name: &str) -> Result<FeeProfile, String> {
+    if !PROFILE_NAMES.contains(&name) {
+        return Err(format!("unknown --profile '{}' (expected {})", name, PROFILE_NAMES.join(", ")));
+    }
+    let profiles: BTreeMap<String, FeeProfile> =
+        serde_json::from_str(json).map_err(|e| format!("invalid fee profile file: {}", e))?;
+    if let Some(unknown) = profiles.keys().find(|key| !PROFILE_NAMES.contains(&key.as_str())) {
+        return Err(format!(
+            "invalid fee profile file: unknown profile '{}' (expected {})",
+            unknown,
+            PROFILE_NAMES.join(", ")
+        ));
+    }
+    for (key, profile) in &profiles {
+        if profile.cu_limit == 0 || profile.cu_limit > MAX_COMPUTE_UNIT_LIMIT {
+            return Err(format!(
+                "invalid fee profile file: '{}' cu_limit {} must be 1-{}",
+                key, profile.cu_limit, MAX_COMPUTE_UNIT_LIMIT
+            ));
+        }
+    }
+    profiles.get(name).copied().ok_or_else(|| {
+        let defined: Vec<&str> = profiles.keys().map(|key| key.as_str()).collect();
+        format!("fee profile file has no '{}' profile (defined: {})", name, defined.join(", "))
+    })
+}
+
+/// Remove `--fee-profile <path>` and `--profile <name>` from `args` and load the selected profile
+pub fn take_fee_profile(args: &mut Vec<String>) -> Result<Option<FeeProfile>, String> {
+    let path = take_flag_value(args, "--fee-profile")?;
+    let name = take_flag_value(args, "--profile")?;
+    let Some(path) = path else {
+        return match name {
+            Some(_) => Err("--profile requires --fee-profile <path>".to_string()),
+            None => Ok(None),
+        };
+    };
+    let json = std::fs::read_to_string(&path).map_err(|e| format!("failed to read fee profile {}: {}", path, e))?;
+    parse_fee_profile(&json, name.as_deref().unwrap_or(DEFAULT_PROFILE))
+        .map(Some)
+        .map_err(|e| format!("{} ({})", e, path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::{pubkey::Pubkey, system_instruction};
+
+    #[test]
+    fn fee_profiles_validate_and_cap_script_limits() {
+        let file = r#"{ "low": { "cu_limit": 200000, "cu_price": 1000 }, "turbo": { "cu_limit": 400000, "cu_price": 1000000 } }"#;
+        let turbo = parse_fee_profile(file, "turbo").unwrap();
+        assert_eq!((turbo.cu_limit, turbo.cu_price), (400_000, 1_000_000));
+        assert_eq!(turbo.max_priority_fee(), 400_000);
+        assert!(parse_fee_profile(file, "normal").unwrap_err().contains("no 'normal' profile"));
+        assert!(parse_fee_profile(file, "fast").unwrap_err().contains("unknown --profile"));
+        assert!(parse_fee_profile(r#"{ "fast": { "cu_limit": 1, "cu_price": 1 } }"#, "low").is_err());
+        assert!(parse_fee_profile(r#"{ "low": { "cu_limit": 0, "cu_price": 1 } }"#, "low").is_err());
+        assert!(parse_fee_profile(r#"{ "low": { "cu_limit": 1 } }"#, "low").is_err());
+
+        // The profile's budget goes first; a limit the script set itself is kept but capped
+        let transfer = system_instruction::transfer(&Pubkey::new_unique(), &Pubkey::new_unique(), 1);
+        let applied = turbo.apply(std::slice::from_ref(&transfer));
+        assert_eq!(applied.len(), 3);
+        assert_eq!(applied[0], ComputeBudgetInstruction::set_compute_unit_limit(400_000));
+        assert_eq!(applied[1], ComputeBudgetInstruction::set_compute_unit_price(1_000_000));
+        assert_eq!(applied[2], transfer);
+        let applied = turbo.apply(&[ComputeBudgetInstruction::set_compute_unit_limit(1_400_000), transfer]);
+        assert_eq!(applied.len(), 3);
+        assert_eq!(applied[0], ComputeBudgetInstruction::set_compute_unit_price(1_000_000));
+        assert_eq!(applied[1], ComputeBudgetInstruction::set_compute_unit_limit(400_000));
+    }
+}
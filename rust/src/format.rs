@@ -0,0 +1,205 @@
+//! Human-readable formatting for values read from on-chain accounts
+
+use crate::args::take_flag_value;
+
+/// Decimals of native SOL (lamports per SOL = 10^9)
+pub const SOL_DECIMALS: u8 = 9;
+
+/// How inspectors print monetary amounts when `--units` is given
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Units {
+    /// Integer smallest units (lamports, token base units), for scripting
+    Raw,
+    /// Decimal amounts scaled by the mint's decimals
+    Ui,
+}
+
+impl Units {
+    /// Remove `--units raw|ui` from `args`. None means the inspector's default output.
+    pub fn take_from(args: &mut Vec<String>) -> Result<Option<Units>, String> {
+        match take_flag_value(args, "--units")?.as_deref() {
+            Some("raw") => Ok(Some(Units::Raw)),
+            Some("ui") => Ok(Some(Units::Ui)),
+            Some(other) => Err(format!("--units must be 'raw' or 'ui', got '{}'", other)),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Format `raw` smallest units as an integer or as a decimal with exactly `decimals` places.
+/// Works on the digit string rather than floats so large amounts don't lose precision.
+pub fn format_amount(raw: u64, decimals: u8, units: Units) -> String {
+    let digits = raw.to_string();
+    let decimals = decimals as usize;
+    match units {
+        Units::Raw => digits,
+        Units::Ui if decimals == 0 => digits,
+        Units::Ui => {
+            let padded = format!("{:0>width$}", digits, width = decimals + 1);
+            let (whole, fraction) = padded.split_at(padded.len() - decimals);
+            format!("{}.{}", whole, fraction)
+        }
+    }
+}
+
+/// Parse a decimal SOL amount such as `0.1` into lamports. Done on the digits, so it is exact:
+/// more than 9 decimal places, a sign, or more than `u64::MAX` lamports is an error.
+pub fn parse_sol(value: &str) -> Result<u64, String> {
+    let invalid = || format!("invalid SOL amount '{}'", value);
+    let (whole, fraction) = value.trim().split_once('.').unwrap_or((value.trim(), ""));
+    if whole.is_empty() && fraction.is_empty() {
+        return Err(invalid());
+    }
+    if !whole.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    if fraction.len() > SOL_DECIMALS as usize {
+        return Err(format!("SOL amount '{}' has more than {} decimal places", value, SOL_DECIMALS));
+    }
+    let digits = format!("{}{:0<width$}", whole, fraction, width = SOL_DECIMALS as usize);
+    digits.parse().map_err(|_| format!("SOL amount '{}' is too large", value))
+}
+
+/// A lamport amount in both units, e.g. `100000000 lamports = 0.1 SOL`
+pub fn describe_lamports(lamports: u64) -> String {
+    let sol = format_amount(lamports, SOL_DECIMALS, Units::Ui);
+    format!("{} lamports = {} SOL", lamports, sol.trim_end_matches('0').trim_end_matches('.'))
+}
+
+/// Percentage of a spending limit's `amount` already spent in the current period, in [0, 100].
+///
+/// A `remaining_amount` above `amount` (which the program shouldn't produce, but a hand-edited
+/// snapshot can hold) counts as nothing spent, and a zero `amount` reports 0%.
+pub fn usage_percent(amount: u64, remaining_amount: u64) -> f64 {
+    if amount == 0 {
+        return 0.0;
+    }
+    let used = amount.saturating_sub(remaining_amount);
+    (used as f64 / amount as f64 * 100.0).clamp(0.0, 100.0)
+}
+
+/// Longest progress bar `progress_bar` draws; larger totals are scaled down
+const MAX_BAR_WIDTH: usize = 20;
+
+/// Text progress bar for `count` of `total` votes, e.g. `[███░░] 3/5`
+pub fn progress_bar(count: usize, total: usize) -> String {
+    let width = total.clamp(1, MAX_BAR_WIDTH);
+    let filled = (count.min(total) * width).checked_div(total).unwrap_or(width);
+    format!("[{}{}] {}/{}", "█".repeat(filled), "░".repeat(width - filled), count, total)
+}
+
+/// Format a unix timestamp (seconds) as a UTC datetime, e.g. `2024-05-01 12:34:56 UTC`
+pub fn format_unix_timestamp(timestamp: i64) -> String {
+    let days = timestamp.div_euclid(86_400);
+    let seconds_of_day = timestamp.rem_euclid(86_400);
+
+    // Convert days since 1970-01-01 to a civil (proleptic Gregorian) date
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        seconds_of_day / 3_600,
+        (seconds_of_day % 3_600) / 60,
+        seconds_of_day % 60
+    )
+}
+
+/// Parse a UTC date `YYYY-MM-DD` or datetime `YYYY-MM-DDTHH:MM:SS` (optional trailing `Z`, a
+/// space instead of `T` also works) into a unix timestamp
+pub fn parse_date(value: &str) -> Result<i64, String> {
+    let invalid = || format!("invalid date '{}' (expected YYYY-MM-DD or YYYY-MM-DDTHH:MM:SS, UTC)", value);
+    let trimmed = value.trim().trim_end_matches('Z');
+    let (date, time) = match trimmed.split_once(['T', ' ']) {
+        Some((date, time)) => (date, Some(time)),
+        None => (trimmed, None),
+    };
+
+    let parts: Vec<&str> = date.split('-').collect();
+    let [year, month, day] = parts[..] else { return Err(invalid()) };
+    let year: i64 = year.parse().map_err(|_| invalid())?;
+    let month: i64 = month.parse().map_err(|_| invalid())?;
+    let day: i64 = day.parse().map_err(|_| invalid())?;
+    let days_in_month = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        _ => return Err(invalid()),
+    };
+    if !(1..=days_in_month).contains(&day) {
+        return Err(invalid());
+    }
+
+    let seconds_of_day = match time {
+        Some(time) => {
+            let parts: Vec<&str> = time.split(':').collect();
+            let [hours, minutes, seconds] = parts[..] else { return Err(invalid()) };
+            let hours: i64 = hours.parse().map_err(|_| invalid())?;
+            let minutes: i64 = minutes.parse().map_err(|_| invalid())?;
+            let seconds: i64 = seconds.parse().map_err(|_| invalid())?;
+            if !(0..24).contains(&hours) || !(0..60).contains(&minutes) || !(0..60).contains(&seconds) {
+                return Err(invalid());
+            }
+            hours * 3_600 + minutes * 60 + seconds
+        }
+        None => 0,
+    };
+
+    // Days since 1970-01-01 from a civil date, the inverse of format_unix_timestamp
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let shifted_month = if month > 2 { month - 3 } else { month + 9 };
+    let day_of_year = (153 * shifted_month + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+
+    Ok(days * 86_400 + seconds_of_day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TIMESTAMP: i64 = 1_700_000_000;
+
+    #[test]
+    fn dates_parse_as_utc_and_round_trip() {
+        assert_eq!(parse_date("1970-01-01"), Ok(0));
+        assert_eq!(parse_date("2023-11-14T22:13:20Z"), Ok(TIMESTAMP));
+        assert_eq!(parse_date("2023-11-14 22:13:20"), Ok(TIMESTAMP));
+        assert_eq!(format_unix_timestamp(parse_date("2024-02-29").unwrap()), "2024-02-29 00:00:00 UTC");
+        assert!(parse_date("2023-02-29").is_err());
+        assert!(parse_date("2024-13-01").is_err());
+        assert!(parse_date("2024-01-01T24:00:00").is_err());
+        assert!(parse_date("yesterday").is_err());
+    }
+
+    #[test]
+    fn sol_amounts_parse_exactly() {
+        assert_eq!(parse_sol("0.1"), Ok(100_000_000));
+        assert_eq!(parse_sol("1"), Ok(1_000_000_000));
+        assert_eq!(parse_sol(".5"), Ok(500_000_000));
+        assert_eq!(parse_sol("0.000000001"), Ok(1));
+        assert_eq!(parse_sol("18446744073.709551615"), Ok(u64::MAX));
+        assert!(parse_sol("18446744073.709551616").is_err());
+        assert!(parse_sol("0.0000000001").is_err());
+        assert!(parse_sol("-1").is_err());
+        assert!(parse_sol("1e9").is_err());
+        assert!(parse_sol(".").is_err());
+        assert_eq!(describe_lamports(100_000_000), "100000000 lamports = 0.1 SOL");
+        assert_eq!(describe_lamports(10_000_000_000), "10000000000 lamports = 10 SOL");
+        assert_eq!(describe_lamports(0), "0 lamports = 0 SOL");
+    }
+}
@@ -0,0 +1,426 @@
+//! Minimal Anchor IDL support for building instructions from JSON
+//!
+//! Reads both the legacy IDL format (`isMut`/`isSigner`, `publicKey`, `{"defined": "Name"}`)
+//! and the 0.30+ format (`writable`/`signer`, `pubkey`, explicit `discriminator`). Args are
+//! Borsh-encoded from a JSON object and checked against the IDL types, reporting the path of
+//! the first mismatch (e.g. `args.config.fee: expected u16, got "abc"`).
+
+use serde_json::{Map, Value};
+use solana_sdk::{hash::hashv, instruction::AccountMeta, pubkey::Pubkey};
+use std::fs;
+
+/// A parsed IDL file
+pub struct Idl {
+    value: Value,
+}
+
+/// Account expected by an IDL instruction, in order (composite accounts flattened)
+pub struct IdlAccount {
+    pub name: String,
+    pub writable: bool,
+    pub signer: bool,
+    pub optional: bool,
+    /// Fixed address given by the IDL (0.30+ format), used when the caller omits the account
+    pub address: Option<Pubkey>,
+}
+
+/// An instruction selected from the IDL
+pub struct IdlInstruction<'a> {
+    pub name: String,
+    pub discriminator: [u8; 8],
+    pub args: Vec<(String, &'a Value)>,
+    pub accounts: Vec<IdlAccount>,
+}
+
+/// Convert camelCase to snake_case, so `--method` and account names match either IDL format
+fn snake_case(name: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 {
+                out.push('_');
+            }
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn describe(value: &Value) -> String {
+    let text = value.to_string();
+    if text.chars().count() > 40 {
+        format!("{}...", text.chars().take(40).collect::<String>())
+    } else {
+        text
+    }
+}
+
+fn mismatch(path: &str, expected: &str, value: &Value) -> String {
+    format!("{}: expected {}, got {}", path, expected, describe(value))
+}
+
+fn flag(account: &Value, legacy: &str, current: &str) -> bool {
+    account.get(current).or_else(|| account.get(legacy)).and_then(Value::as_bool).unwrap_or(false)
+}
+
+fn flatten_accounts(accounts: &[Value], out: &mut Vec<IdlAccount>) -> Result<(), String> {
+    for account in accounts {
+        let name = account.get("name").and_then(Value::as_str).ok_or("IDL account without a name")?;
+        // Composite account groups list their own accounts, which Anchor passes inline
+        if let Some(nested) = account.get("accounts").and_then(Value::as_array) {
+            flatten_accounts(nested, out)?;
+            continue;
+        }
+        let address = match account.get("address").and_then(Value::as_str) {
+            Some(address) => Some(
+                address
+                    .parse()
+                    .map_err(|_| format!("IDL account {} has an invalid address {}", name, address))?,
+            ),
+            None => None,
+        };
+        out.push(IdlAccount {
+            name: name.to_string(),
+            writable: flag(account, "isMut", "writable"),
+            signer: flag(account, "isSigner", "signer"),
+            optional: flag(account, "isOptional", "optional"),
+            address,
+        });
+    }
+    Ok(())
+}
+
+impl Idl {
+    /// Read and parse an IDL JSON file
+    pub fn load(path: &str) -> Result<Idl, String> {
+        let contents = fs::read_to_string(path).map_err(|e| format!("Failed to read IDL {}: {}", path, e))?;
+        let value: Value =
+            serde_json::from_str(&contents).map_err(|e| format!("Failed to parse IDL {}: {}", path, e))?;
+        if !value.get("instructions").map(Value::is_array).unwrap_or(false) {
+            return Err(format!("{} is not an Anchor IDL (no instructions array)", path));
+        }
+        Ok(Idl { value })
+    }
+
+    /// Program address recorded in the IDL, if any (`address` or legacy `metadata.address`)
+    pub fn program_id(&self) -> Option<Pubkey> {
+        self.value
+            .get("address")
+            .or_else(|| self.value.get("metadata").and_then(|m| m.get("address")))
+            .and_then(Value::as_str)
+            .and_then(|s| s.parse().ok())
+    }
+
+    /// Find the instruction called `method` (camelCase or snake_case)
+    pub fn instruction(&self, method: &str) -> Result<IdlInstruction<'_>, String> {
+        let instructions = self.value["instructions"].as_array().expect("checked in load");
+        let wanted = snake_case(method);
+        let instruction = instructions
+            .iter()
+            .find(|ix| ix.get("name").and_then(Value::as_str).map(snake_case).as_deref() == Some(&wanted))
+            .ok_or_else(|| {
+                let names: Vec<&str> = instructions.iter().filter_map(|ix| ix.get("name")?.as_str()).collect();
+                format!("IDL has no instruction '{}'. Available: {}", method, names.join(", "))
+            })?;
+        let name = instruction["name"].as_str().expect("matched by name").to_string();
+
+        // 0.30+ IDLs list the discriminator; legacy ones use sha256("global:<snake_name>")[..8]
+        let discriminator = match instruction.get("discriminator").and_then(Value::as_array) {
+            Some(bytes) => {
+                let bytes: Vec<u8> = bytes.iter().filter_map(|b| b.as_u64().map(|b| b as u8)).collect();
+                bytes
+                    .try_into()
+                    .map_err(|_| format!("instruction {} has a malformed discriminator", name))?
+            }
+            None => {
+                let hash = hashv(&[format!("global:{}", snake_case(&name)).as_bytes()]);
+                hash.to_bytes()[..8].try_into().expect("hash is 32 bytes")
+            }
+        };
+
+        let args = instruction
+            .get("args")
+            .and_then(Value::as_array)
+            .map(|args| {
+                args.iter()
+                    .map(|arg| {
+                        let arg_name = arg.get("name").and_then(Value::as_str).ok_or("IDL arg without a name")?;
+                        let ty = arg.get("type").ok_or_else(|| format!("IDL arg {} has no type", arg_name))?;
+                        Ok((arg_name.to_string(), ty))
+                    })
+                    .collect::<Result<Vec<_>, String>>()
+            })
+            .transpose()?
+            .unwrap_or_default();
+
+        let mut accounts = Vec::new();
+        if let Some(list) = instruction.get("accounts").and_then(Value::as_array) {
+            flatten_accounts(list, &mut accounts)?;
+        }
+
+        Ok(IdlInstruction { name, discriminator, args, accounts })
+    }
+
+    /// Discriminator followed by the Borsh encoding of `args` (a JSON object keyed by arg name)
+    pub fn encode_instruction_data(&self, instruction: &IdlInstruction, args: &Value) -> Result<Vec<u8>, String> {
+        let provided = args.as_object().ok_or_else(|| mismatch("args", "a JSON object", args))?;
+        if let Some(unknown) = provided
+            .keys()
+            .find(|key| !instruction.args.iter().any(|(name, _)| snake_case(name) == snake_case(key)))
+        {
+            return Err(format!("args.{}: not an argument of {}", unknown, instruction.name));
+        }
+
+        let mut data = instruction.discriminator.to_vec();
+        for (name, ty) in &instruction.args {
+            let path = format!("args.{}", name);
+            let value = value_or_none(lookup(provided, name), ty).ok_or_else(|| format!("{}: missing", path))?;
+            self.encode(ty, value, &path, &mut data)?;
+        }
+        Ok(data)
+    }
+
+    fn type_definition(&self, name: &str, path: &str) -> Result<&Value, String> {
+        self.value
+            .get("types")
+            .and_then(Value::as_array)
+            .and_then(|types| types.iter().find(|t| t.get("name").and_then(Value::as_str) == Some(name)))
+            .and_then(|t| t.get("type"))
+            .ok_or_else(|| format!("{}: type {} is not defined in the IDL", path, name))
+    }
+
+    fn encode(&self, ty: &Value, value: &Value, path: &str, out: &mut Vec<u8>) -> Result<(), String> {
+        if let Some(primitive) = ty.as_str() {
+            return encode_primitive(primitive, value, path, out);
+        }
+
+        if let Some(inner) = ty.get("option") {
+            if value.is_null() {
+                out.push(0);
+                return Ok(());
+            }
+            out.push(1);
+            return self.encode(inner, value, path, out);
+        }
+        if let Some(inner) = ty.get("vec") {
+            let items = value.as_array().ok_or_else(|| mismatch(path, "an array", value))?;
+            out.extend_from_slice(&(items.len() as u32).to_le_bytes());
+            for (i, item) in items.iter().enumerate() {
+                self.encode(inner, item, &format!("{}[{}]", path, i), out)?;
+            }
+            return Ok(());
+        }
+        if let Some(array) = ty.get("array").and_then(Value::as_array) {
+            let (inner, len) = match array.as_slice() {
+                [inner, len] => (inner, len.as_u64().ok_or_else(|| format!("{}: unsupported array length", path))?),
+                _ => return Err(format!("{}: malformed array type in IDL", path)),
+            };
+            let items = value.as_array().ok_or_else(|| mismatch(path, "an array", value))?;
+            if items.len() as u64 != len {
+                return Err(format!("{}: expected {} elements, got {}", path, len, items.len()));
+            }
+            for (i, item) in items.iter().enumerate() {
+                self.encode(inner, item, &format!("{}[{}]", path, i), out)?;
+            }
+            return Ok(());
+        }
+        if let Some(defined) = ty.get("defined") {
+            let name = defined
+                .as_str()
+                .or_else(|| defined.get("name").and_then(Value::as_str))
+                .ok_or_else(|| format!("{}: malformed defined type in IDL", path))?;
+            let definition = self.type_definition(name, path)?;
+            return self.encode_defined(name, definition, value, path, out);
+        }
+
+        Err(format!("{}: unsupported IDL type {}", path, ty))
+    }
+
+    fn encode_defined(&self, name: &str, definition: &Value, value: &Value, path: &str, out: &mut Vec<u8>) -> Result<(), String> {
+        match definition.get("kind").and_then(Value::as_str) {
+            Some("struct") => {
+                let fields = definition.get("fields").and_then(Value::as_array).cloned().unwrap_or_default();
+                self.encode_fields(name, &fields, value, path, out)
+            }
+            Some("enum") => {
+                let variants = definition
+                    .get("variants")
+                    .and_then(Value::as_array)
+                    .ok_or_else(|| format!("{}: enum {} has no variants in IDL", path, name))?;
+                // Unit variants are given as a string, others as {"Variant": fields}
+                let (variant_name, fields_value) = match value {
+                    Value::String(variant) => (variant.as_str(), None),
+                    Value::Object(map) if map.len() == 1 => {
+                        let (variant, fields) = map.iter().next().expect("one entry");
+                        (variant.as_str(), Some(fields))
+                    }
+                    _ => return Err(mismatch(path, &format!("a {} variant", name), value)),
+                };
+                let index = variants
+                    .iter()
+                    .position(|v| v.get("name").and_then(Value::as_str) == Some(variant_name))
+                    .ok_or_else(|| {
+                        let names: Vec<&str> = variants.iter().filter_map(|v| v.get("name")?.as_str()).collect();
+                        format!("{}: {} is not a {} variant ({})", path, variant_name, name, names.join(", "))
+                    })?;
+                out.push(index as u8);
+
+                let fields = variants[index].get("fields").and_then(Value::as_array).cloned().unwrap_or_default();
+                let variant_path = format!("{}.{}", path, variant_name);
+                match fields_value {
+                    Some(fields_value) => self.encode_fields(variant_name, &fields, fields_value, &variant_path, out),
+                    None if fields.is_empty() => Ok(()),
+                    None => Err(format!("{}: variant {} has fields; pass {{\"{}\": ...}}", path, variant_name, variant_name)),
+                }
+            }
+            _ => Err(format!("{}: unsupported kind for type {} in IDL", path, name)),
+        }
+    }
+
+    /// Named fields come from a JSON object, tuple fields from a JSON array
+    fn encode_fields(&self, name: &str, fields: &[Value], value: &Value, path: &str, out: &mut Vec<u8>) -> Result<(), String> {
+        let named = fields.iter().all(|f| f.get("name").is_some());
+        if named {
+            let map = value.as_object().ok_or_else(|| mismatch(path, &format!("a {} object", name), value))?;
+            for field in fields {
+                let field_name = field["name"].as_str().ok_or_else(|| format!("{}: malformed field in IDL", path))?;
+                let field_path = format!("{}.{}", path, field_name);
+                let ty = field.get("type").ok_or_else(|| format!("{}: field has no type in IDL", field_path))?;
+                let field_value =
+                    value_or_none(lookup(map, field_name), ty).ok_or_else(|| format!("{}: missing", field_path))?;
+                self.encode(ty, field_value, &field_path, out)?;
+            }
+        } else {
+            let items = value.as_array().ok_or_else(|| mismatch(path, &format!("a {} tuple (array)", name), value))?;
+            if items.len() != fields.len() {
+                return Err(format!("{}: expected {} tuple elements, got {}", path, fields.len(), items.len()));
+            }
+            for (i, (ty, item)) in fields.iter().zip(items).enumerate() {
+                self.encode(ty, item, &format!("{}[{}]", path, i), out)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl IdlInstruction<'_> {
+    /// Resolve the instruction's accounts from a JSON object of name -> address.
+    /// Omitted accounts fall back to the IDL's fixed address, or the program id if optional.
+    pub fn account_metas(&self, accounts: &Value, program_id: &Pubkey) -> Result<Vec<AccountMeta>, String> {
+        let provided = accounts.as_object().ok_or_else(|| mismatch("accounts", "a JSON object", accounts))?;
+        if let Some(unknown) = provided
+            .keys()
+            .find(|key| !self.accounts.iter().any(|a| snake_case(&a.name) == snake_case(key)))
+        {
+            return Err(format!("accounts.{}: not an account of {}", unknown, self.name));
+        }
+
+        self.accounts
+            .iter()
+            .map(|account| {
+                let path = format!("accounts.{}", account.name);
+                let pubkey = match lookup(provided, &account.name) {
+                    Some(value) => value
+                        .as_str()
+                        .and_then(|s| s.parse().ok())
+                        .ok_or_else(|| mismatch(&path, "a base58 address", value))?,
+                    None => match account.address {
+                        Some(address) => address,
+                        // Anchor's placeholder for an omitted optional account
+                        None if account.optional => *program_id,
+                        None => return Err(format!("{}: missing", path)),
+                    },
+                };
+                Ok(AccountMeta { pubkey, is_signer: account.signer, is_writable: account.writable })
+            })
+            .collect()
+    }
+}
+
+/// Look up `name` in a JSON object, accepting either camelCase or snake_case keys
+fn lookup<'a>(map: &'a Map<String, Value>, name: &str) -> Option<&'a Value> {
+    map.get(name).or_else(|| {
+        let wanted = snake_case(name);
+        map.iter().find(|(key, _)| snake_case(key) == wanted).map(|(_, v)| v)
+    })
+}
+
+/// An omitted option is None; any other omitted value is an error (None here)
+fn value_or_none<'a>(value: Option<&'a Value>, ty: &Value) -> Option<&'a Value> {
+    const NULL: &Value = &Value::Null;
+    value.or(if ty.get("option").is_some() { Some(NULL) } else { None })
+}
+
+/// Integers may be JSON numbers or strings, since JSON numbers lose precision above 2^53
+fn parse_int(value: &Value) -> Option<i128> {
+    match value {
+        Value::Number(n) => n.as_i64().map(i128::from).or_else(|| n.as_u64().map(i128::from)),
+        Value::String(s) => s.parse::<i128>().ok(),
+        _ => None,
+    }
+}
+
+fn encode_primitive(ty: &str, value: &Value, path: &str, out: &mut Vec<u8>) -> Result<(), String> {
+    macro_rules! int {
+        ($t:ty) => {{
+            let n = parse_int(value)
+                .and_then(|n| <$t>::try_from(n).ok())
+                .ok_or_else(|| mismatch(path, ty, value))?;
+            out.extend_from_slice(&n.to_le_bytes());
+        }};
+    }
+
+    match ty {
+        "bool" => out.push(value.as_bool().ok_or_else(|| mismatch(path, ty, value))? as u8),
+        "u8" => int!(u8),
+        "i8" => int!(i8),
+        "u16" => int!(u16),
+        "i16" => int!(i16),
+        "u32" => int!(u32),
+        "i32" => int!(i32),
+        "u64" => int!(u64),
+        "i64" => int!(i64),
+        "i128" => int!(i128),
+        "u128" => {
+            // Values above i128::MAX only fit as strings
+            let n = value
+                .as_str()
+                .and_then(|s| s.parse::<u128>().ok())
+                .or_else(|| parse_int(value).and_then(|n| u128::try_from(n).ok()))
+                .ok_or_else(|| mismatch(path, ty, value))?;
+            out.extend_from_slice(&n.to_le_bytes());
+        }
+        "f32" => {
+            let n = value.as_f64().ok_or_else(|| mismatch(path, ty, value))? as f32;
+            out.extend_from_slice(&n.to_le_bytes());
+        }
+        "f64" => {
+            let n = value.as_f64().ok_or_else(|| mismatch(path, ty, value))?;
+            out.extend_from_slice(&n.to_le_bytes());
+        }
+        "string" => {
+            let s = value.as_str().ok_or_else(|| mismatch(path, ty, value))?;
+            out.extend_from_slice(&(s.len() as u32).to_le_bytes());
+            out.extend_from_slice(s.as_bytes());
+        }
+        "publicKey" | "pubkey" => {
+            let pubkey: Pubkey = value
+                .as_str()
+                .and_then(|s| s.parse().ok())
+                .ok_or_else(|| mismatch(path, "a base58 pubkey", value))?;
+            out.extend_from_slice(pubkey.as_ref());
+        }
+        "bytes" => {
+            let bytes: Vec<u8> = value
+                .as_array()
+                .and_then(|items| items.iter().map(|b| b.as_u64().and_then(|b| u8::try_from(b).ok())).collect())
+                .ok_or_else(|| mismatch(path, "an array of bytes", value))?;
+            out.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+            out.extend_from_slice(&bytes);
+        }
+        _ => return Err(format!("{}: unsupported IDL type {}", path, ty)),
+    }
+    Ok(())
+}
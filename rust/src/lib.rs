@@ -0,0 +1,29 @@
+//! Shared helpers for the Squads multisig scripts in `src/bin`
+
+pub mod addressbook;
+pub mod airdrop;
+pub mod audit;
+pub mod cache;
+pub mod args;
+pub mod confirm;
+pub mod decode;
+pub mod expect;
+pub mod fee;
+pub mod fee_profile;
+pub mod format;
+pub mod idl;
+pub mod message;
+pub mod multisig;
+pub mod network;
+pub mod output;
+pub mod pda;
+pub mod permissions;
+pub mod program;
+pub mod proposal;
+pub mod recipients;
+pub mod registry;
+pub mod send;
+pub mod snapshot;
+pub mod token;
+pub mod vanity;
+pub mod vault;
@@ -8,21 +8,72 @@
 //! Usage:
 //!   cargo run              # Creates on devnet (default)
 //!   cargo run -- mainnet   # Creates on mainnet
+//!   cargo run -- --airdrop 1   # Airdrop 1 SOL to the creator first (devnet/localnet only)
+//!   cargo run -- --json        # Print the result as JSON instead of the banner
+//!   cargo run -- --quiet       # Print only the multisig address
+//!   cargo run -- --vanity Sq   # Grind a create_key until the multisig address starts with "Sq"
+//!   cargo run -- --rent-collector self   # Let closed transaction/proposal rent return to the vault
+//!   cargo run -- --name "Ops Treasury"   # Name the multisig (creation memo + local registry)
+//!   cargo run -- --yes         # Create without the confirmation prompt
+//!   cargo run -- --vaults 3 --prefund 1000000   # List vaults 0-2 and fund each in the creation transaction
+//!
+//! Vanity options:
+//!   --vanity <prefix>          Base58 prefix the multisig address must start with
+//!   --vanity-ignore-case       Match the prefix case-insensitively (much faster for long prefixes)
+//!   --vanity-timeout <secs>    Give up after this long (default: 300)
+//!   --create-key <path>        Use a saved create_key keypair instead of a fresh one
+//!
+//! `--rent-collector <addr|self>` sets who can reclaim rent from executed, rejected or cancelled
+//! transaction and proposal accounts once they are closed (`self` is the new multisig's vault 0).
+//! The default is none, so that rent stays locked.
+//!
+//! `--name <string>` is written to the creation memo and to `~/.squads/registry.json`, where
+//! inspect_multisig finds it; the multisig account itself has no name field.
+//!
+//! Before sending, the script prints the full configuration (threshold, members with their
+//! permissions, config authority, time lock, rent collector) and the Squads creation fee read
+//! from the program config, and asks for confirmation: a multisig can't be deleted, and a
+//! mistyped member can only be removed by the other members. `--yes` skips the prompt, and is
+//! required with `--json` or `--quiet`.
+//!
+//! `--vaults <n>` prints the addresses of vaults 0..n after creation. Vault PDAs exist lazily, so
+//! this is only guidance; with `--prefund <lamports>` the creator also transfers that amount to
+//! each of them in the creation transaction, so they are rent-exempt system accounts right away
+//! (e.g. ready to own ATAs). The amount must be at least the rent-exempt minimum.
+//!
+//! The winning create_key is saved to create-key-<multisig>.json before anything is sent, so a
+//! failed creation can be retried with --create-key without grinding again.
 
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::{
     commitment_config::CommitmentConfig,
+    message::Message,
     pubkey::Pubkey,
-    signature::{read_keypair_file, Keypair, Signer},
-    system_program,
-    transaction::Transaction,
+    signature::{read_keypair_file, write_keypair_file, Keypair, Signer},
+    system_instruction, system_program,
 };
 use squads_multisig::{
     client::{multisig_create_v2, MultisigCreateAccountsV2, MultisigCreateArgsV2},
     pda::{get_multisig_pda, get_program_config_pda, get_vault_pda},
-    state::{Member, Permission, Permissions},
+    state::{Member, Permissions},
 };
+use squads_rust::airdrop::airdrop;
+use squads_rust::args::{reject_unknown_flags_or_exit, take_flag, take_flag_value, take_help};
+use squads_rust::audit::record;
+use squads_rust::confirm::confirm;
+use squads_rust::format::describe_lamports;
+use squads_rust::multisig::{
+    check_new_multisig_or_exit, fault_tolerance, print_create_args, CreationCost, RentCollector,
+};
+use squads_rust::network::{squads_app_url, Network};
+use squads_rust::output::{multisig_json, OutputMode};
+use squads_rust::permissions::PermissionsExt;
+use squads_rust::program::{check_program_or_exit, program_id, take_program_id_or_exit};
+use squads_rust::registry::{record_name, take_name};
+use squads_rust::send::{build_and_send, SendOptions, PACKET_DATA_SIZE};
+use squads_rust::vanity::{grind_create_key, VanityPrefix};
 use std::env;
+use std::time::Duration;
 
 // ============================================================================
 // CONFIGURATION - Edit these values before running
@@ -41,29 +92,135 @@ const MEMBER5: &str = "53Sb8FiUTRJbqs6SC5KgbMLqfwT98qPPTVroodLJKQ9m";
 /// Signature threshold (how many approvals needed)
 const THRESHOLD: u16 = 3;
 
+/// How long --vanity searches before giving up, unless --vanity-timeout says otherwise
+const DEFAULT_VANITY_TIMEOUT_SECS: u64 = 300;
+
+/// Vault indexes are a u8
+const MAX_VAULTS: usize = 256;
+
 // ============================================================================
 // Network Configuration (don't edit unless you know what you're doing)
 // ============================================================================
-const DEVNET_RPC: &str = "https://api.devnet.solana.com";
-const MAINNET_RPC: &str = "https://api.mainnet-beta.solana.com";
-
-const SQUADS_TREASURY_DEVNET: &str = "HM5y4mz3Bt9JY9mr1hkyhnvqxSH4H2u2451j7Hc2dtvK";
-const SQUADS_TREASURY_MAINNET: &str = "5DH2e3cJmFpyi6mk65EGFediunm4ui6BiKNUNrhWtD1b";
 
 fn main() {
     // Parse CLI args: cargo run -- mainnet OR cargo run (devnet default)
-    let args: Vec<String> = env::args().collect();
-    let network = args.get(1).map(|s| s.as_str()).unwrap_or("devnet");
-
-    let (rpc_url, treasury_addr, cluster_param) = match network {
-        "mainnet" => (MAINNET_RPC, SQUADS_TREASURY_MAINNET, ""),
-        _ => (DEVNET_RPC, SQUADS_TREASURY_DEVNET, "?cluster=devnet"),
+    let mut args: Vec<String> = env::args().collect();
+    let help = take_help(&mut args);
+    take_program_id_or_exit(&mut args);
+    let send_options = SendOptions::take_from_or_exit(&mut args);
+    let output = OutputMode::take_from(&mut args).unwrap_or_else(|e| {
+        println!("Error: {}", e);
+        std::process::exit(1);
+    });
+    // Progress and banners are only for the interactive (default) output
+    macro_rules! say {
+        ($($arg:tt)*) => {
+            if output.is_human() {
+                println!($($arg)*);
+            }
+        };
+    }
+    let yes = take_flag(&mut args, "--yes");
+    let airdrop_sol: Option<f64> = match take_flag_value(&mut args, "--airdrop") {
+        Ok(value) => value.map(|v| v.parse().expect("Invalid --airdrop amount (SOL)")),
+        Err(e) => {
+            println!("Error: {}", e);
+            return;
+        }
     };
+    let rent_collector = RentCollector::take_from(&mut args).unwrap_or_else(|e| {
+        println!("Error: {}", e);
+        std::process::exit(1);
+    });
+    let name = take_name(&mut args).unwrap_or_else(|e| {
+        println!("Error: {}", e);
+        std::process::exit(1);
+    });
+    let vanity_ignore_case = take_flag(&mut args, "--vanity-ignore-case");
+    let (vanity, vanity_timeout, create_key_path) = match (
+        take_flag_value(&mut args, "--vanity"),
+        take_flag_value(&mut args, "--vanity-timeout"),
+        take_flag_value(&mut args, "--create-key"),
+    ) {
+        (Ok(vanity), Ok(timeout), Ok(path)) => (vanity, timeout, path),
+        (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => {
+            println!("Error: {}", e);
+            return;
+        }
+    };
+    let vanity = vanity.map(|prefix| {
+        VanityPrefix::new(&prefix, vanity_ignore_case).unwrap_or_else(|e| {
+            println!("Error: Invalid --vanity prefix: {}", e);
+            std::process::exit(1);
+        })
+    });
+    let vanity_timeout = Duration::from_secs(
+        vanity_timeout
+            .map(|v| v.parse().expect("Invalid --vanity-timeout (seconds)"))
+            .unwrap_or(DEFAULT_VANITY_TIMEOUT_SECS),
+    );
+    if vanity.is_some() && create_key_path.is_some() {
+        println!("Error: --vanity and --create-key cannot be combined");
+        std::process::exit(1);
+    }
+    let (vault_count, prefund) = match (take_flag_value(&mut args, "--vaults"), take_flag_value(&mut args, "--prefund")) {
+        (Ok(vaults), Ok(prefund)) => (vaults, prefund),
+        (Err(e), _) | (_, Err(e)) => {
+            println!("Error: {}", e);
+            return;
+        }
+    };
+    let vault_count: Option<usize> = vault_count.map(|value| match value.parse() {
+        Ok(count) if (1..=MAX_VAULTS).contains(&count) => count,
+        _ => {
+            println!("Error: --vaults must be 1-{}, got '{}'", MAX_VAULTS, value);
+            std::process::exit(1);
+        }
+    });
+    let prefund: Option<u64> = prefund.map(|value| {
+        value.parse().unwrap_or_else(|_| {
+            println!("Error: invalid --prefund value '{}' (lamports)", value);
+            std::process::exit(1);
+        })
+    });
+    if prefund.is_some() && vault_count.is_none() {
+        println!("Error: --prefund requires --vaults <n>");
+        std::process::exit(1);
+    }
+    reject_unknown_flags_or_exit(&args);
+
+    if help {
+        println!("Create a {}/5 Squads v4 multisig", THRESHOLD);
+        println!();
+        println!("Usage:");
+        println!("  cargo run -- [options] [mainnet|localnet]");
+        println!();
+        println!("Options:");
+        println!("  --airdrop <sol>              - Airdrop SOL to the creator first (devnet/localnet only)");
+        println!("  --json                       - Print the result as JSON instead of the banner");
+        println!("  --quiet                      - Print only the multisig address");
+        println!("  --yes                        - Create without the confirmation prompt (required with --json or --quiet)");
+        println!("  --rent-collector <addr|self> - Who can reclaim rent from closed accounts (default: none)");
+        println!("  --name <string>              - Name the multisig (creation memo + local registry)");
+        println!("  --vanity <prefix>            - Grind a create_key until the address starts with prefix");
+        println!("  --vanity-ignore-case         - Match the prefix case-insensitively");
+        println!("  --vanity-timeout <secs>      - Give up grinding after this long (default: {})", DEFAULT_VANITY_TIMEOUT_SECS);
+        println!("  --create-key <path>          - Use a saved create_key keypair instead of a fresh one");
+        println!("  --vaults <n>                 - List vaults 0..n after creation");
+        println!("  --prefund <lamports>         - With --vaults, fund each vault in the creation transaction");
+        return;
+    }
+    if !yes && !output.is_human() {
+        println!("Error: --json and --quiet can't prompt for confirmation; pass --yes");
+        std::process::exit(1);
+    }
+    let network = Network::from_arg(args.get(1).map(|s| s.as_str()).unwrap_or("devnet"));
 
-    println!("=== Creating {}/{} Multisig ({}) ===\n", THRESHOLD, 5, network.to_uppercase());
+    say!("=== Creating {}/{} Multisig ({}) ===\n", THRESHOLD, 5, network.name().to_uppercase());
 
     // Connect to Solana
-    let client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
+    let client = RpcClient::new_with_commitment(network.rpc_url(), CommitmentConfig::confirmed());
+    check_program_or_exit(&client);
 
     // Load member1 keypair (creator and config authority)
     let member1 = read_keypair_file(MEMBER1_KEYPAIR_PATH)
@@ -76,30 +233,125 @@ fn main() {
     let member4_pubkey: Pubkey = MEMBER4.parse().expect("Invalid MEMBER4 address");
     let member5_pubkey: Pubkey = MEMBER5.parse().expect("Invalid MEMBER5 address");
 
+    // All members get full permissions
+    let all_permissions = Permissions::all();
+    let mut members = vec![
+        Member { key: member1_pubkey, permissions: all_permissions },
+        Member { key: member2_pubkey, permissions: all_permissions },
+        Member { key: member3_pubkey, permissions: all_permissions },
+        Member { key: member4_pubkey, permissions: all_permissions },
+        Member { key: member5_pubkey, permissions: all_permissions },
+    ];
+
+    // Catch a bad MEMBER or THRESHOLD edit before spending anything on an unusable multisig
+    check_new_multisig_or_exit("Multisig", &mut members, THRESHOLD);
+
+    if let Some(sol) = airdrop_sol {
+        say!("Requesting airdrop of {} SOL to {}...", sol, member1_pubkey);
+        match airdrop(&client, network, &member1_pubkey, (sol * 1_000_000_000.0) as u64) {
+            Ok(sig) => say!("Airdrop confirmed: {}\n", sig),
+            Err(e) => {
+                println!("ERROR: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
     // Check creator has funds for transaction
     let balance = client.get_balance(&member1_pubkey).expect("Failed to get balance");
-    println!("Creator: {}", member1_pubkey);
-    println!("Balance: {} SOL\n", balance as f64 / 1_000_000_000.0);
+    say!("Creator: {}", member1_pubkey);
+    say!("Balance: {} SOL\n", balance as f64 / 1_000_000_000.0);
+
+    // Prefunded vaults have to end up rent-exempt, or the transfers fail
+    if let Some(lamports) = prefund {
+        let rent_minimum = client
+            .get_minimum_balance_for_rent_exemption(0)
+            .expect("Failed to fetch rent-exempt minimum");
+        if lamports < rent_minimum {
+            println!(
+                "ERROR: --prefund {} is below the rent-exempt minimum of {}",
+                lamports,
+                describe_lamports(rent_minimum)
+            );
+            std::process::exit(1);
+        }
+    }
+    let prefund_total = prefund.unwrap_or(0).saturating_mul(vault_count.unwrap_or(0) as u64);
 
-    if balance < 10_000_000 {
-        // 0.01 SOL minimum
-        println!("ERROR: Insufficient balance. Need at least 0.01 SOL for transaction fees.");
+    // The creation fee is whatever the program config says, so read it instead of assuming
+    let priority_fee = send_options.fee_profile.map_or(0, |profile| profile.max_priority_fee());
+    let cost = CreationCost::fetch(&client, &[members.len()], priority_fee).unwrap_or_else(|e| {
+        println!("ERROR: Failed to read the creation cost: {}", e);
+        std::process::exit(1);
+    });
+    if balance < cost.total() + prefund_total {
+        println!(
+            "ERROR: Insufficient balance. Creation costs {} (Squads fee, rent and network fee).",
+            describe_lamports(cost.total())
+        );
+        if prefund_total > 0 {
+            println!("Plus {} to prefund the vaults.", describe_lamports(prefund_total));
+        }
         println!("Fund this wallet: {}", member1_pubkey);
-        return;
+        if network != Network::Mainnet {
+            println!("Or rerun with --airdrop 1 to request devnet/localnet SOL.");
+        }
+        std::process::exit(1);
     }
 
-    // Generate unique create_key for this multisig
-    let create_key = Keypair::new();
+    // Generate unique create_key for this multisig (or grind / load one)
+    let create_key = match (&vanity, &create_key_path) {
+        (Some(prefix), _) => {
+            say!("Searching for a multisig address starting with {}...", prefix);
+            say!("Expected attempts: ~{:.0} (timeout {}s)", prefix.expected_attempts(), vanity_timeout.as_secs());
+            let mut result = grind_create_key(prefix, vanity_timeout, |attempts, elapsed| {
+                eprintln!(
+                    "  {} attempts in {:.0}s ({:.0}/sec)",
+                    attempts,
+                    elapsed.as_secs_f64(),
+                    attempts as f64 / elapsed.as_secs_f64()
+                );
+            });
+            let create_key = result.create_key.take().unwrap_or_else(|| {
+                println!(
+                    "ERROR: No address starting with {} after {} attempts in {:.0}s ({:.0}/sec).",
+                    prefix,
+                    result.attempts,
+                    result.elapsed.as_secs_f64(),
+                    result.attempts_per_sec()
+                );
+                println!("Raise --vanity-timeout, use --vanity-ignore-case, or shorten the prefix.");
+                std::process::exit(1);
+            });
+            say!(
+                "Found after {} attempts in {:.1}s ({:.0}/sec)",
+                result.attempts,
+                result.elapsed.as_secs_f64(),
+                result.attempts_per_sec()
+            );
+            create_key
+        }
+        (None, Some(path)) => read_keypair_file(path).unwrap_or_else(|e| {
+            println!("Error: Failed to read --create-key {}: {}", path, e);
+            std::process::exit(1);
+        }),
+        (None, None) => Keypair::new(),
+    };
 
     // Derive PDAs
-    let (multisig_pda, _) = get_multisig_pda(&create_key.pubkey(), None);
-    let (program_config_pda, _) = get_program_config_pda(None);
-    let treasury: Pubkey = treasury_addr.parse().unwrap();
+    let (multisig_pda, _) = get_multisig_pda(&create_key.pubkey(), Some(&program_id()));
 
-    // All members get full permissions
-    let all_permissions = Permissions {
-        mask: Permission::Initiate as u8 | Permission::Vote as u8 | Permission::Execute as u8,
-    };
+    // Keep a ground create_key before sending, so a failed creation doesn't lose the search
+    let create_key_file = vanity.as_ref().map(|_| {
+        let path = format!("create-key-{}.json", multisig_pda);
+        write_keypair_file(&create_key, &path).expect("Failed to save create_key");
+        say!("Saved create_key to {} (retry with --create-key {})\n", path, path);
+        path
+    });
+    let (program_config_pda, _) = get_program_config_pda(Some(&program_id()));
+    let treasury = network
+        .squads_treasury(&client)
+        .expect("Failed to resolve Squads treasury");
 
     // Build multisig creation accounts
     let accounts = MultisigCreateAccountsV2 {
@@ -111,59 +363,148 @@ fn main() {
         system_program: system_program::ID,
     };
 
+    let member_keys: Vec<Pubkey> = members.iter().map(|m| m.key).collect();
+    let rent_collector_address = rent_collector.map(|collector| collector.resolve(&multisig_pda));
+
     // Build multisig creation args
     let args = MultisigCreateArgsV2 {
         config_authority: Some(member1_pubkey), // Member1 can change settings without proposals
         threshold: THRESHOLD,
-        members: vec![
-            Member { key: member1_pubkey, permissions: all_permissions },
-            Member { key: member2_pubkey, permissions: all_permissions },
-            Member { key: member3_pubkey, permissions: all_permissions },
-            Member { key: member4_pubkey, permissions: all_permissions },
-            Member { key: member5_pubkey, permissions: all_permissions },
-        ],
+        members,
         time_lock: 0,         // No time lock on execution
-        rent_collector: None, // No rent collection
-        memo: None,
+        rent_collector: rent_collector_address, // None unless --rent-collector
+        memo: name.clone(), // Logged with the create instruction; not stored on the account
     };
 
-    // Create the instruction
-    let instruction = multisig_create_v2(accounts, args, None);
+    let vaults: Vec<(u8, Pubkey)> = (0..vault_count.unwrap_or(0))
+        .map(|index| {
+            let index = index as u8;
+            (index, get_vault_pda(&multisig_pda, index, Some(&program_id())).0)
+        })
+        .collect();
 
-    println!("Creating multisig...");
+    // Creation can't be undone, so show exactly what will be created and ask first
+    if output.is_human() {
+        println!();
+        print_create_args("Multisig", &multisig_pda, &args);
+        cost.print(&treasury);
+        if let Some(lamports) = prefund {
+            println!(
+                "Prefund: {} to each of vaults 0-{} ({} total)",
+                describe_lamports(lamports),
+                vaults.len() - 1,
+                describe_lamports(prefund_total)
+            );
+        }
+        println!();
+    }
+    if !yes && !confirm("Create this multisig?") {
+        println!("Aborted; nothing was sent.");
+        return;
+    }
 
-    // Build and send transaction
-    let recent_blockhash = client.get_latest_blockhash().expect("Failed to get blockhash");
-    let transaction = Transaction::new_signed_with_payer(
-        &[instruction],
-        Some(&member1_pubkey),
-        &[&member1, &create_key],
-        recent_blockhash,
-    );
+    // Create the instruction, then any vault prefunding in the same transaction
+    let mut instructions = vec![multisig_create_v2(accounts, args, Some(program_id()))];
+    if let Some(lamports) = prefund {
+        instructions.extend(vaults.iter().map(|(_, vault)| system_instruction::transfer(&member1_pubkey, vault, lamports)));
+        let size = 1 + 2 * 64 + Message::new(&instructions, Some(&member1_pubkey)).serialize().len();
+        if size > PACKET_DATA_SIZE {
+            println!(
+                "ERROR: Creating and prefunding {} vaults takes a {}-byte transaction (limit {}); use fewer --vaults.",
+                vaults.len(),
+                size,
+                PACKET_DATA_SIZE
+            );
+            std::process::exit(1);
+        }
+    }
 
-    let signature = client
-        .send_and_confirm_transaction(&transaction)
+    say!("Creating multisig...");
+
+    // Build and send transaction
+    let signature = build_and_send(&client, &instructions, &member1_pubkey, &[&member1, &create_key], &send_options)
         .expect("Failed to create multisig");
+    record(network, &multisig_pda, "create multisig", &member1_pubkey, &signature);
 
     // Get vault address (where funds are stored)
-    let (vault_pda, _) = get_vault_pda(&multisig_pda, 0, None);
+    let (vault_pda, _) = get_vault_pda(&multisig_pda, 0, Some(&program_id()));
+
+    // The multisig exists now, so a registry failure is only a warning
+    let registry = name.as_ref().and_then(|name| match record_name(&multisig_pda, name) {
+        Ok(path) => Some(path),
+        Err(e) => {
+            eprintln!("WARNING: Could not save the name to the local registry: {}", e);
+            None
+        }
+    });
+
+    match output {
+        OutputMode::Json => {
+            let mut report =
+                multisig_json(&multisig_pda, &vault_pda, Some(&member1_pubkey), THRESHOLD, &member_keys, &signature);
+            report["network"] = network.name().into();
+            report["create_key"] = create_key.pubkey().to_string().into();
+            report["rent_collector"] = rent_collector_address.map(|key| key.to_string()).into();
+            report["name"] = name.clone().into();
+            if let Some(path) = &create_key_file {
+                report["create_key_file"] = path.as_str().into();
+            }
+            if !vaults.is_empty() {
+                report["vaults"] = vaults
+                    .iter()
+                    .map(|(index, vault)| {
+                        serde_json::json!({
+                            "index": index,
+                            "address": vault.to_string(),
+                            "funded_lamports": prefund.unwrap_or(0).to_string(),
+                        })
+                    })
+                    .collect();
+            }
+            println!("{}", serde_json::to_string_pretty(&report).expect("Failed to serialize result"));
+            return;
+        }
+        OutputMode::Quiet => {
+            println!("{}", multisig_pda);
+            return;
+        }
+        OutputMode::Human => {}
+    }
 
     // Print summary
     println!("\n========== SUCCESS ==========");
-    println!("Network: {}", network.to_uppercase());
+    println!("Network: {}", network.name().to_uppercase());
+    if let Some(name) = &name {
+        match &registry {
+            Some(path) => println!("Name: {} (saved to {})", name, path.display()),
+            None => println!("Name: {} (in the creation memo only)", name),
+        }
+    }
     println!("Multisig Address: {}", multisig_pda);
     println!("Vault Address: {} (send funds here)", vault_pda);
     println!("Config Authority: {}", member1_pubkey);
-    println!("Threshold: {} of 5", THRESHOLD);
+    println!("Threshold: {} of 5 (tolerates {} lost key(s))", THRESHOLD, fault_tolerance(THRESHOLD, 5));
+    if let Some(address) = rent_collector_address {
+        println!("Rent Collector: {}", address);
+        println!("  Rent from closed transaction and proposal accounts can be reclaimed to this address.");
+    }
     println!("\nMembers:");
     println!("  1. {} (Config Authority)", member1_pubkey);
     println!("  2. {}", member2_pubkey);
     println!("  3. {}", member3_pubkey);
     println!("  4. {}", member4_pubkey);
     println!("  5. {}", member5_pubkey);
+    if !vaults.is_empty() {
+        println!("\nVaults (derived from the multisig; any index can hold funds):");
+        println!("  {:<5} {:<44} Funded", "Index", "Address");
+        for (index, vault) in &vaults {
+            let funded = prefund.map_or("-".to_string(), describe_lamports);
+            println!("  {:<5} {:<44} {}", index, vault.to_string(), funded);
+        }
+    }
     println!("\nTransaction: {}", signature);
     println!("\nView on Solana Explorer:");
-    println!("https://explorer.solana.com/address/{}{}", multisig_pda, cluster_param);
+    println!("{}", network.explorer_address_url(&multisig_pda));
     println!("\nView on Squads App:");
-    println!("https://v4.squads.so/squads/{}/home", multisig_pda);
+    println!("{}", squads_app_url(&multisig_pda));
 }
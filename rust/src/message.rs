@@ -0,0 +1,61 @@
+//! Debug output for compiled vault transaction messages
+//!
+//! Proposal scripts print this with `--dump-message` so account-ordering and signer-flag problems
+//! can be diagnosed before anything is sent.
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use squads_multisig::anchor_lang::AnchorDeserialize;
+use squads_multisig::squads_multisig_program::TransactionMessage;
+use squads_multisig::state::VaultTransactionMessage;
+
+/// Print the decoded form of serialized `TransactionMessage` bytes, followed by the raw base64
+pub fn print_compiled_message(message_bytes: &[u8]) {
+    let message = match TransactionMessage::deserialize(&mut &message_bytes[..])
+        .map_err(|e| e.to_string())
+        .and_then(|m| VaultTransactionMessage::try_from(m).map_err(|e| e.to_string()))
+    {
+        Ok(message) => message,
+        Err(e) => {
+            println!("Error: Failed to decode compiled message: {}", e);
+            return;
+        }
+    };
+
+    println!("=== Compiled Transaction Message ===");
+    println!("num_signers: {}", message.num_signers);
+    println!("num_writable_signers: {}", message.num_writable_signers);
+    println!("num_writable_non_signers: {}", message.num_writable_non_signers);
+    println!();
+    println!("Account Keys:");
+    for (i, key) in message.account_keys.iter().enumerate() {
+        let signer = if message.is_signer_index(i) { "signer" } else { "-" };
+        let writable = if message.is_static_writable_index(i) { "writable" } else { "readonly" };
+        println!("  [{}] {} ({}, {})", i, key, signer, writable);
+    }
+    println!();
+    println!("Instructions:");
+    for (i, ix) in message.instructions.iter().enumerate() {
+        let program = message
+            .account_keys
+            .get(usize::from(ix.program_id_index))
+            .map(|k| k.to_string())
+            .unwrap_or_else(|| "<out of range>".to_string());
+        println!("  #{}: program [{}] {}", i, ix.program_id_index, program);
+        println!("      accounts: {:?}", ix.account_indexes);
+        println!("      data: {} bytes", ix.data.len());
+    }
+    if !message.address_table_lookups.is_empty() {
+        println!();
+        println!("Address Table Lookups:");
+        for lookup in &message.address_table_lookups {
+            println!(
+                "  {} writable: {:?} readonly: {:?}",
+                lookup.account_key, lookup.writable_indexes, lookup.readonly_indexes
+            );
+        }
+    }
+    println!();
+    println!("Message (base64, {} bytes):", message_bytes.len());
+    println!("{}", STANDARD.encode(message_bytes));
+    println!();
+}
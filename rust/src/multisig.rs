@@ -0,0 +1,432 @@
+//! Loading and validating multisig accounts and configurations
+//!
+//! A wrong address otherwise surfaces as a deserialize panic deep in a script, so the account's
+//! owner and discriminator are checked first and reported in plain terms.
+
+use crate::addressbook::try_resolve_pubkey;
+use crate::args::take_flag_value;
+use crate::format::{format_amount, progress_bar, Units};
+use crate::network::Network;
+use crate::permissions::PermissionsExt;
+use crate::program::{check_program, fetch_program_config, program_id};
+use solana_client::client_error::ClientError;
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::{
+    account::Account, commitment_config::CommitmentConfig, fee_calculator::DEFAULT_TARGET_LAMPORTS_PER_SIGNATURE,
+    pubkey::Pubkey,
+};
+use squads_multisig::anchor_lang::{AccountDeserialize, Discriminator};
+use squads_multisig::client::MultisigCreateArgsV2;
+use squads_multisig::pda::{get_proposal_pda, get_vault_pda};
+use squads_multisig::state::{Member, Multisig, Permission, Proposal, SpendingLimit};
+
+/// `get_multiple_accounts` accepts at most 100 keys per request
+const MAX_ACCOUNTS_PER_REQUEST: usize = 100;
+
+/// Validate and deserialize an already-fetched account as a Squads multisig
+pub fn parse_multisig(address: &Pubkey, account: &Account) -> Result<Multisig, String> {
+    if account.owner != program_id() {
+        return Err(format!(
+            "account {} is not a Squads multisig (owner: {})",
+            address, account.owner
+        ));
+    }
+    if !account.data.starts_with(&Multisig::DISCRIMINATOR) {
+        return Err(format!(
+            "account {} is a Squads account but not a multisig (wrong discriminator)",
+            address
+        ));
+    }
+    Multisig::try_deserialize(&mut account.data.as_slice())
+        .map_err(|e| format!("failed to deserialize multisig {}: {}", address, e))
+}
+
+/// Fetch and validate the multisig at `address`.
+///
+/// If the account doesn't exist, the public devnet and mainnet clusters the client isn't
+/// using are probed for it, since a missing or extra `mainnet` argument is the usual cause.
+pub fn fetch_multisig(client: &RpcClient, address: &Pubkey) -> Result<Multisig, String> {
+    check_program(client)?;
+    let account = client
+        .get_account_with_commitment(address, client.commitment())
+        .map_err(|e| format!("failed to fetch multisig account {}: {}", address, e))?
+        .value;
+    match account {
+        Some(account) => parse_multisig(address, &account),
+        None => {
+            let current = Network::from_rpc_url(&client.url());
+            let mut message = format!(
+                "multisig account {} not found on {}",
+                address,
+                current.map_or("this cluster", |network| network.name())
+            );
+            if let Some(other) = find_on_other_cluster(current, address) {
+                message.push_str(&format!(
+                    "\nThis multisig exists on {}; did you forget the '{}' argument?",
+                    other.name(),
+                    other.name()
+                ));
+            }
+            Err(message)
+        }
+    }
+}
+
+/// The first public cluster other than `current` where `address` is a Squads account.
+/// Probe failures are ignored; this only adds a hint.
+fn find_on_other_cluster(current: Option<Network>, address: &Pubkey) -> Option<Network> {
+    [Network::Devnet, Network::Mainnet]
+        .into_iter()
+        .filter(|network| Some(*network) != current)
+        .find(|network| {
+            let probe = RpcClient::new_with_commitment(network.rpc_url(), CommitmentConfig::confirmed());
+            probe
+                .get_account_with_commitment(address, CommitmentConfig::confirmed())
+                .ok()
+                .and_then(|response| response.value)
+                .is_some_and(|account| account.owner == program_id())
+        })
+}
+
+/// `fetch_multisig`, printing the error and exiting if it fails
+pub fn fetch_multisig_or_exit(client: &RpcClient, address: &Pubkey) -> Multisig {
+    fetch_multisig(client, address).unwrap_or_else(|e| {
+        println!("Error: {}", e);
+        std::process::exit(1);
+    })
+}
+
+/// Fetch `keys` in chunks of at most 100, preserving order
+#[allow(clippy::result_large_err)]
+pub fn fetch_accounts(client: &RpcClient, keys: &[Pubkey]) -> Result<Vec<Option<Account>>, ClientError> {
+    let mut accounts = Vec::with_capacity(keys.len());
+    for chunk in keys.chunks(MAX_ACCOUNTS_PER_REQUEST) {
+        accounts.extend(client.get_multiple_accounts(chunk)?);
+    }
+    Ok(accounts)
+}
+
+/// Every proposal still on-chain for transaction indices 1..=`multisig.transaction_index`,
+/// as (index, address, proposal). Closed or never-created proposals are skipped.
+pub fn fetch_proposals(
+    client: &RpcClient,
+    multisig_pda: &Pubkey,
+    multisig: &Multisig,
+) -> Result<Vec<(u64, Pubkey, Proposal)>, String> {
+    let indices: Vec<u64> = (1..=multisig.transaction_index).collect();
+    let keys: Vec<Pubkey> = indices.iter().map(|&i| get_proposal_pda(multisig_pda, i, Some(&program_id())).0).collect();
+    let accounts = fetch_accounts(client, &keys).map_err(|e| format!("failed to fetch proposals: {}", e))?;
+
+    let mut proposals = Vec::new();
+    for ((index, key), account) in indices.into_iter().zip(keys).zip(accounts) {
+        if let Some(account) = account {
+            let proposal = Proposal::try_deserialize(&mut account.data.as_slice())
+                .map_err(|e| format!("failed to deserialize proposal {} (index {}): {}", key, index, e))?;
+            proposals.push((index, key, proposal));
+        }
+    }
+    Ok(proposals)
+}
+
+/// Every spending limit of `multisig_pda`, whatever its create_key, via getProgramAccounts
+/// (which the public mainnet RPC disables)
+pub fn fetch_spending_limits(client: &RpcClient, multisig_pda: &Pubkey) -> Result<Vec<(Pubkey, SpendingLimit)>, String> {
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, SpendingLimit::DISCRIMINATOR.to_vec())),
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(8, multisig_pda.to_bytes().to_vec())),
+        ]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            commitment: Some(client.commitment()),
+            ..RpcAccountInfoConfig::default()
+        },
+        with_context: None,
+    };
+    let accounts = client
+        .get_program_accounts_with_config(&program_id(), config)
+        .map_err(|e| format!("getProgramAccounts failed: {}", e))?;
+    accounts
+        .into_iter()
+        .map(|(address, account)| {
+            SpendingLimit::try_deserialize(&mut account.data.as_slice())
+                .map(|limit| (address, limit))
+                .map_err(|e| format!("failed to deserialize spending limit {}: {}", address, e))
+        })
+        .collect()
+}
+
+/// Members with Vote permission who are not in `voted`, in member order.
+/// Approve, reject and cancel all need Vote, so these are the votes still outstanding.
+pub fn pending_voters(multisig: &Multisig, voted: &[Pubkey]) -> Vec<Pubkey> {
+    multisig
+        .members
+        .iter()
+        .filter(|m| m.permissions.contains(Permission::Vote) && !voted.contains(&m.key))
+        .map(|m| m.key)
+        .collect()
+}
+
+/// Print `label` with a progress bar of `voted` against the threshold, the voters, and, while
+/// the threshold isn't met, the members who haven't voted yet
+pub fn print_vote_progress(label: &str, voted: &[Pubkey], multisig: &Multisig) {
+    let threshold = usize::from(multisig.threshold);
+    println!("{}: {}", label, progress_bar(voted.len(), threshold));
+    for voter in voted {
+        println!("  - {}", voter);
+    }
+    let remaining = threshold.saturating_sub(voted.len());
+    if remaining > 0 {
+        println!("{} more vote(s) needed. Not yet voted:", remaining);
+        for member in pending_voters(multisig, voted) {
+            println!("  - {}", member);
+        }
+    }
+}
+
+/// Sort `members` by pubkey, the order the program stores them in, and reject duplicates and
+/// an empty list.
+/// Works for `Member` lists (multisig creation) and plain pubkeys (spending limits).
+pub fn normalize_members<T>(members: &mut [T], key: impl Fn(&T) -> Pubkey) -> Result<(), String> {
+    if members.is_empty() {
+        return Err("the member list is empty".to_string());
+    }
+    members.sort_by_key(&key);
+    match members.windows(2).find(|pair| key(&pair[0]) == key(&pair[1])) {
+        Some(pair) => Err(format!("{} is listed more than once", key(&pair[0]))),
+        None => Ok(()),
+    }
+}
+
+/// Check that `threshold` members of `members` can vote, which the program requires
+pub fn check_voters(members: &[Member], threshold: u16) -> Result<(), String> {
+    let voters = members.iter().filter(|m| m.permissions.contains(Permission::Vote)).count();
+    if voters < usize::from(threshold) {
+        return Err(format!(
+            "only {} member(s) would have Vote permission, fewer than the threshold {}",
+            voters, threshold
+        ));
+    }
+    Ok(())
+}
+
+/// Check that some member of `members` can initiate and some member can execute, which the
+/// program requires
+pub fn check_roles(members: &[Member]) -> Result<(), String> {
+    for (permission, name) in [(Permission::Initiate, "Initiate"), (Permission::Execute, "Execute")] {
+        if !members.iter().any(|m| m.permissions.contains(permission)) {
+            return Err(format!("no member would have {} permission", name));
+        }
+    }
+    Ok(())
+}
+
+/// Normalize a new multisig's member list and reject unset (default) pubkeys
+pub fn check_members(members: &mut [Member]) -> Result<(), String> {
+    if let Some(i) = members.iter().position(|m| m.key == Pubkey::default()) {
+        return Err(format!("member {} is the default pubkey; was an address left unset?", i + 1));
+    }
+    normalize_members(members, |m| m.key)
+}
+
+/// The smallest threshold that is a strict majority of `member_count`
+pub fn majority_threshold(member_count: usize) -> usize {
+    member_count / 2 + 1
+}
+
+/// How many keys `threshold` of `member_count` can lose and still approve proposals
+pub fn fault_tolerance(threshold: u16, member_count: usize) -> usize {
+    member_count.saturating_sub(usize::from(threshold))
+}
+
+/// Check a threshold for a new multisig with `member_count` members.
+/// Errors if it can never be met or is zero; returns a warning for a single-signer, minority or
+/// unanimous threshold.
+pub fn check_threshold(threshold: u16, member_count: usize) -> Result<Option<String>, String> {
+    if threshold == 0 {
+        return Err("threshold must be at least 1".to_string());
+    }
+    if usize::from(threshold) > member_count {
+        return Err(format!(
+            "threshold {} exceeds the member count {}; proposals could never be approved",
+            threshold, member_count
+        ));
+    }
+    if threshold == 1 && member_count > 1 {
+        return Ok(Some(format!(
+            "threshold 1 of {} is effectively single-sig; any one member key can move funds",
+            member_count
+        )));
+    }
+    if usize::from(threshold) < majority_threshold(member_count) {
+        return Ok(Some(format!(
+            "threshold {} of {} is below a majority ({}); a minority of members can approve proposals",
+            threshold,
+            member_count,
+            majority_threshold(member_count)
+        )));
+    }
+    if usize::from(threshold) == member_count && member_count > 1 {
+        return Ok(Some(format!(
+            "threshold {} of {} is unanimous; losing any one key bricks the multisig",
+            threshold, member_count
+        )));
+    }
+    Ok(None)
+}
+
+/// `check_members`, `check_roles`, `check_threshold` and `check_voters` for the multisig
+/// described by `label`, printing any warning and exiting on an invalid member list or threshold.
+/// Leaves `members` sorted.
+pub fn check_new_multisig_or_exit(label: &str, members: &mut [Member], threshold: u16) {
+    if let Err(e) = check_members(members).and_then(|()| check_roles(members)) {
+        println!("Error: {}: {}", label, e);
+        std::process::exit(1);
+    }
+    match check_threshold(threshold, members.len()) {
+        // stderr, so creation scripts run with --json/--quiet keep a clean stdout
+        Ok(Some(warning)) => eprintln!("WARNING: {}: {}\n", label, warning),
+        Ok(None) => {}
+        Err(e) => {
+            println!("Error: {}: {}", label, e);
+            std::process::exit(1);
+        }
+    }
+    if let Err(e) = check_voters(members, threshold) {
+        println!("Error: {}: {}", label, e);
+        std::process::exit(1);
+    }
+}
+
+/// Print the configuration `args` creates at `multisig_pda`, for review before sending
+pub fn print_create_args(label: &str, multisig_pda: &Pubkey, args: &MultisigCreateArgsV2) {
+    println!("{}: {}", label, multisig_pda);
+    if let Some(name) = &args.memo {
+        println!("  Name: {}", name);
+    }
+    println!(
+        "  Threshold: {} of {} (tolerates {} lost key(s))",
+        args.threshold,
+        args.members.len(),
+        fault_tolerance(args.threshold, args.members.len())
+    );
+    match args.config_authority {
+        Some(authority) => println!("  Config Authority: {} (changes members and threshold without proposals)", authority),
+        None => println!("  Config Authority: none (changes go through config proposals)"),
+    }
+    match args.time_lock {
+        0 => println!("  Time Lock: none"),
+        seconds => println!("  Time Lock: {} seconds between approval and execution", seconds),
+    }
+    match args.rent_collector {
+        Some(address) => println!("  Rent Collector: {}", address),
+        None => println!("  Rent Collector: none (rent of closed accounts stays locked)"),
+    }
+    println!("  Members:");
+    for (i, member) in args.members.iter().enumerate() {
+        println!("    {}. {} ({})", i + 1, member.key, member.permissions.to_string());
+    }
+}
+
+/// What creating new multisigs costs the creator: the Squads creation fee, the rent for each
+/// multisig account, and the network fee of one transaction per multisig (creator and
+/// create_key sign)
+pub struct CreationCost {
+    /// The program config's `multisig_creation_fee`, paid to its treasury per multisig
+    pub creation_fee: u64,
+    /// The program config's treasury
+    pub treasury: Pubkey,
+    pub count: usize,
+    pub rent: u64,
+    pub network_fee: u64,
+    /// Upper bound of the `--fee-profile` priority fees, 0 without one
+    pub priority_fee: u64,
+}
+
+impl CreationCost {
+    /// Read the creation fee from the program config and the rent for multisigs with
+    /// `member_counts` members each. `priority_fee` is the most one creation transaction's
+    /// priority fee can cost.
+    pub fn fetch(client: &RpcClient, member_counts: &[usize], priority_fee: u64) -> Result<Self, String> {
+        let config = fetch_program_config(client)?;
+        let mut rent = 0;
+        for &members in member_counts {
+            rent += client
+                .get_minimum_balance_for_rent_exemption(Multisig::size(members))
+                .map_err(|e| format!("failed to fetch the multisig rent: {}", e))?;
+        }
+        let count = member_counts.len();
+        Ok(CreationCost {
+            creation_fee: config.multisig_creation_fee,
+            treasury: config.treasury,
+            count,
+            rent,
+            network_fee: count as u64 * 2 * DEFAULT_TARGET_LAMPORTS_PER_SIGNATURE,
+            priority_fee: count as u64 * priority_fee,
+        })
+    }
+
+    pub fn total(&self) -> u64 {
+        self.creation_fee * self.count as u64 + self.rent + self.network_fee + self.priority_fee
+    }
+
+    /// Print the breakdown, and warn if the program config's treasury isn't the `treasury` the
+    /// creation instructions pay
+    pub fn print(&self, treasury: &Pubkey) {
+        let sol = |lamports: u64| format_amount(lamports, 9, Units::Ui);
+        println!(
+            "Squads Creation Fee: {} SOL per multisig, {} SOL total (to treasury {})",
+            sol(self.creation_fee),
+            sol(self.creation_fee * self.count as u64),
+            self.treasury
+        );
+        if self.treasury != *treasury {
+            println!("WARNING: the program config's treasury differs from {}; creation will fail", treasury);
+        }
+        println!("Rent: {} SOL for {} multisig account(s)", sol(self.rent), self.count);
+        println!("Network Fee: {} SOL", sol(self.network_fee));
+        if self.priority_fee > 0 {
+            println!("Priority Fee: up to {} SOL (--fee-profile)", sol(self.priority_fee));
+        }
+        println!("Total Cost: {} SOL", sol(self.total()));
+    }
+}
+
+/// `--rent-collector` for multisig creation: who can reclaim rent from executed, rejected or
+/// cancelled transaction and proposal accounts once they are closed. Without one, that rent
+/// stays locked in those accounts.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RentCollector {
+    Address(Pubkey),
+    /// `self`: the new multisig's vault 0, so reclaimed rent returns to the treasury
+    OwnVault,
+}
+
+impl RentCollector {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "self" => Ok(RentCollector::OwnVault),
+            _ => try_resolve_pubkey(value)
+                .map(RentCollector::Address)
+                .map_err(|e| format!("invalid --rent-collector (expected an address, a name or self): {}", e)),
+        }
+    }
+
+    /// Remove `--rent-collector <addr|self>` from `args`
+    pub fn take_from(args: &mut Vec<String>) -> Result<Option<Self>, String> {
+        take_flag_value(args, "--rent-collector")?
+            .map(|value| Self::parse(&value))
+            .transpose()
+    }
+
+    /// The rent collector address for the multisig at `multisig_pda`
+    pub fn resolve(&self, multisig_pda: &Pubkey) -> Pubkey {
+        match self {
+            RentCollector::Address(address) => *address,
+            RentCollector::OwnVault => get_vault_pda(multisig_pda, 0, Some(&program_id())).0,
+        }
+    }
+}
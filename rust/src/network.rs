@@ -0,0 +1,171 @@
+//! Cluster selection shared by every script
+//!
+//! Scripts take an optional trailing network argument: `mainnet`, `localnet` (or `local`),
+//! and anything else (including nothing) means devnet.
+//!
+//! Explorer and Squads app links are built here so every script gets the right cluster suffix.
+//! Scripts that take `--links` print the full set with `print_links` after the operation, and
+//! `--open` launches the browser at the Squads app page with `open_in_browser`.
+
+use crate::program::{fetch_program_config, program_id};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use squads_multisig::pda::{get_proposal_pda, get_vault_pda};
+use std::process::{Command, Stdio};
+
+const DEVNET_RPC: &str = "https://api.devnet.solana.com";
+const MAINNET_RPC: &str = "https://api.mainnet-beta.solana.com";
+const LOCALNET_RPC: &str = "http://127.0.0.1:8899";
+
+const DEVNET_WS: &str = "wss://api.devnet.solana.com";
+const MAINNET_WS: &str = "wss://api.mainnet-beta.solana.com";
+const LOCALNET_WS: &str = "ws://127.0.0.1:8900";
+
+const SQUADS_APP: &str = "https://v4.squads.so";
+
+const SQUADS_TREASURY_DEVNET: &str = "HM5y4mz3Bt9JY9mr1hkyhnvqxSH4H2u2451j7Hc2dtvK";
+const SQUADS_TREASURY_MAINNET: &str = "5DH2e3cJmFpyi6mk65EGFediunm4ui6BiKNUNrhWtD1b";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Network {
+    Devnet,
+    Mainnet,
+    /// A local `solana-test-validator` with the Squads program loaded
+    Localnet,
+}
+
+impl Network {
+    /// Parse a network argument. Unrecognized values fall back to devnet.
+    pub fn from_arg(arg: &str) -> Self {
+        match arg {
+            "mainnet" => Network::Mainnet,
+            "localnet" | "local" => Network::Localnet,
+            _ => Network::Devnet,
+        }
+    }
+
+    /// The network whose RPC endpoint is `url`, if it is one of ours
+    pub fn from_rpc_url(url: &str) -> Option<Self> {
+        [Network::Devnet, Network::Mainnet, Network::Localnet]
+            .into_iter()
+            .find(|network| network.rpc_url() == url)
+    }
+
+    /// Whether `arg` names a network explicitly (for scripts that scan flags)
+    pub fn is_network_arg(arg: &str) -> bool {
+        matches!(arg, "mainnet" | "devnet" | "localnet" | "local")
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Network::Devnet => "devnet",
+            Network::Mainnet => "mainnet",
+            Network::Localnet => "localnet",
+        }
+    }
+
+    pub fn rpc_url(&self) -> &'static str {
+        match self {
+            Network::Devnet => DEVNET_RPC,
+            Network::Mainnet => MAINNET_RPC,
+            Network::Localnet => LOCALNET_RPC,
+        }
+    }
+
+    pub fn ws_url(&self) -> &'static str {
+        match self {
+            Network::Devnet => DEVNET_WS,
+            Network::Mainnet => MAINNET_WS,
+            Network::Localnet => LOCALNET_WS,
+        }
+    }
+
+    /// Query string appended to Solana Explorer links for this cluster
+    pub fn cluster_param(&self) -> &'static str {
+        match self {
+            Network::Devnet => "?cluster=devnet",
+            Network::Mainnet => "",
+            Network::Localnet => "?cluster=custom&customUrl=http%3A%2F%2F127.0.0.1%3A8899",
+        }
+    }
+
+    /// Solana Explorer link for a transaction on this cluster
+    pub fn explorer_tx_url(&self, signature: &Signature) -> String {
+        format!("https://explorer.solana.com/tx/{}{}", signature, self.cluster_param())
+    }
+
+    /// Solana Explorer link for an account on this cluster
+    pub fn explorer_address_url(&self, address: &Pubkey) -> String {
+        format!("https://explorer.solana.com/address/{}{}", address, self.cluster_param())
+    }
+
+    /// Print explorer and Squads app links for `multisig`, plus its proposal at
+    /// `transaction_index` and the transaction `signature` when given
+    pub fn print_links(&self, multisig: &Pubkey, transaction_index: Option<u64>, signature: Option<&Signature>) {
+        println!("\nLinks:");
+        println!("  Multisig:  {}", self.explorer_address_url(multisig));
+        println!("  Vault 0:   {}", self.explorer_address_url(&get_vault_pda(multisig, 0, Some(&program_id())).0));
+        println!("  Squads:    {}", squads_app_url(multisig));
+        if let Some(index) = transaction_index {
+            let (proposal_pda, _) = get_proposal_pda(multisig, index, Some(&program_id()));
+            println!("  Proposal {}: {}", index, self.explorer_address_url(&proposal_pda));
+            println!("  Squads Tx: {}", squads_app_tx_url(multisig, index));
+        }
+        if let Some(signature) = signature {
+            println!("  Transaction: {}", self.explorer_tx_url(signature));
+        }
+    }
+
+    /// Trailing argument to pass to other scripts so they target the same cluster
+    pub fn cli_arg(&self) -> &'static str {
+        match self {
+            Network::Devnet => "",
+            Network::Mainnet => "mainnet",
+            Network::Localnet => "localnet",
+        }
+    }
+
+    /// The Squads treasury that receives multisig creation fees.
+    ///
+    /// Devnet and mainnet use the verified addresses. A local validator has its own
+    /// program config, so the treasury is read from that account.
+    pub fn squads_treasury(&self, client: &RpcClient) -> Result<Pubkey, String> {
+        match self {
+            Network::Devnet => Ok(SQUADS_TREASURY_DEVNET.parse().unwrap()),
+            Network::Mainnet => Ok(SQUADS_TREASURY_MAINNET.parse().unwrap()),
+            Network::Localnet => fetch_program_config(client).map(|config| config.treasury).map_err(|e| {
+                format!("{} on localnet. Is the Squads program loaded and its program config initialized?", e)
+            }),
+        }
+    }
+}
+
+/// Squads app home page for a multisig. The app picks its cluster itself, so there is no
+/// cluster suffix.
+pub fn squads_app_url(multisig: &Pubkey) -> String {
+    format!("{}/squads/{}/home", SQUADS_APP, multisig)
+}
+
+/// Squads app page for the transaction at `transaction_index`
+pub fn squads_app_tx_url(multisig: &Pubkey, transaction_index: u64) -> String {
+    format!("{}/squads/{}/tx/{}", SQUADS_APP, multisig, transaction_index)
+}
+
+/// Open `url` in the default browser (`open` on macOS, `start` on Windows, `xdg-open` elsewhere).
+/// Headless machines usually have no browser, so a failed launch prints the URL instead.
+pub fn open_in_browser(url: &str) {
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(windows) {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        Command::new("xdg-open")
+    };
+    let status = command.arg(url).stdout(Stdio::null()).stderr(Stdio::null()).status();
+    match status {
+        Ok(status) if status.success() => println!("\nOpened {}", url),
+        _ => println!("\nCould not open a browser; visit {}", url),
+    }
+}
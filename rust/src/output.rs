@@ -0,0 +1,52 @@
+//! Machine-readable output for the multisig creation scripts
+//!
+//! `--json` prints a single JSON document and `--quiet` prints only the created multisig
+//! address(es), one per line. Either way the human banners are suppressed so stdout can be
+//! consumed by deployment scripts; warnings still go to stderr.
+
+use crate::args::take_flag;
+use serde_json::{json, Value};
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+
+/// How a creation script reports its result
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OutputMode {
+    Human,
+    Json,
+    Quiet,
+}
+
+impl OutputMode {
+    /// Remove `--json` / `--quiet` from `args`
+    pub fn take_from(args: &mut Vec<String>) -> Result<Self, String> {
+        match (take_flag(args, "--json"), take_flag(args, "--quiet")) {
+            (true, true) => Err("--json and --quiet cannot be combined".to_string()),
+            (true, false) => Ok(OutputMode::Json),
+            (false, true) => Ok(OutputMode::Quiet),
+            (false, false) => Ok(OutputMode::Human),
+        }
+    }
+
+    pub fn is_human(&self) -> bool {
+        *self == OutputMode::Human
+    }
+}
+
+/// JSON description of a newly created multisig
+pub fn multisig_json(
+    multisig: &Pubkey,
+    vault: &Pubkey,
+    config_authority: Option<&Pubkey>,
+    threshold: u16,
+    members: &[Pubkey],
+    signature: &Signature,
+) -> Value {
+    json!({
+        "multisig": multisig.to_string(),
+        "vault": vault.to_string(),
+        "config_authority": config_authority.map(|key| key.to_string()),
+        "threshold": threshold,
+        "members": members.iter().map(|key| key.to_string()).collect::<Vec<_>>(),
+        "signature": signature.to_string(),
+    })
+}
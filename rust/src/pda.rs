@@ -0,0 +1,38 @@
+//! PDA derivations the scripts share, on top of `squads_multisig::pda`
+//!
+//! The SDK covers single PDAs; these cover the derivations scripts were repeating by hand.
+
+use crate::program::program_id;
+use solana_sdk::pubkey::Pubkey;
+use squads_multisig::pda::get_ephemeral_signer_pda;
+use squads_multisig::squads_multisig_program::{SEED_BATCH_TRANSACTION, SEED_PREFIX, SEED_TRANSACTION};
+
+/// The first `count` ephemeral signer PDAs of a vault transaction, in index order.
+///
+/// Seeds are `["multisig", transaction_pda, "ephemeral_signer", index]`. The program signs for
+/// these during execution, so an instruction can create an account at one (e.g. a new stake
+/// account) without a keypair.
+pub fn ephemeral_signer_pdas(transaction_pda: &Pubkey, count: u8) -> Vec<Pubkey> {
+    (0..count)
+        .map(|index| get_ephemeral_signer_pda(transaction_pda, index, Some(&program_id())).0)
+        .collect()
+}
+
+/// Inner transaction `transaction_index` (1-based) of the batch at `batch_index`.
+///
+/// Seeds are `["multisig", multisig, "transaction", batch_index, "batch_transaction",
+/// transaction_index]`; the SDK has no helper for these.
+pub fn get_batch_transaction_pda(multisig: &Pubkey, batch_index: u64, transaction_index: u32) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            SEED_PREFIX,
+            multisig.as_ref(),
+            SEED_TRANSACTION,
+            &batch_index.to_le_bytes(),
+            SEED_BATCH_TRANSACTION,
+            &transaction_index.to_le_bytes(),
+        ],
+        &program_id(),
+    )
+    .0
+}
@@ -0,0 +1,104 @@
+//! Member permissions as a typed set
+//!
+//! The program stores a member's permissions as a raw `u8` mask. `PermissionsExt` builds,
+//! queries and prints it in terms of `Permission`, so the bit values live only here.
+
+use squads_multisig::state::{Permission, Permissions};
+
+/// Every permission with its display name, in mask bit order
+const PERMISSION_NAMES: [(Permission, &str); 3] = [
+    (Permission::Initiate, "Initiate"),
+    (Permission::Vote, "Vote"),
+    (Permission::Execute, "Execute"),
+];
+
+pub trait PermissionsExt {
+    /// The set holding exactly the permissions whose flag is true
+    fn from_flags(initiate: bool, vote: bool, execute: bool) -> Self;
+    /// Initiate, Vote and Execute
+    fn all() -> Self;
+    fn contains(&self, permission: Permission) -> bool;
+    /// Permission names, e.g. "Initiate, Vote, Execute", or "None" for an empty set
+    fn to_string(&self) -> String;
+}
+
+impl PermissionsExt for Permissions {
+    fn from_flags(initiate: bool, vote: bool, execute: bool) -> Self {
+        let mask = [initiate, vote, execute]
+            .into_iter()
+            .zip(PERMISSION_NAMES)
+            .filter(|(set, _)| *set)
+            .fold(0, |mask, (_, (permission, _))| mask | permission as u8);
+        Permissions { mask }
+    }
+
+    fn all() -> Self {
+        Self::from_flags(true, true, true)
+    }
+
+    fn contains(&self, permission: Permission) -> bool {
+        self.mask & permission as u8 != 0
+    }
+
+    fn to_string(&self) -> String {
+        let names: Vec<&str> = PERMISSION_NAMES
+            .into_iter()
+            .filter(|(permission, _)| self.contains(*permission))
+            .map(|(_, name)| name)
+            .collect();
+        if names.is_empty() {
+            "None".to_string()
+        } else {
+            names.join(", ")
+        }
+    }
+}
+
+/// Parse a permission string of `i` (Initiate), `v` (Vote) and `e` (Execute), e.g. `ive` or `v`
+pub fn parse_permissions(value: &str) -> Result<Permissions, String> {
+    let mut flags = [false; 3];
+    for c in value.chars() {
+        let index = match c.to_ascii_lowercase() {
+            'i' => 0,
+            'v' => 1,
+            'e' => 2,
+            _ => {
+                return Err(format!(
+                    "invalid permission '{}' in '{}' (use i = Initiate, v = Vote, e = Execute)",
+                    c, value
+                ))
+            }
+        };
+        if flags[index] {
+            return Err(format!("permission '{}' is repeated in '{}'", c, value));
+        }
+        flags[index] = true;
+    }
+    if flags == [false; 3] {
+        return Err("permissions cannot be empty".to_string());
+    }
+    let [initiate, vote, execute] = flags;
+    Ok(Permissions::from_flags(initiate, vote, execute))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn permissions_round_trip_through_flags() {
+        assert_eq!(Permissions::all().mask, 7);
+        assert_eq!(Permissions::from_flags(false, true, false).mask, Permission::Vote as u8);
+        assert_eq!(parse_permissions("ive"), Ok(Permissions::all()));
+        assert_eq!(parse_permissions("EI"), Ok(Permissions::from_flags(true, false, true)));
+        assert!(parse_permissions("vv").is_err());
+        assert!(parse_permissions("x").is_err());
+        assert!(parse_permissions("").is_err());
+
+        let execute_only = Permissions::from_flags(false, false, true);
+        assert!(execute_only.contains(Permission::Execute));
+        assert!(!execute_only.contains(Permission::Vote));
+        assert_eq!(execute_only.to_string(), "Execute");
+        assert_eq!(Permissions::from_flags(false, false, false).to_string(), "None");
+    }
+}
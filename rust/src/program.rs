@@ -0,0 +1,106 @@
+//! Which Squads program the scripts talk to
+//!
+//! Everything defaults to `squads_multisig_program::ID`, the canonical deployment. On a local
+//! validator or a forked deployment the program lives elsewhere, so every script accepts
+//! `--program-id <address>`; `program_id()` then returns it for every instruction and PDA. An
+//! override is checked once against the cluster: the account must exist and be executable.
+//! program-call-proposal's own `--program-id` names its target program, so there the override
+//! is `--squads-program-id`.
+//!
+//! `fetch_program_config` reads the program's global config, which holds the multisig creation
+//! fee and the treasury it is paid to.
+
+use crate::addressbook::try_resolve_pubkey;
+use crate::args::take_flag_value;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use squads_multisig::anchor_lang::AccountDeserialize;
+use squads_multisig::pda::get_program_config_pda;
+use squads_multisig::squads_multisig_program;
+use squads_multisig::squads_multisig_program::state::ProgramConfig;
+use std::sync::OnceLock;
+
+static PROGRAM_ID_OVERRIDE: OnceLock<Pubkey> = OnceLock::new();
+static PROGRAM_CHECKED: OnceLock<()> = OnceLock::new();
+
+/// The Squads program id: the `--program-id` override, or the canonical deployment
+pub fn program_id() -> Pubkey {
+    PROGRAM_ID_OVERRIDE.get().copied().unwrap_or(squads_multisig_program::ID)
+}
+
+/// Whether `--program-id` replaced the canonical program id
+pub fn is_overridden() -> bool {
+    PROGRAM_ID_OVERRIDE.get().is_some()
+}
+
+/// Remove `--program-id <address>` from `args` and make it the program id for this process
+pub fn take_program_id(args: &mut Vec<String>) -> Result<(), String> {
+    take_program_id_flag(args, "--program-id")
+}
+
+/// `take_program_id` under another flag name
+pub fn take_program_id_flag(args: &mut Vec<String>, flag: &str) -> Result<(), String> {
+    let Some(value) = take_flag_value(args, flag)? else {
+        return Ok(());
+    };
+    let id = try_resolve_pubkey(&value).map_err(|e| format!("invalid {}: {}", flag, e))?;
+    if id != squads_multisig_program::ID {
+        println!("Squads Program: {} (--program-id override)", id);
+    }
+    PROGRAM_ID_OVERRIDE.set(id).map_err(|_| "--program-id was already set".to_string())
+}
+
+/// `take_program_id`, printing the error and exiting if it fails
+pub fn take_program_id_or_exit(args: &mut Vec<String>) {
+    if let Err(e) = take_program_id(args) {
+        println!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+/// Check that an overridden program id is an executable account on `client`'s cluster. The
+/// canonical id isn't checked, and the check runs at most once per process.
+pub fn check_program(client: &RpcClient) -> Result<(), String> {
+    if !is_overridden() || PROGRAM_CHECKED.get().is_some() {
+        return Ok(());
+    }
+    let id = program_id();
+    let account = client
+        .get_account_with_commitment(&id, client.commitment())
+        .map_err(|e| format!("failed to fetch program {}: {}", id, e))?
+        .value;
+    check_program_account(account.as_ref())?;
+    let _ = PROGRAM_CHECKED.set(());
+    Ok(())
+}
+
+/// The check behind `check_program`, on the already-fetched account at `program_id()`
+/// (for async clients)
+pub fn check_program_account(account: Option<&Account>) -> Result<(), String> {
+    let id = program_id();
+    match account {
+        None => Err(format!("--program-id {} does not exist on this cluster", id)),
+        Some(account) if !account.executable => {
+            Err(format!("--program-id {} is not an executable program (owner: {})", id, account.owner))
+        }
+        Some(_) => Ok(()),
+    }
+}
+
+/// `check_program`, printing the error and exiting if it fails
+pub fn check_program_or_exit(client: &RpcClient) {
+    if let Err(e) = check_program(client) {
+        println!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+/// Fetch the program config of `program_id()`
+pub fn fetch_program_config(client: &RpcClient) -> Result<ProgramConfig, String> {
+    let (program_config_pda, _) = get_program_config_pda(Some(&program_id()));
+    let account = client
+        .get_account(&program_config_pda)
+        .map_err(|e| format!("program config {} not found ({})", program_config_pda, e))?;
+    ProgramConfig::try_deserialize(&mut account.data.as_slice())
+        .map_err(|e| format!("failed to deserialize program config: {}", e))
+}
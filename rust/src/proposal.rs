@@ -0,0 +1,442 @@
+//! Instruction builders for the vault transaction proposal lifecycle
+//!
+//! create-proposal, execute-proposal and propose-and-execute all build the same four
+//! instructions: create the vault transaction, create its proposal, approve, and execute.
+//! config-transaction-proposal creates a config transaction in place of the vault transaction;
+//! batch-execute executes both kinds. `ProposalState` is what each status allows along the way.
+
+use crate::format::format_unix_timestamp;
+use crate::pda::ephemeral_signer_pdas;
+use crate::program::program_id;
+use solana_client::client_error::ClientError;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    address_lookup_table::AddressLookupTableAccount,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    message::Message,
+    pubkey::Pubkey,
+    system_program,
+    transaction::TransactionError,
+};
+use squads_multisig::anchor_lang::error::ErrorCode;
+use squads_multisig::anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use squads_multisig::pda::{get_proposal_pda, get_transaction_pda, get_vault_pda};
+use squads_multisig::pda::get_spending_limit_pda;
+use squads_multisig::squads_multisig_program::{self, ConfigAction, ConfigTransaction, VaultTransaction};
+use squads_multisig::state::{Proposal, ProposalStatus, VaultTransactionMessage};
+
+/// A proposal's status, converted once from the program's `ProposalStatus` so the scripts
+/// share one set of rules for what each status allows.
+///
+/// `Executing` is a deprecated transient status: current program versions never store it, but
+/// proposals from older deployments may still carry it. `Unknown` is a status added by a newer
+/// program version.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProposalState {
+    Draft { timestamp: i64 },
+    Active { timestamp: i64 },
+    Rejected { timestamp: i64 },
+    Approved { timestamp: i64 },
+    Executing,
+    Executed { timestamp: i64 },
+    Cancelled { timestamp: i64 },
+    Unknown,
+}
+
+impl From<&ProposalStatus> for ProposalState {
+    #[allow(deprecated)]
+    fn from(status: &ProposalStatus) -> Self {
+        match *status {
+            ProposalStatus::Draft { timestamp } => ProposalState::Draft { timestamp },
+            ProposalStatus::Active { timestamp } => ProposalState::Active { timestamp },
+            ProposalStatus::Rejected { timestamp } => ProposalState::Rejected { timestamp },
+            ProposalStatus::Approved { timestamp } => ProposalState::Approved { timestamp },
+            ProposalStatus::Executing => ProposalState::Executing,
+            ProposalStatus::Executed { timestamp } => ProposalState::Executed { timestamp },
+            ProposalStatus::Cancelled { timestamp } => ProposalState::Cancelled { timestamp },
+            _ => ProposalState::Unknown,
+        }
+    }
+}
+
+impl ProposalState {
+    pub fn of(proposal: &Proposal) -> Self {
+        Self::from(&proposal.status)
+    }
+
+    /// Display name, e.g. `Approved`
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProposalState::Draft { .. } => "Draft",
+            ProposalState::Active { .. } => "Active",
+            ProposalState::Rejected { .. } => "Rejected",
+            ProposalState::Approved { .. } => "Approved",
+            ProposalState::Executing => "Executing",
+            ProposalState::Executed { .. } => "Executed",
+            ProposalState::Cancelled { .. } => "Cancelled",
+            ProposalState::Unknown => "Unknown",
+        }
+    }
+
+    /// When the status was set (`Executing` and `Unknown` carry no timestamp)
+    pub fn timestamp(&self) -> Option<i64> {
+        match *self {
+            ProposalState::Draft { timestamp }
+            | ProposalState::Active { timestamp }
+            | ProposalState::Rejected { timestamp }
+            | ProposalState::Approved { timestamp }
+            | ProposalState::Executed { timestamp }
+            | ProposalState::Cancelled { timestamp } => Some(timestamp),
+            ProposalState::Executing | ProposalState::Unknown => None,
+        }
+    }
+
+    /// `name` with when the status was set, e.g. `Approved (since 2024-01-01 00:00:00 UTC)`
+    pub fn label(&self) -> String {
+        match self.timestamp() {
+            Some(timestamp) => format!("{} (since {})", self.name(), format_unix_timestamp(timestamp)),
+            None => self.name().to_string(),
+        }
+    }
+
+    /// Nothing can change the proposal any more; it can only be closed to reclaim rent
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, ProposalState::Rejected { .. } | ProposalState::Executed { .. } | ProposalState::Cancelled { .. })
+    }
+
+    /// Members can approve or reject it
+    pub fn is_votable(&self) -> bool {
+        matches!(self, ProposalState::Active { .. })
+    }
+
+    /// It reached the threshold, so it can be executed (once the time lock has passed) or
+    /// cancelled
+    pub fn is_executable(&self) -> bool {
+        matches!(self, ProposalState::Approved { .. })
+    }
+}
+
+/// Fetch the proposal at `proposal_pda` along with its state
+pub fn fetch_proposal_state(client: &RpcClient, proposal_pda: &Pubkey) -> Result<(Proposal, ProposalState), String> {
+    let account = client
+        .get_account_with_commitment(proposal_pda, client.commitment())
+        .map_err(|e| format!("failed to fetch proposal {}: {}", proposal_pda, e))?
+        .value
+        .ok_or_else(|| format!("proposal {} not found. Does this proposal exist?", proposal_pda))?;
+    let proposal = Proposal::try_deserialize(&mut account.data.as_slice())
+        .map_err(|e| format!("failed to deserialize proposal {}: {}", proposal_pda, e))?;
+    let state = ProposalState::of(&proposal);
+    Ok((proposal, state))
+}
+
+/// True if creating the transaction at `attempted_index` failed because another proposal took
+/// that index first.
+///
+/// The program derives the transaction PDA from the multisig's own `transaction_index + 1`, so a
+/// stale index fails the create instruction's seeds constraint. The error alone doesn't say which
+/// program raised it (and a fee profile shifts instruction positions), so it only counts if the
+/// re-fetched multisig's `current_index` has since reached `attempted_index`.
+pub fn is_index_collision(error: &ClientError, attempted_index: u64, current_index: u64) -> bool {
+    let seeds_mismatch = matches!(
+        error.get_transaction_error(),
+        Some(TransactionError::InstructionError(_, InstructionError::Custom(code)))
+            if code == ErrorCode::ConstraintSeeds as u32
+    );
+    seeds_mismatch && current_index >= attempted_index
+}
+
+/// Create the vault transaction at `transaction_index` from a compiled, serialized message.
+/// `creator` also pays the rent.
+pub fn vault_transaction_create_ix(
+    multisig_pda: &Pubkey,
+    transaction_index: u64,
+    creator: &Pubkey,
+    vault_index: u8,
+    ephemeral_signers: u8,
+    transaction_message: Vec<u8>,
+) -> Instruction {
+    let (transaction_pda, _) = get_transaction_pda(multisig_pda, transaction_index, Some(&program_id()));
+    let accounts = squads_multisig_program::accounts::VaultTransactionCreate {
+        multisig: *multisig_pda,
+        transaction: transaction_pda,
+        creator: *creator,
+        rent_payer: *creator,
+        system_program: system_program::ID,
+    };
+    let data = squads_multisig_program::instruction::VaultTransactionCreate {
+        args: squads_multisig_program::instructions::VaultTransactionCreateArgs {
+            vault_index,
+            ephemeral_signers,
+            transaction_message,
+            memo: None,
+        },
+    };
+    Instruction {
+        program_id: program_id(),
+        accounts: accounts.to_account_metas(Some(false)),
+        data: data.data(),
+    }
+}
+
+/// Create the config transaction at `transaction_index`, applying `actions` in order when
+/// executed. `creator` also pays the rent.
+pub fn config_transaction_create_ix(
+    multisig_pda: &Pubkey,
+    transaction_index: u64,
+    creator: &Pubkey,
+    actions: Vec<ConfigAction>,
+) -> Instruction {
+    let (transaction_pda, _) = get_transaction_pda(multisig_pda, transaction_index, Some(&program_id()));
+    let accounts = squads_multisig_program::accounts::ConfigTransactionCreate {
+        multisig: *multisig_pda,
+        transaction: transaction_pda,
+        creator: *creator,
+        rent_payer: *creator,
+        system_program: system_program::ID,
+    };
+    let data = squads_multisig_program::instruction::ConfigTransactionCreate {
+        args: squads_multisig_program::instructions::ConfigTransactionCreateArgs { actions, memo: None },
+    };
+    Instruction {
+        program_id: program_id(),
+        accounts: accounts.to_account_metas(Some(false)),
+        data: data.data(),
+    }
+}
+
+/// Create the proposal for `transaction_index`, active immediately so members can vote
+pub fn proposal_create_ix(multisig_pda: &Pubkey, transaction_index: u64, creator: &Pubkey) -> Instruction {
+    let (proposal_pda, _) = get_proposal_pda(multisig_pda, transaction_index, Some(&program_id()));
+    let accounts = squads_multisig_program::accounts::ProposalCreate {
+        multisig: *multisig_pda,
+        proposal: proposal_pda,
+        creator: *creator,
+        rent_payer: *creator,
+        system_program: system_program::ID,
+    };
+    let data = squads_multisig_program::instruction::ProposalCreate {
+        args: squads_multisig_program::instructions::ProposalCreateArgs {
+            transaction_index,
+            draft: false,
+        },
+    };
+    Instruction {
+        program_id: program_id(),
+        accounts: accounts.to_account_metas(Some(false)),
+        data: data.data(),
+    }
+}
+
+/// Approve the proposal for `transaction_index` as `member`
+pub fn proposal_approve_ix(multisig_pda: &Pubkey, transaction_index: u64, member: &Pubkey) -> Instruction {
+    let (proposal_pda, _) = get_proposal_pda(multisig_pda, transaction_index, Some(&program_id()));
+    let accounts = squads_multisig_program::accounts::ProposalVote {
+        multisig: *multisig_pda,
+        proposal: proposal_pda,
+        member: *member,
+    };
+    let data = squads_multisig_program::instruction::ProposalApprove {
+        args: squads_multisig_program::instructions::ProposalVoteArgs { memo: None },
+    };
+    Instruction {
+        program_id: program_id(),
+        accounts: accounts.to_account_metas(Some(false)),
+        data: data.data(),
+    }
+}
+
+/// Reject the proposal for `transaction_index` as `member`
+pub fn proposal_reject_ix(multisig_pda: &Pubkey, transaction_index: u64, member: &Pubkey) -> Instruction {
+    let (proposal_pda, _) = get_proposal_pda(multisig_pda, transaction_index, Some(&program_id()));
+    let accounts = squads_multisig_program::accounts::ProposalVote {
+        multisig: *multisig_pda,
+        proposal: proposal_pda,
+        member: *member,
+    };
+    let data = squads_multisig_program::instruction::ProposalReject {
+        args: squads_multisig_program::instructions::ProposalVoteArgs { memo: None },
+    };
+    Instruction {
+        program_id: program_id(),
+        accounts: accounts.to_account_metas(Some(false)),
+        data: data.data(),
+    }
+}
+
+/// SPL Token (and Token-2022) `SetAuthority` instruction tag
+const TOKEN_SET_AUTHORITY: u8 = 6;
+
+/// Inner instructions whose effect can't be undone by another proposal (e.g. handing a mint
+/// authority to an outside key), described for the finality prompt
+pub fn irreversible_actions(message: &VaultTransactionMessage) -> Vec<String> {
+    message
+        .instructions
+        .iter()
+        .filter_map(|ix| {
+            let program_id = message.account_keys.get(ix.program_id_index as usize)?;
+            let is_token = *program_id == spl_token::ID || *program_id == spl_token_2022::ID;
+            if !is_token || ix.data.first() != Some(&TOKEN_SET_AUTHORITY) {
+                return None;
+            }
+            let authority = match ix.data.get(1) {
+                Some(0) => "mint",
+                Some(1) => "freeze",
+                Some(2) => "account owner",
+                Some(3) => "close",
+                _ => "token",
+            };
+            Some(format!("SPL Token SetAuthority ({} authority)", authority))
+        })
+        .collect()
+}
+
+/// Execute the approved config transaction at `transaction_index` as `member`, who also pays
+/// for any reallocation. Spending limits that the actions create or close are passed as
+/// remaining accounts.
+pub fn config_transaction_execute_ix(
+    multisig_pda: &Pubkey,
+    transaction_index: u64,
+    config_transaction: &ConfigTransaction,
+    member: &Pubkey,
+) -> Instruction {
+    let (transaction_pda, _) = get_transaction_pda(multisig_pda, transaction_index, Some(&program_id()));
+    let (proposal_pda, _) = get_proposal_pda(multisig_pda, transaction_index, Some(&program_id()));
+    let accounts = squads_multisig_program::accounts::ConfigTransactionExecute {
+        multisig: *multisig_pda,
+        member: *member,
+        proposal: proposal_pda,
+        transaction: transaction_pda,
+        rent_payer: Some(*member),
+        system_program: Some(system_program::ID),
+    };
+    let mut account_metas = accounts.to_account_metas(Some(false));
+    account_metas.extend(config_transaction.actions.iter().filter_map(|action| match action {
+        ConfigAction::AddSpendingLimit { create_key, .. } => {
+            Some(AccountMeta::new(get_spending_limit_pda(multisig_pda, create_key, Some(&program_id())).0, false))
+        }
+        ConfigAction::RemoveSpendingLimit { spending_limit } => Some(AccountMeta::new(*spending_limit, false)),
+        _ => None,
+    }));
+
+    Instruction {
+        program_id: program_id(),
+        accounts: account_metas,
+        data: squads_multisig_program::instruction::ConfigTransactionExecute {}.data(),
+    }
+}
+
+/// Execute the approved vault transaction at `transaction_index` as `member`.
+///
+/// The inner message's accounts are passed as remaining accounts in the order the program reads
+/// them: the address lookup tables the message uses, its static accounts, then the addresses it
+/// loads from each table (writable, then readonly). `lookup_tables` must hold every table in
+/// `message.address_table_lookups`; it is empty for a message compiled without them. The vault and
+/// any ephemeral signers are not marked as signers: the program signs for them via CPI.
+pub fn vault_transaction_execute_ix(
+    multisig_pda: &Pubkey,
+    transaction_index: u64,
+    vault_transaction: &VaultTransaction,
+    lookup_tables: &[AddressLookupTableAccount],
+    member: &Pubkey,
+) -> Result<Instruction, String> {
+    execute_ix_for_message(
+        multisig_pda,
+        transaction_index,
+        vault_transaction.vault_index,
+        vault_transaction.ephemeral_signer_bumps.len() as u8,
+        &vault_transaction.message,
+        lookup_tables,
+        member,
+    )
+}
+
+fn execute_ix_for_message(
+    multisig_pda: &Pubkey,
+    transaction_index: u64,
+    vault_index: u8,
+    ephemeral_signer_count: u8,
+    message: &VaultTransactionMessage,
+    lookup_tables: &[AddressLookupTableAccount],
+    member: &Pubkey,
+) -> Result<Instruction, String> {
+    let (transaction_pda, _) = get_transaction_pda(multisig_pda, transaction_index, Some(&program_id()));
+    let (proposal_pda, _) = get_proposal_pda(multisig_pda, transaction_index, Some(&program_id()));
+    let (vault_pda, _) = get_vault_pda(multisig_pda, vault_index, Some(&program_id()));
+    let ephemeral_signers = ephemeral_signer_pdas(&transaction_pda, ephemeral_signer_count);
+
+    let mut remaining_accounts: Vec<AccountMeta> = message
+        .address_table_lookups
+        .iter()
+        .map(|lookup| AccountMeta::new_readonly(lookup.account_key, false))
+        .collect();
+    remaining_accounts.extend(message.account_keys.iter().enumerate().map(|(index, pubkey)| AccountMeta {
+        pubkey: *pubkey,
+        is_signer: message.is_signer_index(index) && *pubkey != vault_pda && !ephemeral_signers.contains(pubkey),
+        is_writable: message.is_static_writable_index(index),
+    }));
+    for lookup in &message.address_table_lookups {
+        let table = lookup_tables
+            .iter()
+            .find(|table| table.key == lookup.account_key)
+            .ok_or_else(|| format!("address lookup table {} was not provided", lookup.account_key))?;
+        let loaded = |indexes: &[u8], is_writable: bool| -> Result<Vec<AccountMeta>, String> {
+            indexes
+                .iter()
+                .map(|&index| match table.addresses.get(usize::from(index)) {
+                    Some(&pubkey) => Ok(AccountMeta { pubkey, is_signer: false, is_writable }),
+                    None => Err(format!(
+                        "index {} is out of range for lookup table {} ({} addresses)",
+                        index,
+                        table.key,
+                        table.addresses.len()
+                    )),
+                })
+                .collect()
+        };
+        remaining_accounts.extend(loaded(&lookup.writable_indexes, true)?);
+        remaining_accounts.extend(loaded(&lookup.readonly_indexes, false)?);
+    }
+
+    let accounts = squads_multisig_program::accounts::VaultTransactionExecute {
+        multisig: *multisig_pda,
+        proposal: proposal_pda,
+        transaction: transaction_pda,
+        member: *member,
+    };
+    let mut account_metas = accounts.to_account_metas(Some(false));
+    account_metas.extend(remaining_accounts);
+
+    Ok(Instruction {
+        program_id: program_id(),
+        accounts: account_metas,
+        data: squads_multisig_program::instruction::VaultTransactionExecute {}.data(),
+    })
+}
+
+/// Size of the legacy transaction that will execute a vault transaction, known before the
+/// vault transaction exists
+pub struct ExecuteSize {
+    /// Distinct accounts the execute transaction locks
+    pub accounts: usize,
+    /// Serialized bytes, signatures included
+    pub bytes: usize,
+}
+
+/// Estimate the execute transaction for `message`, with `member` as the only signer and payer.
+/// `message` must not use address table lookups.
+pub fn estimate_execute_size(
+    multisig_pda: &Pubkey,
+    transaction_index: u64,
+    vault_index: u8,
+    ephemeral_signer_count: u8,
+    message: &VaultTransactionMessage,
+    member: &Pubkey,
+) -> ExecuteSize {
+    let instruction =
+        execute_ix_for_message(multisig_pda, transaction_index, vault_index, ephemeral_signer_count, message, &[], member)
+            .expect("message uses address table lookups");
+    let message = Message::new(&[instruction], Some(member));
+    // Compact-u16 signature count (one byte here), the signatures, then the message
+    let bytes = 1 + 64 * usize::from(message.header.num_required_signatures) + message.serialize().len();
+    ExecuteSize { accounts: message.account_keys.len(), bytes }
+}
@@ -0,0 +1,64 @@
+//! Client-side recipient allowlists
+//!
+//! `--recipients-file <path>` points at a text file with one base58 address per line. Anything
+//! after the address on a line is a free-form label, and blank lines and `#` comments are
+//! ignored:
+//!
+//! ```text
+//! # Payroll
+//! DestWallet1... alice
+//! DestWallet2... bob
+//! ```
+
+use solana_sdk::pubkey::Pubkey;
+use std::fs;
+
+/// An allowlisted address and its optional label
+pub struct Recipient {
+    pub address: Pubkey,
+    pub label: Option<String>,
+}
+
+/// Read an allowlist file
+pub fn load_recipients(path: &str) -> Result<Vec<Recipient>, String> {
+    let contents = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+
+    let mut recipients = Vec::new();
+    for (line_number, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (address, label) = match line.split_once(char::is_whitespace) {
+            Some((address, label)) => (address, Some(label.trim().to_string())),
+            None => (line, None),
+        };
+        let address = address
+            .parse()
+            .map_err(|_| format!("{}:{}: invalid address '{}'", path, line_number + 1, address))?;
+        recipients.push(Recipient { address, label });
+    }
+
+    Ok(recipients)
+}
+
+/// Check every destination against the allowlist, printing the result for each.
+///
+/// Returns false if any destination is missing.
+pub fn check_recipients(recipients: &[Recipient], destinations: &[Pubkey]) -> bool {
+    let mut all_allowed = true;
+
+    println!("Recipient allowlist check:");
+    for destination in destinations {
+        match recipients.iter().find(|r| r.address == *destination) {
+            Some(Recipient { label: Some(label), .. }) => println!("  OK: {} ({})", destination, label),
+            Some(_) => println!("  OK: {}", destination),
+            None => {
+                println!("  NOT ALLOWED: {} is not in the recipients file", destination);
+                all_allowed = false;
+            }
+        }
+    }
+
+    all_allowed
+}
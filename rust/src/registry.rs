@@ -0,0 +1,69 @@
+//! Local names for multisigs
+//!
+//! Squads multisigs have no on-chain name. The creation scripts' `--name` goes into the create
+//! instruction's memo (visible in the transaction logs only) and into `~/.squads/registry.json`,
+//! a JSON object mapping multisig address to name, which inspect_multisig reads back. The
+//! registry is optional: a missing file just means no names.
+
+use crate::args::take_flag_value;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// Longest accepted `--name`; the memo is part of the create transaction
+const MAX_NAME_LEN: usize = 64;
+
+/// Remove `--name <string>` from `args`
+pub fn take_name(args: &mut Vec<String>) -> Result<Option<String>, String> {
+    match take_flag_value(args, "--name")? {
+        Some(name) if name.trim().is_empty() => Err("--name must not be empty".to_string()),
+        Some(name) if name.len() > MAX_NAME_LEN => {
+            Err(format!("--name must be at most {} bytes, got {}", MAX_NAME_LEN, name.len()))
+        }
+        Some(name) => Ok(Some(name)),
+        None => Ok(None),
+    }
+}
+
+/// `~/.squads/<file>`, or None without a home directory
+pub(crate) fn squads_file(file: &str) -> Option<PathBuf> {
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".squads").join(file))
+}
+
+/// `~/.squads/registry.json`, or None without a home directory
+pub fn registry_path() -> Option<PathBuf> {
+    squads_file("registry.json")
+}
+
+/// Read a JSON object of strings; a missing file is an empty map
+pub(crate) fn load(path: &PathBuf) -> Result<BTreeMap<String, String>, String> {
+    match fs::read_to_string(path) {
+        Ok(contents) => serde_json::from_str(&contents)
+            .map_err(|e| format!("invalid registry {}: {}", path.display(), e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(BTreeMap::new()),
+        Err(e) => Err(format!("failed to read {}: {}", path.display(), e)),
+    }
+}
+
+/// The registered name of `multisig`, if any
+pub fn lookup_name(multisig: &Pubkey) -> Result<Option<String>, String> {
+    let Some(path) = registry_path() else {
+        return Ok(None);
+    };
+    Ok(load(&path)?.remove(&multisig.to_string()))
+}
+
+/// Record `name` for `multisig`, replacing any earlier name. Returns the registry path.
+pub fn record_name(multisig: &Pubkey, name: &str) -> Result<PathBuf, String> {
+    let path = registry_path().ok_or("HOME is not set, so there is no registry")?;
+    let mut names = load(&path)?;
+    names.insert(multisig.to_string(), name.to_string());
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("failed to create {}: {}", dir.display(), e))?;
+    }
+    let json = serde_json::to_string_pretty(&names).expect("Failed to serialize registry");
+    fs::write(&path, json + "\n").map_err(|e| format!("failed to write {}: {}", path.display(), e))?;
+    Ok(path)
+}
@@ -0,0 +1,434 @@
+//! Transaction sending shared by every script
+//!
+//! By default this is `send_and_confirm_transaction`. Under mainnet congestion two flags help:
+//!
+//! - `--skip-preflight` skips the RPC's simulation, which can reject a transaction on a stale
+//!   blockhash, or when the simulation sees state that will differ on-chain (e.g. an ATA that will
+//!   exist by execution time). It also skips the check that catches a transaction that is
+//!   certain to fail, so a bad transaction lands on-chain and still pays its fee.
+//! - `--rpc-max-retries <n>` sets how many times the RPC node rebroadcasts the transaction.
+//! - `--timeout <secs>` stops waiting for confirmation after that long, instead of for the
+//!   whole blockhash validity window. The transaction may still land; the error carries its
+//!   signature so it can be checked on an explorer.
+//!
+//! Before sending, every transaction is simulated with `simulateTransaction`, the same signed
+//! transaction (blockhash and signatures) that is then sent. A failed simulation prints the
+//! program logs to stderr and aborts the send with the transaction error, so nothing is paid for
+//! a transaction that would fail (e.g. a vault short of funds or a missing ATA). `--no-simulate`
+//! turns this off, and so does `--skip-preflight`, whose point is to send what simulation rejects.
+//!
+//! `--log-dir <path>` is taken here as well, since every sending script parses these options;
+//! see `audit`. So are `--fee-profile <path>` and `--profile <name>`, whose compute budget is
+//! added to every transaction built here; see `fee_profile`.
+//!
+//! With any of these flags the transaction is sent with `send_transaction_with_config` and confirmed by
+//! polling its status until it lands or its blockhash expires.
+//!
+//! `--tx-version legacy|0` picks the wrapper transaction format. The default is a legacy
+//! transaction; `0` builds a v0 message, which can also load accounts from address lookup
+//! tables to fit a long account list (e.g. execute-proposal's) under the size limit. This is
+//! the outer transaction only: the vault transaction message inside a proposal is unaffected.
+//!
+//! Most scripts call `build_and_send`, which fetches a blockhash, builds the transaction and
+//! sends it with these options; scripts that need the landed slot use `send_and_track`.
+//!
+//! `send_and_track` always polls signature statuses itself. It tells a transaction that failed
+//! on-chain apart from one that was dropped, so a slow confirmation isn't reported as a timeout
+//! for a transaction that actually landed.
+
+use crate::args::{take_flag, take_flag_value};
+use crate::audit::take_log_dir;
+use crate::fee_profile::{take_fee_profile, FeeProfile};
+use solana_client::client_error::{ClientError, ClientErrorKind};
+use solana_client::rpc_client::{RpcClient, SerializableTransaction};
+use solana_client::rpc_config::RpcSendTransactionConfig;
+use solana_sdk::{
+    address_lookup_table::{state::AddressLookupTable, AddressLookupTableAccount},
+    commitment_config::CommitmentConfig,
+    hash::Hash,
+    instruction::Instruction,
+    message::{v0, Message, VersionedMessage},
+    pubkey::Pubkey,
+    signature::Signature,
+    signers::Signers,
+    transaction::{TransactionError, VersionedTransaction},
+};
+use std::fmt;
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+const CONFIRM_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Program log lines kept in a simulation failure; the error is usually in the last few
+const SIMULATION_LOG_LINES: usize = 10;
+
+/// How long `wait_for_finalized` waits; finalization normally takes ~13 seconds after landing
+const FINALIZE_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// Largest serialized transaction the network accepts
+pub const PACKET_DATA_SIZE: usize = 1232;
+
+/// Format of the outer transaction a script sends
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum TxVersion {
+    #[default]
+    Legacy,
+    V0,
+}
+
+impl TxVersion {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "legacy" => Ok(TxVersion::Legacy),
+            "0" | "v0" => Ok(TxVersion::V0),
+            _ => Err(format!("invalid --tx-version '{}' (expected legacy or 0)", value)),
+        }
+    }
+}
+
+/// Send knobs parsed from the command line
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SendOptions {
+    pub skip_preflight: bool,
+    pub rpc_max_retries: Option<usize>,
+    pub tx_version: TxVersion,
+    /// Give up waiting for confirmation after this long
+    pub timeout: Option<Duration>,
+    /// Skip the simulation that guards every send
+    pub no_simulate: bool,
+    /// Compute budget from `--fee-profile`/`--profile`
+    pub fee_profile: Option<FeeProfile>,
+}
+
+impl SendOptions {
+    /// Remove `--skip-preflight`, `--rpc-max-retries <n>`, `--tx-version <v>`,
+    /// `--timeout <secs>` and `--no-simulate` from `args`, along with the audit log's `--log-dir`
+    /// and the fee profile's `--fee-profile <path>` and `--profile <name>`
+    pub fn take_from(args: &mut Vec<String>) -> Result<Self, String> {
+        take_log_dir(args)?;
+        let fee_profile = take_fee_profile(args)?;
+        let skip_preflight = take_flag(args, "--skip-preflight");
+        let no_simulate = take_flag(args, "--no-simulate");
+        let rpc_max_retries = match take_flag_value(args, "--rpc-max-retries")? {
+            Some(value) => Some(
+                value
+                    .parse()
+                    .map_err(|_| format!("invalid --rpc-max-retries value '{}'", value))?,
+            ),
+            None => None,
+        };
+        let tx_version = match take_flag_value(args, "--tx-version")? {
+            Some(value) => TxVersion::parse(&value)?,
+            None => TxVersion::Legacy,
+        };
+        let timeout = match take_flag_value(args, "--timeout")? {
+            Some(value) => Some(Duration::from_secs(
+                value
+                    .parse()
+                    .map_err(|_| format!("invalid --timeout value '{}' (seconds)", value))?,
+            )),
+            None => None,
+        };
+        Ok(SendOptions { skip_preflight, rpc_max_retries, tx_version, timeout, no_simulate, fee_profile })
+    }
+
+    /// Like `take_from`, but prints the error and exits on a bad value
+    pub fn take_from_or_exit(args: &mut Vec<String>) -> Self {
+        Self::take_from(args).unwrap_or_else(|e| {
+            println!("Error: {}", e);
+            std::process::exit(1);
+        })
+    }
+
+    fn is_default(&self) -> bool {
+        !self.skip_preflight && self.rpc_max_retries.is_none() && self.timeout.is_none()
+    }
+
+    fn simulates(&self) -> bool {
+        !self.no_simulate && !self.skip_preflight
+    }
+
+    fn timed_out(&self, started: Instant) -> bool {
+        self.timeout.is_some_and(|timeout| started.elapsed() >= timeout)
+    }
+
+    /// Sign `instructions` into a transaction of the selected `--tx-version`
+    pub fn build_transaction<T: Signers + ?Sized>(
+        &self,
+        instructions: &[Instruction],
+        payer: &Pubkey,
+        signers: &T,
+        recent_blockhash: Hash,
+    ) -> Result<VersionedTransaction, String> {
+        self.build_transaction_with_lookup_tables(instructions, payer, signers, recent_blockhash, &[])
+    }
+
+    /// Like `build_transaction`, loading accounts from `lookup_tables` (v0 only)
+    pub fn build_transaction_with_lookup_tables<T: Signers + ?Sized>(
+        &self,
+        instructions: &[Instruction],
+        payer: &Pubkey,
+        signers: &T,
+        recent_blockhash: Hash,
+        lookup_tables: &[AddressLookupTableAccount],
+    ) -> Result<VersionedTransaction, String> {
+        let budgeted;
+        let instructions = match &self.fee_profile {
+            Some(profile) => {
+                budgeted = profile.apply(instructions);
+                &budgeted[..]
+            }
+            None => instructions,
+        };
+        let message = match self.tx_version {
+            TxVersion::Legacy if !lookup_tables.is_empty() => {
+                return Err("address lookup tables require --tx-version 0".to_string());
+            }
+            TxVersion::Legacy => VersionedMessage::Legacy(Message::new_with_blockhash(
+                instructions,
+                Some(payer),
+                &recent_blockhash,
+            )),
+            TxVersion::V0 => VersionedMessage::V0(
+                v0::Message::try_compile(payer, instructions, lookup_tables, recent_blockhash)
+                    .map_err(|e| format!("failed to compile v0 message: {}", e))?,
+            ),
+        };
+        VersionedTransaction::try_new(message, signers).map_err(|e| format!("failed to sign transaction: {}", e))
+    }
+}
+
+/// Fetch an address lookup table for a v0 transaction
+pub fn fetch_lookup_table(client: &RpcClient, address: &Pubkey) -> Result<AddressLookupTableAccount, String> {
+    let account = client
+        .get_account(address)
+        .map_err(|e| format!("failed to fetch lookup table {}: {}", address, e))?;
+    let table = AddressLookupTable::deserialize(&account.data)
+        .map_err(|e| format!("{} is not an address lookup table: {}", address, e))?;
+    Ok(AddressLookupTableAccount { key: *address, addresses: table.addresses.to_vec() })
+}
+
+/// `fetch_lookup_table` for each of `addresses`, in order
+pub fn fetch_lookup_tables(client: &RpcClient, addresses: &[Pubkey]) -> Result<Vec<AddressLookupTableAccount>, String> {
+    addresses.iter().map(|address| fetch_lookup_table(client, address)).collect()
+}
+
+/// Fetch a blockhash, sign `instructions` with `options.build_transaction` and send them with
+/// `send_transaction`. A build failure is returned as a custom client error.
+#[allow(clippy::result_large_err)]
+pub fn build_and_send<T: Signers + ?Sized>(
+    client: &RpcClient,
+    instructions: &[Instruction],
+    payer: &Pubkey,
+    signers: &T,
+    options: &SendOptions,
+) -> Result<Signature, ClientError> {
+    let recent_blockhash = client.get_latest_blockhash()?;
+    let transaction = options
+        .build_transaction(instructions, payer, signers, recent_blockhash)
+        .map_err(ClientErrorKind::Custom)?;
+    send_transaction(client, &transaction, options)
+}
+
+/// Printed (to stderr) before every send with `--skip-preflight`, since nothing catches a bad
+/// transaction
+fn warn_skip_preflight() {
+    eprintln!();
+    eprintln!("!!! WARNING: --skip-preflight is set !!!");
+    eprintln!("The RPC will not simulate this transaction first. If it would fail, it still lands");
+    eprintln!("on-chain and the fee is burned. Only use this when simulation fails for a reason");
+    eprintln!("that will not apply on-chain (e.g. an account created before execution).");
+    eprintln!();
+}
+
+/// Simulate `transaction` unless the options opt out. A failure prints (to stderr) the tail of
+/// the program logs and is returned as the transaction error itself, so callers can still match
+/// on it (e.g. create-proposal's index collision check).
+#[allow(clippy::result_large_err)]
+fn simulate_before_send(
+    client: &RpcClient,
+    transaction: &impl SerializableTransaction,
+    options: &SendOptions,
+) -> Result<(), ClientError> {
+    if !options.simulates() {
+        return Ok(());
+    }
+    let result = client.simulate_transaction(transaction)?.value;
+    let Some(error) = result.err else {
+        return Ok(());
+    };
+    let logs = result.logs.unwrap_or_default();
+    eprintln!();
+    eprintln!("Simulation failed, nothing was sent: {}", error);
+    if !logs.is_empty() {
+        eprintln!("Program logs:");
+        for line in &logs[logs.len().saturating_sub(SIMULATION_LOG_LINES)..] {
+            eprintln!("  {}", line);
+        }
+    }
+    eprintln!("(rerun with --no-simulate to send anyway)");
+    Err(ClientErrorKind::TransactionError(error).into())
+}
+
+/// Send `transaction` and wait for confirmation at the client's commitment
+// Same error type as `RpcClient::send_and_confirm_transaction`, so call sites are unchanged
+#[allow(clippy::result_large_err)]
+pub fn send_transaction(
+    client: &RpcClient,
+    transaction: &impl SerializableTransaction,
+    options: &SendOptions,
+) -> Result<Signature, ClientError> {
+    simulate_before_send(client, transaction, options)?;
+    if options.is_default() {
+        return client.send_and_confirm_transaction(transaction);
+    }
+
+    if options.skip_preflight {
+        warn_skip_preflight();
+    }
+
+    let config = RpcSendTransactionConfig {
+        skip_preflight: options.skip_preflight,
+        max_retries: options.rpc_max_retries,
+        preflight_commitment: Some(client.commitment().commitment),
+        ..RpcSendTransactionConfig::default()
+    };
+    let signature = client.send_transaction_with_config(transaction, config)?;
+    let started = Instant::now();
+
+    let blockhash = *transaction.get_recent_blockhash();
+    loop {
+        match client.get_signature_status(&signature)? {
+            Some(Ok(())) => return Ok(signature),
+            Some(Err(e)) => return Err(ClientErrorKind::TransactionError(e).into()),
+            None => {
+                if options.timed_out(started) {
+                    let unknown = SendError::Unknown { signature, waited: started.elapsed() };
+                    return Err(ClientErrorKind::Custom(unknown.to_string()).into());
+                }
+                if !client.is_blockhash_valid(&blockhash, client.commitment())? {
+                    return Err(ClientErrorKind::Custom(format!(
+                        "transaction {} was not confirmed before its blockhash expired",
+                        signature
+                    ))
+                    .into());
+                }
+                sleep(CONFIRM_POLL_INTERVAL);
+            }
+        }
+    }
+}
+
+/// A transaction that reached the client's commitment level
+pub struct Landed {
+    pub signature: Signature,
+    pub slot: u64,
+}
+
+/// Why `send_and_track` did not return a landed transaction
+pub enum SendError {
+    /// The RPC rejected the request, including preflight simulation failures
+    Rpc(Box<ClientError>),
+    /// The transaction landed but failed
+    Failed { signature: Signature, slot: u64, error: TransactionError },
+    /// The blockhash expired without the transaction landing, so it can never land
+    Dropped { signature: Signature },
+    /// `--timeout` passed before the transaction landed or was dropped
+    Unknown { signature: Signature, waited: Duration },
+}
+
+impl fmt::Display for SendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SendError::Rpc(e) => write!(f, "{}", e),
+            SendError::Failed { signature, slot, error } => {
+                write!(f, "transaction {} failed in slot {}: {}", signature, slot, error)
+            }
+            SendError::Dropped { signature } => write!(
+                f,
+                "transaction {} was dropped (blockhash expired before it landed); it is safe to resubmit",
+                signature
+            ),
+            SendError::Unknown { signature, waited } => write!(
+                f,
+                "stopped waiting after {}s; transaction {} status unknown - check explorer (it may still land)",
+                waited.as_secs(),
+                signature
+            ),
+        }
+    }
+}
+
+impl From<ClientError> for SendError {
+    fn from(e: ClientError) -> Self {
+        SendError::Rpc(Box::new(e))
+    }
+}
+
+/// Send `transaction` and poll `get_signature_statuses` until it reaches the client's commitment
+/// or the block height passes `last_valid_block_height` (from `get_latest_blockhash_with_commitment`
+/// for the transaction's blockhash).
+pub fn send_and_track(
+    client: &RpcClient,
+    transaction: &impl SerializableTransaction,
+    options: &SendOptions,
+    last_valid_block_height: u64,
+) -> Result<Landed, SendError> {
+    simulate_before_send(client, transaction, options)?;
+    if options.skip_preflight {
+        warn_skip_preflight();
+    }
+
+    let config = RpcSendTransactionConfig {
+        skip_preflight: options.skip_preflight,
+        max_retries: options.rpc_max_retries,
+        preflight_commitment: Some(client.commitment().commitment),
+        ..RpcSendTransactionConfig::default()
+    };
+    let signature = client.send_transaction_with_config(transaction, config)?;
+    let started = Instant::now();
+
+    loop {
+        let statuses = client.get_signature_statuses(&[signature])?.value;
+        match statuses.into_iter().next().flatten() {
+            Some(status) => {
+                if let Some(error) = status.err {
+                    return Err(SendError::Failed { signature, slot: status.slot, error });
+                }
+                if status.satisfies_commitment(client.commitment()) {
+                    return Ok(Landed { signature, slot: status.slot });
+                }
+            }
+            None => {
+                if client.get_block_height()? > last_valid_block_height {
+                    return Err(SendError::Dropped { signature });
+                }
+            }
+        }
+        if options.timed_out(started) {
+            return Err(SendError::Unknown { signature, waited: started.elapsed() });
+        }
+        sleep(CONFIRM_POLL_INTERVAL);
+    }
+}
+
+/// After a transaction has landed, poll until it is finalized and can no longer be rolled back.
+/// Returns the slot it was finalized in.
+pub fn wait_for_finalized(client: &RpcClient, signature: &Signature) -> Result<u64, SendError> {
+    let started = Instant::now();
+    loop {
+        let statuses = client.get_signature_statuses(&[*signature])?.value;
+        if let Some(status) = statuses.into_iter().next().flatten() {
+            if let Some(error) = status.err {
+                return Err(SendError::Failed { signature: *signature, slot: status.slot, error });
+            }
+            if status.satisfies_commitment(CommitmentConfig::finalized()) {
+                return Ok(status.slot);
+            }
+        }
+        if started.elapsed() >= FINALIZE_TIMEOUT {
+            return Err(SendError::Unknown { signature: *signature, waited: started.elapsed() });
+        }
+        sleep(CONFIRM_POLL_INTERVAL);
+    }
+}
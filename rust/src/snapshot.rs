@@ -0,0 +1,94 @@
+//! JSON snapshots of on-chain accounts for offline validation
+//!
+//! `inspect-spending-limit --dump <path>` writes a snapshot and
+//! `use-spending-limit --snapshot <path>` validates a transfer against it without RPC.
+
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use squads_multisig::state::{Period, SpendingLimit};
+use std::fs;
+
+/// Serializable copy of a `SpendingLimit` account, with pubkeys as base58 strings
+#[derive(Serialize, Deserialize)]
+pub struct SpendingLimitSnapshot {
+    pub address: String,
+    pub multisig: String,
+    pub create_key: String,
+    pub vault_index: u8,
+    pub mint: String,
+    pub amount: u64,
+    pub period: String,
+    pub remaining_amount: u64,
+    pub last_reset: i64,
+    pub bump: u8,
+    pub members: Vec<String>,
+    pub destinations: Vec<String>,
+}
+
+impl SpendingLimitSnapshot {
+    pub fn new(address: &Pubkey, limit: &SpendingLimit) -> Self {
+        Self {
+            address: address.to_string(),
+            multisig: limit.multisig.to_string(),
+            create_key: limit.create_key.to_string(),
+            vault_index: limit.vault_index,
+            mint: limit.mint.to_string(),
+            amount: limit.amount,
+            period: format!("{:?}", limit.period),
+            remaining_amount: limit.remaining_amount,
+            last_reset: limit.last_reset,
+            bump: limit.bump,
+            members: limit.members.iter().map(|m| m.to_string()).collect(),
+            destinations: limit.destinations.iter().map(|d| d.to_string()).collect(),
+        }
+    }
+
+    /// Rebuild the spending limit address and account from the snapshot
+    pub fn to_spending_limit(&self) -> Result<(Pubkey, SpendingLimit), String> {
+        let parse = |field: &str, value: &str| -> Result<Pubkey, String> {
+            value.parse().map_err(|_| format!("invalid {} address in snapshot: {}", field, value))
+        };
+        let parse_all = |field: &str, values: &[String]| -> Result<Vec<Pubkey>, String> {
+            values.iter().map(|v| parse(field, v)).collect()
+        };
+
+        let period = match self.period.as_str() {
+            "OneTime" => Period::OneTime,
+            "Day" => Period::Day,
+            "Week" => Period::Week,
+            "Month" => Period::Month,
+            other => return Err(format!("invalid period in snapshot: {}", other)),
+        };
+
+        let limit = SpendingLimit {
+            multisig: parse("multisig", &self.multisig)?,
+            create_key: parse("create_key", &self.create_key)?,
+            vault_index: self.vault_index,
+            mint: parse("mint", &self.mint)?,
+            amount: self.amount,
+            period,
+            remaining_amount: self.remaining_amount,
+            last_reset: self.last_reset,
+            bump: self.bump,
+            members: parse_all("member", &self.members)?,
+            destinations: parse_all("destination", &self.destinations)?,
+        };
+
+        Ok((parse("spending limit", &self.address)?, limit))
+    }
+}
+
+/// Write a spending limit snapshot as pretty-printed JSON
+pub fn write_spending_limit_snapshot(path: &str, address: &Pubkey, limit: &SpendingLimit) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(&SpendingLimitSnapshot::new(address, limit))
+        .map_err(|e| format!("failed to serialize snapshot: {}", e))?;
+    fs::write(path, json).map_err(|e| format!("failed to write {}: {}", path, e))
+}
+
+/// Read a snapshot written by `write_spending_limit_snapshot`
+pub fn read_spending_limit_snapshot(path: &str) -> Result<(Pubkey, SpendingLimit), String> {
+    let json = fs::read_to_string(path).map_err(|e| format!("failed to read {}: {}", path, e))?;
+    let snapshot: SpendingLimitSnapshot =
+        serde_json::from_str(&json).map_err(|e| format!("invalid snapshot {}: {}", path, e))?;
+    snapshot.to_spending_limit()
+}
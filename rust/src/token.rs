@@ -0,0 +1,116 @@
+//! SPL Token and Token-2022 helpers
+//!
+//! A mint's owner is the token program that manages it. ATAs are derived with that program id
+//! and token instructions must target it, so look it up instead of assuming `spl_token::ID`.
+
+use serde_json::json;
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_request::RpcRequest;
+use solana_client::rpc_response::{Response, RpcKeyedAccount};
+use solana_sdk::pubkey::Pubkey;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use spl_token_2022::extension::StateWithExtensions;
+use spl_token_2022::state::{Account, Mint};
+
+/// True for the legacy SPL Token program and Token-2022
+pub fn is_token_program(program_id: &Pubkey) -> bool {
+    *program_id == spl_token::ID || *program_id == spl_token_2022::ID
+}
+
+/// Fetch `mint` and return the token program that owns it
+pub fn resolve_token_program(client: &RpcClient, mint: &Pubkey) -> Result<Pubkey, String> {
+    let account = client
+        .get_account(mint)
+        .map_err(|e| format!("Failed to fetch mint {}: {}", mint, e))?;
+    if !is_token_program(&account.owner) {
+        return Err(format!("{} is not a token mint (owned by {})", mint, account.owner));
+    }
+    Ok(account.owner)
+}
+
+/// Associated token account of `owner` for `mint` under `token_program`
+pub fn derive_ata(owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(owner, mint, token_program)
+}
+
+/// Unpack mint account data from either token program, ignoring any Token-2022 extensions
+pub fn unpack_mint(data: &[u8]) -> Result<Mint, String> {
+    StateWithExtensions::<Mint>::unpack(data)
+        .map(|state| state.base)
+        .map_err(|e| format!("Failed to deserialize mint: {}", e))
+}
+
+/// The token program and decimals of a token spending limit's mint, from its fetched account.
+/// `token_program_override` is used in place of the mint's owner; without it the owner must be
+/// one of the token programs.
+pub fn mint_program_and_decimals(
+    mint: &Pubkey,
+    owner: &Pubkey,
+    data: &[u8],
+    token_program_override: Option<Pubkey>,
+) -> Result<(Pubkey, u8), String> {
+    let token_program = match token_program_override {
+        Some(program) => program,
+        None if is_token_program(owner) => *owner,
+        None => {
+            return Err(format!(
+                "{} is not a token mint (owned by {})\nPass --token-program <pubkey> if this mint uses a different token program.",
+                mint, owner
+            ))
+        }
+    };
+    let decimals = unpack_mint(data)
+        .map_err(|e| format!("could not read mint decimals for {}: {}", mint, e))?
+        .decimals;
+    Ok((token_program, decimals))
+}
+
+/// Unpack token account data from either token program, ignoring any Token-2022 extensions
+pub fn unpack_token_account(data: &[u8]) -> Result<Account, String> {
+    StateWithExtensions::<Account>::unpack(data)
+        .map(|state| state.base)
+        .map_err(|e| format!("Failed to deserialize token account: {}", e))
+}
+
+/// A token account found by `fetch_token_accounts`
+pub struct OwnedTokenAccount {
+    pub address: Pubkey,
+    pub token_program: Pubkey,
+    pub lamports: u64,
+    pub state: Account,
+}
+
+/// Every token account owned by `owner` under both token programs, via getTokenAccountsByOwner
+/// (which the public RPCs serve, unlike getProgramAccounts). Raw data is requested so the
+/// accounts unpack the same way as a direct fetch.
+pub fn fetch_token_accounts(client: &RpcClient, owner: &Pubkey) -> Result<Vec<OwnedTokenAccount>, String> {
+    let mut accounts = Vec::new();
+    for token_program in [spl_token::ID, spl_token_2022::ID] {
+        let params = json!([
+            owner.to_string(),
+            { "programId": token_program.to_string() },
+            { "encoding": UiAccountEncoding::Base64, "commitment": client.commitment() },
+        ]);
+        let response: Response<Vec<RpcKeyedAccount>> = client
+            .send(RpcRequest::GetTokenAccountsByOwner, params)
+            .map_err(|e| format!("failed to fetch token accounts of {}: {}", owner, e))?;
+        for keyed in response.value {
+            let address: Pubkey = keyed
+                .pubkey
+                .parse()
+                .map_err(|e| format!("invalid token account address {}: {}", keyed.pubkey, e))?;
+            let account = keyed
+                .account
+                .decode::<solana_sdk::account::Account>()
+                .ok_or_else(|| format!("failed to decode token account {}", address))?;
+            accounts.push(OwnedTokenAccount {
+                address,
+                token_program,
+                lamports: account.lamports,
+                state: unpack_token_account(&account.data)?,
+            });
+        }
+    }
+    Ok(accounts)
+}
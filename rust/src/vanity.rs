@@ -0,0 +1,143 @@
+//! Grinding `create_key`s for vanity multisig addresses
+//!
+//! A multisig's address is a PDA of its `create_key`, so the only way to choose a prefix is to
+//! generate keypairs until one derives an address that starts with it. Each extra character
+//! multiplies the expected work by up to 58, so anything past four or five characters is slow.
+
+use crate::program::program_id;
+use solana_sdk::signature::{Keypair, Signer};
+use squads_multisig::pda::get_multisig_pda;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// How often `grind_create_key` reports progress
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A prefix to search multisig addresses for
+#[derive(Clone, Debug)]
+pub struct VanityPrefix {
+    prefix: String,
+    ignore_case: bool,
+}
+
+impl VanityPrefix {
+    /// Validate `prefix` against the base58 alphabet (no `0`, `O`, `I` or `l`)
+    pub fn new(prefix: &str, ignore_case: bool) -> Result<Self, String> {
+        if prefix.is_empty() {
+            return Err("Vanity prefix cannot be empty".to_string());
+        }
+        for c in prefix.chars() {
+            let valid = if ignore_case {
+                BASE58_ALPHABET.contains(c.to_ascii_lowercase()) || BASE58_ALPHABET.contains(c.to_ascii_uppercase())
+            } else {
+                BASE58_ALPHABET.contains(c)
+            };
+            if !valid {
+                return Err(format!(
+                    "'{}' can never appear in an address (base58 has no 0, O, I or l)",
+                    c
+                ));
+            }
+        }
+        let prefix = if ignore_case { prefix.to_ascii_lowercase() } else { prefix.to_string() };
+        Ok(VanityPrefix { prefix, ignore_case })
+    }
+
+    pub fn matches(&self, address: &str) -> bool {
+        if self.ignore_case {
+            address.len() >= self.prefix.len() && address[..self.prefix.len()].eq_ignore_ascii_case(&self.prefix)
+        } else {
+            address.starts_with(&self.prefix)
+        }
+    }
+
+    /// Rough number of attempts to expect, treating every base58 character as equally likely
+    pub fn expected_attempts(&self) -> f64 {
+        self.prefix
+            .chars()
+            .map(|c| {
+                let forms = if self.ignore_case && c.is_ascii_alphabetic() {
+                    [c.to_ascii_lowercase(), c.to_ascii_uppercase()]
+                        .iter()
+                        .filter(|form| BASE58_ALPHABET.contains(**form))
+                        .count()
+                } else {
+                    1
+                };
+                58.0 / forms as f64
+            })
+            .product()
+    }
+}
+
+impl std::fmt::Display for VanityPrefix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.prefix, if self.ignore_case { " (case-insensitive)" } else { "" })
+    }
+}
+
+/// Outcome of a vanity search
+pub struct GrindResult {
+    /// The winning create_key, or None if the timeout was reached first
+    pub create_key: Option<Keypair>,
+    pub attempts: u64,
+    pub elapsed: Duration,
+}
+
+impl GrindResult {
+    pub fn attempts_per_sec(&self) -> f64 {
+        self.attempts as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+/// Generate create_keys on every core until one derives a multisig address matching `prefix`
+///
+/// `progress` is called every few seconds with (attempts, elapsed) so long searches show
+/// their rate.
+pub fn grind_create_key(prefix: &VanityPrefix, timeout: Duration, progress: impl Fn(u64, Duration) + Sync) -> GrindResult {
+    let threads = thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+    let found = AtomicBool::new(false);
+    let attempts = AtomicU64::new(0);
+    let winner: Mutex<Option<Keypair>> = Mutex::new(None);
+    let start = Instant::now();
+
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| {
+                while !found.load(Ordering::Relaxed) && start.elapsed() < timeout {
+                    // Batch the shared counter update so threads don't contend on it
+                    for _ in 0..256 {
+                        let create_key = Keypair::new();
+                        let (multisig_pda, _) = get_multisig_pda(&create_key.pubkey(), Some(&program_id()));
+                        if prefix.matches(&multisig_pda.to_string()) {
+                            if !found.swap(true, Ordering::Relaxed) {
+                                *winner.lock().expect("vanity winner lock poisoned") = Some(create_key);
+                            }
+                            break;
+                        }
+                    }
+                    attempts.fetch_add(256, Ordering::Relaxed);
+                }
+            });
+        }
+
+        let mut next_report = PROGRESS_INTERVAL;
+        while !found.load(Ordering::Relaxed) && start.elapsed() < timeout {
+            thread::sleep(Duration::from_millis(100));
+            if start.elapsed() >= next_report {
+                progress(attempts.load(Ordering::Relaxed), start.elapsed());
+                next_report += PROGRESS_INTERVAL;
+            }
+        }
+    });
+
+    GrindResult {
+        create_key: winner.into_inner().expect("vanity winner lock poisoned"),
+        attempts: attempts.load(Ordering::Relaxed),
+        elapsed: start.elapsed(),
+    }
+}
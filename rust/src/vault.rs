@@ -0,0 +1,28 @@
+//! Vault index parsing
+//!
+//! Squads derives vault PDAs from a `u8` index. Almost every multisig only uses vault 0, so an
+//! unusually high index is more likely a typo than intent.
+
+/// Highest vault index that isn't worth a warning
+const TYPICAL_MAX_VAULT_INDEX: u8 = 8;
+
+/// Parse a vault index, rejecting anything outside 0–255 with a specific message.
+///
+/// Prints a warning (but still succeeds) for indexes above 8.
+pub fn parse_vault_index(value: &str) -> Result<u8, String> {
+    let index: i64 = value
+        .trim()
+        .parse()
+        .map_err(|_| format!("vault index must be a number 0–255, got '{}'", value))?;
+
+    let index = u8::try_from(index).map_err(|_| format!("vault index must be 0–255, got {}", index))?;
+
+    if index > TYPICAL_MAX_VAULT_INDEX {
+        println!(
+            "Warning: vault index {} is unusual (most multisigs only use vault 0). Double-check it.",
+            index
+        );
+    }
+
+    Ok(index)
+}
@@ -0,0 +1,568 @@
+//! Offline tests of the library against canned account data in `tests/fixtures/`
+//!
+//! Each fixture is the base64 of a Squads account as the program stores it (discriminator
+//! included), serialized with the squads-multisig version in Cargo.toml, or of a token mint
+//! (`mint_*`) as its token program stores it. Keys are `key(n)` = 32 bytes of `n`. If a
+//! dependency bump changes an account layout, these fail instead of the scripts misreading live
+//! accounts.
+//!
+//! Alongside the parsing tests are the checks that work on Squads types: instruction builders
+//! and decoding, `--expect` classification, member and threshold validation, ephemeral signer
+//! derivation, and the RPC paths (account cache, pre-send simulation) against a mock client.
+//! Plain parsers with no Squads types (dates, SOL amounts, fee profiles, permission strings) are
+//! tested next to their code.
+
+use base64::Engine;
+use serde_json::json;
+use solana_client::client_error::{ClientError, ClientErrorKind};
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_request::RpcRequest;
+use solana_client::rpc_response::{Response, RpcResponseContext, RpcSimulateTransactionResult};
+use solana_sdk::{
+    account::Account, address_lookup_table::AddressLookupTableAccount, hash::Hash,
+    instruction::{Instruction, InstructionError},
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction, system_program,
+    transaction::TransactionError,
+};
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use spl_token::instruction::AuthorityType;
+use squads_multisig::anchor_lang::error::ErrorCode;
+use squads_multisig::anchor_lang::AccountDeserialize;
+use squads_multisig::pda::{get_proposal_pda, get_transaction_pda, get_vault_pda};
+use squads_multisig::squads_multisig_program::{
+    self, MultisigMessageAddressTableLookup, TransactionMessage, VaultTransaction,
+};
+use squads_multisig::state::{
+    ConfigAction, Member, Period, Permissions, Proposal, ProposalStatus, SpendingLimit, VaultTransactionMessage,
+};
+use squads_multisig::vault_transaction::VaultTransactionMessageExt;
+use squads_rust::cache::AccountCache;
+use squads_rust::decode::decode_instruction;
+use squads_rust::expect::{decode_actions, expectation_mismatches, InnerAction};
+use squads_rust::format::{progress_bar, usage_percent};
+use squads_rust::multisig::{
+    check_members, check_roles, check_threshold, check_voters, normalize_members, parse_multisig, pending_voters,
+};
+use squads_rust::pda::ephemeral_signer_pdas;
+use squads_rust::permissions::PermissionsExt;
+use squads_rust::proposal::{
+    config_transaction_create_ix, is_index_collision, proposal_create_ix, vault_transaction_execute_ix, ProposalState,
+};
+use squads_rust::send::{send_transaction, SendOptions};
+use squads_rust::token::{derive_ata, mint_program_and_decimals, resolve_token_program, unpack_mint};
+
+const TIMESTAMP: i64 = 1_700_000_000;
+
+fn key(n: u8) -> Pubkey {
+    Pubkey::new_from_array([n; 32])
+}
+
+fn fixture(name: &str) -> Vec<u8> {
+    let path = format!("{}/tests/fixtures/{}.b64", env!("CARGO_MANIFEST_DIR"), name);
+    let encoded = std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("Failed to read {}: {}", path, e));
+    base64::engine::general_purpose::STANDARD
+        .decode(encoded.trim())
+        .unwrap_or_else(|e| panic!("Invalid base64 in {}: {}", path, e))
+}
+
+fn squads_account(data: Vec<u8>) -> Account {
+    Account { lamports: 1, data, owner: squads_multisig_program::ID, executable: false, rent_epoch: 0 }
+}
+
+/// A client whose first getAccountInfo returns `owner`'s `data`; later ones find no account
+fn mock_account_client(owner: &Pubkey, data: &[u8]) -> RpcClient {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(data);
+    let response = json!({
+        "context": { "slot": 1 },
+        "value": { "lamports": 1, "data": [encoded, "base64"], "owner": owner.to_string(), "executable": false, "rentEpoch": 0 },
+    });
+    RpcClient::new_mock_with_mocks("succeeds", [(RpcRequest::GetAccountInfo, response)].into_iter().collect())
+}
+
+/// Compile `instructions` into the message a vault transaction stores
+fn vault_message(vault: &Pubkey, instructions: &[Instruction]) -> VaultTransactionMessage {
+    let message = TransactionMessage::try_compile(vault, instructions, &[]).unwrap();
+    VaultTransactionMessage::try_from(message).unwrap()
+}
+
+#[test]
+fn multisig_fixture_parses() {
+    let multisig = parse_multisig(&key(3), &squads_account(fixture("multisig"))).unwrap_or_else(|e| panic!("{}", e));
+
+    assert_eq!(multisig.create_key, key(1));
+    assert_eq!(multisig.config_authority, key(2));
+    assert_eq!(multisig.threshold, 2);
+    assert_eq!(multisig.time_lock, 3600);
+    assert_eq!(multisig.transaction_index, 5);
+    assert_eq!(multisig.stale_transaction_index, 2);
+    assert_eq!(multisig.rent_collector, None);
+    assert_eq!(multisig.members.len(), 3);
+    assert_eq!(multisig.members[0].permissions.to_string(), "Initiate, Vote, Execute");
+    assert_eq!(multisig.members[2].permissions.to_string(), "Vote");
+
+    assert!(check_voters(&multisig.members, multisig.threshold).is_ok());
+    assert!(check_voters(&multisig.members, 4).is_err());
+    assert!(check_roles(&multisig.members).is_ok());
+    assert!(check_roles(&multisig.members[2..]).is_err());
+
+    // Every fixture member has Vote, so the pending voters are the members who haven't voted
+    let voted = [multisig.members[1].key];
+    assert_eq!(pending_voters(&multisig, &voted), vec![multisig.members[0].key, multisig.members[2].key]);
+    assert_eq!(progress_bar(voted.len(), usize::from(multisig.threshold)), "[█░] 1/2");
+}
+
+#[test]
+fn account_cache_fetches_and_parses_each_account_once() {
+    let client = mock_account_client(&squads_multisig_program::ID, &fixture("multisig"));
+    let mut cache = AccountCache::new(&client);
+    let threshold = cache.multisig(&key(3)).unwrap().threshold;
+    // The mocked account was consumed, so a second fetch would have found nothing
+    assert!(client.get_account_with_commitment(&key(3), client.commitment()).unwrap().value.is_none());
+    assert_eq!(cache.multisig(&key(3)).unwrap().threshold, threshold);
+
+    let mut mint = vec![0; spl_token::state::Mint::LEN];
+    spl_token::state::Mint { decimals: 6, is_initialized: true, ..Default::default() }.pack_into_slice(&mut mint);
+    let client = mock_account_client(&spl_token::ID, &mint);
+    let mut cache = AccountCache::new(&client);
+    assert_eq!(cache.mint_decimals(&key(50)), Ok(6));
+    assert_eq!(cache.mint_decimals(&key(50)), Ok(6));
+    assert!(cache.mint_decimals(&key(51)).is_err());
+    assert!(cache.mint_decimals(&key(51)).is_err());
+}
+
+#[test]
+fn parse_multisig_rejects_other_accounts() {
+    let mut wrong_owner = squads_account(fixture("multisig"));
+    wrong_owner.owner = system_program::ID;
+    let error = parse_multisig(&key(3), &wrong_owner).err().unwrap();
+    assert!(error.contains("not a Squads multisig"), "{}", error);
+
+    let proposal = squads_account(fixture("proposal_active"));
+    let error = parse_multisig(&key(3), &proposal).err().unwrap();
+    assert!(error.contains("wrong discriminator"), "{}", error);
+}
+
+#[test]
+fn proposal_fixtures_have_each_status() {
+    let cases = [
+        ("draft", "Draft"),
+        ("active", "Active"),
+        ("rejected", "Rejected"),
+        ("approved", "Approved"),
+        ("executed", "Executed"),
+        ("cancelled", "Cancelled"),
+    ];
+    for (name, expected) in cases {
+        let data = fixture(&format!("proposal_{}", name));
+        let proposal = Proposal::try_deserialize(&mut data.as_slice()).unwrap();
+
+        assert_eq!(proposal.multisig, key(3));
+        assert_eq!(proposal.transaction_index, 4);
+        let (status, timestamp) = match proposal.status {
+            ProposalStatus::Draft { timestamp } => ("Draft", timestamp),
+            ProposalStatus::Active { timestamp } => ("Active", timestamp),
+            ProposalStatus::Rejected { timestamp } => ("Rejected", timestamp),
+            ProposalStatus::Approved { timestamp } => ("Approved", timestamp),
+            ProposalStatus::Executed { timestamp } => ("Executed", timestamp),
+            ProposalStatus::Cancelled { timestamp } => ("Cancelled", timestamp),
+            _ => ("Unknown", 0),
+        };
+        assert_eq!((status, timestamp), (expected, TIMESTAMP), "proposal_{}", name);
+    }
+
+    let data = fixture("proposal_approved");
+    let approved = Proposal::try_deserialize(&mut data.as_slice()).unwrap();
+    assert_eq!(approved.approved, vec![key(10), key(11)]);
+    let data = fixture("proposal_rejected");
+    let rejected = Proposal::try_deserialize(&mut data.as_slice()).unwrap();
+    assert_eq!(rejected.rejected, vec![key(11), key(12)]);
+}
+
+#[test]
+fn spending_limit_fixtures_parse() {
+    let data = fixture("spending_limit_sol");
+    let sol = SpendingLimit::try_deserialize(&mut data.as_slice()).unwrap();
+    assert_eq!(sol.multisig, key(3));
+    assert_eq!(sol.mint, Pubkey::default());
+    assert_eq!(sol.amount, 1_000_000_000);
+    assert_eq!(sol.remaining_amount, 400_000_000);
+    assert_eq!(sol.period.to_seconds(), Some(86_400));
+    assert_eq!(sol.last_reset, TIMESTAMP);
+    assert_eq!(sol.members, vec![key(11)]);
+    assert!(sol.destinations.is_empty());
+
+    let data = fixture("spending_limit_token");
+    let token = SpendingLimit::try_deserialize(&mut data.as_slice()).unwrap();
+    assert_eq!(token.vault_index, 1);
+    assert_eq!(token.mint, key(20));
+    assert!(matches!(token.period, Period::Week));
+    assert_eq!(token.destinations, vec![key(30)]);
+}
+
+#[test]
+fn mint_fixtures_parse_with_and_without_extensions() {
+    // 82 bytes: authority key(20), supply 5_000_000, 6 decimals, freeze authority key(21)
+    let data = fixture("mint_spl");
+    assert_eq!(data.len(), spl_token::state::Mint::LEN);
+    let mint = unpack_mint(&data).unwrap();
+    assert_eq!((mint.decimals, mint.supply), (6, 5_000_000));
+    assert_eq!(Option::<Pubkey>::from(mint.freeze_authority), Some(key(21)));
+
+    // The Token-2022 mint's extensions follow the account type byte past the base layout
+    let data = fixture("mint_token_2022");
+    assert!(data.len() > spl_token::state::Mint::LEN);
+    let mint = unpack_mint(&data).unwrap();
+    assert_eq!((mint.decimals, mint.supply), (9, 1_000_000_000_000));
+    assert_eq!(Option::<Pubkey>::from(mint.mint_authority), Some(key(20)));
+    assert_eq!(Option::<Pubkey>::from(mint.freeze_authority), None);
+
+    // use-spending-limit reads decimals this way for either owner, and errors instead of indexing
+    assert_eq!(mint_program_and_decimals(&key(50), &spl_token::ID, &fixture("mint_spl"), None), Ok((spl_token::ID, 6)));
+    assert!(unpack_mint(&data[..81]).is_err());
+    assert!(mint_program_and_decimals(&key(50), &spl_token::ID, &data[..44], None)
+        .unwrap_err()
+        .starts_with("could not read mint decimals"));
+}
+
+#[test]
+fn token_2022_spending_limit_mints_are_accepted() {
+    let data = fixture("spending_limit_token");
+    let limit = SpendingLimit::try_deserialize(&mut data.as_slice()).unwrap();
+    let mint = fixture("mint_token_2022");
+
+    assert_eq!(mint_program_and_decimals(&limit.mint, &spl_token_2022::ID, &mint, None), Ok((spl_token_2022::ID, 9)));
+    // The limit's transfer then pays into the destination's Token-2022 ATA
+    let destination = limit.destinations[0];
+    assert_ne!(
+        derive_ata(&destination, &limit.mint, &spl_token_2022::ID),
+        derive_ata(&destination, &limit.mint, &spl_token::ID)
+    );
+
+    let error = mint_program_and_decimals(&limit.mint, &system_program::ID, &mint, None).unwrap_err();
+    assert!(error.contains("is not a token mint") && error.contains("--token-program"), "{}", error);
+    assert_eq!(mint_program_and_decimals(&limit.mint, &system_program::ID, &mint, Some(key(60))), Ok((key(60), 9)));
+    assert!(mint_program_and_decimals(&limit.mint, &spl_token_2022::ID, &mint[..40], None).is_err());
+}
+
+#[test]
+fn spending_limit_usage_stays_within_bounds() {
+    let data = fixture("spending_limit_sol");
+    let mut limit = SpendingLimit::try_deserialize(&mut data.as_slice()).unwrap();
+    assert_eq!(usage_percent(limit.amount, limit.remaining_amount), 60.0);
+
+    limit.remaining_amount = limit.amount + 1;
+    assert_eq!(usage_percent(limit.amount, limit.remaining_amount), 0.0);
+
+    limit.amount = 0;
+    assert_eq!(usage_percent(limit.amount, limit.remaining_amount), 0.0);
+    assert_eq!(usage_percent(0, 0), 0.0);
+    assert_eq!(usage_percent(u64::MAX, 0), 100.0);
+}
+
+/// inspect-spending-limit --list filters on the multisig at offset 8 and reads a 58-byte slice
+/// at offset 72 (vault_index, mint, amount, period, remaining_amount, last_reset)
+#[test]
+fn spending_limit_layout_matches_list_scan_offsets() {
+    let data = fixture("spending_limit_token");
+    assert_eq!(&data[8..40], key(3).as_ref());
+
+    let slice = &data[72..130];
+    assert_eq!(slice[0], 1);
+    assert_eq!(&slice[1..33], key(20).as_ref());
+    assert_eq!(u64::from_le_bytes(slice[33..41].try_into().unwrap()), 5_000_000);
+    assert_eq!(slice[41], Period::Week as u8);
+    assert_eq!(u64::from_le_bytes(slice[42..50].try_into().unwrap()), 5_000_000);
+    assert_eq!(i64::from_le_bytes(slice[50..58].try_into().unwrap()), TIMESTAMP);
+}
+
+#[test]
+fn vault_transaction_fixture_builds_execute_instruction() {
+    let data = fixture("vault_transaction");
+    let vault_transaction = VaultTransaction::try_deserialize(&mut data.as_slice()).unwrap();
+    let (vault, vault_bump) = get_vault_pda(&key(3), 0, None);
+
+    assert_eq!(vault_transaction.multisig, key(3));
+    assert_eq!(vault_transaction.creator, key(10));
+    assert_eq!(vault_transaction.index, 4);
+    assert_eq!(vault_transaction.vault_bump, vault_bump);
+    assert_eq!(vault_transaction.message.account_keys, vec![vault, key(30), system_program::ID]);
+
+    let ix = vault_transaction_execute_ix(&key(3), 4, &vault_transaction, &[], &key(10)).unwrap();
+    let keys: Vec<Pubkey> = ix.accounts.iter().map(|meta| meta.pubkey).collect();
+    assert_eq!(
+        keys,
+        vec![
+            key(3),
+            get_proposal_pda(&key(3), 4, None).0,
+            get_transaction_pda(&key(3), 4, None).0,
+            key(10),
+            vault,
+            key(30),
+            system_program::ID,
+        ]
+    );
+    // The vault signs via CPI, so only the executing member is a transaction signer
+    assert!(ix.accounts[3].is_signer);
+    assert!(ix.accounts[4..].iter().all(|meta| !meta.is_signer));
+    assert!(ix.accounts[4].is_writable && ix.accounts[5].is_writable && !ix.accounts[6].is_writable);
+}
+
+#[test]
+fn vault_transaction_execute_instruction_loads_lookup_table_accounts() {
+    let data = fixture("vault_transaction");
+    let mut vault_transaction = VaultTransaction::try_deserialize(&mut data.as_slice()).unwrap();
+    vault_transaction.message.address_table_lookups = vec![MultisigMessageAddressTableLookup {
+        account_key: key(40),
+        writable_indexes: vec![2],
+        readonly_indexes: vec![0],
+    }];
+    let table = AddressLookupTableAccount { key: key(40), addresses: vec![key(41), key(42), key(43)] };
+
+    let ix = vault_transaction_execute_ix(&key(3), 4, &vault_transaction, &[table], &key(10)).unwrap();
+    // Lookup tables, then static accounts, then loaded accounts (writable before readonly)
+    let remaining: Vec<(Pubkey, bool)> = ix.accounts[4..].iter().map(|meta| (meta.pubkey, meta.is_writable)).collect();
+    assert_eq!(
+        remaining,
+        vec![
+            (key(40), false),
+            (get_vault_pda(&key(3), 0, None).0, true),
+            (key(30), true),
+            (system_program::ID, false),
+            (key(43), true),
+            (key(41), false),
+        ]
+    );
+
+    let missing = vault_transaction_execute_ix(&key(3), 4, &vault_transaction, &[], &key(10));
+    assert!(missing.is_err());
+}
+
+#[test]
+fn built_instructions_decode_back_to_their_args() {
+    let ix = proposal_create_ix(&key(3), 7, &key(10));
+    let decoded = decode_instruction(&ix.program_id, &ix.data).unwrap();
+    assert_eq!(decoded.name, "proposal_create");
+    assert_eq!(decoded.fields, vec![("transaction_index", "7".to_string()), ("draft", "false".to_string())]);
+
+    let ix = config_transaction_create_ix(&key(3), 8, &key(10), vec![ConfigAction::ChangeThreshold { new_threshold: 2 }]);
+    let decoded = decode_instruction(&ix.program_id, &ix.data).unwrap();
+    assert_eq!(decoded.name, "config_transaction_create");
+    assert_eq!(decoded.fields, vec![("action", "ChangeThreshold 2".to_string()), ("memo", "none".to_string())]);
+
+    // Trailing bytes mean the data isn't the instruction its discriminator claims
+    let mut data = ix.data.clone();
+    data.push(0);
+    assert!(decode_instruction(&ix.program_id, &data).is_err());
+
+    let transfer = solana_sdk::system_instruction::transfer(&key(1), &key(2), 5);
+    let decoded = decode_instruction(&transfer.program_id, &transfer.data).unwrap();
+    assert_eq!((decoded.name.as_str(), decoded.fields), ("transfer", vec![("lamports", "5".to_string())]));
+}
+
+#[test]
+fn expectations_refuse_anything_besides_matching_transfers() {
+    let vault = get_vault_pda(&key(3), 0, None).0;
+    let transfer = system_instruction::transfer(&vault, &key(30), 100);
+
+    let actions = decode_actions(&vault_message(&vault, std::slice::from_ref(&transfer)));
+    assert!(expectation_mismatches(&actions, Some(key(30)), Some(100)).is_empty());
+    assert_eq!(expectation_mismatches(&actions, Some(key(31)), None).len(), 1);
+    assert_eq!(expectation_mismatches(&actions, None, Some(99)).len(), 1);
+
+    // A matching transfer doesn't vouch for the authority change or program call riding along
+    let set_authority =
+        spl_token::instruction::set_authority(&spl_token::ID, &key(40), Some(&key(31)), AuthorityType::MintTokens, &vault, &[])
+            .unwrap();
+    let call = Instruction::new_with_bytes(key(60), &[1, 2, 3], vec![]);
+    let actions = decode_actions(&vault_message(&vault, &[transfer.clone(), set_authority, call]));
+    let mismatches = expectation_mismatches(&actions, Some(key(30)), Some(100));
+    assert_eq!(mismatches.len(), 2, "{:?}", mismatches);
+    assert!(mismatches[0].starts_with("instruction #1:") && mismatches[0].contains("set_authority"), "{}", mismatches[0]);
+    assert!(mismatches[1].starts_with("instruction #2:") && mismatches[1].contains(&key(60).to_string()), "{}", mismatches[1]);
+
+    // Other system instructions aren't plain transfers either
+    let with_seed = system_instruction::transfer_with_seed(&vault, &key(32), "seed".to_string(), &key(33), &key(30), 100);
+    let actions = decode_actions(&vault_message(&vault, &[with_seed]));
+    assert_eq!(expectation_mismatches(&actions, Some(key(30)), Some(100)).len(), 2);
+
+    // ATA creation passes only for the account a transfer pays into
+    let mint = key(50);
+    let create_ata = create_associated_token_account_idempotent(&vault, &key(30), &mint, &spl_token::ID);
+    let transfer_checked = spl_token::instruction::transfer_checked(
+        &spl_token::ID,
+        &derive_ata(&vault, &mint, &spl_token::ID),
+        &mint,
+        &derive_ata(&key(30), &mint, &spl_token::ID),
+        &vault,
+        &[],
+        100,
+        6,
+    )
+    .unwrap();
+    let actions = decode_actions(&vault_message(&vault, &[create_ata.clone(), transfer_checked]));
+    assert!(expectation_mismatches(&actions, Some(key(30)), Some(100)).is_empty());
+    let actions = decode_actions(&vault_message(&vault, &[create_ata, transfer]));
+    assert_eq!(expectation_mismatches(&actions, Some(key(30)), Some(100)).len(), 1);
+
+    assert_eq!(expectation_mismatches(&[], Some(key(30)), None), vec!["proposal contains no transfer to verify".to_string()]);
+}
+
+#[test]
+fn token_2022_mints_derive_atas_and_transfers_with_their_program() {
+    // Mint authority key(20), 9 decimals, with MintCloseAuthority and MetadataPointer extensions
+    let data = fixture("mint_token_2022");
+    let client = mock_account_client(&spl_token_2022::ID, &data);
+    let mint = key(50);
+    let token_program = resolve_token_program(&client, &mint).unwrap();
+    assert_eq!(token_program, spl_token_2022::ID);
+    let decimals = unpack_mint(&data).unwrap().decimals;
+
+    let vault = get_vault_pda(&key(3), 0, None).0;
+    let destination_ata = derive_ata(&key(30), &mint, &token_program);
+    assert_ne!(destination_ata, derive_ata(&key(30), &mint, &spl_token::ID));
+    let create_ata = create_associated_token_account_idempotent(&vault, &key(30), &mint, &token_program);
+    assert_eq!(create_ata.accounts[1].pubkey, destination_ata);
+    assert_eq!(create_ata.accounts[5].pubkey, spl_token_2022::ID);
+
+    let transfer = spl_token_2022::instruction::transfer_checked(
+        &token_program,
+        &derive_ata(&vault, &mint, &token_program),
+        &mint,
+        &destination_ata,
+        &vault,
+        &[],
+        100,
+        decimals,
+    )
+    .unwrap();
+    assert_eq!(transfer.program_id, spl_token_2022::ID);
+
+    // --expect-destination takes the wallet and derives its ATA with the transfer's program
+    let actions = decode_actions(&vault_message(&vault, &[create_ata, transfer]));
+    match &actions[1] {
+        InnerAction::Transfer(decoded) => {
+            assert_eq!((decoded.destination, decoded.token_program), (destination_ata, spl_token_2022::ID))
+        }
+        _ => panic!("transfer_checked under Token-2022 should decode as a transfer"),
+    }
+    assert!(expectation_mismatches(&actions, Some(key(30)), Some(100)).is_empty());
+}
+
+#[test]
+fn only_a_seeds_mismatch_on_a_taken_index_is_a_collision() {
+    let failed = |code: u32| -> ClientError {
+        ClientErrorKind::TransactionError(TransactionError::InstructionError(2, InstructionError::Custom(code))).into()
+    };
+    let seeds = ErrorCode::ConstraintSeeds as u32;
+
+    // Another proposal took index 5 (a fee profile put the create instruction at position 2)
+    assert!(is_index_collision(&failed(seeds), 5, 5));
+    assert!(is_index_collision(&failed(seeds), 5, 6));
+    // Same error, but nobody moved the index: something else is wrong with the transaction
+    assert!(!is_index_collision(&failed(seeds), 5, 4));
+    // An occupied account (SystemError::AccountAlreadyInUse = 0) isn't the index moving
+    assert!(!is_index_collision(&failed(0), 5, 5));
+    assert!(!is_index_collision(&ClientErrorKind::Custom("timed out".to_string()).into(), 5, 5));
+
+    // A seeds failure caught by the pre-send simulation keeps its transaction error
+    let simulation = RpcSimulateTransactionResult {
+        err: Some(TransactionError::InstructionError(2, InstructionError::Custom(seeds))),
+        logs: Some(vec!["Program log: AnchorError caused by account: transaction. Error Code: ConstraintSeeds.".into()]),
+        accounts: None,
+        units_consumed: None,
+        return_data: None,
+        inner_instructions: None,
+    };
+    let response = Response { context: RpcResponseContext { slot: 1, api_version: None }, value: simulation };
+    let mocks = [(RpcRequest::SimulateTransaction, serde_json::to_value(response).unwrap())];
+    let client = RpcClient::new_mock_with_mocks("succeeds", mocks.into_iter().collect());
+    let payer = Keypair::new();
+    let options = SendOptions::default();
+    let transaction = options
+        .build_transaction(&[proposal_create_ix(&key(3), 5, &payer.pubkey())], &payer.pubkey(), &[&payer], Hash::default())
+        .unwrap();
+    let error = send_transaction(&client, &transaction, &options).unwrap_err();
+    assert!(is_index_collision(&error, 5, 5), "{}", error);
+}
+
+#[test]
+#[allow(deprecated)]
+fn every_proposal_status_has_a_state() {
+    let states = [
+        (ProposalStatus::Draft { timestamp: TIMESTAMP }, "Draft", false, false, false),
+        (ProposalStatus::Active { timestamp: TIMESTAMP }, "Active", false, true, false),
+        (ProposalStatus::Rejected { timestamp: TIMESTAMP }, "Rejected", true, false, false),
+        (ProposalStatus::Approved { timestamp: TIMESTAMP }, "Approved", false, false, true),
+        (ProposalStatus::Executing, "Executing", false, false, false),
+        (ProposalStatus::Executed { timestamp: TIMESTAMP }, "Executed", true, false, false),
+        (ProposalStatus::Cancelled { timestamp: TIMESTAMP }, "Cancelled", true, false, false),
+    ];
+    for (status, name, terminal, votable, executable) in &states {
+        let state = ProposalState::from(status);
+        assert_eq!(state.name(), *name);
+        assert_eq!((state.is_terminal(), state.is_votable(), state.is_executable()), (*terminal, *votable, *executable));
+        assert_eq!(state.timestamp().is_none(), state == ProposalState::Executing);
+    }
+    assert_eq!(ProposalState::Executing.label(), "Executing");
+    assert!(ProposalState::Approved { timestamp: TIMESTAMP }.label().starts_with("Approved (since "));
+}
+
+#[test]
+fn thresholds_are_checked_at_their_boundaries() {
+    assert!(check_threshold(0, 3).unwrap_err().contains("at least 1"));
+    assert!(check_threshold(4, 3).unwrap_err().contains("exceeds the member count 3"));
+    assert!(check_threshold(3, 3).unwrap().unwrap().contains("unanimous"));
+    assert!(check_threshold(1, 3).unwrap().unwrap().contains("single-sig"));
+    assert_eq!(check_threshold(2, 3), Ok(None));
+    assert_eq!(check_threshold(1, 1), Ok(None));
+
+    // Only members with Vote count toward the threshold
+    let member = |n: u8, vote: bool| Member { key: key(n), permissions: Permissions::from_flags(true, vote, true) };
+    let members = [member(1, true), member(2, true), member(3, false)];
+    assert!(check_voters(&members, 0).is_ok());
+    assert!(check_voters(&members, 2).is_ok());
+    assert!(check_voters(&members, 3).unwrap_err().contains("only 2 member(s)"));
+}
+
+#[test]
+fn members_are_sorted_and_duplicates_rejected() {
+    let member = |n: u8| Member { key: key(n), permissions: Permissions::all() };
+    let mut members = vec![member(3), member(1), member(2)];
+    assert_eq!(check_members(&mut members), Ok(()));
+    assert_eq!(members.iter().map(|m| m.key).collect::<Vec<_>>(), vec![key(1), key(2), key(3)]);
+
+    // A repeated member is an error naming the key, never merged: it would quietly shrink the multisig
+    let mut members = vec![member(3), member(1), member(3), member(2)];
+    assert_eq!(check_members(&mut members), Err(format!("{} is listed more than once", key(3))));
+    assert_eq!(members.len(), 4);
+
+    let mut keys = vec![key(9), key(4), key(7)];
+    assert_eq!(normalize_members(&mut keys, |key| *key), Ok(()));
+    assert_eq!(keys, vec![key(4), key(7), key(9)]);
+    let mut keys = vec![key(9), key(4), key(9)];
+    assert_eq!(normalize_members(&mut keys, |key| *key), Err(format!("{} is listed more than once", key(9))));
+
+    assert!(normalize_members(&mut Vec::<Pubkey>::new(), |key| *key).unwrap_err().contains("empty"));
+    assert!(check_members(&mut []).unwrap_err().contains("empty"));
+    assert!(check_members(&mut [Member { key: Pubkey::default(), permissions: Permissions::all() }]).is_err());
+}
+
+#[test]
+fn ephemeral_signers_are_derived_from_the_transaction_in_index_order() {
+    let transaction_pda = get_transaction_pda(&key(3), 1, None).0;
+    assert_eq!(transaction_pda.to_string(), "GKZcmYZrWzDhyEYdi7CYk6PGch6j4Tv1ELXS1StGDi1U");
+
+    let signers = ephemeral_signer_pdas(&transaction_pda, 2);
+    assert_eq!(signers[0].to_string(), "BQsPa6CxMvc1CEpwGXY8mZGfqQTggGdZJLx97XLtmAES");
+    assert_eq!(signers[1].to_string(), "9VDoSe2B1xPGT24EW5MankAKhm7syDJRyDWhWkYvTAkr");
+    // Each index is its own seed, so the order matches the program's ephemeral_signer_index
+    for (index, signer) in signers.iter().enumerate() {
+        let seeds: [&[u8]; 4] = [b"multisig", transaction_pda.as_ref(), b"ephemeral_signer", &[index as u8]];
+        assert_eq!(*signer, Pubkey::find_program_address(&seeds, &squads_multisig_program::ID).0);
+    }
+    assert_eq!(ephemeral_signer_pdas(&transaction_pda, 3)[..2], signers[..]);
+    assert!(ephemeral_signer_pdas(&transaction_pda, 0).is_empty());
+}